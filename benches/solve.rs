@@ -0,0 +1,48 @@
+//! Benchmarks for solving and copying `Board`s.
+//!
+//! Run with `cargo bench`.
+
+#[macro_use]
+extern crate criterion;
+extern crate camps_and_trees;
+
+use camps_and_trees::{process_intersections, samples, Board, CompactGrid, Grid};
+use criterion::{black_box, Criterion};
+
+/// A 15x15 puzzle the solver gets most of the way through.
+fn board() -> Board {
+    samples::by_name("15x15").unwrap()
+}
+
+fn solve(c: &mut Criterion) {
+    let board = board();
+    c.bench_function("solve 15x15", |b| {
+        b.iter(|| black_box(board.clone()).solve_outcome())
+    });
+}
+
+fn solve_large(c: &mut Criterion) {
+    let board = samples::by_name("20x20").unwrap();
+    c.bench_function("solve 20x20", |b| {
+        b.iter(|| black_box(board.clone()).solve_outcome())
+    });
+}
+
+fn intersections(c: &mut Criterion) {
+    let board = board();
+    c.bench_function("process_intersections 15x15", |b| {
+        b.iter(|| process_intersections(&mut black_box(board.clone())))
+    });
+}
+
+fn clone_grid(c: &mut Criterion) {
+    let grid = Grid::clone(&board());
+    c.bench_function("clone 15x15 grid", |b| b.iter(|| black_box(&grid).clone()));
+    let compact = CompactGrid::from(&grid);
+    c.bench_function("clone 15x15 compact grid", |b| {
+        b.iter(|| black_box(&compact).clone())
+    });
+}
+
+criterion_group!(benches, solve, solve_large, intersections, clone_grid);
+criterion_main!(benches);
@@ -0,0 +1,40 @@
+//! Solve a puzzle and render it with coordinate labels.
+//!
+//! When the solver gets stuck, the remaining tiles are listed using
+//! the same labels that are drawn around the grid.
+
+extern crate camps_and_trees;
+use camps_and_trees::render::{cell_name, text, RenderOptions};
+use camps_and_trees::{Board, Error};
+
+fn solve_and_render(mut board: Board) {
+    let options = RenderOptions {
+        labels: true,
+        ..Default::default()
+    };
+    match board.solve() {
+        Ok(()) => println!("Solved:\n{}\n", text(&board, &options)),
+        Err(Error::Unsolved { remaining }) => {
+            println!("Stuck:\n{}", text(&board, &options));
+            let names: Vec<_> = remaining
+                .iter()
+                .map(|pos| cell_name(pos.row, pos.column))
+                .collect();
+            println!("Undecided: {}\n", names.join(", "));
+        }
+        Err(e) => println!("Error: {}\n", e),
+    }
+}
+
+fn main() {
+    solve_and_render(
+        Board::new_parse(
+            vec![1, 1, 0, 2, 1],
+            vec![2, 0, 1, 1, 1],
+            "     \n T T \n     \nTT   \n    T",
+        )
+        .unwrap(),
+    );
+    solve_and_render(Board::new_parse(vec![1, 0, 1], vec![1, 0, 1], " T \n   \n T ").unwrap());
+    solve_and_render(Board::new_parse_clue_free(2, "T-T\n   ").unwrap());
+}
@@ -0,0 +1,37 @@
+/* C interface to the camps_and_trees solver.  Build the library with
+ * `cargo build --release --features ffi` and link against the
+ * resulting cdylib. */
+
+#ifndef CAMPS_AND_TREES_H
+#define CAMPS_AND_TREES_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Results of ct_board_solve. */
+#define CT_SOLVED 0
+#define CT_UNSOLVED 1
+#define CT_INVALID 2
+#define CT_NULL (-1)
+
+typedef struct ct_board ct_board;
+
+/* Parse a board from text or JSON.  Returns NULL if the input is
+ * malformed.  Release the board with ct_board_free. */
+ct_board *ct_board_parse(const char *input);
+
+/* Solve the board in place.  Returns one of the CT_ codes above. */
+int ct_board_solve(ct_board *board);
+
+/* Write the board as text.  Release the string with ct_string_free. */
+char *ct_board_to_string(const ct_board *board);
+
+void ct_board_free(ct_board *board);
+void ct_string_free(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,251 @@
+use board::*;
+use error::*;
+use grid::*;
+use pos::*;
+use std::ops::{Index, IndexMut};
+use tile::*;
+
+/// A pencil mark a player puts on a cell while working out a puzzle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Mark {
+    /// The cell might hold a `Camp`.
+    MaybeCamp,
+    /// The cell definitely holds a `Camp`.
+    Camp,
+    /// The cell is definitely `Grass`.
+    Grass,
+}
+
+impl Mark {
+    /// The [`Tile`] a confirmed `Mark` stands for, or `None` for
+    /// [`Mark::MaybeCamp`].
+    ///
+    /// [`Tile`]: enum.Tile.html
+    /// [`Mark::MaybeCamp`]: enum.Mark.html#variant.MaybeCamp
+    pub fn tile(self) -> Option<Tile> {
+        match self {
+            Mark::MaybeCamp => None,
+            Mark::Camp => Some(Tile::Camp),
+            Mark::Grass => Some(Tile::Grass),
+        }
+    }
+
+    /// The character drawn for the `Mark` by [`render::text`]: `?`
+    /// for [`Mark::MaybeCamp`], `c` for [`Mark::Camp`] and `.` for
+    /// [`Mark::Grass`].
+    ///
+    /// [`render::text`]: render/fn.text.html
+    /// [`Mark::MaybeCamp`]: enum.Mark.html#variant.MaybeCamp
+    /// [`Mark::Camp`]: enum.Mark.html#variant.Camp
+    /// [`Mark::Grass`]: enum.Mark.html#variant.Grass
+    pub fn symbol(self) -> char {
+        match self {
+            Mark::MaybeCamp => '?',
+            Mark::Camp => 'c',
+            Mark::Grass => '.',
+        }
+    }
+}
+
+/// Pencil marks kept beside a [`Grid`], one optional [`Mark`] per
+/// cell.
+///
+/// The marks never change the [`Grid`] and the solver never looks at
+/// them.  [`Board::merge_annotations`] places the confirmed ones once
+/// the player is sure of them.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Annotations, Board, Mark, Pos};
+/// let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+/// let mut annotations = Annotations::for_grid(&board);
+/// annotations[Pos::new(0, 0)] = Some(Mark::MaybeCamp);
+/// annotations[Pos::new(1, 0)] = Some(Mark::Grass);
+/// assert_eq!(board.merge_annotations(&mut annotations), Ok(1));
+/// assert_eq!(board.debug(), " T\n- ");
+/// assert_eq!(annotations.count(), 1);
+/// ```
+///
+/// [`Grid`]: struct.Grid.html
+/// [`Mark`]: enum.Mark.html
+/// [`Board::merge_annotations`]: struct.Board.html#method.merge_annotations
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Annotations {
+    marks: Vec<Option<Mark>>,
+    height: usize,
+    width: usize,
+}
+
+impl Annotations {
+    /// Create `Annotations` with no marks for a [`Grid`] with `rows`
+    /// rows and `columns` columns.
+    ///
+    /// [`Grid`]: struct.Grid.html
+    pub fn new(rows: usize, columns: usize) -> Self {
+        Annotations {
+            marks: vec![None; rows * columns],
+            height: rows,
+            width: columns,
+        }
+    }
+
+    /// Create `Annotations` with no marks the same size as `grid`.
+    pub fn for_grid(grid: &Grid) -> Self {
+        Annotations::new(grid.num_rows(), grid.num_columns())
+    }
+
+    /// Get the number of rows.
+    pub fn num_rows(&self) -> usize {
+        self.height
+    }
+
+    /// Get the number of columns.
+    pub fn num_columns(&self) -> usize {
+        self.width
+    }
+
+    /// Does `grid` have the same size as these `Annotations`?
+    pub fn fits(&self, grid: &Grid) -> bool {
+        grid.num_rows() == self.height && grid.num_columns() == self.width
+    }
+
+    /// Get the number of cells with a mark.
+    pub fn count(&self) -> usize {
+        self.marks.iter().filter(|x| x.is_some()).count()
+    }
+
+    /// Get the positions of every mark along with the mark, in
+    /// reading order.
+    pub fn marks<'a>(&'a self) -> impl Iterator<Item = (Pos, Mark)> + 'a {
+        let width = self.width;
+        self.marks
+            .iter()
+            .enumerate()
+            .filter_map(move |(i, mark)| mark.map(|x| (Pos::new(i / width, i % width), x)))
+    }
+
+    /// Remove every mark.
+    pub fn clear(&mut self) {
+        for mark in &mut self.marks {
+            *mark = None;
+        }
+    }
+
+    fn offset(&self, pos: Pos) -> usize {
+        assert!(
+            pos.row < self.height && pos.column < self.width,
+            "{:?} is outside {}x{} annotations",
+            pos,
+            self.height,
+            self.width
+        );
+        pos.row * self.width + pos.column
+    }
+}
+
+impl Index<Pos> for Annotations {
+    type Output = Option<Mark>;
+    fn index(&self, pos: Pos) -> &Option<Mark> {
+        &self.marks[self.offset(pos)]
+    }
+}
+
+impl IndexMut<Pos> for Annotations {
+    fn index_mut(&mut self, pos: Pos) -> &mut Option<Mark> {
+        let offset = self.offset(pos);
+        &mut self.marks[offset]
+    }
+}
+
+impl Board {
+    /// Place the [`Tile`] of every confirmed [`Mark`] in
+    /// `annotations` with [`Board::place`], in reading order, and
+    /// remove those marks.
+    ///
+    /// [`Mark::MaybeCamp`]s are left alone.  Returns the number of
+    /// marks merged.
+    ///
+    /// # Errors
+    ///
+    /// The first error from [`Board::place`] is returned, such as for
+    /// a [`Mark::Camp`] that touches another [`Camp`].  The marks
+    /// before it stay merged and the rest stay in `annotations`.
+    ///
+    /// # Panics
+    ///
+    /// This will `panic` if `annotations` is a different size than
+    /// the `Board`.
+    ///
+    /// [`Tile`]: enum.Tile.html
+    /// [`Mark`]: enum.Mark.html
+    /// [`Mark::MaybeCamp`]: enum.Mark.html#variant.MaybeCamp
+    /// [`Mark::Camp`]: enum.Mark.html#variant.Camp
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Board::place`]: struct.Board.html#method.place
+    pub fn merge_annotations(&mut self, annotations: &mut Annotations) -> Result<usize, Error> {
+        assert!(
+            annotations.fits(self),
+            "annotations don't fit the board"
+        );
+        let confirmed: Vec<(Pos, Tile)> = annotations
+            .marks()
+            .filter_map(|(pos, mark)| mark.tile().map(|tile| (pos, tile)))
+            .collect();
+        for &(pos, tile) in &confirmed {
+            self.place(pos.row, pos.column, tile)?;
+            annotations[pos] = None;
+        }
+        Ok(confirmed.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tile::Tile::*;
+
+    #[test]
+    fn annotations_index() {
+        let mut annotations = Annotations::new(2, 3);
+        assert_eq!(annotations.count(), 0);
+        annotations[Pos::new(1, 2)] = Some(Mark::Camp);
+        annotations[Pos::new(0, 1)] = Some(Mark::MaybeCamp);
+        assert_eq!(annotations[Pos::new(1, 2)], Some(Mark::Camp));
+        assert_eq!(annotations[Pos::new(1, 1)], None);
+        assert_eq!(
+            annotations.marks().collect::<Vec<_>>(),
+            vec![
+                (Pos::new(0, 1), Mark::MaybeCamp),
+                (Pos::new(1, 2), Mark::Camp)
+            ]
+        );
+        annotations.clear();
+        assert_eq!(annotations, Annotations::new(2, 3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn annotations_outside() {
+        let _ = Annotations::new(2, 3)[Pos::new(0, 3)];
+    }
+
+    #[test]
+    fn merge_annotations_errors() {
+        let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        let mut annotations = Annotations::for_grid(&board);
+        annotations[Pos::new(0, 0)] = Some(Mark::Grass);
+        annotations[Pos::new(0, 1)] = Some(Mark::Grass);
+        annotations[Pos::new(1, 1)] = Some(Mark::Camp);
+        assert_eq!(
+            board.merge_annotations(&mut annotations),
+            Err(Error::TreeChanged {
+                pos: Pos::new(0, 1)
+            })
+        );
+        assert_eq!(board.debug(), "-T\n  ");
+        assert_eq!(annotations[Pos::new(0, 0)], None);
+        assert_eq!(annotations.count(), 2);
+        assert_eq!(board.count(Unassigned), 2);
+    }
+}
@@ -0,0 +1,192 @@
+use board::*;
+use clue::*;
+use grid::*;
+use pos::*;
+use quickcheck::{Arbitrary, Gen};
+use tile::Tile::{self, *};
+
+/// The largest number of rows or columns generated.
+const MAX_SIDE: usize = 8;
+
+/// The characters [`GarbageInput`] is mostly made of, so that it gets
+/// past the first few checks of the parsers.
+///
+/// [`GarbageInput`]: struct.GarbageInput.html
+const PUZZLE_CHARS: &[char] = &[
+    ' ', '-', 'C', 'T', '#', '.', '\n', '\n', ',', ',', '0', '1', '2', '9', ':', '=',
+];
+
+impl Arbitrary for Tile {
+    fn arbitrary(g: &mut Gen) -> Tile {
+        *g.choose(&[Unassigned, Grass, Camp, Tree, Blocked]).unwrap()
+    }
+}
+
+impl Arbitrary for Grid {
+    /// A `Grid` of random `Tile`s, which mostly won't follow the
+    /// rules of the game.
+    fn arbitrary(g: &mut Gen) -> Grid {
+        let (rows, columns) = (side(g), side(g));
+        Grid::new(
+            (0..rows)
+                .map(|_| (0..columns).map(|_| Tile::arbitrary(g)).collect())
+                .collect(),
+        )
+    }
+}
+
+impl Arbitrary for Board {
+    /// A puzzle with at least one solution.
+    ///
+    /// `Camp`s are placed at random, each with a `Tree` next to it.
+    /// The clues are counted from the `Camp`s, which are then
+    /// removed, leaving only the `Tree`s.  A third of the puzzles
+    /// have some of the other `Tile`s `Blocked`.  A quarter of the
+    /// puzzles are clue-free, and a third of the rest omit some clues
+    /// or give ranges that contain the count instead.
+    fn arbitrary(g: &mut Gen) -> Board {
+        let (rows, columns) = (side(g), side(g));
+        let mut grid = Grid::blank(rows, columns);
+        for row in 0..rows {
+            for column in 0..columns {
+                let pos = Pos::new(row, column);
+                if grid[pos] != Unassigned || !grid.can_place_camp(pos) || !chance(g, 3) {
+                    continue;
+                }
+                let trees: Vec<Pos> = grid
+                    .neighbors4(pos)
+                    .into_iter()
+                    .filter(|&x| grid[x] == Unassigned)
+                    .collect();
+                if let Some(&tree) = g.choose(&trees) {
+                    grid[pos] = Camp;
+                    grid[tree] = Tree;
+                }
+            }
+        }
+        if chance(g, 3) {
+            for pos in grid.positions_of(Unassigned).collect::<Vec<_>>() {
+                if chance(g, 6) {
+                    grid[pos] = Blocked;
+                }
+            }
+        }
+        let loosen = chance(g, 3);
+        let mut clue = |camps: usize| {
+            if !loosen || !chance(g, 3) {
+                return Clue::exactly(camps);
+            }
+            match usize::arbitrary(g) % 4 {
+                0 => Clue::any(),
+                1 => Clue::at_most(camps + 1),
+                2 => Clue::at_least(camps.saturating_sub(1)),
+                _ => Clue::between(camps.saturating_sub(1), camps + 1),
+            }
+        };
+        let row_clues = (0..rows)
+            .map(|x| clue(grid.count_in_row(x, Camp)))
+            .collect();
+        let column_clues = (0..columns)
+            .map(|x| clue(grid.count_in_column(x, Camp)))
+            .collect();
+        let total = grid.count(Camp);
+        for pos in grid.positions_of(Camp).collect::<Vec<_>>() {
+            grid[pos] = Unassigned;
+        }
+        if chance(g, 4) {
+            Board::new_clue_free(total, grid)
+        } else {
+            Board::new_ranged(row_clues, column_clues, grid)
+        }
+    }
+}
+
+/// Random text for fuzzing the parsers, mostly made of the characters
+/// found in puzzles.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate camps_and_trees;
+/// # extern crate quickcheck;
+/// # use camps_and_trees::{Board, GarbageInput};
+/// # use quickcheck::{Arbitrary, Gen};
+/// # fn main() {
+/// let mut g = Gen::new(20);
+/// for _ in 0..100 {
+///     let GarbageInput(text) = GarbageInput::arbitrary(&mut g);
+///     let _ = Board::from_text(&text);
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GarbageInput(pub String);
+
+impl Arbitrary for GarbageInput {
+    fn arbitrary(g: &mut Gen) -> GarbageInput {
+        let len = usize::arbitrary(g) % (g.size() + 1);
+        GarbageInput(
+            (0..len)
+                .map(|_| {
+                    if chance(g, 8) {
+                        char::arbitrary(g)
+                    } else {
+                        *g.choose(PUZZLE_CHARS).unwrap()
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = GarbageInput>> {
+        Box::new(self.0.shrink().map(GarbageInput))
+    }
+}
+
+/// A random number of rows or columns, from `1` to [`MAX_SIDE`].
+///
+/// [`MAX_SIDE`]: constant.MAX_SIDE.html
+fn side(g: &mut Gen) -> usize {
+    usize::arbitrary(g) % MAX_SIDE + 1
+}
+
+/// Randomly decide to do something one in `n` times.
+fn chance(g: &mut Gen, n: usize) -> bool {
+    usize::arbitrary(g) % n == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::quickcheck;
+    use solve_outcome::*;
+    use transform::*;
+
+    quickcheck! {
+        fn boards_are_consistent(board: Board) -> bool {
+            board.validate().is_ok()
+                && !matches!(
+                    board.clone().solve_outcome(),
+                    SolveOutcome::Contradiction { .. }
+                )
+        }
+
+        fn solving_agrees_with_symmetries(board: Board) -> bool {
+            let mut solved = board.clone();
+            let solved_ok = solved.solve().is_ok();
+            let mut rotated = board.rotated(RotationDirection::Clockwise);
+            rotated.solve().is_ok() == solved_ok
+                && (!solved_ok || rotated == solved.rotated(RotationDirection::Clockwise))
+        }
+
+        fn parsing_garbage_doesnt_panic(input: GarbageInput) -> bool {
+            let _ = Board::from_text(&input.0);
+            let _ = Grid::parse(&input.0);
+            true
+        }
+
+        fn grids_round_trip(grid: Grid) -> bool {
+            Grid::parse(&grid.debug()) == Ok(grid)
+        }
+    }
+}
@@ -1,4 +1,6 @@
+use contradiction::*;
 use grid::*;
+use pos::*;
 use tile::Tile::*;
 
 /// The association of a certain `Tile`.
@@ -22,10 +24,7 @@ use self::Association::*;
 impl Association {
     /// True if `self` is a `CampAt`.
     fn is_camp_at(&self) -> bool {
-        match self {
-            CampAt(_, _) => true,
-            _ => false,
-        }
+        matches!(self, CampAt(_, _))
     }
 }
 
@@ -35,40 +34,51 @@ fn associate_surrounding_trees(
     row: usize,
     column: usize,
     associations: &mut Vec<Vec<Association>>,
-) {
-    for (r, c) in grid.surrounding_tiles(row, column) {
-        associate_tree(grid, r, c, associations);
+) -> Result<(), (Pos, Contradiction)> {
+    for pos in grid.surrounding_tiles(Pos::new(row, column)) {
+        associate_tree(grid, pos.row, pos.column, associations)?;
     }
+    Ok(())
 }
 
 /// Populate the `associations` table for the `Tile` at `(row, column)`.
+///
+/// # Errors
+///
+/// If a `Camp` has no `Tree`s next to it, or its only `Tree` is
+/// already taken by another `Camp`, the `Camp` and the
+/// `Contradiction` are returned.
 fn associate_tree(
     grid: &Grid,
     row: usize,
     column: usize,
     associations: &mut Vec<Vec<Association>>,
-) {
+) -> Result<(), (Pos, Contradiction)> {
     if associations[row][column] == Unprocessed {
         if grid[(row, column)] == Tree {
             associations[row][column] = NoCampAssociated;
-            associate_surrounding_trees(grid, row, column, associations);
+            associate_surrounding_trees(grid, row, column, associations)?;
         } else if grid[(row, column)] == Camp {
             associations[row][column] = UnassignedCamp;
-            associate_surrounding_trees(grid, row, column, associations);
+            associate_surrounding_trees(grid, row, column, associations)?;
             // `Camp` handles assigning itself to `Tree`s around it.
             let trees: Vec<_> = grid
-                .surrounding_tiles(row, column)
+                .surrounding_tiles(Pos::new(row, column))
                 .into_iter()
                 .filter(|&p| grid[p] == Tree)
                 .collect();
-            assert!(trees.len() >= 1);
+            if trees.is_empty() {
+                return Err((Pos::new(row, column), Contradiction::LonelyCamp));
+            }
             assert!(trees.len() <= 4);
             // If there is exactly one Tree next to this Camp, then we
             // associate ourselves with it.  Otherwise it can be
             // ambiguous.
             if trees.len() == 1 {
-                let (r, c) = trees[0];
-                assert_eq!(associations[r][c], NoCampAssociated);
+                let Pos { row: r, column: c } = trees[0];
+                if associations[r][c] != NoCampAssociated {
+                    return Err((Pos::new(row, column), Contradiction::SharedTree));
+                }
                 associations[r][c] = CampAt(row, column);
                 associations[row][column] = NoTree;
             }
@@ -76,6 +86,7 @@ fn associate_tree(
             associations[row][column] = NoTree;
         }
     }
+    Ok(())
 }
 
 /// Generate the initial associations table.
@@ -95,47 +106,59 @@ fn generate_associations(rows: usize, columns: usize) -> Vec<Vec<Association>> {
 /// ```
 /// # use camps_and_trees::{Grid, associate_trees};
 /// let mut grid = Grid::parse("---\n TC\n---").unwrap();
-/// associate_trees(&mut grid);
+/// assert_eq!(associate_trees(&mut grid), Ok(true));
 /// assert_eq!(grid, Grid::parse("---\n-TC\n---").unwrap());
 /// ```
 ///
+/// Nothing is associated if the [`RuleSet`] has `no_matching` set.
+///
 /// However it also acts conservatively, not filling in [`Grass`] unless
 /// it can show it to be needed:
 ///
 /// ```
 /// # use camps_and_trees::{Grid, associate_trees};
 /// let mut grid = Grid::parse("T--\n TC\nT--").unwrap();
-/// associate_trees(&mut grid);
+/// assert_eq!(associate_trees(&mut grid), Ok(false));
 /// assert_eq!(grid, Grid::parse("T--\n TC\nT--").unwrap());
 /// ```
 ///
+/// # Errors
+///
+/// If a [`Camp`] has no [`Tree`] next to it, or two [`Camp`]s can only
+/// be associated with the same [`Tree`], the offending [`Camp`] and
+/// [`Contradiction`] are returned.
+///
 /// [`Tree`]: enum.Tile.html#variant.Tree
 /// [`Camp`]: enum.Tile.html#variant.Camp
 /// [`Grass`]: enum.Tile.html#variant.Grass
-pub fn associate_trees(grid: &mut Grid) -> bool {
+/// [`Contradiction`]: enum.Contradiction.html
+/// [`RuleSet`]: struct.RuleSet.html
+pub fn associate_trees(grid: &mut Grid) -> Result<bool, (Pos, Contradiction)> {
+    if grid.rules().no_matching {
+        return Ok(false);
+    }
     let mut changed = false;
     let mut associations: Vec<Vec<Association>> =
         generate_associations(grid.num_rows(), grid.num_columns());
     for row in 0..grid.num_rows() {
         for column in 0..grid.num_columns() {
-            associate_tree(grid, row, column, &mut associations);
+            associate_tree(grid, row, column, &mut associations)?;
         }
     }
     for row in 0..grid.num_rows() {
         for column in 0..grid.num_columns() {
-            if grid[(row, column)] == Unassigned {
-                if grid
-                    .surrounding_tiles(row, column)
+            if grid[(row, column)] == Unassigned
+                && grid
+                    .surrounding_tiles(Pos::new(row, column))
                     .into_iter()
-                    .all(|x| grid[x] != Tree || associations[x.0][x.1].is_camp_at())
-                {
-                    grid[(row, column)] = Grass;
-                    changed = true;
-                }
+                    .all(|x| grid[x] != Tree || associations[x.row][x.column].is_camp_at())
+            {
+                grid[(row, column)] = Grass;
+                changed = true;
             }
         }
     }
-    changed
+    Ok(changed)
 }
 
 #[cfg(test)]
@@ -146,7 +169,7 @@ mod tests {
     fn associate_tree_no_camp() {
         let grid = Grid::parse(" T \n   \n   ").unwrap();
         let mut associations = generate_associations(3, 3);
-        associate_tree(&grid, 0, 1, &mut associations);
+        assert_eq!(associate_tree(&grid, 0, 1, &mut associations), Ok(()));
         assert_eq!(
             associations,
             vec![
@@ -161,7 +184,7 @@ mod tests {
     fn associate_tree_associate_tree() {
         let grid = Grid::parse(" TC\n --\n   ").unwrap();
         let mut associations = generate_associations(3, 3);
-        associate_tree(&grid, 0, 1, &mut associations);
+        assert_eq!(associate_tree(&grid, 0, 1, &mut associations), Ok(()));
         assert_eq!(
             associations,
             vec![
@@ -175,7 +198,25 @@ mod tests {
     #[test]
     fn associate_trees_horizontal() {
         let mut grid = Grid::parse(" TC\n---\n---").unwrap();
-        assert!(associate_trees(&mut grid));
+        assert_eq!(associate_trees(&mut grid), Ok(true));
         assert_eq!(grid.debug(), "-TC\n---\n---");
     }
+
+    #[test]
+    fn associate_trees_lonely_camp() {
+        let mut grid = Grid::parse("C-T\n---").unwrap();
+        assert_eq!(
+            associate_trees(&mut grid),
+            Err((Pos::new(0, 0), Contradiction::LonelyCamp))
+        );
+    }
+
+    #[test]
+    fn associate_trees_shared_tree() {
+        let mut grid = Grid::parse("CTC\n---").unwrap();
+        assert_eq!(
+            associate_trees(&mut grid),
+            Err((Pos::new(0, 0), Contradiction::SharedTree))
+        );
+    }
 }
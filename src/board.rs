@@ -1,20 +1,89 @@
+use board_move::*;
+use board_observer::*;
+use clue::*;
+use contradiction::*;
+use counter::*;
+use counts::*;
+use error::*;
 use grid::*;
+use pos::*;
+use rule_set::*;
+use solve_outcome::*;
+use solve_stats::*;
+use solver_config::*;
+use solver_limits::*;
+use strategy::*;
+use tile::Tile;
+use work_queue::*;
+use std::cmp::Ordering;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
-use std::ops::{Deref, DerefMut};
+use std::mem;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 /// The game `Board`.
 ///
-/// This automatically dereferences to the field `grid` for easier
-/// usage.
-#[derive(Clone, PartialEq, Eq)]
+/// This automatically dereferences to its [`Grid`] for easier usage.
+///
+/// The `Board` keeps count of the [`Tile`]s in every row and column.
+/// [`Tile`]s are written with [`Board::set`], which keeps these counts
+/// up to date, so the [`Grid`] can only be read through the `Board`.
+/// Use [`Board::place`] for moves that have to follow the rules of the
+/// game.
+///
+/// [`Grid`]: struct.Grid.html
+/// [`Tile`]: enum.Tile.html
+/// [`Board::set`]: struct.Board.html#method.set
+/// [`Board::place`]: struct.Board.html#method.place
+#[derive(Clone)]
 pub struct Board {
-    /// The number of `Camp`s on every row.
-    pub rows: Vec<usize>,
-    /// The number of `Camp`s on every column.
-    pub columns: Vec<usize>,
-    /// The `Grid` of `Tile`s.
-    pub grid: Grid,
+    /// Limits on how much work solving may do.
+    ///
+    /// These don't affect equality, hashing or ordering.
+    pub limits: SolverLimits,
+    /// Told about every `Tile` changed through the `Board`.
+    ///
+    /// See [`BoardObserver`].  This doesn't affect equality,
+    /// hashing or ordering.
+    ///
+    /// [`BoardObserver`]: trait.BoardObserver.html
+    pub observer: Option<Arc<dyn BoardObserver>>,
+    /// The moves made with [`Board::place`] and [`Board::clear`],
+    /// oldest first.
+    ///
+    /// See [`Board::undo`].  This doesn't affect equality,
+    /// hashing or ordering.
+    ///
+    /// [`Board::place`]: struct.Board.html#method.place
+    /// [`Board::clear`]: struct.Board.html#method.clear
+    /// [`Board::undo`]: struct.Board.html#method.undo
+    pub history: Vec<Move>,
+    grid: Grid,
+    /// The clue of every row, or nothing for a clue-free `Board`.
+    row_ranges: Vec<Clue>,
+    /// The clue of every column, or nothing for a clue-free `Board`.
+    column_ranges: Vec<Clue>,
+    /// The total number of `Camp`s when the clues don't give it.
+    total: usize,
+    counts: Counts,
+    /// The lines to process again while solving.
+    queue: Option<WorkQueue>,
+    stats: Option<SolveStats>,
+    /// When the current solve has to stop by, from
+    /// [`SolverLimits::max_duration`].
+    ///
+    /// [`SolverLimits::max_duration`]: struct.SolverLimits.html#structfield.max_duration
+    deadline: Option<Instant>,
+    /// The name of the strategy being applied while solving.
+    strategy: Option<&'static str>,
+    /// How many possibilities the current solve has gone through, from
+    /// [`Board::record_possibilities`].
+    ///
+    /// [`Board::record_possibilities`]: struct.Board.html#method.record_possibilities
+    possibilities: Counter,
     marker: PhantomData<()>,
 }
 
@@ -24,131 +93,1667 @@ impl Board {
     /// # Panics
     ///
     /// This will ensure that the [`Grid`] is of a valid size and
-    /// `panic` if it isn't.  That is if the length of `rows` is
-    /// different than the number of rows in the `grid`, or the same
-    /// for `columns`.
+    /// `panic` if it isn't.  See [`Board::try_new`].
     ///
     /// [`Grid`]: struct.Grid.html
+    /// [`Board::try_new`]: struct.Board.html#method.try_new
     pub fn new(rows: Vec<usize>, columns: Vec<usize>, grid: Grid) -> Self {
-        assert_eq!(grid.array.len(), rows.len());
-        assert!(grid.array.iter().all(|r| r.len() == rows.len()));
+        match Self::try_new(rows, columns, grid) {
+            Ok(board) => board,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Create a new `Board`, checking the size of the [`Grid`].
+    ///
+    /// # Errors
+    ///
+    /// If the length of `rows` is different than the number of rows
+    /// in the `grid`, or the same for `columns`, an
+    /// [`Error::DimensionMismatch`] is returned.
+    ///
+    /// [`Grid`]: struct.Grid.html
+    /// [`Error::DimensionMismatch`]: enum.Error.html#variant.DimensionMismatch
+    pub fn try_new(rows: Vec<usize>, columns: Vec<usize>, grid: Grid) -> Result<Self, Error> {
+        let exactly = |clues: Vec<usize>| clues.into_iter().map(Clue::exactly).collect();
+        Self::try_new_ranged(exactly(rows), exactly(columns), grid)
+    }
+
+    /// Create a new `Board` where some clues may be omitted.
+    ///
+    /// An omitted clue, given as `None`, constrains nothing.  The total
+    /// number of [`Camp`]s is the sum of the row clues if none are
+    /// omitted, otherwise the sum of the column clues if none of those
+    /// are, and otherwise the number of [`Tree`]s.
+    ///
+    /// # Panics
+    ///
+    /// This will `panic` if the [`Grid`] is the wrong size.  See
+    /// [`Board::try_new_partial`].
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Grid`]: struct.Grid.html
+    /// [`Board::try_new_partial`]: struct.Board.html#method.try_new_partial
+    pub fn new_partial(rows: Vec<Option<usize>>, columns: Vec<Option<usize>>, grid: Grid) -> Self {
+        match Self::try_new_partial(rows, columns, grid) {
+            Ok(board) => board,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Create a new `Board` where some clues may be omitted, checking
+    /// the size of the [`Grid`].
+    ///
+    /// See [`Board::new_partial`].  A `Board` without omitted clues is
+    /// the same as one made by [`Board::try_new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, Grid};
+    /// let grid = Grid::parse(" T\n  ").unwrap();
+    /// let board = Board::try_new_partial(vec![Some(1), None], vec![None, None], grid).unwrap();
+    /// assert_eq!(board.row_clue(0), Some(1));
+    /// assert_eq!(board.row_clue(1), None);
+    /// assert_eq!(board.total(), 1);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`Board::try_new`].
+    ///
+    /// [`Grid`]: struct.Grid.html
+    /// [`Board::new_partial`]: struct.Board.html#method.new_partial
+    /// [`Board::try_new`]: struct.Board.html#method.try_new
+    pub fn try_new_partial(
+        rows: Vec<Option<usize>>,
+        columns: Vec<Option<usize>>,
+        grid: Grid,
+    ) -> Result<Self, Error> {
+        let rows = rows.into_iter().map(Clue::from).collect();
+        let columns = columns.into_iter().map(Clue::from).collect();
+        Self::try_new_ranged(rows, columns, grid)
+    }
+
+    /// Create a new `Board` whose clues may be ranges or bounds, such
+    /// as "at most 2".
+    ///
+    /// The total number of [`Camp`]s is the sum of the row clues if
+    /// they are all single numbers, otherwise the sum of the column
+    /// clues if those are, and otherwise the number of [`Tree`]s.  Call
+    /// [`Board::set_total`] afterwards if the [`Grid`]'s [`RuleSet`]
+    /// has `no_matching` set, since then the [`Tree`]s don't give it.
+    ///
+    /// # Panics
+    ///
+    /// This will `panic` if the [`Grid`] is the wrong size.  See
+    /// [`Board::try_new_ranged`].
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Grid`]: struct.Grid.html
+    /// [`RuleSet`]: struct.RuleSet.html
+    /// [`Board::try_new_ranged`]: struct.Board.html#method.try_new_ranged
+    /// [`Board::set_total`]: struct.Board.html#method.set_total
+    pub fn new_ranged(rows: Vec<Clue>, columns: Vec<Clue>, grid: Grid) -> Self {
+        match Self::try_new_ranged(rows, columns, grid) {
+            Ok(board) => board,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Create a new `Board` whose clues may be ranges or bounds,
+    /// checking the size of the [`Grid`].
+    ///
+    /// See [`Board::new_ranged`].  A `Board` whose clues are all
+    /// single numbers is the same as one made by [`Board::try_new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, Clue, Grid};
+    /// let grid = Grid::parse(" T\n  ").unwrap();
+    /// let rows = vec![Clue::at_most(1), Clue::at_least(1)];
+    /// let columns = vec![Clue::exactly(0), Clue::exactly(1)];
+    /// let mut board = Board::try_new_ranged(rows, columns, grid).unwrap();
+    /// assert_eq!(board.row_range(1), Clue::at_least(1));
+    /// assert_eq!(board.row_clue(1), None);
+    /// assert_eq!(board.total(), 1);
+    /// board.solve().unwrap();
+    /// assert_eq!(board.debug(), "-T\n-C");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`Board::try_new`].
+    ///
+    /// [`Grid`]: struct.Grid.html
+    /// [`Board::new_ranged`]: struct.Board.html#method.new_ranged
+    /// [`Board::try_new`]: struct.Board.html#method.try_new
+    pub fn try_new_ranged(rows: Vec<Clue>, columns: Vec<Clue>, grid: Grid) -> Result<Self, Error> {
+        if grid.num_rows() != rows.len() || grid.num_columns() != columns.len() {
+            Err(Error::DimensionMismatch {
+                rows: rows.len(),
+                columns: columns.len(),
+                grid_rows: grid.num_rows(),
+                grid_columns: grid.num_columns(),
+            })?
+        }
+        let mut board = Self::new_clue_free(grid.count(Tile::Tree), grid);
+        board.row_ranges = rows;
+        board.column_ranges = columns;
+        Ok(board)
+    }
+
+    /// Create a new clue-free `Board`.
+    ///
+    /// Clue-free `Board`s have no row or column clues.  Only the
+    /// `total` number of [`Camp`]s is known.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub fn new_clue_free(total: usize, grid: Grid) -> Self {
         Board {
-            rows,
-            columns,
+            total,
+            counts: Counts::new(&grid),
+            limits: SolverLimits::default(),
+            observer: None,
+            history: Vec::new(),
+            stats: None,
+            queue: None,
+            deadline: None,
+            strategy: None,
+            possibilities: Counter::default(),
             grid,
+            row_ranges: Vec::new(),
+            column_ranges: Vec::new(),
             marker: PhantomData,
         }
     }
 
-    /// Create a new `Board` by parsing a string as the [`Grid`].
-    ///
-    /// This method wraps a call to [`Grid::parse`] and [`Board::new`].
-    ///
-    /// # Panics
-    ///
-    /// See [`Board::new`].
-    ///
-    /// [`Grid`]: struct.Grid.html
-    /// [`Grid::parse`]: struct.Grid.html#method.parse
-    /// [`Board::new`]: struct.Board.html#method.new
-    pub fn new_parse(rows: Vec<usize>, columns: Vec<usize>, s: &str) -> Result<Self, String> {
-        Ok(Self::new(rows, columns, Grid::parse(s)?))
+    /// Create a new `Board` by parsing a string as the [`Grid`].
+    ///
+    /// This method wraps a call to [`Grid::parse`] and
+    /// [`Board::try_new`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Grid::parse`] and [`Board::try_new`].
+    ///
+    /// [`Grid`]: struct.Grid.html
+    /// [`Grid::parse`]: struct.Grid.html#method.parse
+    /// [`Board::try_new`]: struct.Board.html#method.try_new
+    pub fn new_parse(rows: Vec<usize>, columns: Vec<usize>, s: &str) -> Result<Self, Error> {
+        Self::try_new(rows, columns, Grid::parse(s)?)
+    }
+
+    /// Create a new `Board` where some clues may be omitted by parsing
+    /// a string as the [`Grid`].
+    ///
+    /// This method wraps a call to [`Grid::parse`] and
+    /// [`Board::try_new_partial`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Grid::parse`] and [`Board::try_new_partial`].
+    ///
+    /// [`Grid`]: struct.Grid.html
+    /// [`Grid::parse`]: struct.Grid.html#method.parse
+    /// [`Board::try_new_partial`]: struct.Board.html#method.try_new_partial
+    pub fn new_parse_partial(
+        rows: Vec<Option<usize>>,
+        columns: Vec<Option<usize>>,
+        s: &str,
+    ) -> Result<Self, Error> {
+        Self::try_new_partial(rows, columns, Grid::parse(s)?)
+    }
+
+    /// Create a new `Board` whose clues may be ranges or bounds by
+    /// parsing a string as the [`Grid`].
+    ///
+    /// This method wraps a call to [`Grid::parse`] and
+    /// [`Board::try_new_ranged`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Grid::parse`] and [`Board::try_new_ranged`].
+    ///
+    /// [`Grid`]: struct.Grid.html
+    /// [`Grid::parse`]: struct.Grid.html#method.parse
+    /// [`Board::try_new_ranged`]: struct.Board.html#method.try_new_ranged
+    pub fn new_parse_ranged(rows: Vec<Clue>, columns: Vec<Clue>, s: &str) -> Result<Self, Error> {
+        Self::try_new_ranged(rows, columns, Grid::parse(s)?)
+    }
+
+    /// Create a new clue-free `Board` by parsing a string as the [`Grid`].
+    ///
+    /// This method wraps a call to [`Grid::parse`] and
+    /// [`Board::new_clue_free`].
+    ///
+    /// [`Grid`]: struct.Grid.html
+    /// [`Grid::parse`]: struct.Grid.html#method.parse
+    /// [`Board::new_clue_free`]: struct.Board.html#method.new_clue_free
+    pub fn new_parse_clue_free(total: usize, s: &str) -> Result<Self, Error> {
+        Ok(Self::new_clue_free(total, Grid::parse(s)?))
+    }
+
+    /// Create a new `Board` with a blank [`Grid`] of the correct size.
+    ///
+    /// This method wraps a call to [`Grid::blank`] and [`Board::new`].
+    ///
+    /// [`Grid`]: struct.Grid.html
+    /// [`Grid::parse`]: struct.Grid.html#method.parse
+    /// [`Board::new`]: struct.Board.html#method.new
+    pub fn new_blank(rows: Vec<usize>, columns: Vec<usize>) -> Self {
+        let grid = Grid::blank(rows.len(), columns.len());
+        Self::new(rows, columns, grid)
+    }
+
+    /// Is this a clue-free `Board`?
+    ///
+    /// See [`Board::new_clue_free`].
+    ///
+    /// [`Board::new_clue_free`]: struct.Board.html#method.new_clue_free
+    pub fn is_clue_free(&self) -> bool {
+        self.row_ranges.is_empty() && self.column_ranges.is_empty()
+    }
+
+    /// Does this `Board` leave out any row or column clues?
+    ///
+    /// This is true of clue-free `Board`s, which leave them all out.
+    /// See [`Board::new_partial`].
+    ///
+    /// [`Board::new_partial`]: struct.Board.html#method.new_partial
+    pub fn has_omitted_clues(&self) -> bool {
+        self.is_clue_free()
+            || self
+                .row_ranges
+                .iter()
+                .chain(&self.column_ranges)
+                .any(|x| x.is_any())
+    }
+
+    /// Is every row and column clue a single number?
+    ///
+    /// Only then do the clues alone say how many [`Camp`]s there are.
+    /// This is false of clue-free `Board`s and of those with omitted
+    /// or range clues.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub fn has_exact_clues(&self) -> bool {
+        !self.is_clue_free()
+            && self
+                .row_ranges
+                .iter()
+                .chain(&self.column_ranges)
+                .all(|x| x.exact().is_some())
+    }
+
+    /// The [`Clue`] of `row`.
+    ///
+    /// Every clue of a clue-free `Board` is [`Clue::any`].
+    ///
+    /// [`Clue`]: struct.Clue.html
+    /// [`Clue::any`]: struct.Clue.html#method.any
+    pub fn row_range(&self, row: usize) -> Clue {
+        self.row_ranges.get(row).cloned().unwrap_or_default()
+    }
+
+    /// The [`Clue`] of `column`.
+    ///
+    /// Every clue of a clue-free `Board` is [`Clue::any`].
+    ///
+    /// [`Clue`]: struct.Clue.html
+    /// [`Clue::any`]: struct.Clue.html#method.any
+    pub fn column_range(&self, column: usize) -> Clue {
+        self.column_ranges.get(column).cloned().unwrap_or_default()
+    }
+
+    /// The [`Clue`] of every row.
+    ///
+    /// This is empty for clue-free `Board`s.
+    ///
+    /// [`Clue`]: struct.Clue.html
+    pub fn row_ranges(&self) -> Vec<Clue> {
+        self.row_ranges.clone()
+    }
+
+    /// The [`Clue`] of every column.
+    ///
+    /// This is empty for clue-free `Board`s.
+    ///
+    /// [`Clue`]: struct.Clue.html
+    pub fn column_ranges(&self) -> Vec<Clue> {
+        self.column_ranges.clone()
+    }
+
+    /// The clue of `row`, or `None` if it is omitted or isn't a single
+    /// number.
+    ///
+    /// Every clue of a clue-free `Board` is omitted.
+    pub fn row_clue(&self, row: usize) -> Option<usize> {
+        self.row_range(row).exact()
+    }
+
+    /// The clue of `column`, or `None` if it is omitted or isn't a
+    /// single number.
+    ///
+    /// Every clue of a clue-free `Board` is omitted.
+    pub fn column_clue(&self, column: usize) -> Option<usize> {
+        self.column_range(column).exact()
+    }
+
+    /// The clue of every row, with `None` for those omitted or that
+    /// aren't a single number.
+    ///
+    /// This is empty for clue-free `Board`s.
+    pub fn row_clues(&self) -> Vec<Option<usize>> {
+        self.row_ranges.iter().map(|x| x.exact()).collect()
+    }
+
+    /// The clue of every column, with `None` for those omitted or that
+    /// aren't a single number.
+    ///
+    /// This is empty for clue-free `Board`s.
+    pub fn column_clues(&self) -> Vec<Option<usize>> {
+        self.column_ranges.iter().map(|x| x.exact()).collect()
+    }
+
+    /// The total number of [`Camp`]s on the `Board`.
+    ///
+    /// This is the sum of the row clues if they are all single
+    /// numbers, otherwise the sum of the column clues if those are,
+    /// and otherwise the total the `Board` was made with or given by
+    /// [`Board::set_total`].
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Board::set_total`]: struct.Board.html#method.set_total
+    pub fn total(&self) -> usize {
+        if self.is_clue_free() {
+            return self.total;
+        }
+        let exact_sum = |clues: &[Clue]| clues.iter().map(|x| x.exact()).sum::<Option<usize>>();
+        exact_sum(&self.row_ranges)
+            .or_else(|| exact_sum(&self.column_ranges))
+            .unwrap_or(self.total)
+    }
+
+    /// Set the total number of [`Camp`]s on the `Board`.
+    ///
+    /// This only matters when neither the row nor the column clues are
+    /// all single numbers.  See [`Board::total`].
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Board::total`]: struct.Board.html#method.total
+    pub fn set_total(&mut self, total: usize) {
+        self.total = total;
+    }
+
+    /// Set the `Tile` at `pos` to `tile`, returning the old `Tile`.
+    ///
+    /// This keeps the counts used by [`Board::count_in_row`],
+    /// [`Board::count_in_column`], and [`Board::count`] up to date, and
+    /// tells the [`Board::observer`] if the `Tile` changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, Pos, Tile::*};
+    /// let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+    /// assert_eq!(board.set(Pos::new(0, 0), Camp), Unassigned);
+    /// assert_eq!(board.count_in_row(0, Camp), 1);
+    /// assert_eq!(board.debug(), "CT\n  ");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This will `panic` if `pos` is outside the `Board`.
+    ///
+    /// [`Board::count_in_row`]: struct.Board.html#method.count_in_row
+    /// [`Board::count_in_column`]: struct.Board.html#method.count_in_column
+    /// [`Board::count`]: struct.Board.html#method.count
+    /// [`Board::observer`]: struct.Board.html#structfield.observer
+    pub fn set(&mut self, pos: Pos, tile: Tile) -> Tile {
+        let old = self.grid[pos];
+        self.grid[pos] = tile;
+        self.counts.update(pos, old, tile);
+        if old != tile {
+            if let Some(ref mut queue) = self.queue {
+                queue.push_tile(pos, tile, self.grid.rules().wrap);
+            }
+            if let Some(ref observer) = self.observer {
+                observer.on_tile_set(pos, old, tile, self.strategy);
+            }
+            #[cfg(feature = "logging")]
+            trace!(
+                "{}: row {}, column {}: '{:?}' -> '{:?}'",
+                self.strategy.unwrap_or("set"),
+                pos.row,
+                pos.column,
+                old,
+                tile
+            );
+        }
+        old
+    }
+
+    /// Play the `Board` by `rules` from now on.
+    ///
+    /// See [`Grid::set_rules`].  Every row and column is queued again
+    /// while solving, since the rules change what each line allows.
+    ///
+    /// [`Grid::set_rules`]: struct.Grid.html#method.set_rules
+    pub fn set_rules(&mut self, rules: RuleSet) {
+        self.grid.set_rules(rules);
+        self.queue_all_lines();
+    }
+
+    /// Set the `Tile` at `pos` to a [`Camp`] like [`Grid::set_camp`],
+    /// going through [`Board::set`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Grid::set_camp`].
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Grid::set_camp`]: struct.Grid.html#method.set_camp
+    /// [`Board::set`]: struct.Board.html#method.set
+    pub fn set_camp(&mut self, pos: Pos) -> Result<(), Error> {
+        if !self.can_place_camp(pos) {
+            // Report the conflict.  This doesn't modify the `Grid`.
+            return self.grid.set_camp(pos);
+        }
+        self.set(pos, Tile::Camp);
+        for x in self.touching(pos) {
+            if self[x] == Tile::Unassigned {
+                self.set(x, Tile::Grass);
+            }
+        }
+        Ok(())
+    }
+
+    /// Set the `Tile` at `(row, column)` to `tile` if the rules of the
+    /// game allow it, returning the old `Tile`.
+    ///
+    /// Unlike [`Board::set`], this never places a [`Camp`] that
+    /// touches another [`Camp`] or goes over a clue, and never adds or
+    /// removes a [`Tree`].  Nothing else is filled in, unlike
+    /// [`Board::set_camp`].  Setting a `Tile` to what it already is
+    /// does nothing.
+    ///
+    /// Each change is added to [`Board::history`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, Tile::*};
+    /// let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+    /// assert_eq!(board.place(0, 0, Camp), Ok(Unassigned));
+    /// assert!(board.place(1, 0, Camp).is_err());
+    /// assert!(board.place(0, 1, Grass).is_err());
+    /// assert_eq!(board.debug(), "CT\n  ");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::TreeChanged`] if `tile` or the old `Tile` is a
+    ///   [`Tree`].
+    /// * [`Error::BlockedChanged`] if `tile` or the old `Tile` is
+    ///   [`Blocked`].
+    /// * [`Error::CampConflict`] if a [`Camp`] would touch another
+    ///   [`Camp`], possibly diagonally.
+    /// * [`Error::ClueExceeded`] if a [`Camp`] would put its row, its
+    ///   column or the `Board` over its clue.
+    ///
+    /// The `Board` is not modified on an error.
+    ///
+    /// # Panics
+    ///
+    /// This will `panic` if `(row, column)` is outside the `Board`.
+    ///
+    /// [`Board::set`]: struct.Board.html#method.set
+    /// [`Board::set_camp`]: struct.Board.html#method.set_camp
+    /// [`Board::history`]: struct.Board.html#structfield.history
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Blocked`]: enum.Tile.html#variant.Blocked
+    /// [`Error::TreeChanged`]: enum.Error.html#variant.TreeChanged
+    /// [`Error::BlockedChanged`]: enum.Error.html#variant.BlockedChanged
+    /// [`Error::CampConflict`]: enum.Error.html#variant.CampConflict
+    /// [`Error::ClueExceeded`]: enum.Error.html#variant.ClueExceeded
+    pub fn place(&mut self, row: usize, column: usize, tile: Tile) -> Result<Tile, Error> {
+        let pos = Pos::new(row, column);
+        let old = self[pos];
+        if old == tile {
+            return Ok(old);
+        }
+        if old == Tile::Tree || tile == Tile::Tree {
+            return Err(Error::TreeChanged { pos });
+        }
+        if old == Tile::Blocked || tile == Tile::Blocked {
+            return Err(Error::BlockedChanged { pos });
+        }
+        if tile == Tile::Camp {
+            if !self.can_place_camp(pos) {
+                // Report the conflict.  This doesn't modify the `Grid`.
+                self.grid.set_camp(pos)?;
+            }
+            if let Some(reason) = self.clue_exceeded(pos) {
+                return Err(Error::ClueExceeded { pos, reason });
+            }
+        }
+        self.set(pos, tile);
+        self.history.push(Move { pos, old, new: tile });
+        Ok(old)
+    }
+
+    /// Which clue would another [`Camp`] at `pos` put over, if any?
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    fn clue_exceeded(&self, pos: Pos) -> Option<Contradiction> {
+        let (row_camps, column_camps) = (
+            self.count_in_row(pos.row, Tile::Camp),
+            self.count_in_column(pos.column, Tile::Camp),
+        );
+        if self.row_range(pos.row).is_full(row_camps) {
+            Some(Contradiction::RowOverfilled)
+        } else if self.column_range(pos.column).is_full(column_camps) {
+            Some(Contradiction::ColumnOverfilled)
+        } else if self.count(Tile::Camp) >= self.total() {
+            Some(Contradiction::TotalOverfilled)
+        } else {
+            None
+        }
+    }
+
+    /// Get the cells beside the [`Tree`] at `pos` where its [`Camp`]
+    /// could go right now.
+    ///
+    /// These are the [`Unassigned`] neighbors of `pos`, in the order
+    /// of [`Grid::neighbors4`], that don't touch a [`Camp`] and whose
+    /// row, column and `Board` aren't already full.  Only the current
+    /// `Tile`s are looked at, so a candidate may still turn out wrong.
+    ///
+    /// # Examples
+    ///
+    /// The top row has no room left and the bottom left [`Camp`] rules
+    /// out two more cells:
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, Pos};
+    /// let board = Board::new_parse(vec![0, 1, 1], vec![1, 0, 1], "   \n T \nC T").unwrap();
+    /// assert_eq!(board.camp_candidates_for_tree(Pos::new(1, 1)), vec![(1, 2)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This will `panic` if `pos` is outside the `Board`.
+    ///
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    /// [`Grid::neighbors4`]: struct.Grid.html#method.neighbors4
+    pub fn camp_candidates_for_tree(&self, pos: Pos) -> Vec<Pos> {
+        self.neighbors4(pos)
+            .into_iter()
+            .filter(|&x| {
+                self[x] == Tile::Unassigned
+                    && self.can_place_camp(x)
+                    && self.clue_exceeded(x).is_none()
+            })
+            .collect()
+    }
+
+    /// Set the `Tile` at `(row, column)` back to [`Unassigned`],
+    /// returning the old `Tile`.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error::TreeChanged`] is returned if there is a [`Tree`] at
+    /// `(row, column)`.
+    ///
+    /// # Panics
+    ///
+    /// This will `panic` if `(row, column)` is outside the `Board`.
+    ///
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Error::TreeChanged`]: enum.Error.html#variant.TreeChanged
+    pub fn clear(&mut self, row: usize, column: usize) -> Result<Tile, Error> {
+        self.place(row, column, Tile::Unassigned)
+    }
+
+    /// Revert the last move in [`Board::history`], returning it.
+    ///
+    /// Returns `None` if there are no moves to undo.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, Tile::*};
+    /// let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+    /// board.place(0, 0, Camp).unwrap();
+    /// assert_eq!(board.undo().map(|x| x.new), Some(Camp));
+    /// assert_eq!(board.debug(), " T\n  ");
+    /// assert_eq!(board.undo(), None);
+    /// ```
+    ///
+    /// [`Board::history`]: struct.Board.html#structfield.history
+    pub fn undo(&mut self) -> Option<Move> {
+        let last = self.history.pop()?;
+        self.set(last.pos, last.old);
+        Some(last)
+    }
+
+    /// Get the number of `Tile`s equal to `tile` in the given row.
+    ///
+    /// This is the same as [`Grid::count_in_row`] but takes constant
+    /// time.
+    ///
+    /// # Panics
+    ///
+    /// This will `panic` if `row >= num_rows()`.
+    ///
+    /// [`Grid::count_in_row`]: struct.Grid.html#method.count_in_row
+    pub fn count_in_row(&self, row: usize, tile: Tile) -> usize {
+        self.counts.row(row, tile)
+    }
+
+    /// Get the number of `Tile`s equal to `tile` in the given column.
+    ///
+    /// This is the same as [`Grid::count_in_column`] but takes
+    /// constant time.
+    ///
+    /// # Panics
+    ///
+    /// This will `panic` if `column >= num_columns()`.
+    ///
+    /// [`Grid::count_in_column`]: struct.Grid.html#method.count_in_column
+    pub fn count_in_column(&self, column: usize, tile: Tile) -> usize {
+        self.counts.column(column, tile)
+    }
+
+    /// Get the number of `Tile`s equal to `tile` on the whole `Board`.
+    ///
+    /// This is the same as [`Grid::count`] but takes constant time.
+    ///
+    /// [`Grid::count`]: struct.Grid.html#method.count
+    pub fn count(&self, tile: Tile) -> usize {
+        self.counts.total(tile)
+    }
+
+    /// Check that the `Board` is a sensible puzzle before solving it.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::ClueSumMismatch`] if the row clues and column clues
+    ///   add up to different totals.  This is only checked when every
+    ///   clue is a single number.
+    /// * [`Error::TreeCountMismatch`] if the number of [`Tree`]s
+    ///   differs from the number of [`Camp`]s required.
+    /// * [`Error::CampConflict`] if two [`Camp`]s already touch.
+    /// * [`Error::IsolatedTree`] if a [`Tree`] has no neighbors a
+    ///   [`Camp`] could go on.  See [`Grid::orphaned_trees`].
+    ///
+    /// [`Tree`]s aren't checked if the [`RuleSet`] has `no_matching`
+    /// set.
+    ///
+    /// [`Error::ClueSumMismatch`]: enum.Error.html#variant.ClueSumMismatch
+    /// [`Error::TreeCountMismatch`]: enum.Error.html#variant.TreeCountMismatch
+    /// [`Error::CampConflict`]: enum.Error.html#variant.CampConflict
+    /// [`Error::IsolatedTree`]: enum.Error.html#variant.IsolatedTree
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Grid::orphaned_trees`]: struct.Grid.html#method.orphaned_trees
+    /// [`RuleSet`]: struct.RuleSet.html
+    pub fn validate(&self) -> Result<(), Error> {
+        use tile::Tile::*;
+        if self.has_exact_clues() {
+            let sum = |clues: &[Clue]| clues.iter().filter_map(|x| x.exact()).sum();
+            let rows = sum(&self.row_ranges);
+            let columns = sum(&self.column_ranges);
+            if rows != columns {
+                Err(Error::ClueSumMismatch { rows, columns })?
+            }
+        }
+        if let Some(pos) = self.find_adjacent_camps() {
+            let mut touching = self.touching(pos).into_iter();
+            let conflict = touching.find(|&x| self[x] == Camp).unwrap();
+            Err(Error::CampConflict { pos, conflict })?
+        }
+        if self.rules().no_matching {
+            return Ok(());
+        }
+        let trees = self.count(Tree);
+        if trees != self.total() {
+            Err(Error::TreeCountMismatch {
+                trees,
+                camps: self.total(),
+            })?
+        }
+        if let Some(&pos) = self.orphaned_trees().first() {
+            Err(Error::IsolatedTree { pos })?
+        }
+        Ok(())
+    }
+
+    /// Solve the `Board` in place.
+    ///
+    /// This wraps [`Board::solve_outcome`], converting its result
+    /// into a `Result`.
+    ///
+    /// # Errors
+    ///
+    /// If the `Board` cannot be solved automatically, an
+    /// [`Error::Unsolved`] is returned.  The `Board` will be
+    /// populated with as much information as can be deduced
+    /// automatically.
+    ///
+    /// If the `Board` breaks the rules, an [`Error::Contradiction`]
+    /// is returned instead.
+    ///
+    /// If the solution breaks a rule anyway, the solver has a bug and
+    /// [`Error::InvalidSolution`] is returned.  See
+    /// [`SolverConfig::verify`].
+    ///
+    /// [`Board::solve_outcome`]: struct.Board.html#method.solve_outcome
+    /// [`Error::Unsolved`]: enum.Error.html#variant.Unsolved
+    /// [`Error::Contradiction`]: enum.Error.html#variant.Contradiction
+    /// [`Error::InvalidSolution`]: enum.Error.html#variant.InvalidSolution
+    /// [`SolverConfig::verify`]: struct.SolverConfig.html#structfield.verify
+    pub fn solve(&mut self) -> Result<(), Error> {
+        self.solve_with(&SolverConfig::default())
+    }
+
+    /// Solve the `Board` in place like [`Board::solve`], using the
+    /// settings in `config`.
+    ///
+    /// # Errors
+    ///
+    /// See [`Board::solve`].
+    ///
+    /// [`Board::solve`]: struct.Board.html#method.solve
+    pub fn solve_with(&mut self, config: &SolverConfig) -> Result<(), Error> {
+        let result: Result<(), Error> = self.solve_outcome_with(config).into();
+        result?;
+        if config.verify {
+            self.check_solution()?;
+        }
+        Ok(())
+    }
+
+    /// Return an [`Error::InvalidSolution`] if the solved `Board`
+    /// breaks a rule.
+    ///
+    /// [`Error::InvalidSolution`]: enum.Error.html#variant.InvalidSolution
+    fn check_solution(&self) -> Result<(), Error> {
+        let violations = self.verify_solution();
+        if !violations.is_empty() {
+            Err(Error::InvalidSolution { violations })?
+        }
+        Ok(())
+    }
+
+    /// Solve the `Board` in place like [`Board::solve`], running
+    /// `strategies` in order instead of the usual ones.
+    ///
+    /// # Errors
+    ///
+    /// See [`Board::solve`].
+    ///
+    /// [`Board::solve`]: struct.Board.html#method.solve
+    pub fn solve_using(&mut self, strategies: &[Box<dyn Strategy>]) -> Result<(), Error> {
+        self.solve_outcome_using(strategies).into()
+    }
+
+    /// Solve the `Board` in place, reporting how far the solver got.
+    ///
+    /// Before every pass the `Board` is checked for broken rules via
+    /// [`find_contradiction`].  If one is found, solving stops and
+    /// [`SolveOutcome::Contradiction`] is returned.
+    ///
+    /// [`find_contradiction`]: fn.find_contradiction.html
+    /// [`SolveOutcome::Contradiction`]: enum.SolveOutcome.html#variant.Contradiction
+    pub fn solve_outcome(&mut self) -> SolveOutcome {
+        self.solve_outcome_with(&SolverConfig::default())
+    }
+
+    /// Solve the `Board` in place like [`Board::solve_outcome`], using
+    /// the settings in `config`.
+    ///
+    /// [`Board::solve_outcome`]: struct.Board.html#method.solve_outcome
+    pub fn solve_outcome_with(&mut self, config: &SolverConfig) -> SolveOutcome {
+        self.solve_strategies(&default_strategies(config), config.collect_stats)
+    }
+
+    /// Solve the `Board` in place like [`Board::solve_outcome`],
+    /// running `strategies` in order instead of the usual ones.
+    ///
+    /// [`Board::solve_outcome`]: struct.Board.html#method.solve_outcome
+    pub fn solve_outcome_using(&mut self, strategies: &[Box<dyn Strategy>]) -> SolveOutcome {
+        self.solve_strategies(strategies, false)
+    }
+
+    /// The [`SolveStats`] of the last solve that collected them.
+    ///
+    /// See [`SolverConfig::collect_stats`].
+    ///
+    /// [`SolveStats`]: struct.SolveStats.html
+    /// [`SolverConfig::collect_stats`]: struct.SolverConfig.html#structfield.collect_stats
+    pub fn stats(&self) -> Option<&SolveStats> {
+        self.stats.as_ref()
+    }
+
+    /// Run `strategies`, collecting [`SolveStats`] if `collect_stats`
+    /// is set, and report how far they got.
+    ///
+    /// [`SolveStats`]: struct.SolveStats.html
+    fn solve_strategies(
+        &mut self,
+        strategies: &[Box<dyn Strategy>],
+        collect_stats: bool,
+    ) -> SolveOutcome {
+        #[cfg(feature = "tracing")]
+        let _span = ::tracing::info_span!(
+            "solve",
+            rows = self.num_rows(),
+            columns = self.num_columns(),
+            remaining = self.count(Tile::Unassigned)
+        )
+        .entered();
+        self.queue = Some(WorkQueue::new(self.num_rows(), self.num_columns()));
+        let mut stats = if collect_stats {
+            Some(SolveStats::new(strategies))
+        } else {
+            None
+        };
+        self.deadline = self
+            .limits
+            .max_duration
+            .and_then(|duration| Instant::now().checked_add(duration));
+        self.possibilities.reset();
+        let result = self.run_strategies(strategies, &mut stats);
+        self.queue = None;
+        self.deadline = None;
+        if let Some(mut stats) = stats {
+            stats.possibilities = self.possibilities.get();
+            self.stats = Some(stats);
+        }
+        let stopped = match result {
+            Ok(stopped) => stopped,
+            Err((cell, reason)) => return SolveOutcome::Contradiction { cell, reason },
+        };
+        if let Some((cell, reason)) = find_contradiction(self) {
+            SolveOutcome::Contradiction { cell, reason }
+        } else if self.is_solved() {
+            SolveOutcome::Solved
+        } else if stopped {
+            SolveOutcome::Stopped {
+                partial: self.grid.clone(),
+            }
+        } else {
+            SolveOutcome::Stuck {
+                partial: self.grid.clone(),
+            }
+        }
+    }
+
+    /// Run `strategies` until none of them make progress.
+    ///
+    /// Whenever a strategy changes something, they start again from
+    /// the first one.  Each pass is recorded in `stats`, if any.
+    ///
+    /// Returns whether the [`SolverLimits`] stopped them early.
+    ///
+    /// [`SolverLimits`]: struct.SolverLimits.html
+    fn run_strategies(
+        &mut self,
+        strategies: &[Box<dyn Strategy>],
+        stats: &mut Option<SolveStats>,
+    ) -> Result<bool, (Pos, Contradiction)> {
+        use initialize_grass::*;
+        self.strategy = Some("initialize_grass");
+        if initialize_grass(self) {
+            self.notify_step("initialize_grass");
+        }
+        self.strategy = None;
+        let mut iterations = 0;
+        'solve: loop {
+            if let Some((cell, reason)) = find_contradiction(self) {
+                #[cfg(feature = "logging")]
+                debug!("contradiction at {:?}: {}", cell, reason);
+                return Err((cell, reason));
+            }
+            if let Some(stopped) = self.solve_regions(strategies, stats, iterations)? {
+                return Ok(stopped);
+            }
+            if self
+                .limits
+                .max_iterations
+                .is_some_and(|max| iterations >= max)
+            {
+                #[cfg(feature = "logging")]
+                debug!("stopped after {} iterations", iterations);
+                return Ok(true);
+            }
+            iterations += 1;
+            if let Some(stats) = stats.as_mut() {
+                stats.iterations = iterations;
+            }
+            #[cfg(feature = "tracing")]
+            let _span = ::tracing::debug_span!(
+                "iteration",
+                iteration = iterations,
+                remaining = self.count(Tile::Unassigned)
+            )
+            .entered();
+            for (index, strategy) in strategies.iter().enumerate() {
+                if self.should_stop() {
+                    #[cfg(feature = "logging")]
+                    debug!("stopped before {}", strategy.name());
+                    return Ok(true);
+                }
+                let unassigned = self.count(Tile::Unassigned);
+                let start = stats.as_ref().map(|_| Instant::now());
+                self.strategy = Some(strategy.name());
+                let result = {
+                    #[cfg(feature = "tracing")]
+                    let _span = ::tracing::debug_span!(
+                        "strategy",
+                        name = strategy.name(),
+                        remaining = self.count(Tile::Unassigned)
+                    )
+                    .entered();
+                    strategy.apply(self)
+                };
+                self.strategy = None;
+                let tiles_changed = unassigned.saturating_sub(self.count(Tile::Unassigned));
+                if let (Some(stats), Some(start)) = (stats.as_mut(), start) {
+                    stats.record(index, tiles_changed, start.elapsed());
+                }
+                match result {
+                    StepResult::Unchanged => {
+                        #[cfg(feature = "logging")]
+                        trace!("{} made no progress", strategy.name());
+                    }
+                    StepResult::Changed => {
+                        #[cfg(feature = "logging")]
+                        debug!("{} made progress", strategy.name());
+                        if tiles_changed > 0 {
+                            self.notify_step(strategy.name());
+                        }
+                        continue 'solve;
+                    }
+                    StepResult::Contradiction { cell, reason } => {
+                        #[cfg(feature = "logging")]
+                        debug!(
+                            "{} found a contradiction at {:?}: {}",
+                            strategy.name(),
+                            cell,
+                            reason
+                        );
+                        return Err((cell, reason));
+                    }
+                }
+            }
+            return Ok(false);
+        }
+    }
+
+    /// Solve each of the [`Grid::independent_regions`] on its own
+    /// with `strategies`, if there is more than one.
+    ///
+    /// Each region is solved as a [`Board::sub_board`], so searches
+    /// through the ways of placing [`Camp`]s only ever see one region.
+    /// What they deduce is then set here, in the order it was deduced,
+    /// so the [`Board::observer`] and `stats` hear about it as if the
+    /// whole `Board` had been solved.  Under [`RuleSet::no_matching`]
+    /// the regions share the `total`, so the `Board` isn't split.
+    ///
+    /// The regions share what is left of [`SolverLimits::max_iterations`]
+    /// after the `iterations` already made, in the order they are
+    /// solved.
+    ///
+    /// Returns `None` if the `Board` wasn't split, and otherwise
+    /// whether the [`SolverLimits`] stopped solving early.  A
+    /// [`Contradiction`] found in a region is returned as an error.
+    ///
+    /// [`Grid::independent_regions`]: struct.Grid.html#method.independent_regions
+    /// [`Board::sub_board`]: struct.Board.html#method.sub_board
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Board::observer`]: struct.Board.html#structfield.observer
+    /// [`RuleSet::no_matching`]: struct.RuleSet.html#structfield.no_matching
+    /// [`SolverLimits::max_iterations`]: struct.SolverLimits.html#structfield.max_iterations
+    /// [`SolverLimits`]: struct.SolverLimits.html
+    /// [`Contradiction`]: enum.Contradiction.html
+    fn solve_regions(
+        &mut self,
+        strategies: &[Box<dyn Strategy>],
+        stats: &mut Option<SolveStats>,
+        mut iterations: usize,
+    ) -> Result<Option<bool>, (Pos, Contradiction)> {
+        if self.rules().no_matching {
+            return Ok(None);
+        }
+        let regions = self.independent_regions();
+        if regions.len() < 2 {
+            return Ok(None);
+        }
+        let mut stopped = false;
+        for (rows, columns) in regions {
+            let mut region = self.sub_board(rows.clone(), columns.clone());
+            region.limits.max_duration = self
+                .deadline
+                .map(|deadline| deadline.saturating_duration_since(Instant::now()));
+            region.limits.max_iterations = self
+                .limits
+                .max_iterations
+                .map(|max| max.saturating_sub(iterations));
+            let recorder = Arc::new(StepRecorder::default());
+            region.observer = Some(recorder.clone());
+            let collect_stats = stats.is_some() || self.limits.max_iterations.is_some();
+            let outcome = region.solve_strategies(strategies, collect_stats);
+            region.observer = None;
+            self.record_possibilities(region.possibilities.get());
+            let region_stats = region.stats.unwrap_or_default();
+            iterations += region_stats.iterations;
+            if let Some(stats) = stats.as_mut() {
+                stats.iterations += region_stats.iterations;
+                for (total, region) in stats.strategies.iter_mut().zip(region_stats.strategies) {
+                    total.passes += region.passes;
+                    total.tiles_changed += region.tiles_changed;
+                    total.time += region.time;
+                }
+            }
+            let offset = |pos: Pos| Pos::new(rows.start + pos.row, columns.start + pos.column);
+            for event in recorder.take() {
+                match event {
+                    Recorded::Set(pos, tile, strategy) => {
+                        self.strategy = strategy;
+                        self.set(offset(pos), tile);
+                        self.strategy = None;
+                    }
+                    Recorded::Step(strategy) => self.notify_step(strategy),
+                }
+            }
+            match outcome {
+                SolveOutcome::Contradiction { cell, reason } => {
+                    return Err((offset(cell), reason));
+                }
+                SolveOutcome::Stopped { .. } => stopped = true,
+                SolveOutcome::Solved | SolveOutcome::Stuck { .. } => (),
+            }
+            if self.should_stop() {
+                return Ok(Some(true));
+            }
+        }
+        Ok(Some(stopped))
+    }
+
+    /// Tell the [`Board::observer`] that `strategy` changed the
+    /// `Board`.
+    ///
+    /// [`Board::observer`]: struct.Board.html#structfield.observer
+    fn notify_step(&self, strategy: &'static str) {
+        if let Some(ref observer) = self.observer {
+            observer.on_step(self, strategy);
+        }
+    }
+
+    /// Should solving stop now?
+    ///
+    /// This is `true` once the [`SolverLimits::cancel`] token is
+    /// cancelled, or the current solve has run for longer than
+    /// [`SolverLimits::max_duration`].  Long running strategies should
+    /// check this every so often and give up when it is `true`.
+    ///
+    /// [`SolverLimits::cancel`]: struct.SolverLimits.html#structfield.cancel
+    /// [`SolverLimits::max_duration`]: struct.SolverLimits.html#structfield.max_duration
+    pub fn should_stop(&self) -> bool {
+        self.limits
+            .cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.is_cancelled())
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Record that a strategy went through `possibilities` ways of
+    /// placing `Camp`s.
+    ///
+    /// Strategies that search through possibilities should call this
+    /// so the total shows up in [`SolveStats::possibilities`].
+    ///
+    /// [`SolveStats::possibilities`]: struct.SolveStats.html#structfield.possibilities
+    pub fn record_possibilities(&self, possibilities: usize) {
+        self.possibilities.add(possibilities);
+    }
+
+    /// Process the intersections of the queued lines until the work
+    /// queue is empty.
+    ///
+    /// Intersections are only processed for the lines in the work
+    /// queue, which [`Board::set`] fills with the lines around each
+    /// changed `Tile`.  When every line is queued they are processed
+    /// together by [`process_intersections`] instead.  There is only a
+    /// work queue while solving.
+    ///
+    /// Returns whether the `Board` changed.
+    ///
+    /// # Errors
+    ///
+    /// See [`process_intersections`].
+    ///
+    /// [`Board::set`]: struct.Board.html#method.set
+    /// [`process_intersections`]: fn.process_intersections.html
+    pub fn process_queue(&mut self) -> Result<bool, (Pos, Contradiction)> {
+        use intersection::*;
+        if self.queue.as_mut().is_some_and(|queue| queue.take_all()) {
+            return process_intersections(self);
+        }
+        let mut changed = false;
+        while let Some(line) = self.queue.as_mut().and_then(|queue| queue.pop()) {
+            changed |= match line {
+                Line::Row(row) => process_row_intersections(self, row)?,
+                Line::Column(column) => process_column_intersections(self, column)?,
+            };
+            if let Some(ref mut queue) = self.queue {
+                queue.finish(line);
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Queue every row and column for [`Board::process_queue`].
+    ///
+    /// Call this when every line may allow different placements than
+    /// before, which [`Board::set`] can't tell.  [`Board::set_rules`]
+    /// does so itself.
+    ///
+    /// [`Board::process_queue`]: struct.Board.html#method.process_queue
+    /// [`Board::set`]: struct.Board.html#method.set
+    /// [`Board::set_rules`]: struct.Board.html#method.set_rules
+    pub fn queue_all_lines(&mut self) {
+        if let Some(ref mut queue) = self.queue {
+            queue.push_all();
+        }
+    }
+
+    /// Queue every row and column again if a `Tile` has changed since
+    /// they were last all queued.
+    ///
+    /// A line's placements also depend on the lines crossing it, which
+    /// [`Board::set`] doesn't queue.  Returns whether they were queued.
+    ///
+    /// [`Board::set`]: struct.Board.html#method.set
+    pub fn requeue_stale_lines(&mut self) -> bool {
+        match self.queue {
+            Some(ref mut queue) if queue.is_stale() => {
+                queue.push_all();
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq for Board {
+    fn eq(&self, other: &Board) -> bool {
+        self.total() == other.total()
+            && self.grid == other.grid
+            && self.row_ranges == other.row_ranges
+            && self.column_ranges == other.column_ranges
+    }
+}
+
+impl Eq for Board {}
+
+impl Hash for Board {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.total().hash(state);
+        self.grid.hash(state);
+        self.row_ranges.hash(state);
+        self.column_ranges.hash(state);
+    }
+}
+
+impl PartialOrd for Board {
+    fn partial_cmp(&self, other: &Board) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Board {
+    /// Order by the row clues, then the column clues, then the total,
+    /// and last the [`Grid`].
+    ///
+    /// [`Grid`]: struct.Grid.html
+    fn cmp(&self, other: &Board) -> Ordering {
+        self.row_ranges
+            .cmp(&other.row_ranges)
+            .then_with(|| self.column_ranges.cmp(&other.column_ranges))
+            .then_with(|| self.total().cmp(&other.total()))
+            .then_with(|| self.grid.cmp(&other.grid))
+    }
+}
+
+impl fmt::Debug for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.grid)
+    }
+}
+
+impl Deref for Board {
+    type Target = Grid;
+
+    fn deref(&self) -> &Grid {
+        &self.grid
+    }
+}
+
+/// Something a [`StepRecorder`] heard.
+///
+/// [`StepRecorder`]: struct.StepRecorder.html
+enum Recorded {
+    Set(Pos, Tile, Option<&'static str>),
+    Step(&'static str),
+}
+
+/// A [`BoardObserver`] that keeps what it hears so it can be played
+/// back on another `Board`.
+///
+/// [`BoardObserver`]: trait.BoardObserver.html
+#[derive(Default)]
+struct StepRecorder {
+    events: Mutex<Vec<Recorded>>,
+}
+
+impl StepRecorder {
+    /// Take everything heard so far, in order.
+    fn take(&self) -> Vec<Recorded> {
+        mem::take(&mut *self.events.lock().unwrap())
+    }
+}
+
+impl BoardObserver for StepRecorder {
+    fn on_tile_set(&self, pos: Pos, _old: Tile, new: Tile, strategy: Option<&'static str>) {
+        let event = Recorded::Set(pos, new, strategy);
+        self.events.lock().unwrap().push(event);
+    }
+
+    fn on_step(&self, _board: &Board, strategy: &'static str) {
+        self.events.lock().unwrap().push(Recorded::Step(strategy));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cancel_token::*;
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use tile::Tile::*;
+
+    #[test]
+    fn debug_test() {
+        assert_eq!(
+            "TC-\n - \n---",
+            Board::new(
+                vec![1, 2, 3],
+                vec![3, 2, 1],
+                vec![
+                    vec![Tree, Camp, Grass],
+                    vec![Unassigned, Grass, Unassigned],
+                    vec![Grass, Grass, Grass]
+                ].into()
+            ).debug()
+        );
+    }
+
+    #[test]
+    fn set_counts() {
+        let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        assert_eq!(board.set(Pos::new(0, 0), Camp), Unassigned);
+        assert_eq!(board.count_in_row(0, Camp), 1);
+        assert_eq!(board.count_in_column(0, Unassigned), 1);
+        assert_eq!(board.count(Unassigned), 2);
+        assert_eq!(board.counts, Counts::new(&board.grid));
+    }
+
+    #[test]
+    fn try_new_rectangular() {
+        let board = Board::try_new(vec![0, 0], vec![0, 0, 0], Grid::blank(2, 3)).unwrap();
+        assert_eq!(board.row_ranges().len(), 2);
+        assert_eq!(board.column_ranges().len(), 3);
+        assert!(Board::try_new(vec![0, 0, 0], vec![0, 0], Grid::blank(2, 3)).is_err());
+    }
+
+    #[test]
+    fn try_new_dimension_mismatch() {
+        assert_eq!(
+            Board::try_new(vec![0, 0], vec![0, 0, 0], Grid::blank(2, 2)),
+            Err(Error::DimensionMismatch {
+                rows: 2,
+                columns: 3,
+                grid_rows: 2,
+                grid_columns: 2,
+            })
+        );
+        assert!(Board::new_parse(vec![0, 0], vec![0, 0], "  \n ").is_err());
+    }
+
+    #[test]
+    fn validate_ok() {
+        let board = Board::new_parse(vec![1, 0], vec![0, 1], " T\n  ").unwrap();
+        assert_eq!(board.validate(), Ok(()));
+        let board = Board::new_parse_clue_free(1, " T\n  ").unwrap();
+        assert_eq!(board.validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_clue_sum_mismatch() {
+        let board = Board::new_parse(vec![1, 0], vec![1, 1], " T\nT ").unwrap();
+        assert_eq!(
+            board.validate(),
+            Err(Error::ClueSumMismatch {
+                rows: 1,
+                columns: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn validate_tree_count_mismatch() {
+        let board = Board::new_parse(vec![1, 0], vec![0, 1], " T\nT ").unwrap();
+        assert_eq!(
+            board.validate(),
+            Err(Error::TreeCountMismatch { trees: 2, camps: 1 })
+        );
+    }
+
+    #[test]
+    fn validate_adjacent_camps() {
+        let board = Board::new_parse(vec![1, 1], vec![1, 1], "CT\nTC").unwrap();
+        assert_eq!(
+            board.validate(),
+            Err(Error::CampConflict {
+                pos: Pos::new(0, 0),
+                conflict: Pos::new(1, 1),
+            })
+        );
+    }
+
+    #[test]
+    fn validate_isolated_tree() {
+        let board = Board::new_parse(vec![1], vec![1], "T").unwrap();
+        assert_eq!(
+            board.validate(),
+            Err(Error::IsolatedTree {
+                pos: Pos::new(0, 0)
+            })
+        );
+        let board = Board::new_parse(vec![0, 1], vec![1], "#\nT").unwrap();
+        assert_eq!(
+            board.validate(),
+            Err(Error::IsolatedTree {
+                pos: Pos::new(1, 0)
+            })
+        );
+        let board = Board::new_parse(vec![0, 1], vec![0, 1], "  \n-T").unwrap();
+        assert_eq!(board.validate(), Ok(()));
+        let board = Board::new_parse(vec![0, 1], vec![0, 1], " -\n-T").unwrap();
+        assert_eq!(
+            board.validate(),
+            Err(Error::IsolatedTree {
+                pos: Pos::new(1, 1)
+            })
+        );
+    }
+
+    #[test]
+    fn solve_unsolvable() {
+        let mut board = Board::new_parse(vec![1, 0, 1], vec![1, 0, 1], " T \n   \n T ").unwrap();
+        assert_eq!(
+            board.solve(),
+            Err(Error::Unsolved {
+                remaining: vec![
+                    Pos::new(0, 0),
+                    Pos::new(0, 2),
+                    Pos::new(2, 0),
+                    Pos::new(2, 2)
+                ],
+            })
+        );
+        // but it should make some progress
+        assert_eq!(board.debug(), " T \n---\n T ");
+    }
+
+    #[test]
+    fn solve_outcome_stuck() {
+        let mut board = Board::new_parse(vec![1, 0, 1], vec![1, 0, 1], " T \n   \n T ").unwrap();
+        assert_eq!(
+            board.solve_outcome(),
+            SolveOutcome::Stuck {
+                partial: Grid::parse(" T \n---\n T ").unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn solve_outcome_row_exceeds_clue() {
+        let mut board = Board::new_parse(vec![1, 0, 1], vec![1, 0, 1], "CTC\n   \n T ").unwrap();
+        assert_eq!(
+            board.solve_outcome(),
+            SolveOutcome::Contradiction {
+                cell: Pos::new(0, 2),
+                reason: Contradiction::RowOverfilled,
+            }
+        );
+    }
+
+    #[test]
+    fn solve_outcome_adjacent_camps() {
+        let mut board = Board::new_parse(vec![1, 1, 0], vec![1, 1, 0], " T \nT  \n---").unwrap();
+        assert_eq!(
+            board.solve_outcome(),
+            SolveOutcome::Contradiction {
+                cell: Pos::new(0, 0),
+                reason: Contradiction::AdjacentCamps,
+            }
+        );
+    }
+
+    #[test]
+    fn solve_outcome_untrusted_input() {
+        // These used to panic inside the strategies.
+        let mut board = Board::new_parse(vec![1, 0], vec![1, 0], "C-\n-T").unwrap();
+        assert_eq!(
+            board.solve_outcome(),
+            SolveOutcome::Contradiction {
+                cell: Pos::new(0, 0),
+                reason: Contradiction::LonelyCamp,
+            }
+        );
+        let mut board = Board::new_parse(vec![2, 0], vec![1, 0, 1], "CTC\n---").unwrap();
+        assert_eq!(
+            board.solve_outcome(),
+            SolveOutcome::Contradiction {
+                cell: Pos::new(0, 0),
+                reason: Contradiction::SharedTree,
+            }
+        );
+    }
+
+    #[test]
+    fn solve_clue_free() {
+        let mut board = Board::new_parse_clue_free(2, "T-T\n   ").unwrap();
+        assert!(board.is_clue_free());
+        board.solve().unwrap();
+        assert_eq!(board.debug(), "T-T\nC-C");
+    }
+
+    #[test]
+    fn solve_clue_free_unsolvable() {
+        let mut board = Board::new_parse_clue_free(2, "T  \n   \n  T").unwrap();
+        assert!(board.solve().is_err());
+        assert_eq!(board.debug(), "T -\n - \n- T");
     }
 
-    /// Create a new `Board` with a blank [`Grid`] of the correct size.
-    ///
-    /// This method wraps a call to [`Grid::blank`] and [`Board::new`].
-    ///
-    /// [`Grid`]: struct.Grid.html
-    /// [`Grid::parse`]: struct.Grid.html#method.parse
-    /// [`Board::new`]: struct.Board.html#method.new
-    pub fn new_blank(rows: Vec<usize>, columns: Vec<usize>) -> Self {
-        let grid = Grid::blank(rows.len(), columns.len());
-        Self::new(rows, columns, grid)
+    #[test]
+    fn solve_omitted_clues() {
+        let mut board = Board::new_parse_partial(
+            vec![None, Some(1), None, Some(0), None],
+            vec![Some(1), Some(1), Some(1), Some(1), Some(2)],
+            "     \n T  T\n   T \nTT   \n  T  ",
+        )
+        .unwrap();
+        assert!(board.has_omitted_clues());
+        assert_eq!(board.total(), 6);
+        board.solve().unwrap();
+        assert_eq!(board.debug(), "----C\n-TC-T\nC--TC\nTT---\n-CTC-");
+        assert_eq!(board.verify_solution(), vec![]);
     }
 
-    /// Solve the `Board` in place.
-    ///
-    /// # Errors
-    ///
-    /// If the `Board` cannot be solved automatically, an `Err` is
-    /// returned.  The `Board` will be populated with as much
-    /// information as can be deduced automatically.
-    pub fn solve(&mut self) -> Result<(), String> {
-        use associate_trees::*;
-        use fill_camps::*;
-        use fill_zeros::*;
-        use initialize_grass::*;
-        use intersection::*;
-        initialize_grass(self);
-        loop {
-            fill_zeros(self);
-            if fill_camps(self) {
-                continue;
-            }
-            if process_intersections(self) {
-                continue;
-            }
-            if associate_trees(self) {
-                continue;
-            }
-            break;
-        }
-        if self.is_solved() {
-            Ok(())
-        } else {
-            Err(format!("Reached steady state\n{:?}", self))
-        }
+    #[test]
+    fn total_follows_clues() {
+        let mut board = Board::new_parse(vec![1], vec![1, 0], " T").unwrap();
+        board.set_total(3);
+        assert_eq!(board.total(), 1);
+        let mut board = Board::new_parse_partial(vec![None], vec![Some(1), None], " T").unwrap();
+        assert_eq!(board.total(), 1);
+        board.set_total(3);
+        assert_eq!(board.total(), 3);
+        let mut board = Board::new_parse_clue_free(1, " T").unwrap();
+        board.set_total(2);
+        assert_eq!(board.total(), 2);
     }
-}
 
-impl fmt::Debug for Board {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self.grid)
+    #[test]
+    fn solve_torus() {
+        let grid = "   T \n  T  \n     \nT   T\n     ";
+        let (rows, columns) = (vec![0, 1, 1, 0, 2], vec![1, 1, 0, 1, 1]);
+        // The classic rules have no solution.  On a torus the Tree in
+        // the top row gets the Camp at the bottom of its column.
+        let mut board = Board::new_parse(rows.clone(), columns.clone(), grid).unwrap();
+        assert!(board.solve().is_err());
+        let mut board = Board::new_parse(rows, columns, grid).unwrap();
+        board.set_rules(RuleSet::torus());
+        board.solve().unwrap();
+        assert_eq!(board.debug(), "---T-\n-CT--\n----C\nT---T\nC--C-");
+        assert_eq!(board.verify_solution(), vec![]);
     }
-}
 
-impl Deref for Board {
-    type Target = Grid;
+    #[test]
+    fn solve_orthogonal_only() {
+        let mut grid = Grid::parse("     \nT    \n  T  \nT   T\n T T ").unwrap();
+        grid.set_rules(RuleSet {
+            orthogonal_only: true,
+            ..RuleSet::default()
+        });
+        let mut board = Board::try_new(vec![0, 0, 2, 1, 3], vec![2, 1, 1, 1, 1], grid).unwrap();
+        board.solve().unwrap();
+        assert_eq!(board.debug(), "-----\nT----\nC-TC-\nTC--T\nCTCTC");
+        assert_eq!(board.verify_solution(), vec![]);
+    }
 
-    fn deref(&self) -> &Grid {
-        &self.grid
+    #[test]
+    fn solve_no_matching() {
+        let mut grid = Grid::parse(" T   \n     \n     \n   TT\n  T  ").unwrap();
+        grid.set_rules(RuleSet {
+            no_matching: true,
+            ..RuleSet::default()
+        });
+        let mut board = Board::try_new(vec![2, 0, 1, 1, 1], vec![1, 0, 2, 1, 1], grid).unwrap();
+        board.solve().unwrap();
+        assert_eq!(board.debug(), "-TC-C\n-----\n--C--\nC--TT\n--TC-");
+        assert_eq!(board.verify_solution(), vec![]);
     }
-}
 
-impl DerefMut for Board {
-    fn deref_mut(&mut self) -> &mut Grid {
-        &mut self.grid
+    #[test]
+    fn solve_blocked() {
+        // Without the `Blocked` cells this has more than one solution.
+        let grid = "#    \nTT ##\n     \nT    \n T  #";
+        let mut board = Board::new_parse(vec![1, 0, 1, 0, 2], vec![2, 1, 1, 0, 0], grid).unwrap();
+        board.solve().unwrap();
+        assert_eq!(board.debug(), "#C---\nTT-##\nC----\nT----\nCTC-#");
+        assert_eq!(board.verify_solution(), vec![]);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tile::Tile::*;
+    #[test]
+    fn omitted_clues_constrain_nothing() {
+        let board =
+            Board::new_parse_partial(vec![None, Some(0)], vec![None, None], " T\n  ").unwrap();
+        assert_eq!(board.row_clues(), vec![None, Some(0)]);
+        assert_eq!(board.column_clue(1), None);
+        assert_eq!(board.total(), 1);
+        assert_eq!(board.validate(), Ok(()));
+        let clued = Board::new_parse(vec![0, 0], vec![0, 0], " T\n  ").unwrap();
+        assert_ne!(board, clued);
+        assert_eq!(board.clone().place(0, 0, Camp), Ok(Unassigned));
+        assert_eq!(
+            board.clone().place(1, 0, Camp),
+            Err(Error::ClueExceeded {
+                pos: Pos::new(1, 0),
+                reason: Contradiction::RowOverfilled,
+            })
+        );
+        let clues = vec![Some(1), Some(0)];
+        let full = Board::new_parse_partial(clues.clone(), clues, " T\n  ");
+        assert_eq!(full, Board::new_parse(vec![1, 0], vec![1, 0], " T\n  "));
+        assert!(!full.unwrap().has_omitted_clues());
+        let clue_free = Board::new_parse_clue_free(1, " T\n  ").unwrap();
+        assert!(clue_free.has_omitted_clues());
+    }
 
     #[test]
-    fn debug_test() {
+    fn new_blank_rectangular() {
+        let board = Board::new_blank(vec![1, 0], vec![0, 1, 0]);
+        assert_eq!(board.num_rows(), 2);
+        assert_eq!(board.num_columns(), 3);
+        assert_eq!(board.debug(), "   \n   ");
+    }
+
+    #[test]
+    fn solve_4x7() {
+        let mut board = Board::new_parse(
+            vec![3, 0, 2, 1],
+            vec![1, 1, 0, 1, 1, 1, 1],
+            " T     \n   TT T\nT      \n      T",
+        ).unwrap();
+        board.solve().unwrap();
         assert_eq!(
-            "TC-\n - \n---",
-            Board::new(
-                vec![1, 2, 3],
-                vec![3, 2, 1],
-                vec![
-                    vec![Tree, Camp, Grass],
-                    vec![Unassigned, Grass, Unassigned],
-                    vec![Grass, Grass, Grass]
-                ].into()
-            ).debug()
+            board.debug(),
+            "CT--C-C\n---TT-T\nTC-C---\n-----CT"
         );
     }
 
     #[test]
-    fn solve_unsolvable() {
-        let mut board = Board::new_parse(vec![1, 0, 1], vec![1, 0, 1], " T \n   \n T ").unwrap();
-        assert!(board.solve().is_err());
-        // but it should make some progress
-        assert_eq!(board.debug(), " T \n---\n T ");
+    fn solve_4x7_2() {
+        let mut board = Board::new_parse(
+            vec![2, 1, 2, 0],
+            vec![1, 1, 0, 1, 1, 0, 1],
+            "  T    \n    T  \n T    T\n   T   ",
+        ).unwrap();
+        board.solve().unwrap();
+        assert_eq!(
+            board.debug(),
+            "-CT-C--\n----T-C\nCT-C--T\n---T---"
+        );
+    }
+
+    #[test]
+    fn solve_7x4() {
+        let mut board = Board::new_parse(
+            vec![1, 1, 0, 1, 1, 1, 1],
+            vec![3, 0, 2, 1],
+            "  T \nT   \n    \n T  \n T  \n    \n T T",
+        ).unwrap();
+        board.solve().unwrap();
+        assert_eq!(
+            board.debug(),
+            "C-T-\nT-C-\n----\n-TC-\nCT--\n---C\nCT-T"
+        );
     }
 
     #[test]
@@ -337,4 +1942,437 @@ mod tests {
             "T--C--CT\nC--T----\n--C--C--\nC-T--T-T\nT---TC-C\n-CTC-T--\n-T-T-C--\n-C------"
         );
     }
+
+    #[test]
+    fn solve_8x8_pairs() {
+        let mut board = Board::new_parse(
+            vec![1, 2, 2, 2, 1, 2, 1, 2],
+            vec![1, 2, 1, 1, 3, 1, 1, 3],
+            "   T   T\nT       \n      T \n  TT  T \nT       \n T   T T\n        \n   T  T ",
+        ).unwrap();
+        board.solve().unwrap();
+        assert_eq!(
+            board.debug(),
+            "---TC--T\nTC-----C\n---C-CT-\nC-TT--TC\nT-C-----\n-T--CTCT\n-C------\n---TC-TC"
+        );
+    }
+
+    #[test]
+    fn solve_5x5_matching() {
+        let mut board = Board::new_parse(
+            vec![1, 1, 2, 0, 2],
+            vec![1, 1, 1, 1, 2],
+            "     \n T  T\n   T \nTT   \n  T  ",
+        ).unwrap();
+        board.solve().unwrap();
+        assert_eq!(board.debug(), "----C\n-TC-T\nC--TC\nTT---\n-CTC-");
+    }
+
+    #[test]
+    fn solve_10x10_pairs() {
+        let mut board = Board::new_parse(
+            vec![3, 1, 3, 0, 3, 1, 1, 3, 1, 4],
+            vec![3, 2, 0, 3, 1, 2, 3, 1, 2, 3],
+            "  T    T T\n    T     \n T     T  \n         T\nT T T    T\n          \n T        \n     TT   \nT  T    TT\n      T T ",
+        ).unwrap();
+        board.solve().unwrap();
+        assert_eq!(
+            board.debug(),
+            "-CT---CTCT\n---CT-----\nCT----CT-C\n---------T\nT-TCTC--CT\nC---------\n-T----C---\n-C--CTT--C\nT--T---CTT\nC--C-CT-TC"
+        );
+    }
+
+    #[test]
+    fn solve_12x12_trials() {
+        let mut board = Board::new_parse(
+            vec![2, 4, 2, 3, 0, 6, 0, 2, 4, 1, 3, 2],
+            vec![2, 3, 2, 3, 1, 3, 2, 3, 1, 4, 1, 4],
+            " T        T \n T      T T \n    TT  T   \n  T    T    \n       T T T\n    T T     \n T         T\n      T     \n T  T   T   \nT       T TT\n   T T      \n T   T      ",
+        ).unwrap();
+        assert!(!board.clone().solve_outcome().is_solved());
+        let config = SolverConfig {
+            trial_depth: 1,
+            ..Default::default()
+        };
+        board.solve_with(&config).unwrap();
+        assert_eq!(
+            board.debug(),
+            "-TC------CT-\nCT---C-CT-TC\n---CTT--TC--\n-CT---CT---C\n-------T-T-T\n-C-CTCTC-C-C\n-T---------T\n----C-TC----\nCTC-T---TC-C\nT----C--T-TT\n-C-T-T--C-C-\n-T-C-TC-----"
+        );
+    }
+
+    #[test]
+    fn solve_12x12_no_guessing() {
+        let mut board = Board::new_parse(
+            vec![2, 4, 2, 3, 0, 6, 0, 2, 4, 1, 3, 2],
+            vec![2, 3, 2, 3, 1, 3, 2, 3, 1, 4, 1, 4],
+            " T        T \n T      T T \n    TT  T   \n  T    T    \n       T T T\n    T T     \n T         T\n      T     \n T  T   T   \nT       T TT\n   T T      \n T   T      ",
+        ).unwrap();
+        let config = SolverConfig {
+            profile: Profile::Fast,
+            trial_depth: 1,
+            no_guessing: true,
+            ..Default::default()
+        };
+        match board.solve_outcome_with(&config) {
+            SolveOutcome::Stuck { .. } => (),
+            outcome => panic!("{:?}", outcome),
+        }
+    }
+
+    #[test]
+    fn solve_20x20() {
+        let mut board = Board::new_parse(
+            vec![7, 3, 7, 1, 6, 3, 3, 5, 3, 5, 3, 4, 3, 4, 3, 5, 3, 5, 4, 5],
+            vec![6, 2, 5, 4, 3, 4, 4, 5, 3, 4, 4, 5, 2, 5, 2, 7, 2, 5, 3, 7],
+            "  T     T T       T \nT   T  T   T   T T T\n    T     T TT    T \n            T  T    \n T T  T   T         \n     T     TT   T   \n              T T  T\n    TT   T  T     T \n T     T  T   T     \n T    T     T    TT \nT   T               \n   T  TT       T   T\nT           T T  TT \n T  T               \n  T  T   T        T \n   T      T  TT     \n      T  T T T      \nT  T       T     TT \n T T          TT   T\n   T T    T         ",
+        ).unwrap();
+        board.solve().unwrap();
+        assert_eq!(
+            board.debug(),
+            "--TC---CTCTC---C-CTC\nTC--TC-T---T-C-T-T-T\n---CT--C-CTCTT-C-CTC\n------------TC-T----\nCT-T-CTC-CTC----C---\n---C-T-----TTC--T--C\n-----C--------TCTC-T\n-C--TT-C-TC-TC----TC\n-T--C--T--T---TC-C--\nCTC---TC--C-T----TTC\nT---TC------C--C----\nC-CT--TTC------T--CT\nT-----C----CT-T-CTT-\nCT-CT---------C---C-\n--T--TC-CTC-------T-\n--CTC-----T-CTTC--C-\nC-----T-CTCT-T------\nT-CT--C----T-C-C-TTC\nCT-TC------C--TT-C-T\n--CT-TC--CT---C----C"
+        );
+    }
+
+    #[test]
+    fn camp_candidates_for_tree() {
+        let board = Board::new_parse_clue_free(2, "#T \n   ").unwrap();
+        assert_eq!(
+            board.camp_candidates_for_tree(Pos::new(0, 1)),
+            vec![(0, 2), (1, 1)]
+        );
+        // The only `Camp` has been placed.
+        let board = Board::new_parse_clue_free(1, "C- T ").unwrap();
+        assert!(board.camp_candidates_for_tree(Pos::new(0, 3)).is_empty());
+    }
+
+    #[test]
+    fn check_solution() {
+        let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        board.solve().unwrap();
+        assert_eq!(board.check_solution(), Ok(()));
+        // A strategy that filled in the wrong `Tile`.
+        board.set(Pos::new(1, 0), Camp);
+        match board.check_solution() {
+            Err(Error::InvalidSolution { violations }) => assert!(!violations.is_empty()),
+            result => panic!("{:?}", result),
+        }
+    }
+
+    #[test]
+    fn solve_cancelled() {
+        let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        board.limits.cancel = Some(cancel);
+        match board.solve_outcome() {
+            SolveOutcome::Stopped { partial } => assert_eq!(partial.debug(), " T\n- "),
+            outcome => panic!("{:?}", outcome),
+        }
+        assert_eq!(
+            board.solve(),
+            Err(Error::Stopped {
+                remaining: vec![Pos::new(0, 0), Pos::new(1, 1)],
+            })
+        );
+    }
+
+    #[test]
+    fn solve_max_duration() {
+        let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        board.limits.max_duration = Some(Duration::new(0, 0));
+        assert!(!board.should_stop());
+        match board.solve_outcome() {
+            SolveOutcome::Stopped { .. } => (),
+            outcome => panic!("{:?}", outcome),
+        }
+        assert!(!board.should_stop());
+    }
+
+    #[test]
+    fn solve_max_iterations() {
+        let mut board = Board::new_parse(
+            vec![3, 1, 3, 0, 3, 1, 1, 3, 1, 4],
+            vec![3, 2, 0, 3, 1, 2, 3, 1, 2, 3],
+            "  T    T T\n    T     \n T     T  \n         T\nT T T    T\n          \n T        \n     TT   \nT  T    TT\n      T T ",
+        ).unwrap();
+        board.limits.max_iterations = Some(1);
+        match board.clone().solve_outcome() {
+            SolveOutcome::Stopped { .. } => (),
+            outcome => panic!("{:?}", outcome),
+        }
+        board.limits.max_iterations = Some(1000);
+        board.solve().unwrap();
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Event {
+        pos: Pos,
+        old: Tile,
+        new: Tile,
+        strategy: Option<&'static str>,
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Mutex<Vec<Event>>,
+        steps: Mutex<Vec<(&'static str, usize)>>,
+    }
+
+    impl BoardObserver for Recorder {
+        fn on_tile_set(&self, pos: Pos, old: Tile, new: Tile, strategy: Option<&'static str>) {
+            let event = Event {
+                pos,
+                old,
+                new,
+                strategy,
+            };
+            self.events.lock().unwrap().push(event);
+        }
+
+        fn on_step(&self, board: &Board, strategy: &'static str) {
+            let remaining = board.count(Unassigned);
+            self.steps.lock().unwrap().push((strategy, remaining));
+        }
+    }
+
+    /// An `Event` for a change made outside of solving.
+    fn event(pos: Pos, old: Tile, new: Tile) -> Event {
+        Event {
+            pos,
+            old,
+            new,
+            strategy: None,
+        }
+    }
+
+    #[test]
+    fn set_camp_observed() {
+        let recorder = Arc::new(Recorder::default());
+        let mut board = Board::new_parse(vec![1, 0], vec![0, 1, 0], "T  \n T ").unwrap();
+        board.observer = Some(recorder.clone());
+        board.set_camp(Pos::new(0, 1)).unwrap();
+        assert_eq!(board.debug(), "TC-\n-T-");
+        assert_eq!(board.count_in_row(1, Grass), 2);
+        assert_eq!(
+            *recorder.events.lock().unwrap(),
+            vec![
+                event(Pos::new(0, 1), Unassigned, Camp),
+                event(Pos::new(0, 2), Unassigned, Grass),
+                event(Pos::new(1, 0), Unassigned, Grass),
+                event(Pos::new(1, 2), Unassigned, Grass),
+            ]
+        );
+        assert_eq!(
+            board.set_camp(Pos::new(1, 2)),
+            Err(Error::CampConflict {
+                pos: Pos::new(1, 2),
+                conflict: Pos::new(0, 1),
+            })
+        );
+        assert_eq!(recorder.events.lock().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn place_enforces_rules() {
+        let mut board = Board::new_parse(vec![1, 1, 0], vec![1, 0, 1], "T T\n   \n-  ").unwrap();
+        assert_eq!(
+            board.place(0, 0, Grass),
+            Err(Error::TreeChanged {
+                pos: Pos::new(0, 0)
+            })
+        );
+        assert_eq!(
+            board.place(1, 1, Tree),
+            Err(Error::TreeChanged {
+                pos: Pos::new(1, 1)
+            })
+        );
+        assert_eq!(
+            board.place(2, 1, Camp),
+            Err(Error::ClueExceeded {
+                pos: Pos::new(2, 1),
+                reason: Contradiction::RowOverfilled,
+            })
+        );
+        assert_eq!(board.place(1, 0, Camp), Ok(Unassigned));
+        assert_eq!(board.place(1, 0, Camp), Ok(Camp));
+        assert_eq!(
+            board.place(1, 1, Camp),
+            Err(Error::CampConflict {
+                pos: Pos::new(1, 1),
+                conflict: Pos::new(1, 0),
+            })
+        );
+        assert_eq!(board.place(1, 0, Grass), Ok(Camp));
+        assert_eq!(
+            board.place(1, 1, Camp),
+            Err(Error::ClueExceeded {
+                pos: Pos::new(1, 1),
+                reason: Contradiction::ColumnOverfilled,
+            })
+        );
+        assert_eq!(board.place(1, 2, Camp), Ok(Unassigned));
+        assert_eq!(board.clear(1, 2), Ok(Camp));
+        assert_eq!(board.clear(2, 0), Ok(Grass));
+        assert_eq!(
+            board.clear(0, 2),
+            Err(Error::TreeChanged {
+                pos: Pos::new(0, 2)
+            })
+        );
+        assert_eq!(board.debug(), "T T\n-  \n   ");
+        assert_eq!(board.count_in_row(1, Camp), 0);
+    }
+
+    #[test]
+    fn place_keeps_blocked() {
+        let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n# ").unwrap();
+        assert_eq!(
+            board.place(1, 0, Grass),
+            Err(Error::BlockedChanged {
+                pos: Pos::new(1, 0)
+            })
+        );
+        assert_eq!(
+            board.place(1, 1, Blocked),
+            Err(Error::BlockedChanged {
+                pos: Pos::new(1, 1)
+            })
+        );
+    }
+
+    #[test]
+    fn place_total() {
+        let mut board = Board::new_parse_clue_free(1, "T T\n   ").unwrap();
+        board.place(1, 0, Camp).unwrap();
+        assert_eq!(
+            board.place(1, 2, Camp),
+            Err(Error::ClueExceeded {
+                pos: Pos::new(1, 2),
+                reason: Contradiction::TotalOverfilled,
+            })
+        );
+    }
+
+    #[test]
+    fn solve_observed() {
+        let recorder = Arc::new(Recorder::default());
+        let mut board = Board::new_parse(
+            vec![1, 2, 2, 2, 1, 2, 1, 2],
+            vec![1, 2, 1, 1, 3, 1, 1, 3],
+            "   T   T\nT       \n      T \n  TT  T \nT       \n T   T T\n        \n   T  T ",
+        ).unwrap();
+        let mut replay = board.grid.clone();
+        board.observer = Some(recorder.clone());
+        board.solve().unwrap();
+        for event in recorder.events.lock().unwrap().iter() {
+            assert_eq!(replay[event.pos], event.old);
+            assert!(event.strategy.is_some());
+            replay[event.pos] = event.new;
+        }
+        assert_eq!(replay, board.grid);
+        let steps = recorder.steps.lock().unwrap();
+        assert_eq!(steps[0].0, "initialize_grass");
+        assert!(steps.windows(2).all(|pair| pair[0].1 > pair[1].1));
+        assert_eq!(steps.last().unwrap().1, 0);
+    }
+
+    #[test]
+    fn hash_and_ord() {
+        use std::collections::hash_map::DefaultHasher;
+        let hash = |board: &Board| {
+            let mut hasher = DefaultHasher::new();
+            board.hash(&mut hasher);
+            hasher.finish()
+        };
+        let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        let mut moved = board.clone();
+        moved.place(0, 0, Camp).unwrap();
+        moved.undo();
+        moved.limits.max_iterations = Some(1);
+        assert_eq!(hash(&moved), hash(&board));
+        assert_eq!(moved.cmp(&board), Ordering::Equal);
+        let clue_free = Board::new_parse_clue_free(1, " T\n  ").unwrap();
+        let mut boards = [board.clone(), clue_free.clone()];
+        boards.sort();
+        assert_eq!(boards, [clue_free, board]);
+    }
+
+    #[test]
+    fn solve_counts_iterations_and_possibilities() {
+        let mut board = Board::new_parse(
+            vec![1, 1, 0, 2, 1],
+            vec![2, 0, 1, 1, 1],
+            "     \n T T \n     \nTT   \n    T",
+        )
+        .unwrap();
+        let config = SolverConfig {
+            collect_stats: true,
+            ..Default::default()
+        };
+        let mut copy = board.clone();
+        copy.solve_with(&config).unwrap();
+        board.solve_with(&config).unwrap();
+        let stats = board.stats().unwrap();
+        assert!(stats.iterations > 1);
+        assert!(stats.possibilities > 0);
+        assert_eq!(
+            (stats.iterations, stats.possibilities),
+            (copy.stats().unwrap().iterations, copy.stats().unwrap().possibilities)
+        );
+        // Each solve starts counting again.
+        let possibilities = stats.possibilities;
+        board.solve_with(&config).unwrap();
+        assert_eq!(board.stats().unwrap().iterations, 1);
+        assert!(board.stats().unwrap().possibilities <= possibilities);
+    }
+
+    #[test]
+    fn solve_regions_separately() {
+        let mut board =
+            Board::new_parse(vec![1, 0, 1], vec![0, 1, 0, 1, 0], "T ---\n-----\n--- T").unwrap();
+        assert_eq!(board.independent_regions().len(), 2);
+        let recorder = Arc::new(Recorder::default());
+        board.observer = Some(recorder.clone());
+        let config = SolverConfig {
+            collect_stats: true,
+            ..Default::default()
+        };
+        board.solve_with(&config).unwrap();
+        assert_eq!(board.debug(), "TC---\n-----\n---CT");
+        let events = recorder.events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| event.strategy.is_some()));
+        assert_eq!(events[1].pos, Pos::new(2, 3));
+        let strategies = board.stats().unwrap().strategies.iter();
+        assert_eq!(strategies.map(|x| x.tiles_changed).sum::<usize>(), 2);
+    }
+
+    #[test]
+    fn solve_regions_max_iterations() {
+        let mut board =
+            Board::new_parse(vec![1, 0, 1], vec![0, 1, 0, 1, 0], "T ---\n-----\n--- T").unwrap();
+        board.limits.max_iterations = Some(1);
+        let config = SolverConfig {
+            collect_stats: true,
+            ..Default::default()
+        };
+        match board.solve_outcome_with(&config) {
+            SolveOutcome::Stopped { .. } => (),
+            outcome => panic!("{:?}", outcome),
+        }
+        assert_eq!(board.debug(), "TC---\n-----\n--- T");
+        assert_eq!(board.stats().unwrap().iterations, 1);
+    }
+
+    #[test]
+    fn solve_regions_contradiction() {
+        let mut board =
+            Board::new_parse(vec![1, 0, 0], vec![0, 1, 0, 0, 0], "T ---\n-----\n--- T").unwrap();
+        match board.solve_outcome() {
+            SolveOutcome::Contradiction { cell, .. } => assert_eq!(cell.row, 2),
+            outcome => panic!("{:?}", outcome),
+        }
+    }
 }
@@ -0,0 +1,21 @@
+use pos::*;
+use tile::*;
+
+/// A move made with [`Board::place`] or [`Board::clear`].
+///
+/// The moves are kept in [`Board::history`] so they can be undone
+/// with [`Board::undo`].
+///
+/// [`Board::place`]: struct.Board.html#method.place
+/// [`Board::clear`]: struct.Board.html#method.clear
+/// [`Board::history`]: struct.Board.html#structfield.history
+/// [`Board::undo`]: struct.Board.html#method.undo
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Move {
+    /// Where the `Tile` was set.
+    pub pos: Pos,
+    /// The `Tile` before the move.
+    pub old: Tile,
+    /// The `Tile` after the move.
+    pub new: Tile,
+}
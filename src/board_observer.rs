@@ -0,0 +1,125 @@
+use board::*;
+use pos::*;
+use std::sync::Arc;
+use tile::*;
+
+/// Watches the [`Tile`]s of a [`Board`] change.
+///
+/// Set [`Board::observer`] to be told about every [`Tile`] changed by
+/// [`Board::set`] and [`Board::set_camp`], including the ones set while
+/// solving.  A frontend can use this to animate a solve as it happens
+/// instead of comparing whole [`Grid`]s after every call.
+///
+/// While solving, it is also shown the whole [`Board`] after each
+/// strategy that makes progress, through [`BoardObserver::on_step`].
+///
+/// The placements the solver only tries out on copies of the
+/// [`Board`], as in [`trial_placements`], aren't seen.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Board, BoardObserver, Pos, Tile};
+/// # use std::sync::{Arc, Mutex};
+/// #[derive(Default)]
+/// struct Recorder(Mutex<Vec<(Pos, Tile, Option<&'static str>)>>);
+///
+/// impl BoardObserver for Recorder {
+///     fn on_tile_set(&self, pos: Pos, _old: Tile, new: Tile, strategy: Option<&'static str>) {
+///         self.0.lock().unwrap().push((pos, new, strategy));
+///     }
+/// }
+///
+/// let recorder = Arc::new(Recorder::default());
+/// let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n--").unwrap();
+/// board.observer = Some(recorder.clone());
+/// board.solve().unwrap();
+/// assert_eq!(
+///     *recorder.0.lock().unwrap(),
+///     vec![(Pos::new(0, 0), Tile::Camp, Some("fill_camps"))]
+/// );
+/// ```
+///
+/// [`Tile`]: enum.Tile.html
+/// [`Board`]: struct.Board.html
+/// [`Board::observer`]: struct.Board.html#structfield.observer
+/// [`Board::set`]: struct.Board.html#method.set
+/// [`Board::set_camp`]: struct.Board.html#method.set_camp
+/// [`Grid`]: struct.Grid.html
+/// [`trial_placements`]: fn.trial_placements.html
+/// [`BoardObserver::on_step`]: trait.BoardObserver.html#method.on_step
+pub trait BoardObserver: Send + Sync {
+    /// The [`Tile`] at `pos` changed from `old` to `new`.
+    ///
+    /// `strategy` is the [`Strategy::name`] of the strategy that made
+    /// the change, or `None` if it was made outside of solving.
+    ///
+    /// [`Tile`]: enum.Tile.html
+    /// [`Strategy::name`]: trait.Strategy.html#tymethod.name
+    fn on_tile_set(&self, pos: Pos, old: Tile, new: Tile, strategy: Option<&'static str>);
+
+    /// The strategy called `strategy` just changed `board` while
+    /// solving.
+    ///
+    /// This comes after [`BoardObserver::on_tile_set`] has been called
+    /// for each of the changes.  It does nothing by default.
+    ///
+    /// [`BoardObserver::on_tile_set`]: trait.BoardObserver.html#tymethod.on_tile_set
+    fn on_step(&self, board: &Board, strategy: &'static str) {
+        let _ = (board, strategy);
+    }
+}
+
+impl BoardObserver for Vec<Arc<dyn BoardObserver>> {
+    /// Pass the change on to each observer in turn.
+    fn on_tile_set(&self, pos: Pos, old: Tile, new: Tile, strategy: Option<&'static str>) {
+        for observer in self {
+            observer.on_tile_set(pos, old, new, strategy);
+        }
+    }
+
+    /// Pass the step on to each observer in turn.
+    fn on_step(&self, board: &Board, strategy: &'static str) {
+        for observer in self {
+            observer.on_step(board, strategy);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tile::Tile::*;
+
+    #[derive(Default)]
+    struct Counter {
+        tiles: AtomicUsize,
+        steps: AtomicUsize,
+    }
+
+    impl BoardObserver for Counter {
+        fn on_tile_set(&self, _: Pos, _: Tile, _: Tile, _: Option<&'static str>) {
+            self.tiles.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_step(&self, _: &Board, _: &'static str) {
+            self.steps.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn vec_passes_on_to_each() {
+        let first = Arc::new(Counter::default());
+        let second = Arc::new(Counter::default());
+        let observers: Vec<Arc<dyn BoardObserver>> = vec![first.clone(), second.clone()];
+        let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        board.observer = Some(Arc::new(observers));
+        board.set(Pos::new(1, 0), Grass);
+        board.solve().unwrap();
+        for counter in &[first, second] {
+            assert_eq!(counter.tiles.load(Ordering::Relaxed), 3);
+            assert_eq!(counter.steps.load(Ordering::Relaxed), 2);
+        }
+    }
+}
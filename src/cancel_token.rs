@@ -0,0 +1,71 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A flag for stopping a solve from another thread.
+///
+/// Clones share the same flag.  Put one in [`SolverLimits::cancel`]
+/// and call [`CancelToken::cancel`] on a clone to make the solver stop
+/// at its next check and report [`SolveOutcome::Stopped`].
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::CancelToken;
+/// let token = CancelToken::new();
+/// let clone = token.clone();
+/// assert!(!token.is_cancelled());
+/// clone.cancel();
+/// assert!(token.is_cancelled());
+/// ```
+///
+/// [`SolverLimits::cancel`]: struct.SolverLimits.html#structfield.cancel
+/// [`CancelToken::cancel`]: struct.CancelToken.html#method.cancel
+/// [`SolveOutcome::Stopped`]: enum.SolveOutcome.html#variant.Stopped
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    /// Create a `CancelToken` that hasn't been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask every solve using this `CancelToken` to stop.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Has [`CancelToken::cancel`] been called on this `CancelToken`
+    /// or one of its clones?
+    ///
+    /// [`CancelToken::cancel`]: struct.CancelToken.html#method.cancel
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+impl PartialEq for CancelToken {
+    /// `CancelToken`s are equal if they share the same flag.
+    fn eq(&self, other: &CancelToken) -> bool {
+        Arc::ptr_eq(&self.cancelled, &other.cancelled)
+    }
+}
+
+impl Eq for CancelToken {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_the_flag() {
+        let token = CancelToken::new();
+        let clone = token.clone();
+        assert_eq!(token, clone);
+        assert_ne!(token, CancelToken::new());
+        token.cancel();
+        assert!(clone.is_cancelled());
+    }
+}
@@ -0,0 +1,140 @@
+use board::*;
+use clue::*;
+use compact_grid::*;
+use std::cmp::Ordering;
+
+/// A puzzle reduced to the same value however it is rotated or
+/// reflected, from [`Board::canonical_form`].
+///
+/// This implements `Hash`, so archives can find puzzles they already
+/// have with a `HashSet` or `HashMap`.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Board, RotationDirection};
+/// let board = Board::new_parse(vec![1, 0], vec![0, 0, 1], " TC\n   ").unwrap();
+/// let rotated = board.rotated(RotationDirection::Clockwise);
+/// assert_ne!(board, rotated);
+/// assert_eq!(board.canonical_form(), rotated.canonical_form());
+/// ```
+///
+/// [`Board::canonical_form`]: struct.Board.html#method.canonical_form
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CanonicalForm {
+    /// The clue of every row.
+    ///
+    /// This is empty for clue-free puzzles.
+    pub rows: Vec<Clue>,
+    /// The clue of every column.
+    ///
+    /// This is empty for clue-free puzzles.
+    pub columns: Vec<Clue>,
+    /// The total number of `Camp`s.
+    pub total: usize,
+    /// The `Tile`s.
+    pub grid: CompactGrid,
+}
+
+impl CanonicalForm {
+    /// Turn the `CanonicalForm` back into a `Board`.
+    ///
+    /// This is the symmetry of the original `Board` that
+    /// [`Board::canonical_form`] picked.
+    ///
+    /// [`Board::canonical_form`]: struct.Board.html#method.canonical_form
+    pub fn to_board(&self) -> Board {
+        let grid = (&self.grid).into();
+        if self.rows.is_empty() && self.columns.is_empty() {
+            Board::new_clue_free(self.total, grid)
+        } else {
+            let mut board = Board::new_ranged(self.rows.clone(), self.columns.clone(), grid);
+            board.set_total(self.total);
+            board
+        }
+    }
+}
+
+impl Board {
+    /// The smallest of the [`Board::symmetries`] of the `Board`.
+    ///
+    /// Boards are ordered by their number of rows, then columns, then
+    /// their row clues, then their column clues, compared as
+    /// [`Clue`]s, and last their `Tile`s in reading order, where
+    /// `Unassigned` < `Grass` < `Camp` < `Tree`.  Two puzzles have the
+    /// same `CanonicalForm` exactly when one is a rotation or reflection
+    /// of the other.
+    ///
+    /// [`Board::symmetries`]: struct.Board.html#method.symmetries
+    /// [`Clue`]: struct.Clue.html
+    pub fn canonical_form(&self) -> CanonicalForm {
+        let board = self
+            .symmetries()
+            .into_iter()
+            .min_by(compare_symmetries)
+            .unwrap();
+        CanonicalForm {
+            grid: (&*board).into(),
+            rows: board.row_ranges(),
+            columns: board.column_ranges(),
+            total: board.total(),
+        }
+    }
+}
+
+/// Order two symmetries of a `Board` as described in
+/// [`Board::canonical_form`].
+///
+/// [`Board::canonical_form`]: ../struct.Board.html#method.canonical_form
+fn compare_symmetries(a: &Board, b: &Board) -> Ordering {
+    (a.num_rows(), a.num_columns())
+        .cmp(&(b.num_rows(), b.num_columns()))
+        .then_with(|| a.row_ranges().cmp(&b.row_ranges()))
+        .then_with(|| a.column_ranges().cmp(&b.column_ranges()))
+        .then_with(|| (**a).cmp(&**b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pos::*;
+    use std::collections::HashSet;
+    use tile::Tile;
+
+    #[test]
+    fn canonical_form_of_symmetries() {
+        let board = Board::new_parse(
+            vec![1, 1, 0, 2, 1],
+            vec![2, 0, 1, 1, 1],
+            "     \n T T \n     \nTT   \n    T",
+        )
+        .unwrap();
+        let canonical = board.canonical_form();
+        let forms: HashSet<CanonicalForm> = board
+            .symmetries()
+            .iter()
+            .map(|x| x.canonical_form())
+            .collect();
+        assert_eq!(forms.len(), 1);
+        assert!(forms.contains(&canonical));
+        assert!(board.symmetries().contains(&canonical.to_board()));
+
+        let mut other = board.clone();
+        other.set(Pos::new(0, 0), Tile::Tree);
+        assert_ne!(other.canonical_form(), canonical);
+    }
+
+    #[test]
+    fn canonical_form_of_rectangles() {
+        let wide = Board::new_parse(vec![1, 0], vec![0, 0, 1], " TC\n   ").unwrap();
+        let canonical = wide.canonical_form();
+        assert_eq!(canonical.grid.num_rows(), 2);
+        assert_eq!(canonical.to_board().debug(), "   \n TC");
+        assert_eq!(canonical, wide.transposed().canonical_form());
+
+        let clue_free = Board::new_parse_clue_free(1, "CT-").unwrap();
+        let canonical = clue_free.canonical_form();
+        assert!(canonical.to_board().is_clue_free());
+        assert_eq!(canonical.to_board().debug(), "-TC");
+    }
+}
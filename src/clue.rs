@@ -0,0 +1,241 @@
+use error::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// The number of [`Camp`]s a row or column clue allows.
+///
+/// Most clues are a single number, but some puzzles only bound the
+/// count, such as "at most 2", or leave the clue out entirely.  A
+/// `Clue` allows every count from `min` up to `max`, with no upper
+/// bound if `max` is `None`.  The default `Clue` allows any count,
+/// like an omitted one.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::Clue;
+/// let clue: Clue = "<=2".parse().unwrap();
+/// assert_eq!(clue, Clue::at_most(2));
+/// assert!(clue.allows(0) && clue.allows(2) && !clue.allows(3));
+/// assert_eq!("1..3".parse(), Ok(Clue::between(1, 3)));
+/// assert_eq!(Clue::at_least(1).to_string(), ">=1");
+/// assert_eq!(Clue::exactly(2).exact(), Some(2));
+/// ```
+///
+/// [`Camp`]: enum.Tile.html#variant.Camp
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Clue {
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+impl Clue {
+    /// A `Clue` allowing exactly `n` [`Camp`]s.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub fn exactly(n: usize) -> Self {
+        Clue::between(n, n)
+    }
+
+    /// A `Clue` allowing at most `n` [`Camp`]s.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub fn at_most(n: usize) -> Self {
+        Clue::between(0, n)
+    }
+
+    /// A `Clue` allowing at least `n` [`Camp`]s.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub fn at_least(n: usize) -> Self {
+        Clue { min: n, max: None }
+    }
+
+    /// A `Clue` allowing from `min` to `max` [`Camp`]s.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub fn between(min: usize, max: usize) -> Self {
+        Clue {
+            min,
+            max: Some(max),
+        }
+    }
+
+    /// A `Clue` allowing any number of [`Camp`]s, like an omitted one.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub fn any() -> Self {
+        Clue::default()
+    }
+
+    /// The only count this `Clue` allows, if it is a single number.
+    pub fn exact(self) -> Option<usize> {
+        self.max.filter(|&max| max == self.min)
+    }
+
+    /// Does this `Clue` allow any count at all?
+    pub fn is_any(self) -> bool {
+        self == Clue::any()
+    }
+
+    /// Does this `Clue` allow `camps` [`Camp`]s?
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub fn allows(self, camps: usize) -> bool {
+        self.min <= camps && self.max.is_none_or(|max| camps <= max)
+    }
+
+    /// Does this `Clue` allow some count from `camps` up to `most`?
+    ///
+    /// A line with `camps` [`Camp`]s and room for at most `most` can
+    /// only meet its clue if this is true.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub fn allows_any(self, camps: usize, most: usize) -> bool {
+        !self.is_over(camps) && self.is_reachable(most)
+    }
+
+    /// Does a line with `camps` [`Camp`]s have more than this `Clue`
+    /// allows?
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub fn is_over(self, camps: usize) -> bool {
+        self.max.is_some_and(|max| camps > max)
+    }
+
+    /// Can a line with room for at most `most` [`Camp`]s have as many
+    /// as this `Clue` needs?
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub fn is_reachable(self, most: usize) -> bool {
+        most >= self.min
+    }
+
+    /// Is a line with `camps` [`Camp`]s full, so no more can go on it?
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub fn is_full(self, camps: usize) -> bool {
+        self.max.is_some_and(|max| camps >= max)
+    }
+
+    /// Does a line with room for at most `most` [`Camp`]s need every
+    /// one of them?
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub fn needs_all(self, most: usize) -> bool {
+        most <= self.min
+    }
+}
+
+impl From<usize> for Clue {
+    fn from(n: usize) -> Self {
+        Clue::exactly(n)
+    }
+}
+
+impl From<Option<usize>> for Clue {
+    /// An exact `Clue`, or [`Clue::any`] for `None`.
+    ///
+    /// [`Clue::any`]: struct.Clue.html#method.any
+    fn from(clue: Option<usize>) -> Self {
+        clue.map_or(Clue::any(), Clue::exactly)
+    }
+}
+
+impl fmt::Display for Clue {
+    /// Write `n` for an exact `Clue`, `<=n`, `>=n` or `a..b` for a
+    /// bound, or `?` for [`Clue::any`].
+    ///
+    /// [`Clue::any`]: struct.Clue.html#method.any
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.min, self.max) {
+            (0, None) => write!(f, "?"),
+            (min, None) => write!(f, ">={}", min),
+            (min, Some(max)) if min == max => write!(f, "{}", min),
+            (0, Some(max)) => write!(f, "<={}", max),
+            (min, Some(max)) => write!(f, "{}..{}", min, max),
+        }
+    }
+}
+
+impl FromStr for Clue {
+    type Err = Error;
+
+    /// Parse a `Clue` written by its `Display` implementation.  `≤`
+    /// and `≥` work in place of `<=` and `>=`.
+    ///
+    /// # Errors
+    ///
+    /// Anything else, or a range from a larger number to a smaller
+    /// one, gives an [`Error::Format`].
+    ///
+    /// [`Error::Format`]: enum.Error.html#variant.Format
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let number = |x: &str| {
+            x.trim().parse().map_err(|_| Error::Format {
+                format: "clue",
+                message: format!("expected a number, range or ? but found {:?}", s),
+            })
+        };
+        let s = s.trim();
+        let clue = if s == "?" {
+            Clue::any()
+        } else if let Some(x) = s.strip_prefix("<=").or_else(|| s.strip_prefix('≤')) {
+            Clue::at_most(number(x)?)
+        } else if let Some(x) = s.strip_prefix(">=").or_else(|| s.strip_prefix('≥')) {
+            Clue::at_least(number(x)?)
+        } else if let Some((min, max)) = s.split_once("..") {
+            Clue::between(number(min)?, number(max)?)
+        } else {
+            Clue::exactly(number(s)?)
+        };
+        if clue.max.is_some_and(|max| max < clue.min) {
+            Err(Error::Format {
+                format: "clue",
+                message: format!("{:?} allows no count", s),
+            })?
+        }
+        Ok(clue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clue_round_trip() {
+        for clue in &[
+            Clue::exactly(2),
+            Clue::at_most(2),
+            Clue::at_least(1),
+            Clue::between(1, 3),
+            Clue::any(),
+        ] {
+            assert_eq!(clue.to_string().parse(), Ok(*clue));
+        }
+    }
+
+    #[test]
+    fn clue_from_str() {
+        assert_eq!("≤2".parse(), Ok(Clue::at_most(2)));
+        assert_eq!(" ≥ 1".parse(), Ok(Clue::at_least(1)));
+        assert_eq!("0..0".parse(), Ok(Clue::exactly(0)));
+        assert!("3..1".parse::<Clue>().is_err());
+        assert!("<2".parse::<Clue>().is_err());
+        assert!("".parse::<Clue>().is_err());
+    }
+
+    #[test]
+    fn clue_comparisons() {
+        let clue = Clue::between(1, 2);
+        assert!(!clue.allows(0) && clue.allows(1) && !clue.allows(3));
+        assert!(clue.allows_any(0, 1) && !clue.allows_any(0, 0) && !clue.allows_any(3, 4));
+        assert!(!clue.is_over(2) && clue.is_over(3));
+        assert!(!clue.is_reachable(0) && clue.is_reachable(1));
+        assert!(!clue.is_full(1) && clue.is_full(2));
+        assert!(clue.needs_all(1) && !clue.needs_all(2));
+        assert!(!Clue::any().is_full(100));
+        assert_eq!(Clue::at_most(2).exact(), None);
+    }
+}
@@ -0,0 +1,357 @@
+use error::*;
+use grid::*;
+use pos::*;
+use rule_set::*;
+use std::fmt;
+use tile::Tile::{self, *};
+
+/// The number of bits each `Tile` is packed into.
+const BITS_PER_TILE: usize = 3;
+
+/// The number of `Tile`s packed into each word.
+const TILES_PER_WORD: usize = 64 / BITS_PER_TILE;
+
+/// The bits of one `Tile`.
+const TILE_BITS: u64 = 0b111;
+
+/// Every low bit of the 3-bit `Tile`s in a word.
+const LOW_BITS: u64 = 0x1249_2492_4924_9249;
+
+/// A [`Grid`] that packs each [`Tile`] into 3 bits.
+///
+/// This is less than half the size of a [`Grid`], so it is cheap to
+/// copy when searching through many possible [`Grid`]s.  Convert
+/// between the two with `From`.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{CompactGrid, Grid, Pos, Tile::*};
+/// let grid = Grid::parse(" T \n   ").unwrap();
+/// let mut compact = CompactGrid::from(&grid);
+/// assert_eq!(compact.get(0, 1), Some(Tree));
+/// assert!(compact.set_camp(Pos::new(1, 1)).is_ok());
+/// assert_eq!(Grid::from(&compact), Grid::parse("-T-\n-C-").unwrap());
+/// ```
+///
+/// [`Grid`]: struct.Grid.html
+/// [`Tile`]: enum.Tile.html
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct CompactGrid {
+    words: Vec<u64>,
+    height: usize,
+    width: usize,
+    rules: RuleSet,
+}
+
+impl CompactGrid {
+    /// Create a new blank `CompactGrid` of given dimensions.
+    ///
+    /// Every element of this `CompactGrid` is [`Unassigned`].
+    ///
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    pub fn blank(rows: usize, columns: usize) -> CompactGrid {
+        let words = (rows * columns).div_ceil(TILES_PER_WORD);
+        CompactGrid {
+            words: vec![0; words],
+            height: rows,
+            width: columns,
+            rules: RuleSet::default(),
+        }
+    }
+
+    /// Get the number of rows in the `CompactGrid`.
+    pub fn num_rows(&self) -> usize {
+        self.height
+    }
+
+    /// Get the number of columns in the `CompactGrid`.
+    pub fn num_columns(&self) -> usize {
+        self.width
+    }
+
+    /// Get the `Tile` at `(row, column)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `None` if the coordinates are out of bounds.
+    pub fn get(&self, row: usize, column: usize) -> Option<Tile> {
+        if row < self.height && column < self.width {
+            Some(self.tile(Pos::new(row, column)))
+        } else {
+            None
+        }
+    }
+
+    /// Get the `Tile` at `pos`.
+    ///
+    /// # Panics
+    ///
+    /// This will `panic` if `pos` is outside the `CompactGrid`.
+    pub fn tile(&self, pos: Pos) -> Tile {
+        let (word, shift) = self.locate(pos);
+        match (self.words[word] >> shift) & TILE_BITS {
+            0 => Unassigned,
+            1 => Grass,
+            2 => Camp,
+            3 => Tree,
+            _ => Blocked,
+        }
+    }
+
+    /// Set the `Tile` at `pos` to `tile`.
+    ///
+    /// # Panics
+    ///
+    /// This will `panic` if `pos` is outside the `CompactGrid`.
+    pub fn set(&mut self, pos: Pos, tile: Tile) {
+        let (word, shift) = self.locate(pos);
+        self.words[word] &= !(TILE_BITS << shift);
+        self.words[word] |= (tile as u64) << shift;
+    }
+
+    /// Set the [`Tile`] at `pos` to a [`Camp`].
+    ///
+    /// This is the same as [`Grid::set_camp`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Grid::set_camp`].
+    ///
+    /// [`Tile`]: enum.Tile.html
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Grid::set_camp`]: struct.Grid.html#method.set_camp
+    pub fn set_camp(&mut self, pos: Pos) -> Result<(), Error> {
+        self.set_camp_logged(pos, &mut Vec::new())
+    }
+
+    /// Set the [`Tile`] at `pos` to a [`Camp`], recording every
+    /// change in `log` so that it can be undone.
+    ///
+    /// Each change is pushed onto `log` as the position and the
+    /// [`Tile`] that was there before.  Pass the length of `log`
+    /// from before this call to [`CompactGrid::undo`] to roll back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{CompactGrid, Grid, Pos};
+    /// let grid = CompactGrid::from(&Grid::parse(" T \n   ").unwrap());
+    /// let mut compact = grid.clone();
+    /// let mut log = Vec::new();
+    /// compact.set_camp_logged(Pos::new(1, 1), &mut log).unwrap();
+    /// assert_ne!(compact, grid);
+    /// compact.undo(&mut log, 0);
+    /// assert_eq!(compact, grid);
+    /// assert!(log.is_empty());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`Grid::set_camp`].  Nothing is changed or recorded on an
+    /// error.
+    ///
+    /// [`Tile`]: enum.Tile.html
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`CompactGrid::undo`]: struct.CompactGrid.html#method.undo
+    /// [`Grid::set_camp`]: struct.Grid.html#method.set_camp
+    pub fn set_camp_logged(&mut self, pos: Pos, log: &mut Vec<(Pos, Tile)>) -> Result<(), Error> {
+        if let Some(conflict) = self.camp_conflict(pos) {
+            Err(Error::CampConflict { pos, conflict })?;
+        }
+        log.push((pos, self.tile(pos)));
+        self.set(pos, Camp);
+        for x in self.rules.touching(pos, self.height, self.width) {
+            if self.tile(x) == Unassigned {
+                log.push((x, Unassigned));
+                self.set(x, Grass);
+            }
+        }
+        Ok(())
+    }
+
+    /// Undo the changes recorded in `log` after its first `len`
+    /// entries, most recent first, and remove them from `log`.
+    ///
+    /// See [`CompactGrid::set_camp_logged`].
+    ///
+    /// [`CompactGrid::set_camp_logged`]: struct.CompactGrid.html#method.set_camp_logged
+    pub fn undo(&mut self, log: &mut Vec<(Pos, Tile)>, len: usize) {
+        while log.len() > len {
+            let (pos, tile) = log.pop().unwrap();
+            self.set(pos, tile);
+        }
+    }
+
+    /// Can a [`Camp`] be placed at `pos`?
+    ///
+    /// This is the same as [`Grid::can_place_camp`].
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Grid::can_place_camp`]: struct.Grid.html#method.can_place_camp
+    pub fn can_place_camp(&self, pos: Pos) -> bool {
+        self.camp_conflict(pos).is_none()
+    }
+
+    /// Make every `Tile` that differs from `other` [`Unassigned`].
+    ///
+    /// # Panics
+    ///
+    /// This will `panic` if `other` has different dimensions.
+    ///
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    pub fn intersect(&mut self, other: &CompactGrid) {
+        assert_eq!(
+            (self.height, self.width),
+            (other.height, other.width),
+            "Can't intersect CompactGrids of different sizes"
+        );
+        for (word, &other) in self.words.iter_mut().zip(&other.words) {
+            let diff = *word ^ other;
+            let tiles = (diff | diff >> 1 | diff >> 2) & LOW_BITS;
+            *word &= !(tiles * TILE_BITS);
+        }
+    }
+
+    /// Find a [`Camp`] at `pos` or touching it, possibly diagonally.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    fn camp_conflict(&self, pos: Pos) -> Option<Pos> {
+        if self.tile(pos) == Camp {
+            return Some(pos);
+        }
+        self.rules
+            .touching(pos, self.height, self.width)
+            .into_iter()
+            .find(|&x| self.tile(x) == Camp)
+    }
+
+    /// Get the word holding `pos` and the shift of its bits.
+    fn locate(&self, pos: Pos) -> (usize, usize) {
+        assert!(
+            pos.row < self.height && pos.column < self.width,
+            "({}, {}) is outside the {}x{} CompactGrid",
+            pos.row,
+            pos.column,
+            self.height,
+            self.width
+        );
+        let index = pos.row * self.width + pos.column;
+        (
+            index / TILES_PER_WORD,
+            index % TILES_PER_WORD * BITS_PER_TILE,
+        )
+    }
+}
+
+impl<'a> From<&'a Grid> for CompactGrid {
+    fn from(grid: &'a Grid) -> CompactGrid {
+        let mut compact = CompactGrid::blank(grid.num_rows(), grid.num_columns());
+        compact.rules = grid.rules();
+        for (pos, tile) in grid.cells() {
+            compact.set(pos, tile);
+        }
+        compact
+    }
+}
+
+impl<'a> From<&'a CompactGrid> for Grid {
+    fn from(compact: &'a CompactGrid) -> Grid {
+        let mut grid = Grid::blank(compact.height, compact.width);
+        grid.set_rules(compact.rules);
+        for row in 0..compact.height {
+            for column in 0..compact.width {
+                let pos = Pos::new(row, column);
+                grid[pos] = compact.tile(pos);
+            }
+        }
+        grid
+    }
+}
+
+impl fmt::Debug for CompactGrid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", Grid::from(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let grid = Grid::parse("TC-#\n -CT\n--#-").unwrap();
+        let compact = CompactGrid::from(&grid);
+        assert_eq!(compact.num_rows(), 3);
+        assert_eq!(compact.num_columns(), 4);
+        assert_eq!(Grid::from(&compact), grid);
+        assert_eq!(format!("{:?}", compact), grid.debug());
+    }
+
+    #[test]
+    fn spans_several_words() {
+        let mut compact = CompactGrid::blank(9, 9);
+        compact.set(Pos::new(3, 5), Tree);
+        compact.set(Pos::new(8, 8), Camp);
+        assert_eq!(compact.get(3, 5), Some(Tree));
+        assert_eq!(compact.get(3, 4), Some(Unassigned));
+        assert_eq!(compact.get(8, 8), Some(Camp));
+        assert_eq!(compact.get(9, 0), None);
+        compact.set(Pos::new(3, 5), Grass);
+        assert_eq!(compact.get(3, 5), Some(Grass));
+        // 21 `Tile`s fit in a word, so this one starts the fourth.
+        compact.set(Pos::new(7, 0), Blocked);
+        assert_eq!(compact.get(7, 0), Some(Blocked));
+        assert_eq!(compact.get(6, 8), Some(Unassigned));
+        assert_eq!(compact.get(7, 1), Some(Unassigned));
+    }
+
+    #[test]
+    fn set_camp_matches_grid() {
+        let mut grid = Grid::parse(" T \nT T\n T ").unwrap();
+        let mut compact = CompactGrid::from(&grid);
+        for &pos in &[Pos::new(0, 0), Pos::new(1, 1), Pos::new(2, 2)] {
+            assert_eq!(compact.can_place_camp(pos), grid.can_place_camp(pos));
+            assert_eq!(compact.set_camp(pos), grid.set_camp(pos));
+        }
+        assert_eq!(Grid::from(&compact), grid);
+    }
+
+    #[test]
+    fn set_camp_wraps() {
+        let mut grid = Grid::parse("T  T\n    ").unwrap();
+        grid.set_rules(RuleSet::torus());
+        let mut compact = CompactGrid::from(&grid);
+        compact.set_camp(Pos::new(0, 1)).unwrap();
+        assert!(!compact.can_place_camp(Pos::new(1, 1)));
+        compact.set_camp(Pos::new(1, 3)).unwrap();
+        assert_eq!(Grid::from(&compact).debug(), "TC-T\n---C");
+        assert_eq!(Grid::from(&compact).rules(), RuleSet::torus());
+    }
+
+    #[test]
+    fn undo_nested_camps() {
+        let grid = CompactGrid::from(&Grid::parse("T T T\n     ").unwrap());
+        let mut compact = grid.clone();
+        let mut log = Vec::new();
+        compact.set_camp_logged(Pos::new(1, 0), &mut log).unwrap();
+        let len = log.len();
+        let after_first = compact.clone();
+        compact.set_camp_logged(Pos::new(1, 2), &mut log).unwrap();
+        assert!(compact.set_camp_logged(Pos::new(1, 3), &mut log).is_err());
+        assert_eq!(Grid::from(&compact), Grid::parse("T-T-T\nC-C- ").unwrap());
+        compact.undo(&mut log, len);
+        assert_eq!(compact, after_first);
+        compact.undo(&mut log, 0);
+        assert_eq!(compact, grid);
+    }
+
+    #[test]
+    fn intersect_unassigns_differences() {
+        let mut a = CompactGrid::from(&Grid::parse("CT-#\n-TC#").unwrap());
+        let b = CompactGrid::from(&Grid::parse("-TC#\n-T-C").unwrap());
+        a.intersect(&b);
+        assert_eq!(Grid::from(&a), Grid::parse(" T #\n-T  ").unwrap());
+    }
+}
@@ -0,0 +1,215 @@
+use board::*;
+use pos::*;
+use std::fmt;
+use tile::Tile::*;
+
+/// The reason a `Board` can't be solved.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Contradiction {
+    /// A row has more [`Camp`]s than its clue.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    RowOverfilled,
+    /// A column has more [`Camp`]s than its clue.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    ColumnOverfilled,
+    /// A row doesn't have enough space left to reach its clue.
+    RowUnderfilled,
+    /// A column doesn't have enough space left to reach its clue.
+    ColumnUnderfilled,
+    /// There are more [`Camp`]s than the total.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    TotalOverfilled,
+    /// There isn't enough space left to reach the total.
+    TotalUnderfilled,
+    /// Two [`Camp`]s touch, possibly diagonally.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    AdjacentCamps,
+    /// A [`Camp`] has no [`Tree`] next to it.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    LonelyCamp,
+    /// Two [`Camp`]s can only be associated with the same [`Tree`].
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    SharedTree,
+    /// Some [`Tree`]s are next to fewer places for [`Camp`]s than there
+    /// are [`Tree`]s, so one of them can't have a [`Camp`].
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    TreeWithoutCamp,
+}
+
+impl fmt::Display for Contradiction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Contradiction::RowOverfilled => "row has more camps than its clue",
+                Contradiction::ColumnOverfilled => "column has more camps than its clue",
+                Contradiction::RowUnderfilled => "row can't reach its clue",
+                Contradiction::ColumnUnderfilled => "column can't reach its clue",
+                Contradiction::TotalOverfilled => "board has more camps than the total",
+                Contradiction::TotalUnderfilled => "board can't reach the total",
+                Contradiction::AdjacentCamps => "camps are next to each other",
+                Contradiction::LonelyCamp => "camp has no tree next to it",
+                Contradiction::SharedTree => "camps share their only tree",
+                Contradiction::TreeWithoutCamp => "tree has no room for a camp",
+            }
+        )
+    }
+}
+
+/// Find a rule the `Board` already breaks.
+///
+/// Returns the position of an offending [`Tile`] alongside the
+/// [`Contradiction`].  For rows and columns that can't reach their
+/// clue, the first [`Tile`] of the line is used.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Board, Contradiction, Pos, find_contradiction};
+/// let board = Board::new_parse(vec![1, 0], vec![1, 1], "CC\nTT").unwrap();
+/// assert_eq!(
+///     find_contradiction(&board),
+///     Some((Pos::new(0, 1), Contradiction::RowOverfilled))
+/// );
+/// ```
+///
+/// [`Tile`]: enum.Tile.html
+/// [`Contradiction`]: enum.Contradiction.html
+pub fn find_contradiction(board: &Board) -> Option<(Pos, Contradiction)> {
+    for row in 0..board.num_rows() {
+        let clue = board.row_range(row);
+        let camps = board.count_in_row(row, Camp);
+        if clue.is_over(camps) {
+            let column = (0..board.num_columns())
+                .rev()
+                .find(|&c| board[(row, c)] == Camp)
+                .unwrap();
+            return Some((Pos::new(row, column), Contradiction::RowOverfilled));
+        }
+        if !clue.is_reachable(camps + board.count_in_row(row, Unassigned)) {
+            return Some((Pos::new(row, 0), Contradiction::RowUnderfilled));
+        }
+    }
+    for column in 0..board.num_columns() {
+        let clue = board.column_range(column);
+        let camps = board.count_in_column(column, Camp);
+        if clue.is_over(camps) {
+            let row = (0..board.num_rows())
+                .rev()
+                .find(|&r| board[(r, column)] == Camp)
+                .unwrap();
+            return Some((Pos::new(row, column), Contradiction::ColumnOverfilled));
+        }
+        if !clue.is_reachable(camps + board.count_in_column(column, Unassigned)) {
+            return Some((Pos::new(0, column), Contradiction::ColumnUnderfilled));
+        }
+    }
+    let camps = board.count(Camp);
+    if camps > board.total() {
+        return Some((Pos::new(0, 0), Contradiction::TotalOverfilled));
+    }
+    if camps + board.count(Unassigned) < board.total() {
+        return Some((Pos::new(0, 0), Contradiction::TotalUnderfilled));
+    }
+    if let Some(cell) = board.find_adjacent_camps() {
+        return Some((cell, Contradiction::AdjacentCamps));
+    }
+    if let Some(&cell) = board.treeless_camps().first() {
+        return Some((cell, Contradiction::LonelyCamp));
+    }
+    if let Some(&cell) = board.orphaned_trees().first() {
+        return Some((cell, Contradiction::TreeWithoutCamp));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_contradiction_none() {
+        let board = Board::new_parse(vec![1, 0], vec![1, 0], "CT\n  ").unwrap();
+        assert_eq!(find_contradiction(&board), None);
+    }
+
+    #[test]
+    fn find_contradiction_orphans() {
+        let board = Board::new_parse_clue_free(1, "C-\n-T").unwrap();
+        assert_eq!(
+            find_contradiction(&board),
+            Some((Pos::new(0, 0), Contradiction::LonelyCamp))
+        );
+        let board = Board::new_parse_clue_free(2, "T-  \n-  T").unwrap();
+        assert_eq!(
+            find_contradiction(&board),
+            Some((Pos::new(0, 0), Contradiction::TreeWithoutCamp))
+        );
+    }
+
+    #[test]
+    fn find_contradiction_row_overfilled() {
+        let board = Board::new_parse(vec![1, 0, 0], vec![1, 0, 1], "C C\nT T\n   ").unwrap();
+        assert_eq!(
+            find_contradiction(&board),
+            Some((Pos::new(0, 2), Contradiction::RowOverfilled))
+        );
+    }
+
+    #[test]
+    fn find_contradiction_column_overfilled() {
+        let board = Board::new_parse(vec![1, 0, 1], vec![1, 0, 0], "CT \n   \nCT ").unwrap();
+        assert_eq!(
+            find_contradiction(&board),
+            Some((Pos::new(2, 0), Contradiction::ColumnOverfilled))
+        );
+    }
+
+    #[test]
+    fn find_contradiction_underfilled() {
+        let board = Board::new_parse(vec![1, 0], vec![0, 1], "-T\n- ").unwrap();
+        assert_eq!(
+            find_contradiction(&board),
+            Some((Pos::new(0, 0), Contradiction::RowUnderfilled))
+        );
+        let board = Board::new_parse(vec![0, 1], vec![1, 0], "-T\n- ").unwrap();
+        assert_eq!(
+            find_contradiction(&board),
+            Some((Pos::new(0, 0), Contradiction::ColumnUnderfilled))
+        );
+    }
+
+    #[test]
+    fn find_contradiction_total() {
+        let board = Board::new_parse_clue_free(0, "CT").unwrap();
+        assert_eq!(
+            find_contradiction(&board),
+            Some((Pos::new(0, 0), Contradiction::TotalOverfilled))
+        );
+        let board = Board::new_parse_clue_free(1, "-T").unwrap();
+        assert_eq!(
+            find_contradiction(&board),
+            Some((Pos::new(0, 0), Contradiction::TotalUnderfilled))
+        );
+    }
+
+    #[test]
+    fn find_contradiction_adjacent_camps() {
+        let board = Board::new_parse_clue_free(2, " CT\nCT ").unwrap();
+        assert_eq!(
+            find_contradiction(&board),
+            Some((Pos::new(0, 1), Contradiction::AdjacentCamps))
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A count that can be added to through a shared reference, even from
+/// several threads at once.
+///
+/// Cloning a `Counter` copies its current value into a new, separate
+/// count.
+#[derive(Debug, Default)]
+pub struct Counter(AtomicUsize);
+
+impl Counter {
+    /// Add `n` to the count.
+    pub fn add(&self, n: usize) {
+        self.0.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Get the current count.
+    pub fn get(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Set the count back to `0`.
+    pub fn reset(&self) {
+        self.0.store(0, Ordering::Relaxed);
+    }
+}
+
+impl Clone for Counter {
+    fn clone(&self) -> Self {
+        Counter(AtomicUsize::new(self.get()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_are_separate() {
+        let counter = Counter::default();
+        counter.add(2);
+        let clone = counter.clone();
+        counter.add(3);
+        assert_eq!(counter.get(), 5);
+        assert_eq!(clone.get(), 2);
+        counter.reset();
+        assert_eq!(counter.get(), 0);
+    }
+}
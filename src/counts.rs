@@ -0,0 +1,99 @@
+use grid::*;
+use pos::*;
+use tile::Tile;
+
+/// The number of kinds of [`Tile`].
+///
+/// [`Tile`]: enum.Tile.html
+const KINDS: usize = 5;
+
+/// The number of each kind of [`Tile`] in every row and column of a
+/// [`Grid`].
+///
+/// [`Board`] keeps these up to date as [`Tile`]s are written so that
+/// counting doesn't have to scan the [`Grid`].
+///
+/// [`Tile`]: enum.Tile.html
+/// [`Grid`]: struct.Grid.html
+/// [`Board`]: struct.Board.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Counts {
+    rows: Vec<[usize; KINDS]>,
+    columns: Vec<[usize; KINDS]>,
+    total: [usize; KINDS],
+}
+
+impl Counts {
+    /// Count every `Tile` in the `Grid`.
+    pub fn new(grid: &Grid) -> Self {
+        let mut counts = Counts {
+            rows: vec![[0; KINDS]; grid.num_rows()],
+            columns: vec![[0; KINDS]; grid.num_columns()],
+            total: [0; KINDS],
+        };
+        for (pos, tile) in grid.cells() {
+            counts.add(pos, tile, 1);
+        }
+        counts
+    }
+
+    /// Get the number of `Tile`s equal to `tile` in the given row.
+    pub fn row(&self, row: usize, tile: Tile) -> usize {
+        self.rows[row][tile as usize]
+    }
+
+    /// Get the number of `Tile`s equal to `tile` in the given column.
+    pub fn column(&self, column: usize, tile: Tile) -> usize {
+        self.columns[column][tile as usize]
+    }
+
+    /// Get the number of `Tile`s equal to `tile` in the whole `Grid`.
+    pub fn total(&self, tile: Tile) -> usize {
+        self.total[tile as usize]
+    }
+
+    /// Record that the `Tile` at `pos` changed from `old` to `new`.
+    pub fn update(&mut self, pos: Pos, old: Tile, new: Tile) {
+        self.add(pos, old, -1);
+        self.add(pos, new, 1);
+    }
+
+    fn add(&mut self, pos: Pos, tile: Tile, delta: isize) {
+        let tile = tile as usize;
+        for count in [
+            &mut self.rows[pos.row][tile],
+            &mut self.columns[pos.column][tile],
+            &mut self.total[tile],
+        ] {
+            *count = (*count as isize + delta) as usize;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tile::Tile::*;
+
+    #[test]
+    fn new_counts_grid() {
+        let counts = Counts::new(&Grid::parse("TC-\n - ").unwrap());
+        assert_eq!(counts.row(0, Tree), 1);
+        assert_eq!(counts.row(1, Unassigned), 2);
+        assert_eq!(counts.column(2, Grass), 1);
+        assert_eq!(counts.column(1, Camp), 1);
+        assert_eq!(counts.total(Grass), 2);
+    }
+
+    #[test]
+    fn update_moves_tile() {
+        let mut grid = Grid::parse("TC-\n - ").unwrap();
+        let mut counts = Counts::new(&grid);
+        grid[(1, 0)] = Camp;
+        counts.update(Pos::new(1, 0), Unassigned, Camp);
+        assert_eq!(counts, Counts::new(&grid));
+        assert_eq!(counts.row(1, Camp), 1);
+        assert_eq!(counts.column(0, Unassigned), 0);
+        assert_eq!(counts.total(Camp), 2);
+    }
+}
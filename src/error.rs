@@ -0,0 +1,268 @@
+use contradiction::*;
+use pos::*;
+use std::error;
+use std::fmt;
+use violation::*;
+
+/// The errors produced by this crate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A character couldn't be parsed as a [`Tile`].
+    ///
+    /// `position` is the position of the character when it is known.
+    ///
+    /// [`Tile`]: enum.Tile.html
+    Parse {
+        character: char,
+        position: Option<Pos>,
+    },
+    /// Row `row` of a [`Grid`] has `columns` [`Tile`]s but `expected`
+    /// were expected.
+    ///
+    /// [`Grid`]: struct.Grid.html
+    /// [`Tile`]: enum.Tile.html
+    RaggedRow {
+        row: usize,
+        columns: usize,
+        expected: usize,
+    },
+    /// Input in an interchange `format`, such as JSON, was malformed.
+    Format {
+        format: &'static str,
+        message: String,
+    },
+    /// A [`Camp`] couldn't be placed at `pos`, or is already there,
+    /// but there is another [`Camp`] at `conflict`.
+    ///
+    /// `conflict` is either `pos` itself or one of its neighbors,
+    /// possibly diagonally.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    CampConflict { pos: Pos, conflict: Pos },
+    /// A [`Camp`] couldn't be placed at `pos` because its row, its
+    /// column or the whole `Board` already has all of its [`Camp`]s.
+    ///
+    /// `reason` is [`Contradiction::RowOverfilled`],
+    /// [`Contradiction::ColumnOverfilled`] or
+    /// [`Contradiction::TotalOverfilled`].
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Contradiction::RowOverfilled`]: enum.Contradiction.html#variant.RowOverfilled
+    /// [`Contradiction::ColumnOverfilled`]: enum.Contradiction.html#variant.ColumnOverfilled
+    /// [`Contradiction::TotalOverfilled`]: enum.Contradiction.html#variant.TotalOverfilled
+    ClueExceeded { pos: Pos, reason: Contradiction },
+    /// A [`Tree`] would be added or removed at `pos`.  [`Tree`]s are
+    /// fixed once a `Board` is set up.
+    ///
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    TreeChanged { pos: Pos },
+    /// A [`Blocked`] `Tile` would be added or removed at `pos`.
+    ///
+    /// [`Blocked`]: enum.Tile.html#variant.Blocked
+    BlockedChanged { pos: Pos },
+    /// The solver reached a steady state before solving the `Board`.
+    ///
+    /// `remaining` holds the position of every [`Unassigned`]
+    /// [`Tile`].
+    ///
+    /// [`Tile`]: enum.Tile.html
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    Unsolved { remaining: Vec<Pos> },
+    /// The [`SolverLimits`] stopped the solver before it finished.
+    ///
+    /// `remaining` holds the position of every [`Unassigned`]
+    /// [`Tile`].
+    ///
+    /// [`SolverLimits`]: struct.SolverLimits.html
+    /// [`Tile`]: enum.Tile.html
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    Stopped { remaining: Vec<Pos> },
+    /// The `Board` doesn't have exactly one solution.
+    ///
+    /// `solutions` is `0` if it has none and `2` if it has several.
+    NotUnique { solutions: usize },
+    /// The solver finished a `Board` that breaks `violations`.
+    ///
+    /// This is a bug in the solver.  See [`SolverConfig::verify`].
+    ///
+    /// [`SolverConfig::verify`]: struct.SolverConfig.html#structfield.verify
+    InvalidSolution { violations: Vec<Violation> },
+    /// The `Board` breaks the rules at `cell` and can't be solved.
+    Contradiction { cell: Pos, reason: Contradiction },
+    /// The row clues add up to `rows` but the column clues add up to
+    /// `columns`.
+    ClueSumMismatch { rows: usize, columns: usize },
+    /// There are `trees` [`Tree`]s but `camps` [`Camp`]s are required.
+    ///
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    TreeCountMismatch { trees: usize, camps: usize },
+    /// The [`Tree`] at `pos` has no neighbors to put a [`Camp`] on.
+    ///
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    IsolatedTree { pos: Pos },
+    /// The number of clues doesn't match the size of the [`Grid`].
+    ///
+    /// [`Grid`]: struct.Grid.html
+    DimensionMismatch {
+        rows: usize,
+        columns: usize,
+        grid_rows: usize,
+        grid_columns: usize,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse {
+                character,
+                position: None,
+            } => write!(f, "Couldn't parse tile: '{}'", character),
+            Error::Parse {
+                character,
+                position: Some(pos),
+            } => write!(
+                f,
+                "Couldn't parse tile: '{}' at row {}, column {}",
+                character, pos.row, pos.column
+            ),
+            Error::RaggedRow {
+                row,
+                columns,
+                expected,
+            } => write!(
+                f,
+                "Row {} has {} tiles but {} were expected",
+                row, columns, expected
+            ),
+            Error::Format { format, message } => write!(f, "Invalid {}: {}", format, message),
+            Error::CampConflict { pos, conflict } => write!(
+                f,
+                "Can't place a camp at row {}, column {}: there is a camp at row {}, column {}",
+                pos.row, pos.column, conflict.row, conflict.column
+            ),
+            Error::ClueExceeded { pos, reason } => write!(
+                f,
+                "Can't place a camp at row {}, column {}: the {} already has all of its camps",
+                pos.row,
+                pos.column,
+                match reason {
+                    Contradiction::RowOverfilled => "row",
+                    Contradiction::ColumnOverfilled => "column",
+                    _ => "board",
+                }
+            ),
+            Error::TreeChanged { pos } => write!(
+                f,
+                "Can't add or remove a tree at row {}, column {}",
+                pos.row, pos.column
+            ),
+            Error::BlockedChanged { pos } => write!(
+                f,
+                "Can't add or remove a blocked cell at row {}, column {}",
+                pos.row, pos.column
+            ),
+            Error::Unsolved { remaining } => write!(
+                f,
+                "Reached steady state with {} tiles remaining",
+                remaining.len()
+            ),
+            Error::Stopped { remaining } => {
+                write!(f, "Stopped early with {} tiles remaining", remaining.len())
+            }
+            Error::NotUnique { solutions: 0 } => write!(f, "The puzzle has no solution"),
+            Error::NotUnique { .. } => write!(f, "The puzzle has more than one solution"),
+            Error::InvalidSolution { ref violations } => {
+                write!(
+                    f,
+                    "Internal error: the solver broke {} rule{}",
+                    violations.len(),
+                    if violations.len() == 1 { "" } else { "s" }
+                )?;
+                for (i, violation) in violations.iter().enumerate() {
+                    write!(f, "{}{}", if i == 0 { ": " } else { "; " }, violation)?;
+                }
+                Ok(())
+            }
+            Error::Contradiction { cell, reason } => write!(
+                f,
+                "Puzzle is inconsistent at row {}, column {}: {}",
+                cell.row, cell.column, reason
+            ),
+            Error::ClueSumMismatch { rows, columns } => write!(
+                f,
+                "Row clues add up to {} but column clues add up to {}",
+                rows, columns
+            ),
+            Error::TreeCountMismatch { trees, camps } => write!(
+                f,
+                "There are {} trees but {} camps are required",
+                trees, camps
+            ),
+            Error::IsolatedTree { pos } => write!(
+                f,
+                "Tree at row {}, column {} has no room for a camp",
+                pos.row, pos.column
+            ),
+            Error::DimensionMismatch {
+                rows,
+                columns,
+                grid_rows,
+                grid_columns,
+            } => write!(
+                f,
+                "There are {} row and {} column clues but the grid is {}x{}",
+                rows, columns, grid_rows, grid_columns
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clue::*;
+
+    #[test]
+    fn invalid_solution_display() {
+        assert_eq!(
+            Error::InvalidSolution { violations: vec![] }.to_string(),
+            "Internal error: the solver broke 0 rules"
+        );
+        let row = Violation {
+            kind: ViolationKind::RowClue {
+                row: 0,
+                clue: Clue::exactly(1),
+                camps: 0,
+            },
+            cells: vec![Pos::new(0, 0)],
+        };
+        let column = Violation {
+            kind: ViolationKind::ColumnClue {
+                column: 1,
+                clue: Clue::exactly(0),
+                camps: 1,
+            },
+            cells: vec![Pos::new(0, 1)],
+        };
+        assert_eq!(
+            Error::InvalidSolution {
+                violations: vec![row.clone()]
+            }
+            .to_string(),
+            "Internal error: the solver broke 1 rule: Row 0 has 0 camps but needs 1"
+        );
+        assert_eq!(
+            Error::InvalidSolution {
+                violations: vec![row, column]
+            }
+            .to_string(),
+            "Internal error: the solver broke 2 rules: Row 0 has 0 camps but needs 1; \
+             Column 1 has 1 camps but needs 0"
+        );
+    }
+}
@@ -0,0 +1,401 @@
+use board::*;
+use board_observer::*;
+use clue::*;
+use pos::*;
+use render::{cell_name, column_name, row_name};
+use std::io::{self, Write};
+use std::sync::Mutex;
+use tile::Tile::{self, *};
+
+/// Explain a step of a solve in plain sentences.
+///
+/// `strategy` is the [`Strategy::name`] of the strategy that made the
+/// step, `changes` are the `Tile`s it set and `board` is the `Board`
+/// after the step.  Cells are named by [`cell_name`], so the sentences
+/// match the labels drawn by the renderers.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{explain, Board, Pos, Tile::*};
+/// let board = Board::new_parse(vec![1, 1], vec![2, 0], "C-\nTC").unwrap();
+/// assert_eq!(
+///     explain("fill_zeros", &board, &[(Pos::new(0, 1), Grass)]),
+///     vec!["Row 1 already has its 1 camp, so the rest of row 1 is grass."]
+/// );
+/// ```
+///
+/// [`Strategy::name`]: trait.Strategy.html#tymethod.name
+/// [`cell_name`]: render/fn.cell_name.html
+pub fn explain(strategy: &str, board: &Board, changes: &[(Pos, Tile)]) -> Vec<String> {
+    match strategy {
+        "fill_zeros" => explain_lines(board, changes, |line| {
+            let most = line.clue.max.unwrap_or(0);
+            if most == 0 {
+                format!(
+                    "{} needs no camps, so every open cell in {} is grass.",
+                    capitalize(&line.name),
+                    line.name
+                )
+            } else {
+                format!(
+                    "{} already has its {}, so the rest of {} is grass.",
+                    capitalize(&line.name),
+                    camps(most),
+                    line.name
+                )
+            }
+        }),
+        "fill_camps" => explain_lines(board, changes, |line| {
+            format!(
+                "{} has just enough room for its {}, so {}.",
+                capitalize(&line.name),
+                camps(line.clue.min),
+                tiles(&line.changes)
+            )
+        }),
+        "fill_segments" => explain_lines(board, changes, |line| {
+            format!(
+                "{} can only fit its {} by filling every other cell, so {}.",
+                capitalize(&line.name),
+                camps(line.clue.min),
+                tiles(&line.changes)
+            )
+        }),
+        "fill_tree_camps" => changes
+            .iter()
+            .map(|&(pos, _)| match lonely_tree(board, pos) {
+                Some(tree) => format!(
+                    "The tree at {} has nowhere else for its camp, so {} is a camp.",
+                    cell_name(tree.row, tree.column),
+                    cell_name(pos.row, pos.column)
+                ),
+                None => format!("{} is a camp.", cell_name(pos.row, pos.column)),
+            })
+            .collect(),
+        _ => vec![format!(
+            "{}, so {}.",
+            reason(strategy, board, changes),
+            tiles(changes)
+        )],
+    }
+}
+
+/// Why `strategy` made `changes`, for strategies that don't work one
+/// line at a time.
+fn reason(strategy: &str, board: &Board, changes: &[(Pos, Tile)]) -> String {
+    match strategy {
+        "initialize_grass" => format!(
+            "{} next to any tree",
+            if changes.len() == 1 {
+                "This cell isn't"
+            } else {
+                "These cells aren't"
+            }
+        ),
+        "fill_total" if changes.iter().all(|&(_, tile)| tile == Grass) => {
+            format!("All {} have been placed", camps(board.total()))
+        }
+        "fill_total" => "There are exactly as many open cells as camps left to place".to_string(),
+        "intersections" => {
+            "Every way of placing the camps of these rows and columns agrees".to_string()
+        }
+        "associate_trees" => "No tree that still needs a camp is next to these cells".to_string(),
+        "match_trees" => "Giving every tree its own camp only works one way here".to_string(),
+        "grass_unmatched" => {
+            "No way of giving every tree its own camp uses these cells".to_string()
+        }
+        "pair_intersections" => {
+            "Every way of placing the camps of these neighboring rows or columns agrees".to_string()
+        }
+        "trials" => "Anything else quickly leads to a contradiction".to_string(),
+        _ => format!("The {} strategy found that", strategy),
+    }
+}
+
+/// The changes of a step made in a single row or column.
+struct LineChanges {
+    /// The row or column, such as `"row 2"` or `"column B"`.
+    name: String,
+    /// The clue of the line.
+    clue: Clue,
+    changes: Vec<(Pos, Tile)>,
+}
+
+/// Explain `changes` one line at a time with `sentence`.
+///
+/// A change is put in its row if the row is now full, with as many
+/// [`Camp`]s as its clue allows, since then the row explains it.
+/// Otherwise it is put in its column.
+///
+/// [`Camp`]: enum.Tile.html#variant.Camp
+fn explain_lines<F: Fn(&LineChanges) -> String>(
+    board: &Board,
+    changes: &[(Pos, Tile)],
+    sentence: F,
+) -> Vec<String> {
+    let mut lines: Vec<LineChanges> = Vec::new();
+    for &(pos, tile) in changes {
+        let row_clue = board.row_range(pos.row);
+        let by_row = board.count_in_row(pos.row, Unassigned) == 0
+            && !row_clue.is_any()
+            && row_clue.allows(board.count_in_row(pos.row, Camp));
+        let (name, clue) = if by_row {
+            (format!("row {}", row_name(pos.row)), row_clue)
+        } else {
+            let name = format!("column {}", column_name(pos.column));
+            (name, board.column_range(pos.column))
+        };
+        match lines.iter_mut().find(|line| line.name == name) {
+            Some(line) => line.changes.push((pos, tile)),
+            None => lines.push(LineChanges {
+                name,
+                clue,
+                changes: vec![(pos, tile)],
+            }),
+        }
+    }
+    lines.iter().map(sentence).collect()
+}
+
+/// Find the [`Tree`] next to `pos` whose only [`Camp`] is at `pos`.
+///
+/// [`Tree`]: enum.Tile.html#variant.Tree
+/// [`Camp`]: enum.Tile.html#variant.Camp
+fn lonely_tree(board: &Board, pos: Pos) -> Option<Pos> {
+    board
+        .surrounding_tiles(pos)
+        .into_iter()
+        .filter(|&tree| board[tree] == Tree)
+        .find(|&tree| {
+            board
+                .surrounding_tiles(tree)
+                .into_iter()
+                .all(|x| x == pos || board[x] != Camp)
+        })
+}
+
+/// Say what each of `changes` became, like `"A1 is a camp and B2 and
+/// C2 are grass"`.
+fn tiles(changes: &[(Pos, Tile)]) -> String {
+    let mut parts = Vec::new();
+    for &(tile, singular, plural) in &[
+        (Camp, "is a camp", "are camps"),
+        (Grass, "is grass", "are grass"),
+    ] {
+        let matching: Vec<_> = changes.iter().filter(|x| x.1 == tile).cloned().collect();
+        match matching.len() {
+            0 => (),
+            1 => parts.push(format!("{} {}", cells(&matching), singular)),
+            _ => parts.push(format!("{} {}", cells(&matching), plural)),
+        }
+    }
+    parts.join(" and ")
+}
+
+/// List the cells of `changes`, like `"A1, B2 and C3"`.
+fn cells(changes: &[(Pos, Tile)]) -> String {
+    let names: Vec<_> = changes
+        .iter()
+        .map(|&(pos, _)| cell_name(pos.row, pos.column))
+        .collect();
+    match names.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+        Some((last, _)) => last.clone(),
+        None => String::new(),
+    }
+}
+
+/// `count` camps, like `"1 camp"` or `"2 camps"`.
+fn camps(count: usize) -> String {
+    if count == 1 {
+        "1 camp".to_string()
+    } else {
+        format!("{} camps", count)
+    }
+}
+
+/// `s` with its first letter in upper case.
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// A [`BoardObserver`] that writes an [`explain`]ed sentence per line
+/// for every step of a solve.
+///
+/// Write errors can't be reported while solving, so writing stops at
+/// the first one and [`Explainer::flush`] returns it.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Board, Explainer};
+/// # use std::sync::Arc;
+/// let explainer = Arc::new(Explainer::new(Vec::new()));
+/// let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n--").unwrap();
+/// board.observer = Some(explainer.clone());
+/// board.solve().unwrap();
+/// board.observer = None;
+/// let output = Arc::try_unwrap(explainer).ok().unwrap().into_inner().unwrap();
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "Row 1 has just enough room for its 1 camp, so A1 is a camp.\n"
+/// );
+/// ```
+///
+/// [`BoardObserver`]: trait.BoardObserver.html
+/// [`explain`]: fn.explain.html
+/// [`Explainer::flush`]: struct.Explainer.html#method.flush
+pub struct Explainer<W> {
+    state: Mutex<State<W>>,
+}
+
+/// The parts of an [`Explainer`] that change as it writes.
+///
+/// [`Explainer`]: struct.Explainer.html
+struct State<W> {
+    writer: W,
+    /// The changes made since the last step.
+    changes: Vec<(Pos, Tile)>,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> Explainer<W> {
+    /// Write the explanation to `writer`.
+    pub fn new(writer: W) -> Self {
+        Explainer {
+            state: Mutex::new(State {
+                writer,
+                changes: Vec::new(),
+                error: None,
+            }),
+        }
+    }
+
+    /// Flush the writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from writing a sentence, if there was
+    /// one, or else any error from flushing.
+    pub fn flush(&self) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        match state.error.take() {
+            Some(error) => Err(error),
+            None => state.writer.flush(),
+        }
+    }
+
+    /// Flush the writer and get it back.
+    ///
+    /// # Errors
+    ///
+    /// See [`Explainer::flush`].
+    ///
+    /// [`Explainer::flush`]: struct.Explainer.html#method.flush
+    pub fn into_inner(self) -> io::Result<W> {
+        self.flush()?;
+        Ok(self.state.into_inner().unwrap().writer)
+    }
+}
+
+impl<W: Write + Send> BoardObserver for Explainer<W> {
+    fn on_tile_set(&self, pos: Pos, _old: Tile, new: Tile, strategy: Option<&'static str>) {
+        if strategy.is_some() {
+            self.state.lock().unwrap().changes.push((pos, new));
+        }
+    }
+
+    fn on_step(&self, board: &Board, strategy: &'static str) {
+        let mut state = self.state.lock().unwrap();
+        let changes = std::mem::take(&mut state.changes);
+        if state.error.is_some() {
+            return;
+        }
+        for sentence in explain(strategy, board, &changes) {
+            if let Err(error) = writeln!(state.writer, "{}", sentence) {
+                state.error = Some(error);
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explain_fill_zeros() {
+        let board = Board::new_parse(vec![1, 0], vec![1, 0], "  \n--").unwrap();
+        assert_eq!(
+            explain(
+                "fill_zeros",
+                &board,
+                &[(Pos::new(1, 0), Grass), (Pos::new(1, 1), Grass)]
+            ),
+            vec!["Row 2 needs no camps, so every open cell in row 2 is grass."]
+        );
+        let board = Board::new_parse(vec![1, 1], vec![1, 0, 1], "C- \n-TC").unwrap();
+        assert_eq!(
+            explain("fill_zeros", &board, &[(Pos::new(0, 1), Grass)]),
+            vec!["Column B needs no camps, so every open cell in column B is grass."]
+        );
+    }
+
+    #[test]
+    fn explain_fill_camps() {
+        let board = Board::new_parse(vec![2, 0, 2], vec![2, 0, 2], "CTC\nT-T\nCTC").unwrap();
+        let changes = [
+            (Pos::new(0, 0), Camp),
+            (Pos::new(0, 2), Camp),
+            (Pos::new(2, 0), Camp),
+            (Pos::new(2, 2), Camp),
+        ];
+        assert_eq!(
+            explain("fill_camps", &board, &changes),
+            vec![
+                "Row 1 has just enough room for its 2 camps, so A1 and C1 are camps.",
+                "Row 3 has just enough room for its 2 camps, so A3 and C3 are camps.",
+            ]
+        );
+    }
+
+    #[test]
+    fn explain_fill_tree_camps() {
+        let board = Board::new_parse_clue_free(2, "-TC\n - \nT  ").unwrap();
+        assert_eq!(
+            explain("fill_tree_camps", &board, &[(Pos::new(0, 2), Camp)]),
+            vec!["The tree at B1 has nowhere else for its camp, so C1 is a camp."]
+        );
+    }
+
+    #[test]
+    fn explain_other_strategies() {
+        let board = Board::new_parse_clue_free(1, "CT-\n---").unwrap();
+        let changes = [
+            (Pos::new(0, 0), Camp),
+            (Pos::new(1, 0), Grass),
+            (Pos::new(1, 1), Grass),
+        ];
+        assert_eq!(
+            explain("trials", &board, &changes),
+            vec![
+                "Anything else quickly leads to a contradiction, so A1 is a camp and A2 and B2 \
+                 are grass."
+            ]
+        );
+        assert_eq!(
+            explain("initialize_grass", &board, &changes[1..2]),
+            vec!["This cell isn't next to any tree, so A2 is grass."]
+        );
+        assert_eq!(
+            explain("custom", &board, &changes[..1]),
+            vec!["The custom strategy found that, so A1 is a camp."]
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! A C interface for embedding the solver in other languages.
+//!
+//! A `Board` is created by [`ct_board_parse`] from the text or JSON
+//! formats read by the command line program, and must be released by
+//! [`ct_board_free`].  Strings returned by the library must be
+//! released by [`ct_string_free`].  The matching C declarations are
+//! in `include/camps_and_trees.h`.
+//!
+//! [`ct_board_parse`]: fn.ct_board_parse.html
+//! [`ct_board_free`]: fn.ct_board_free.html
+//! [`ct_string_free`]: fn.ct_string_free.html
+
+use board::*;
+use solve_outcome::*;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+
+/// [`ct_board_solve`] solved the `Board`.
+///
+/// [`ct_board_solve`]: fn.ct_board_solve.html
+pub const CT_SOLVED: c_int = 0;
+/// [`ct_board_solve`] got stuck before solving the `Board`.
+///
+/// [`ct_board_solve`]: fn.ct_board_solve.html
+pub const CT_UNSOLVED: c_int = 1;
+/// [`ct_board_solve`] found that the `Board` breaks the rules.
+///
+/// [`ct_board_solve`]: fn.ct_board_solve.html
+pub const CT_INVALID: c_int = 2;
+/// A null pointer was passed in.
+pub const CT_NULL: c_int = -1;
+
+/// Parse a `Board` from a NUL terminated string.
+///
+/// The string is read as JSON if it starts with `{` and as text
+/// otherwise.  Returns null if the string is malformed or isn't
+/// UTF-8.
+///
+/// # Safety
+///
+/// `input` must be null or point to a NUL terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ct_board_parse(input: *const c_char) -> *mut Board {
+    if input.is_null() {
+        return ptr::null_mut();
+    }
+    let input = match CStr::from_ptr(input).to_str() {
+        Ok(input) => input,
+        Err(_) => return ptr::null_mut(),
+    };
+    let board = if input.trim_start().starts_with('{') {
+        Board::from_json(input)
+    } else {
+        Board::from_text(input)
+    };
+    match board {
+        Ok(board) => Box::into_raw(Box::new(board)),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Solve the `Board` in place.
+///
+/// Returns [`CT_SOLVED`], [`CT_UNSOLVED`] if the `Board` was only
+/// partially solved or the solver was stopped early, [`CT_INVALID`] if it breaks the rules, or
+/// [`CT_NULL`] if `board` is null.
+///
+/// # Safety
+///
+/// `board` must be null or come from [`ct_board_parse`] and not have
+/// been freed.
+///
+/// [`CT_SOLVED`]: constant.CT_SOLVED.html
+/// [`CT_UNSOLVED`]: constant.CT_UNSOLVED.html
+/// [`CT_INVALID`]: constant.CT_INVALID.html
+/// [`CT_NULL`]: constant.CT_NULL.html
+/// [`ct_board_parse`]: fn.ct_board_parse.html
+#[no_mangle]
+pub unsafe extern "C" fn ct_board_solve(board: *mut Board) -> c_int {
+    let board = match board.as_mut() {
+        Some(board) => board,
+        None => return CT_NULL,
+    };
+    if board.validate().is_err() {
+        return CT_INVALID;
+    }
+    match board.solve_outcome() {
+        SolveOutcome::Solved => CT_SOLVED,
+        SolveOutcome::Stuck { .. } | SolveOutcome::Stopped { .. } => CT_UNSOLVED,
+        SolveOutcome::Contradiction { .. } => CT_INVALID,
+    }
+}
+
+/// Write the `Board` in the text format.
+///
+/// Returns null if `board` is null.  The result must be released by
+/// [`ct_string_free`].
+///
+/// # Safety
+///
+/// `board` must be null or come from [`ct_board_parse`] and not have
+/// been freed.
+///
+/// [`ct_board_parse`]: fn.ct_board_parse.html
+/// [`ct_string_free`]: fn.ct_string_free.html
+#[no_mangle]
+pub unsafe extern "C" fn ct_board_to_string(board: *const Board) -> *mut c_char {
+    match board.as_ref() {
+        // The text format never contains NUL.
+        Some(board) => CString::new(board.to_text()).unwrap().into_raw(),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Release a `Board` returned by [`ct_board_parse`].
+///
+/// Does nothing if `board` is null.
+///
+/// # Safety
+///
+/// `board` must be null or come from [`ct_board_parse`] and not have
+/// been freed.
+///
+/// [`ct_board_parse`]: fn.ct_board_parse.html
+#[no_mangle]
+pub unsafe extern "C" fn ct_board_free(board: *mut Board) {
+    if !board.is_null() {
+        drop(Box::from_raw(board));
+    }
+}
+
+/// Release a string returned by [`ct_board_to_string`].
+///
+/// Does nothing if `s` is null.
+///
+/// # Safety
+///
+/// `s` must be null or come from [`ct_board_to_string`] and not have
+/// been freed.
+///
+/// [`ct_board_to_string`]: fn.ct_board_to_string.html
+#[no_mangle]
+pub unsafe extern "C" fn ct_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(input: &str) -> (c_int, Option<String>) {
+        let input = CString::new(input).unwrap();
+        unsafe {
+            let board = ct_board_parse(input.as_ptr());
+            if board.is_null() {
+                return (CT_NULL, None);
+            }
+            let code = ct_board_solve(board);
+            let s = ct_board_to_string(board);
+            let text = CStr::from_ptr(s).to_str().unwrap().to_string();
+            ct_string_free(s);
+            ct_board_free(board);
+            (code, Some(text))
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            solve("1, 0\n1, 0\n T\n  "),
+            (CT_SOLVED, Some("1, 0\n1, 0\nCT\n--".to_string()))
+        );
+        assert_eq!(
+            solve(r#"{"total": 1, "grid": ["T", " "]}"#),
+            (CT_SOLVED, Some("total: 1\nT\nC".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(solve("1, 0\n1, 0\n T"), (CT_NULL, None));
+        assert_eq!(solve("2, 0\n1, 1\nTT\n  ").0, CT_INVALID);
+        assert_eq!(solve("1, 0\n1, 0\nTT\n  ").0, CT_INVALID);
+    }
+
+    #[test]
+    fn test_unsolved() {
+        assert_eq!(
+            solve("total: 1\n T \n   "),
+            (CT_UNSOLVED, Some("total: 1\n T \n- -".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_null() {
+        unsafe {
+            assert!(ct_board_parse(ptr::null()).is_null());
+            assert_eq!(ct_board_solve(ptr::null_mut()), CT_NULL);
+            assert!(ct_board_to_string(ptr::null()).is_null());
+            ct_board_free(ptr::null_mut());
+            ct_string_free(ptr::null_mut());
+        }
+    }
+}
@@ -1,9 +1,13 @@
 use board::*;
+use pos::*;
 use tile::Tile::*;
 
 /// Fill rows and columns with [`Camp`]s where there are [`Unassigned`]
 /// slots.
 ///
+/// A line needs a [`Camp`] in every open slot once it has no more of
+/// them than the fewest its clue allows.
+///
 /// Return whether any values were changed.
 ///
 /// # Examples
@@ -22,23 +26,23 @@ use tile::Tile::*;
 /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
 pub fn fill_camps(board: &mut Board) -> bool {
     let mut changed = false;
-    for row in 0..board.rows.len() {
-        if board.rows[row] == board.count_in_row(row, Unassigned) + board.count_in_row(row, Camp) {
-            for column in 0..board.columns.len() {
-                if board.grid[(row, column)] == Unassigned {
-                    board.grid[(row, column)] = Camp;
+    for row in 0..board.num_rows() {
+        let open = board.count_in_row(row, Unassigned) + board.count_in_row(row, Camp);
+        if board.row_range(row).needs_all(open) {
+            for column in 0..board.num_columns() {
+                if board[(row, column)] == Unassigned {
+                    board.set(Pos::new(row, column), Camp);
                     changed = true;
                 }
             }
         }
     }
-    for column in 0..board.columns.len() {
-        if board.columns[column]
-            == board.count_in_column(column, Unassigned) + board.count_in_column(column, Camp)
-        {
-            for row in 0..board.rows.len() {
-                if board.grid[(row, column)] == Unassigned {
-                    board.grid[(row, column)] = Camp;
+    for column in 0..board.num_columns() {
+        let open = board.count_in_column(column, Unassigned) + board.count_in_column(column, Camp);
+        if board.column_range(column).needs_all(open) {
+            for row in 0..board.num_rows() {
+                if board[(row, column)] == Unassigned {
+                    board.set(Pos::new(row, column), Camp);
                     changed = true;
                 }
             }
@@ -50,6 +54,7 @@ pub fn fill_camps(board: &mut Board) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use clue::*;
 
     #[test]
     fn fill_camps_0_camps() {
@@ -71,4 +76,20 @@ mod tests {
         assert!(fill_camps(&mut board));
         assert_eq!(board.debug(), "CTC\nT-T\nCTC");
     }
+
+    #[test]
+    fn fill_camps_rectangular() {
+        let mut board = Board::new_parse(vec![0, 1], vec![0, 0, 0, 1], "-T--\n--T ").unwrap();
+        assert!(fill_camps(&mut board));
+        assert_eq!(board.debug(), "-T--\n--TC");
+    }
+
+    #[test]
+    fn fill_camps_at_least() {
+        let rows = vec![Clue::at_least(1), Clue::at_most(1)];
+        let columns = vec![Clue::any(), Clue::between(1, 2)];
+        let mut board = Board::new_parse_ranged(rows, columns, "-T\nT ").unwrap();
+        assert!(fill_camps(&mut board));
+        assert_eq!(board.debug(), "-T\nTC");
+    }
 }
@@ -0,0 +1,148 @@
+use board::*;
+use pos::*;
+use tile::Tile::*;
+
+/// Fill rows and columns whose runs of [`Unassigned`] slots only just
+/// have room for the [`Camp`]s left to place.
+///
+/// [`Camp`]s can't touch, so a run of `n` [`Unassigned`] slots holds
+/// at most `(n + 1) / 2` of them.  If the runs of a row or column can
+/// hold exactly as many [`Camp`]s as are left, every run must be full.
+/// A run of odd length is only full with a [`Camp`] on every other
+/// slot, starting at the first one, and [`Grass`] between them.
+///
+/// If the `Board` wraps, the runs at the two ends of a line are one
+/// run.  A line with no slot filled in yet is then a ring of `n`
+/// slots, which holds at most `n / 2` [`Camp`]s in more than one way.
+///
+/// Return whether any values were changed.
+///
+/// # Examples
+///
+/// The top row has room for two [`Camp`]s, and needs both:
+///
+/// ```
+/// # use camps_and_trees::{Board, fill_segments};
+/// let mut board = Board::new_parse(vec![2, 0], vec![1, 0, 1], "   \nT-T").unwrap();
+/// assert!(fill_segments(&mut board));
+/// assert_eq!(board.debug(), "C-C\nT-T");
+/// ```
+///
+/// [`Camp`]: enum.Tile.html#variant.Camp
+/// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+/// [`Grass`]: enum.Tile.html#variant.Grass
+pub fn fill_segments(board: &mut Board) -> bool {
+    let wrap = board.rules().wrap;
+    let mut changed = false;
+    for row in 0..board.num_rows() {
+        let runs = board.row(row).segments();
+        let remaining = board
+            .row_range(row)
+            .min
+            .saturating_sub(board.count_in_row(row, Camp));
+        changed |= fill_line(board, runs, board.num_columns(), remaining, wrap);
+    }
+    for column in 0..board.num_columns() {
+        let runs = board.column(column).segments();
+        let remaining = board
+            .column_range(column)
+            .min
+            .saturating_sub(board.count_in_column(column, Camp));
+        changed |= fill_line(board, runs, board.num_rows(), remaining, wrap);
+    }
+    changed
+}
+
+/// Fill the odd `runs` of a line `len` [`Tile`]s long if they only
+/// just have room for `remaining` [`Camp`]s.
+///
+/// `wrap` is whether the ends of the line touch.
+///
+/// [`Tile`]: enum.Tile.html
+/// [`Camp`]: enum.Tile.html#variant.Camp
+fn fill_line(
+    board: &mut Board,
+    runs: Vec<Vec<Pos>>,
+    len: usize,
+    remaining: usize,
+    wrap: bool,
+) -> bool {
+    if wrap && len > 1 && runs.first().is_some_and(|run| run.len() == len) {
+        // A ring can't be filled in only one way.
+        return false;
+    }
+    if remaining == 0 || runs.iter().map(|run| run.len().div_ceil(2)).sum::<usize>() != remaining {
+        return false;
+    }
+    let mut changed = false;
+    for run in runs.into_iter().filter(|run| run.len() % 2 == 1) {
+        for (i, pos) in run.into_iter().enumerate() {
+            board.set(pos, if i % 2 == 0 { Camp } else { Grass });
+            changed = true;
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rule_set::*;
+
+    #[test]
+    fn fill_segments_odd_run() {
+        let mut board = Board::new_parse(vec![2, 0], vec![1, 0, 1], "   \nT-T").unwrap();
+        assert!(fill_segments(&mut board));
+        assert_eq!(board.debug(), "C-C\nT-T");
+        assert!(!fill_segments(&mut board));
+    }
+
+    #[test]
+    fn fill_segments_even_run_is_left_alone() {
+        // The row needs a `Camp` in each run, which fixes the odd run
+        // but not the even one.
+        let mut board = Board::new_parse(vec![2, 0], vec![1, 0, 0, 1], "  T \n T--").unwrap();
+        assert!(fill_segments(&mut board));
+        assert_eq!(board.debug(), "  TC\n T--");
+    }
+
+    #[test]
+    fn fill_segments_room_to_spare() {
+        let mut board = Board::new_parse(vec![1, 0, 0], vec![1, 0, 0], "   \n   \nT-T").unwrap();
+        assert!(!fill_segments(&mut board));
+        assert_eq!(board.debug(), "   \n   \nT-T");
+    }
+
+    #[test]
+    fn fill_segments_column() {
+        let mut board = Board::new_parse(vec![1, 0, 1], vec![0, 2, 0], "T  \n-  \nT  ").unwrap();
+        assert!(fill_segments(&mut board));
+        assert_eq!(board.debug(), "TC \n-- \nTC ");
+    }
+
+    #[test]
+    fn fill_segments_wrapped_run() {
+        // The runs at the ends of the top row join into one run of 3.
+        let mut board = Board::new_parse(vec![2, 0], vec![1, 0, 1, 0], " T  \n-T--").unwrap();
+        board.set_rules(RuleSet::torus());
+        assert!(fill_segments(&mut board));
+        assert_eq!(board.debug(), "CTC-\n-T--");
+    }
+
+    #[test]
+    fn fill_segments_ring_is_left_alone() {
+        // A ring of 5 only has room for 2 `Camp`s.
+        let mut board = Board::new_parse(vec![3, 0], vec![1, 0, 1, 0, 1], "     \nT-T-T").unwrap();
+        board.set_rules(RuleSet::torus());
+        let runs = board.row(0).segments();
+        assert!(!fill_line(&mut board, runs.clone(), 5, 3, true));
+        assert!(fill_line(&mut board, runs, 5, 3, false));
+    }
+
+    #[test]
+    fn fill_segments_counts_placed_camps() {
+        let mut board = Board::new_parse(vec![3, 0], vec![1, 0, 1, 0, 1], "C-   \nT-T-T").unwrap();
+        assert!(fill_segments(&mut board));
+        assert_eq!(board.debug(), "C-C-C\nT-T-T");
+    }
+}
@@ -0,0 +1,73 @@
+use board::*;
+use pos::*;
+use tile::Tile::*;
+
+/// Fill the `Board` based on the total number of [`Camp`]s.
+///
+/// If every [`Camp`] has been placed, the remaining [`Unassigned`]
+/// slots become [`Grass`].  If there are exactly as many
+/// [`Unassigned`] slots as [`Camp`]s left to place, they all become
+/// [`Camp`]s.  This is the only counting rule available for clue-free
+/// `Board`s.
+///
+/// Return whether any values were changed.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Board, fill_total};
+/// let mut board = Board::new_parse_clue_free(2, "T-T\n - ").unwrap();
+/// assert!(fill_total(&mut board));
+/// assert_eq!(board.debug(), "T-T\nC-C");
+/// ```
+///
+/// [`Camp`]: enum.Tile.html#variant.Camp
+/// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+/// [`Grass`]: enum.Tile.html#variant.Grass
+pub fn fill_total(board: &mut Board) -> bool {
+    let camps = board.count(Camp);
+    let unassigned = board.count(Unassigned);
+    let fill = if camps == board.total() {
+        Grass
+    } else if camps + unassigned == board.total() {
+        Camp
+    } else {
+        return false;
+    };
+    let mut changed = false;
+    for row in 0..board.num_rows() {
+        for column in 0..board.num_columns() {
+            if board[(row, column)] == Unassigned {
+                board.set(Pos::new(row, column), fill);
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_total_all_placed() {
+        let mut board = Board::new_parse_clue_free(1, "CT \n   ").unwrap();
+        assert!(fill_total(&mut board));
+        assert_eq!(board.debug(), "CT-\n---");
+    }
+
+    #[test]
+    fn fill_total_exact_match() {
+        let mut board = Board::new_parse_clue_free(2, "T-T\n - ").unwrap();
+        assert!(fill_total(&mut board));
+        assert_eq!(board.debug(), "T-T\nC-C");
+    }
+
+    #[test]
+    fn fill_total_ambiguous() {
+        let mut board = Board::new_parse_clue_free(1, " T \n---").unwrap();
+        assert!(!fill_total(&mut board));
+        assert_eq!(board.debug(), " T \n---");
+    }
+}
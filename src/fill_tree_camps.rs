@@ -0,0 +1,83 @@
+use board::*;
+use pos::*;
+use tile::Tile::*;
+
+/// Place a [`Camp`] next to each [`Tree`] that has only one place left
+/// for one.
+///
+/// A [`Tree`] without a [`Camp`] next to it still needs one, so if
+/// only one of the [`Tile`]s next to it is [`Unassigned`], that
+/// [`Tile`] must be its [`Camp`].  This is the reverse of
+/// [`associate_trees`], which goes from [`Camp`]s to [`Tree`]s.
+/// Nothing is placed if the [`RuleSet`] has `no_matching` set.
+///
+/// Return whether any values were changed.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Board, fill_tree_camps};
+/// let mut board = Board::new_parse_clue_free(2, "-T \n - \nT  ").unwrap();
+/// assert!(fill_tree_camps(&mut board));
+/// assert_eq!(board.debug(), "-TC\n - \nT  ");
+/// ```
+///
+/// [`Tile`]: enum.Tile.html
+/// [`Tree`]: enum.Tile.html#variant.Tree
+/// [`Camp`]: enum.Tile.html#variant.Camp
+/// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+/// [`associate_trees`]: fn.associate_trees.html
+/// [`RuleSet`]: struct.RuleSet.html
+pub fn fill_tree_camps(board: &mut Board) -> bool {
+    if board.rules().no_matching {
+        return false;
+    }
+    let trees: Vec<Pos> = board.positions_of(Tree).collect();
+    let mut changed = false;
+    for tree in trees {
+        let around = board.surrounding_tiles(tree);
+        if around.iter().any(|&pos| board[pos] == Camp) {
+            continue;
+        }
+        let mut free = around.into_iter().filter(|&pos| board[pos] == Unassigned);
+        if let (Some(pos), None) = (free.next(), free.next()) {
+            board.set(pos, Camp);
+            changed = true;
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_tree_camps_single_neighbor() {
+        let mut board = Board::new_parse_clue_free(2, "-T \n - \nT  ").unwrap();
+        assert!(fill_tree_camps(&mut board));
+        assert_eq!(board.debug(), "-TC\n - \nT  ");
+        assert!(!fill_tree_camps(&mut board));
+    }
+
+    #[test]
+    fn fill_tree_camps_two_neighbors() {
+        let mut board = Board::new_parse_clue_free(1, " T \n---").unwrap();
+        assert!(!fill_tree_camps(&mut board));
+        assert_eq!(board.debug(), " T \n---");
+    }
+
+    #[test]
+    fn fill_tree_camps_tree_with_camp() {
+        let mut board = Board::new_parse_clue_free(1, "CT \n---").unwrap();
+        assert!(!fill_tree_camps(&mut board));
+        assert_eq!(board.debug(), "CT \n---");
+    }
+
+    #[test]
+    fn fill_tree_camps_no_neighbors() {
+        let mut board = Board::new_parse_clue_free(1, "-T-\n---").unwrap();
+        assert!(!fill_tree_camps(&mut board));
+        assert_eq!(board.debug(), "-T-\n---");
+    }
+}
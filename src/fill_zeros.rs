@@ -1,8 +1,12 @@
 use board::*;
+use pos::*;
 use tile::Tile::*;
 
 /// Fill rows and columns with no remaining [`Camp`]s with [`Grass`].
 ///
+/// A line has no [`Camp`]s left once it reaches the most its clue
+/// allows.
+///
 /// Return whether any values were changed.
 ///
 /// # Examples
@@ -18,21 +22,24 @@ use tile::Tile::*;
 /// [`Grass`]: enum.Tile.html#variant.Grass
 pub fn fill_zeros(board: &mut Board) -> bool {
     let mut changed = false;
-    for row in 0..board.rows.len() {
-        if board.count_in_row(row, Camp) == board.rows[row] {
-            for column in 0..board.columns.len() {
-                if board.grid[(row, column)] == Unassigned {
-                    board.grid[(row, column)] = Grass;
+    for row in 0..board.num_rows() {
+        if board.row_range(row).is_full(board.count_in_row(row, Camp)) {
+            for column in 0..board.num_columns() {
+                if board[(row, column)] == Unassigned {
+                    board.set(Pos::new(row, column), Grass);
                     changed = true;
                 }
             }
         }
     }
-    for column in 0..board.columns.len() {
-        if board.count_in_column(column, Camp) == board.columns[column] {
-            for row in 0..board.rows.len() {
-                if board.grid[(row, column)] == Unassigned {
-                    board.grid[(row, column)] = Grass;
+    for column in 0..board.num_columns() {
+        if board
+            .column_range(column)
+            .is_full(board.count_in_column(column, Camp))
+        {
+            for row in 0..board.num_rows() {
+                if board[(row, column)] == Unassigned {
+                    board.set(Pos::new(row, column), Grass);
                     changed = true;
                 }
             }
@@ -44,6 +51,7 @@ pub fn fill_zeros(board: &mut Board) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use clue::*;
 
     #[test]
     fn fill_zeros_0_camps() {
@@ -65,4 +73,20 @@ mod tests {
         assert!(fill_zeros(&mut board));
         assert_eq!(board.debug(), "C-C\n   \n   ");
     }
+
+    #[test]
+    fn fill_zeros_rectangular() {
+        let mut board = Board::new_parse(vec![0, 1], vec![1, 0, 0, 0], "    \n    ").unwrap();
+        assert!(fill_zeros(&mut board));
+        assert_eq!(board.debug(), "----\n ---");
+    }
+
+    #[test]
+    fn fill_zeros_at_most() {
+        let rows = vec![Clue::at_most(1), Clue::at_least(1)];
+        let columns = vec![Clue::any(), Clue::between(0, 1)];
+        let mut board = Board::new_parse_ranged(rows, columns, "C \n  ").unwrap();
+        assert!(fill_zeros(&mut board));
+        assert_eq!(board.debug(), "C-\n  ");
+    }
 }
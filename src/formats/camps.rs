@@ -0,0 +1,125 @@
+use board::*;
+use matching::*;
+use pos::*;
+use tile::Tile::*;
+
+impl Board {
+    /// Pair each [`Camp`] with the [`Tree`] it serves.
+    ///
+    /// The pairs come from a maximum matching of [`Camp`]s with the
+    /// [`Tree`]s next to them, so on a solved `Board` every [`Camp`]
+    /// gets its own [`Tree`].  A [`Camp`] that can't be given one is
+    /// paired with `None`.  The [`Camp`]s are in row major order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, Pos};
+    /// let board = Board::new_parse(vec![1, 0, 1], vec![1, 1], "CT\n-T\n-C").unwrap();
+    /// assert_eq!(
+    ///     board.camp_trees(),
+    ///     vec![
+    ///         (Pos::new(0, 0), Some(Pos::new(0, 1))),
+    ///         (Pos::new(2, 1), Some(Pos::new(1, 1))),
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    pub fn camp_trees(&self) -> Vec<(Pos, Option<Pos>)> {
+        let camps: Vec<Pos> = self.positions_of(Camp).collect();
+        let trees: Vec<Pos> = self.positions_of(Tree).collect();
+        let edges = camps
+            .iter()
+            .map(|&camp| {
+                self.surrounding_tiles(camp)
+                    .into_iter()
+                    .filter_map(|pos| trees.binary_search(&pos).ok())
+                    .collect()
+            })
+            .collect();
+        let matching = Matching::new(edges, trees.len());
+        camps
+            .iter()
+            .enumerate()
+            .map(|(l, &camp)| (camp, matching.matched_left(l).map(|r| trees[r])))
+            .collect()
+    }
+
+    /// List the [`Camp`]s as `row,column` lines, for tools that only
+    /// need their coordinates.
+    ///
+    /// If `with_trees` is set, each line also gives the `row,column`
+    /// of the [`Tree`] the [`Camp`] serves, from
+    /// [`Board::camp_trees`].  Those fields are empty if it has none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// let board = Board::new_parse(vec![1, 0, 1], vec![1, 1], "CT\n-T\n-C").unwrap();
+    /// assert_eq!(board.to_camp_list(false), "0,0\n2,1\n");
+    /// assert_eq!(board.to_camp_list(true), "0,0,0,1\n2,1,1,1\n");
+    /// ```
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Board::camp_trees`]: struct.Board.html#method.camp_trees
+    pub fn to_camp_list(&self, with_trees: bool) -> String {
+        let mut s = String::new();
+        for (camp, tree) in self.camp_trees() {
+            s.push_str(&format!("{},{}", camp.row, camp.column));
+            match tree {
+                Some(tree) if with_trees => s.push_str(&format!(",{},{}", tree.row, tree.column)),
+                None if with_trees => s.push_str(",,"),
+                _ => (),
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn camp_trees_shares_trees() {
+        // The camp at row 0, column 2 could only use the tree the
+        // first camp needs, so it goes without.
+        let board = Board::new_parse(vec![2, 0], vec![1, 0, 1], "CTC\n---").unwrap();
+        assert_eq!(
+            board.camp_trees(),
+            vec![
+                (Pos::new(0, 0), Some(Pos::new(0, 1))),
+                (Pos::new(0, 2), None)
+            ]
+        );
+        assert_eq!(board.to_camp_list(true), "0,0,0,1\n0,2,,\n");
+    }
+
+    #[test]
+    fn camp_list_of_solved_board() {
+        let mut board = Board::new_parse(
+            vec![1, 1, 0, 2, 1],
+            vec![2, 0, 1, 1, 1],
+            "     \n T T \n     \nTT   \n    T",
+        )
+        .unwrap();
+        board.solve().unwrap();
+        let pairs = board.camp_trees();
+        assert_eq!(pairs.len(), 5);
+        for (camp, tree) in pairs {
+            assert!(board.surrounding_tiles(camp).contains(&tree.unwrap()));
+        }
+        assert_eq!(board.to_camp_list(false).lines().count(), 5);
+        assert_eq!(
+            Board::new_parse_clue_free(0, "T ")
+                .unwrap()
+                .to_camp_list(true),
+            ""
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use board::*;
+use error::*;
+use grid::*;
+
+/// Make an [`Error::Format`] for the combined format.
+///
+/// [`Error::Format`]: ../enum.Error.html#variant.Format
+fn combined_error<S: Into<String>>(message: S) -> Error {
+    Error::Format {
+        format: "combined puzzle",
+        message: message.into(),
+    }
+}
+
+/// Parse a clue in the combined format.
+fn clue(s: &str) -> Result<usize, Error> {
+    s.parse()
+        .map_err(|_| combined_error(format!("expected a clue but found {:?}", s)))
+}
+
+impl Board {
+    /// Parse a `Board` with its clues in the same block as the
+    /// [`Grid`].
+    ///
+    /// This is the layout most puzzle books use.  The first line
+    /// holds the column clues separated by spaces or `,`.  Each
+    /// following line is a row of the [`Grid`], one character per
+    /// column as read by [`Grid::parse`], followed by the row clue.
+    /// Spaces between the row and its clue are optional.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// assert_eq!(
+    ///     Board::parse_combined("1 0\n T 1\n   0"),
+    ///     Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ")
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If there are no rows or a clue is missing, an
+    /// [`Error::Format`] is returned.  Errors from parsing the
+    /// [`Grid`] are passed through.
+    ///
+    /// [`Grid`]: struct.Grid.html
+    /// [`Grid::parse`]: struct.Grid.html#method.parse
+    /// [`Error::Format`]: enum.Error.html#variant.Format
+    pub fn parse_combined(s: &str) -> Result<Board, Error> {
+        let mut lines = s.lines();
+        let header = lines
+            .next()
+            .ok_or_else(|| combined_error("missing the column clues"))?;
+        let columns = header
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|x| !x.is_empty())
+            .map(clue)
+            .collect::<Result<Vec<_>, _>>()?;
+        let width = columns.len();
+
+        let mut rows = Vec::new();
+        let mut grid = Vec::new();
+        for (row, line) in lines.enumerate() {
+            let split = line
+                .char_indices()
+                .nth(width)
+                .map(|(i, _)| i)
+                .ok_or_else(|| combined_error(format!("row {} is missing its clue", row + 1)))?;
+            let (tiles, rest) = line.split_at(split);
+            rows.push(clue(rest.trim())?);
+            grid.push(tiles);
+        }
+        if rows.is_empty() {
+            Err(combined_error("missing the rows of the grid"))?
+        }
+        Board::try_new(rows, columns, Grid::parse(&grid.join("\n"))?)
+    }
+
+    /// Write the `Board` in the format read by
+    /// [`Board::parse_combined`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+    /// assert_eq!(board.to_combined(), Ok("1 0\n T 1\n   0".to_string()));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Clue-free `Board`s, `Board`s with omitted or range clues and
+    /// `Board`s played by other than the classic [`RuleSet`] can't be
+    /// written in this format, so an [`Error::Format`] is returned for
+    /// them.
+    ///
+    /// [`RuleSet`]: struct.RuleSet.html
+    /// [`Board::parse_combined`]: struct.Board.html#method.parse_combined
+    /// [`Error::Format`]: enum.Error.html#variant.Format
+    pub fn to_combined(&self) -> Result<String, Error> {
+        let (rows, columns) = self.require_classic_exact("combined puzzle", true)?;
+        let columns: Vec<_> = columns.iter().map(|x| x.to_string()).collect();
+        let mut lines = vec![columns.join(" ")];
+        for (row, clue) in rows.iter().enumerate() {
+            let tiles: String = (0..self.num_columns())
+                .map(|column| format!("{:?}", self[(row, column)]))
+                .collect();
+            lines.push(format!("{} {}", tiles, clue));
+        }
+        Ok(lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rule_set::*;
+
+    #[test]
+    fn parse_combined_separators() {
+        let expected = Board::new_parse(vec![1, 10], vec![1, 0, 10], "TC-\n   ");
+        assert_eq!(Board::parse_combined("1,0,10\nTC-1\n   10"), expected);
+        assert_eq!(
+            Board::parse_combined("  1  0 10\nTC-  1\n    10\n"),
+            expected
+        );
+    }
+
+    #[test]
+    fn parse_combined_errors() {
+        assert!(Board::parse_combined("").is_err());
+        assert!(Board::parse_combined("1 0").is_err());
+        assert!(Board::parse_combined("1 x\n T 1\n   0").is_err());
+        assert!(Board::parse_combined("1 0\n T\n   0").is_err());
+        assert!(Board::parse_combined("1 0\n T 1\n   ").is_err());
+        assert!(Board::parse_combined("1 0\n x 1\n   0").is_err());
+    }
+
+    #[test]
+    fn combined_round_trip() {
+        let board = Board::new_parse(vec![1, 1], vec![1, 0, 1], "CTT\n  -").unwrap();
+        assert_eq!(
+            Board::parse_combined(&board.to_combined().unwrap()),
+            Ok(board)
+        );
+        let board = Board::new_parse_clue_free(1, " T\n  ").unwrap();
+        assert!(board.to_combined().is_err());
+        let mut board = Board::new_parse(vec![1], vec![1, 0], " T").unwrap();
+        board.set_rules(RuleSet::torus());
+        assert!(board.to_combined().is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use board::*;
+use error::*;
+
+/// A file of many puzzles, such as a regression set or a puzzle pack.
+///
+/// Each puzzle is in the format read by [`Board::from_text`].
+/// Puzzles are separated by empty lines or start with a header like
+/// `=== name ===`.  Puzzles without a header are named by their
+/// position in the file, starting with `puzzle 1`.
+///
+/// Rows of spaces are part of a puzzle, so only lines with nothing on
+/// them separate puzzles.
+///
+/// [`Board::from_text`]: struct.Board.html#method.from_text
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Corpus;
+
+impl Corpus {
+    /// Parse every puzzle in `s`, along with its name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, Corpus};
+    /// let text = "=== easy ===\n1, 0\n1, 0\n T\n  \n\ntotal: 1\nT \n  ";
+    /// let puzzles = Corpus::parse(text).unwrap();
+    /// assert_eq!(
+    ///     puzzles,
+    ///     vec![
+    ///         ("easy".to_string(), Board::from_text("1, 0\n1, 0\n T\n  ").unwrap()),
+    ///         ("puzzle 2".to_string(), Board::from_text("total: 1\nT \n  ").unwrap()),
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If a puzzle can't be parsed, or a header has no puzzle after
+    /// it, an [`Error::Format`] naming the puzzle and the line it
+    /// starts on is returned.
+    ///
+    /// [`Error::Format`]: enum.Error.html#variant.Format
+    pub fn parse(s: &str) -> Result<Vec<(String, Board)>, Error> {
+        let mut puzzles = Vec::new();
+        let mut name = None;
+        let mut start = 0;
+        let mut lines: Vec<&str> = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            let header = read_header(line);
+            if header.is_some() || line.is_empty() {
+                if !lines.is_empty() || (header.is_some() && name.is_some()) {
+                    puzzles.push(parse_puzzle(name.take(), start, &lines, puzzles.len())?);
+                    lines.clear();
+                }
+                if header.is_some() {
+                    name = header;
+                    start = i + 1;
+                }
+            } else {
+                if lines.is_empty() && name.is_none() {
+                    start = i + 1;
+                }
+                lines.push(line);
+            }
+        }
+        if !lines.is_empty() || name.is_some() {
+            puzzles.push(parse_puzzle(name, start, &lines, puzzles.len())?);
+        }
+        Ok(puzzles)
+    }
+}
+
+/// Read the name from a header line of the form `=== name ===`.
+///
+/// Returns `None` if `line` isn't a header.
+fn read_header(line: &str) -> Option<String> {
+    let name = line.trim().strip_prefix("===")?.strip_suffix("===")?.trim();
+    Some(name.to_string())
+}
+
+/// Parse the puzzle on `lines`, naming it `name` or after `index`,
+/// its position in the file counting from `0`.
+///
+/// `start` is the line the puzzle starts on, counting from `1`.
+fn parse_puzzle(
+    name: Option<String>,
+    start: usize,
+    lines: &[&str],
+    index: usize,
+) -> Result<(String, Board), Error> {
+    let name = name.unwrap_or_else(|| format!("puzzle {}", index + 1));
+    let board = if lines.is_empty() {
+        Err(Error::Format {
+            format: "corpus",
+            message: format!("{} (line {}): missing puzzle", name, start),
+        })
+    } else {
+        Board::from_text(&lines.join("\n")).map_err(|x| Error::Format {
+            format: "corpus",
+            message: format!("{} (line {}): {}", name, start, x),
+        })
+    }?;
+    Ok((name, board))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_blank_separated() {
+        let puzzles = Corpus::parse("\n1, 0\n1, 0\n T\n  \n\n\n1, 0\n1, 0\n  \n T\n").unwrap();
+        assert_eq!(
+            puzzles,
+            vec![
+                (
+                    "puzzle 1".to_string(),
+                    Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap()
+                ),
+                (
+                    "puzzle 2".to_string(),
+                    Board::new_parse(vec![1, 0], vec![1, 0], "  \n T").unwrap()
+                ),
+            ]
+        );
+        assert_eq!(Corpus::parse(""), Ok(Vec::new()));
+        assert_eq!(Corpus::parse("\n\n"), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn parse_headers() {
+        let puzzles = Corpus::parse(concat!(
+            "=== first ===\n",
+            "\n",
+            "1, 0, 0\n",
+            "1, 0, 0\n",
+            " T \n",
+            "   \n",
+            "   \n",
+            "=== second one===\n",
+            "total: 1\n",
+            "T \n",
+        ))
+        .unwrap();
+        assert_eq!(
+            puzzles,
+            vec![
+                (
+                    "first".to_string(),
+                    Board::new_parse(vec![1, 0, 0], vec![1, 0, 0], " T \n   \n   ").unwrap()
+                ),
+                (
+                    "second one".to_string(),
+                    Board::new_parse_clue_free(1, "T ").unwrap()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Corpus::parse("total: 1\nT \n\n=== bad ===\n1, 0\n1, x\n T\n  "),
+            Err(Error::Format {
+                format: "corpus",
+                message: "bad (line 4): Invalid puzzle text: Invalid clue \"x\"".to_string(),
+            })
+        );
+        assert_eq!(
+            Corpus::parse("total: 1\nT \n\n1, 0\n1, 0"),
+            Err(Error::Format {
+                format: "corpus",
+                message: "puzzle 2 (line 4): Invalid puzzle text: Too few lines.  There must be at least 3."
+                    .to_string(),
+            })
+        );
+        assert_eq!(
+            Corpus::parse("=== a ===\n=== b ===\ntotal: 1\nT "),
+            Err(Error::Format {
+                format: "corpus",
+                message: "a (line 1): missing puzzle".to_string(),
+            })
+        );
+        assert!(Corpus::parse("total: 1\nT \n=== empty ===\n").is_err());
+    }
+}
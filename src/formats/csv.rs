@@ -0,0 +1,402 @@
+use board::*;
+use clue::*;
+use error::*;
+use grid::*;
+
+/// Make an [`Error::Format`] for CSV.
+///
+/// [`Error::Format`]: ../enum.Error.html#variant.Format
+fn csv_error(message: String) -> Error {
+    Error::Format {
+        format: "CSV",
+        message,
+    }
+}
+
+/// The columns [`Csv::write`] uses to describe each `Board`, after its
+/// name.
+///
+/// [`Csv::write`]: struct.Csv.html#method.write
+pub const CSV_BOARD_COLUMNS: [&str; 6] = [
+    "height",
+    "width",
+    "total",
+    "row_clues",
+    "column_clues",
+    "grid",
+];
+
+/// Puzzles as comma separated values, the shape spreadsheet based
+/// puzzle editors export.
+///
+/// The first record is a header naming the columns, which may come in
+/// any order.  Each following record is a puzzle:
+///
+/// * `height` and `width` are the size of the `Board`.
+/// * `row_clues` and `column_clues` list the clues separated by spaces,
+///   `;` or `,`, with `?` for an omitted clue and `<=N`, `>=N` or
+///   `A..B` for a bound.  Clue-free `Board`s leave both empty and give
+///   a `total` instead.  `total` is optional otherwise.
+/// * `grid` is the rows of the [`Grid`] joined together.  `.` and ` `
+///   are both [`Unassigned`], so spreadsheets that trim spaces don't
+///   change the `Grid`.
+/// * `name` is optional.  Puzzles without one are named by their
+///   position, starting with `puzzle 1`.
+///
+/// Other columns are ignored.  Fields may be quoted with `"`, doubling
+/// any `"` inside them.
+///
+/// [`Grid`]: struct.Grid.html
+/// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Csv;
+
+impl Csv {
+    /// Parse every puzzle in `s`, along with its name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, Csv};
+    /// let csv = "name,height,width,row_clues,column_clues,grid\n\
+    ///            easy,2,2,1 0,1 0,.T..\n";
+    /// assert_eq!(
+    ///     Csv::parse(csv),
+    ///     Ok(vec![(
+    ///         "easy".to_string(),
+    ///         Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap()
+    ///     )])
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the header is missing a column, or a record can't be
+    /// parsed, an [`Error::Format`] naming the record is returned.
+    /// Errors from building the `Board` are passed through.
+    ///
+    /// [`Error::Format`]: enum.Error.html#variant.Format
+    pub fn parse(s: &str) -> Result<Vec<(String, Board)>, Error> {
+        let mut records = read_records(s)?.into_iter();
+        let header = match records.next() {
+            Some(header) => header,
+            None => return Ok(Vec::new()),
+        };
+        let column = |name: &str| header.iter().position(|x| x.trim() == name);
+        let required = |name: &str| {
+            column(name).ok_or_else(|| csv_error(format!("Missing column \"{}\"", name)))
+        };
+        let columns = Columns {
+            name: column("name"),
+            height: required("height")?,
+            width: required("width")?,
+            total: column("total"),
+            row_clues: required("row_clues")?,
+            column_clues: required("column_clues")?,
+            grid: required("grid")?,
+        };
+        records
+            .enumerate()
+            .map(|(i, record)| {
+                let name = match columns.name.and_then(|x| record.get(x)) {
+                    Some(name) if !name.trim().is_empty() => name.trim().to_string(),
+                    _ => format!("puzzle {}", i + 1),
+                };
+                let board = columns.parse(&record).map_err(|x| match x {
+                    Error::Format { message, .. } => csv_error(format!("{}: {}", name, message)),
+                    x => x,
+                })?;
+                Ok((name, board))
+            })
+            .collect()
+    }
+
+    /// Write `puzzles` in the format read by [`Csv::parse`], with a
+    /// `name` column followed by [`CSV_BOARD_COLUMNS`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, Csv};
+    /// let board = Board::new_parse(vec![1, 0], vec![1, 0], "CT\n--").unwrap();
+    /// assert_eq!(
+    ///     Csv::write(&[("easy".to_string(), board)]),
+    ///     "name,height,width,total,row_clues,column_clues,grid\n\
+    ///      easy,2,2,1,1 0,1 0,CT--\n"
+    /// );
+    /// ```
+    ///
+    /// [`Csv::parse`]: struct.Csv.html#method.parse
+    /// [`CSV_BOARD_COLUMNS`]: constant.CSV_BOARD_COLUMNS.html
+    pub fn write(puzzles: &[(String, Board)]) -> String {
+        let mut header = vec!["name".to_string()];
+        header.extend(CSV_BOARD_COLUMNS.iter().map(|x| x.to_string()));
+        let mut s = Csv::record(&header);
+        for (name, board) in puzzles {
+            let mut fields = vec![name.clone()];
+            fields.extend(Csv::board_fields(board));
+            s.push_str(&Csv::record(&fields));
+        }
+        s
+    }
+
+    /// The fields describing `board`, in the order of
+    /// [`CSV_BOARD_COLUMNS`].
+    ///
+    /// [`CSV_BOARD_COLUMNS`]: constant.CSV_BOARD_COLUMNS.html
+    pub fn board_fields(board: &Board) -> Vec<String> {
+        let clues = |clues: Vec<Clue>| {
+            let clues: Vec<String> = clues.iter().map(Clue::to_string).collect();
+            clues.join(" ")
+        };
+        vec![
+            board.num_rows().to_string(),
+            board.num_columns().to_string(),
+            board.total().to_string(),
+            clues(board.row_ranges()),
+            clues(board.column_ranges()),
+            board.debug().replace('\n', "").replace(' ', "."),
+        ]
+    }
+
+    /// Format `fields` as one record, ending with a newline.
+    ///
+    /// Fields holding `,`, `"` or a line break are quoted.
+    pub fn record(fields: &[String]) -> String {
+        let fields: Vec<String> = fields
+            .iter()
+            .map(|field| {
+                if field.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                } else {
+                    field.clone()
+                }
+            })
+            .collect();
+        format!("{}\n", fields.join(","))
+    }
+}
+
+/// The positions of the columns [`Csv::parse`] reads.
+///
+/// [`Csv::parse`]: struct.Csv.html#method.parse
+struct Columns {
+    name: Option<usize>,
+    height: usize,
+    width: usize,
+    total: Option<usize>,
+    row_clues: usize,
+    column_clues: usize,
+    grid: usize,
+}
+
+impl Columns {
+    /// Build the `Board` described by `record`.
+    fn parse(&self, record: &[String]) -> Result<Board, Error> {
+        let field = |column: usize| record.get(column).map_or("", |x| x.trim());
+        let number = |column: usize, name: &str| -> Result<usize, Error> {
+            field(column)
+                .parse()
+                .map_err(|_| csv_error(format!("\"{}\" must be a number", name)))
+        };
+        let height = number(self.height, "height")?;
+        let width = number(self.width, "width")?;
+        // Spaces are tiles, so the grid isn't trimmed.
+        let grid = record.get(self.grid).map_or("", |x| x.as_str());
+        let grid = grid.replace('.', " ");
+        let grid: Vec<char> = grid.chars().collect();
+        if grid.len() != height * width || width == 0 {
+            return Err(csv_error(format!(
+                "\"grid\" has {} tiles but should have {}",
+                grid.len(),
+                height * width
+            )));
+        }
+        let grid: Vec<String> = grid.chunks(width).map(|x| x.iter().collect()).collect();
+        let grid = Grid::parse(&grid.join("\n"))?;
+        let rows = read_clues(field(self.row_clues), "row_clues")?;
+        let columns = read_clues(field(self.column_clues), "column_clues")?;
+        let total = self.total.map(field).filter(|x| !x.is_empty());
+        if rows.is_empty() && columns.is_empty() {
+            let total =
+                total.ok_or_else(|| csv_error("clue-free puzzles need a \"total\"".to_string()))?;
+            let total = total
+                .parse()
+                .map_err(|_| csv_error("\"total\" must be a number".to_string()))?;
+            return Ok(Board::new_clue_free(total, grid));
+        }
+        Board::try_new_ranged(rows, columns, grid)
+    }
+}
+
+/// Parse a list of [`Clue`]s separated by spaces, `;` or `,`.
+///
+/// [`Clue`]: ../struct.Clue.html
+fn read_clues(s: &str, name: &str) -> Result<Vec<Clue>, Error> {
+    s.split(|c: char| c == ';' || c == ',' || c.is_whitespace())
+        .filter(|x| !x.is_empty())
+        .map(|x| {
+            x.parse()
+                .map_err(|_| csv_error(format!("\"{}\" must be a list of numbers", name)))
+        })
+        .collect()
+}
+
+/// Split `s` into records of fields, skipping empty lines.
+fn read_records(s: &str) -> Result<Vec<Vec<String>>, Error> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => (),
+            '\n' if !quoted => {
+                if !record.is_empty() || !field.is_empty() {
+                    record.push(std::mem::take(&mut field));
+                    records.push(std::mem::take(&mut record));
+                }
+            }
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(csv_error("unterminated quoted field".to_string()));
+    }
+    if !record.is_empty() || !field.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_records_quoting() {
+        assert_eq!(
+            read_records("a,\"b,c\",\"d \"\"e\"\"\"\r\n\n,x\n\"multi\nline\"").unwrap(),
+            vec![
+                vec!["a".to_string(), "b,c".to_string(), "d \"e\"".to_string()],
+                vec!["".to_string(), "x".to_string()],
+                vec!["multi\nline".to_string()],
+            ]
+        );
+        assert!(read_records("\"a").is_err());
+    }
+
+    #[test]
+    fn parse_columns_in_any_order() {
+        let csv = concat!(
+            "grid,column_clues,row_clues,width,height,notes\n",
+            "\" T   T\",\"1,1\",1;0;1,2,3,hard\n",
+            "\"CT\",,,2,1,\n",
+        );
+        assert_eq!(
+            Csv::parse(csv),
+            Err(csv_error(
+                "puzzle 2: clue-free puzzles need a \"total\"".to_string()
+            ))
+        );
+        let csv = concat!(
+            "grid,column_clues,row_clues,width,height,total\n",
+            "\" T   T\",\"1,1\",1;0;1,2,3,\n",
+            "\"CT\",,,2,1,1\n",
+        );
+        assert_eq!(
+            Csv::parse(csv),
+            Ok(vec![
+                (
+                    "puzzle 1".to_string(),
+                    Board::new_parse(vec![1, 0, 1], vec![1, 1], " T\n  \n T").unwrap()
+                ),
+                (
+                    "puzzle 2".to_string(),
+                    Board::new_parse_clue_free(1, "CT").unwrap()
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let parse = |record: &str| {
+            Csv::parse(&format!(
+                "name,height,width,row_clues,column_clues,grid\n{}",
+                record
+            ))
+        };
+        assert_eq!(
+            Csv::parse("name,height,width,row_clues,grid\n"),
+            Err(csv_error("Missing column \"column_clues\"".to_string()))
+        );
+        assert_eq!(
+            parse("a,x,2,1 0,1 0,.T.."),
+            Err(csv_error("a: \"height\" must be a number".to_string()))
+        );
+        assert_eq!(
+            parse("a,2,2,1 x,1 0,.T.."),
+            Err(csv_error(
+                "a: \"row_clues\" must be a list of numbers".to_string()
+            ))
+        );
+        assert_eq!(
+            parse("a,2,2,1 0,1 0,.T."),
+            Err(csv_error(
+                "a: \"grid\" has 3 tiles but should have 4".to_string()
+            ))
+        );
+        assert!(parse("a,2,2,1 0,1 0,.X..").is_err());
+        assert!(parse("a,2,2,1 0 0,1 0,.T..").is_err());
+        assert_eq!(Csv::parse(""), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn write_round_trip() {
+        let puzzles = vec![
+            (
+                "first, with a comma".to_string(),
+                Board::new_parse(vec![1, 0, 1], vec![1, 1], " T\n- \n T").unwrap(),
+            ),
+            (
+                "clue free".to_string(),
+                Board::new_parse_clue_free(1, "CT").unwrap(),
+            ),
+            (
+                "omitted".to_string(),
+                Board::new_parse_partial(vec![None], vec![Some(1), None], "CT").unwrap(),
+            ),
+            (
+                "ranged".to_string(),
+                Board::new_parse_ranged(
+                    vec![Clue::at_least(1)],
+                    vec![Clue::between(0, 1); 2],
+                    "CT",
+                )
+                .unwrap(),
+            ),
+        ];
+        let csv = Csv::write(&puzzles);
+        assert_eq!(
+            csv,
+            concat!(
+                "name,height,width,total,row_clues,column_clues,grid\n",
+                "\"first, with a comma\",3,2,2,1 0 1,1 1,.T-..T\n",
+                "clue free,1,2,1,,,CT\n",
+                "omitted,1,2,1,?,1 ?,CT\n",
+                "ranged,1,2,1,>=1,<=1 <=1,CT\n",
+            )
+        );
+        assert_eq!(Csv::parse(&csv), Ok(puzzles));
+    }
+}
@@ -0,0 +1,432 @@
+use board::*;
+use clue::*;
+use pos::*;
+use std::fmt::Write;
+use tile::Tile::*;
+
+/// A CNF formula under construction.
+struct Cnf {
+    variables: usize,
+    clauses: Vec<Vec<isize>>,
+}
+
+impl Cnf {
+    /// Allocate a fresh variable.
+    fn variable(&mut self) -> isize {
+        self.variables += 1;
+        self.variables as isize
+    }
+
+    /// Require that at most `k` of `literals` are true.
+    ///
+    /// This uses the sequential counter encoding.
+    fn at_most(&mut self, literals: &[isize], k: usize) {
+        let n = literals.len();
+        if k >= n {
+            return;
+        }
+        if k == 0 {
+            for &x in literals {
+                self.clauses.push(vec![-x]);
+            }
+            return;
+        }
+        // counters[i][j] is true if at least j + 1 of the first i + 1
+        // literals are true.
+        let counters: Vec<Vec<isize>> = (0..n - 1)
+            .map(|_| (0..k).map(|_| self.variable()).collect())
+            .collect();
+        self.clauses.push(vec![-literals[0], counters[0][0]]);
+        for &counter in &counters[0][1..] {
+            self.clauses.push(vec![-counter]);
+        }
+        for i in 1..n - 1 {
+            self.clauses.push(vec![-literals[i], counters[i][0]]);
+            self.clauses.push(vec![-counters[i - 1][0], counters[i][0]]);
+            for j in 1..k {
+                self.clauses
+                    .push(vec![-literals[i], -counters[i - 1][j - 1], counters[i][j]]);
+                self.clauses.push(vec![-counters[i - 1][j], counters[i][j]]);
+            }
+            self.clauses
+                .push(vec![-literals[i], -counters[i - 1][k - 1]]);
+        }
+        self.clauses
+            .push(vec![-literals[n - 1], -counters[n - 2][k - 1]]);
+    }
+
+    /// Require that at least `k` of `literals` are true.
+    fn at_least(&mut self, literals: &[isize], k: usize) {
+        if k > literals.len() {
+            self.clauses.push(Vec::new());
+            return;
+        }
+        let negated: Vec<_> = literals.iter().map(|x| -x).collect();
+        self.at_most(&negated, literals.len() - k);
+    }
+
+    /// Require that exactly `k` of `literals` are true.
+    fn exactly(&mut self, literals: &[isize], k: usize) {
+        self.within(literals, Clue::exactly(k));
+    }
+
+    /// Require that `clue` allows the number of `literals` that are
+    /// true.
+    fn within(&mut self, literals: &[isize], clue: Clue) {
+        if let Some(max) = clue.max {
+            self.at_most(literals, max);
+        }
+        self.at_least(literals, clue.min);
+    }
+}
+
+/// The variable associating a [`Tree`] with a [`Camp`] in a
+/// [`Dimacs`] encoding.
+///
+/// [`Tree`]: enum.Tile.html#variant.Tree
+/// [`Camp`]: enum.Tile.html#variant.Camp
+/// [`Dimacs`]: struct.Dimacs.html
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DimacsAssociation {
+    /// The position of the [`Tree`].
+    ///
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    pub tree: Pos,
+    /// The position of the [`Camp`].
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub camp: Pos,
+    /// The variable that is true when they are associated.
+    pub variable: usize,
+}
+
+/// A CNF encoding of a [`Board`] in DIMACS format.
+///
+/// See [`Board::to_dimacs`].
+///
+/// [`Board`]: struct.Board.html
+/// [`Board::to_dimacs`]: struct.Board.html#method.to_dimacs
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dimacs {
+    /// The formula in DIMACS CNF format.
+    pub cnf: String,
+    /// `cells[row][column]` is the variable that is true when there
+    /// is a [`Camp`] at `(row, column)`.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub cells: Vec<Vec<usize>>,
+    /// The variables associating [`Tree`]s with [`Camp`]s.
+    ///
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub associations: Vec<DimacsAssociation>,
+}
+
+impl Board {
+    /// Encode the `Board` as a SAT problem in DIMACS CNF format.
+    ///
+    /// Every cell has a variable that is true if it holds a
+    /// [`Camp`].  Every [`Tree`] and orthogonally adjacent cell has a
+    /// variable that is true if they are associated.  The clauses
+    /// require that:
+    ///
+    /// * [`Tile`]s already assigned keep their values.
+    /// * Each row and column has as many [`Camp`]s as its clue
+    ///   allows, and the whole `Board` has `total` if the clues don't
+    ///   fix it.
+    /// * No two [`Camp`]s touch, even diagonally.
+    /// * Each [`Tree`] is associated with exactly one [`Camp`] and
+    ///   each [`Camp`] with exactly one [`Tree`].
+    ///
+    /// The [`RuleSet`] can let [`Camp`]s touch diagonally or leave out
+    /// the associations.  Any other variables are auxiliary.  The
+    /// variable map is repeated in comment lines at the top of the
+    /// formula.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Tile`]: enum.Tile.html
+    /// [`RuleSet`]: struct.RuleSet.html
+    pub fn to_dimacs(&self) -> Dimacs {
+        let (height, width) = (self.num_rows(), self.num_columns());
+        let mut cnf = Cnf {
+            variables: 0,
+            clauses: Vec::new(),
+        };
+        let cells: Vec<Vec<isize>> = (0..height)
+            .map(|_| (0..width).map(|_| cnf.variable()).collect())
+            .collect();
+
+        for row in 0..height {
+            for column in 0..width {
+                match self[(row, column)] {
+                    Camp => cnf.clauses.push(vec![cells[row][column]]),
+                    Grass | Tree | Blocked => cnf.clauses.push(vec![-cells[row][column]]),
+                    Unassigned => (),
+                }
+            }
+        }
+
+        for (literals, clue) in cells.iter().zip(self.row_ranges()) {
+            cnf.within(literals, clue);
+        }
+        for (column, clue) in self.column_ranges().into_iter().enumerate() {
+            let literals: Vec<_> = cells.iter().map(|r| r[column]).collect();
+            cnf.within(&literals, clue);
+        }
+        if !self.has_exact_clues() {
+            let literals: Vec<_> = cells.iter().flat_map(|r| r.iter().cloned()).collect();
+            cnf.exactly(&literals, self.total());
+        }
+
+        for row in 0..height {
+            for column in 0..width {
+                let pos = Pos::new(row, column);
+                for Pos { row: r, column: c } in self.touching(pos) {
+                    if (r, c) > (row, column) {
+                        cnf.clauses.push(vec![-cells[row][column], -cells[r][c]]);
+                    }
+                }
+            }
+        }
+
+        let mut associations = Vec::new();
+        // Without matching, `Tree`s only take up room.
+        if !self.rules().no_matching {
+            let mut by_camp = vec![vec![Vec::new(); width]; height];
+            for row in 0..height {
+                for column in 0..width {
+                    if self[(row, column)] != Tree {
+                        continue;
+                    }
+                    let mut literals = Vec::new();
+                    for Pos { row: r, column: c } in self.surrounding_tiles(Pos::new(row, column)) {
+                        if self[(r, c)] != Tree {
+                            let variable = cnf.variable();
+                            associations.push(DimacsAssociation {
+                                tree: Pos::new(row, column),
+                                camp: Pos::new(r, c),
+                                variable: variable as usize,
+                            });
+                            cnf.clauses.push(vec![-variable, cells[r][c]]);
+                            by_camp[r][c].push(variable);
+                            literals.push(variable);
+                        }
+                    }
+                    cnf.exactly(&literals, 1);
+                }
+            }
+            for (trees, cells) in by_camp.iter().zip(&cells) {
+                for (trees, &cell) in trees.iter().zip(cells) {
+                    let mut clause = trees.clone();
+                    clause.push(-cell);
+                    cnf.clauses.push(clause);
+                    cnf.at_most(trees, 1);
+                }
+            }
+        }
+
+        let mut s = String::new();
+        writeln!(s, "c camps_and_trees {}x{}", height, width).unwrap();
+        for (row, cells) in cells.iter().enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                writeln!(s, "c camp {} {} {}", row, column, cell).unwrap();
+            }
+        }
+        for association in &associations {
+            let (tree, camp) = (association.tree, association.camp);
+            writeln!(
+                s,
+                "c association {} {} {} {} {}",
+                tree.row, tree.column, camp.row, camp.column, association.variable
+            )
+            .unwrap();
+        }
+        writeln!(s, "p cnf {} {}", cnf.variables, cnf.clauses.len()).unwrap();
+        for clause in &cnf.clauses {
+            for literal in clause {
+                write!(s, "{} ", literal).unwrap();
+            }
+            writeln!(s, "0").unwrap();
+        }
+
+        Dimacs {
+            cnf: s,
+            cells: cells
+                .into_iter()
+                .map(|r| r.into_iter().map(|x| x as usize).collect())
+                .collect(),
+            associations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grid::*;
+    use rule_set::*;
+
+    /// Read the clauses back out of a DIMACS formula.
+    fn clauses(cnf: &str) -> (usize, Vec<Vec<isize>>) {
+        let mut variables = 0;
+        let mut clauses = Vec::new();
+        for line in cnf.lines() {
+            if line.starts_with('c') {
+                continue;
+            }
+            if line.starts_with('p') {
+                variables = line.split(' ').nth(2).unwrap().parse().unwrap();
+                continue;
+            }
+            let clause: Vec<isize> = line.split(' ').map(|x| x.parse().unwrap()).collect();
+            assert_eq!(clause.last(), Some(&0));
+            clauses.push(clause[..clause.len() - 1].to_vec());
+        }
+        (variables, clauses)
+    }
+
+    /// A minimal DPLL solver used to check the encoding.
+    fn satisfiable(clauses: &[Vec<isize>], assignment: &mut [Option<bool>]) -> bool {
+        loop {
+            let mut unit = None;
+            for clause in clauses {
+                let value = |x: isize| assignment[x.unsigned_abs()].map(|v| v == (x > 0));
+                if clause.iter().any(|&x| value(x) == Some(true)) {
+                    continue;
+                }
+                let open: Vec<_> = clause.iter().filter(|&&x| value(x).is_none()).collect();
+                match open.len() {
+                    0 => return false,
+                    1 => {
+                        unit = Some(*open[0]);
+                        break;
+                    }
+                    _ => (),
+                }
+            }
+            match unit {
+                Some(x) => assignment[x.unsigned_abs()] = Some(x > 0),
+                None => break,
+            }
+        }
+        match (1..assignment.len()).find(|&v| assignment[v].is_none()) {
+            None => true,
+            Some(v) => [true, false].iter().any(|&b| {
+                let mut assignment = assignment.to_vec();
+                assignment[v] = Some(b);
+                satisfiable(clauses, &mut assignment)
+            }),
+        }
+    }
+
+    /// Check that `solution` is the only solution of the encoding.
+    fn assert_unique_solution(board: &Board, solution: &Grid) {
+        let dimacs = board.to_dimacs();
+        let (variables, mut clauses) = clauses(&dimacs.cnf);
+        let mut solved = clauses.clone();
+        for row in 0..solution.num_rows() {
+            for column in 0..solution.num_columns() {
+                let x = dimacs.cells[row][column] as isize;
+                let camp = solution[(row, column)] == Camp;
+                solved.push(vec![if camp { x } else { -x }]);
+            }
+        }
+        assert!(satisfiable(&solved, &mut vec![None; variables + 1]));
+        let mut blocking = Vec::new();
+        for row in 0..solution.num_rows() {
+            for column in 0..solution.num_columns() {
+                let x = dimacs.cells[row][column] as isize;
+                let camp = solution[(row, column)] == Camp;
+                blocking.push(if camp { -x } else { x });
+            }
+        }
+        clauses.push(blocking);
+        assert!(!satisfiable(&clauses, &mut vec![None; variables + 1]));
+    }
+
+    #[test]
+    fn to_dimacs_header() {
+        let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        let dimacs = board.to_dimacs();
+        assert_eq!(dimacs.cells, vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(
+            dimacs.associations,
+            vec![
+                DimacsAssociation {
+                    tree: Pos::new(0, 1),
+                    camp: Pos::new(0, 0),
+                    variable: 9,
+                },
+                DimacsAssociation {
+                    tree: Pos::new(0, 1),
+                    camp: Pos::new(1, 1),
+                    variable: 10,
+                },
+            ]
+        );
+        assert!(dimacs
+            .cnf
+            .starts_with("c camps_and_trees 2x2\nc camp 0 0 1\n"));
+        assert!(dimacs.cnf.contains("\nc association 0 1 1 1 10\np cnf "));
+    }
+
+    #[test]
+    fn to_dimacs_unique_solution() {
+        let board = Board::new_parse(
+            vec![1, 1, 0, 2, 1],
+            vec![2, 0, 1, 1, 1],
+            "     \n T T \n     \nTT   \n    T",
+        )
+        .unwrap();
+        let solution = Grid::parse("---C-\nCT-T-\n-----\nTTC-C\nC---T").unwrap();
+        assert_unique_solution(&board, &solution);
+    }
+
+    #[test]
+    fn to_dimacs_clue_free() {
+        let board = Board::new_parse_clue_free(2, "T-T\n   ").unwrap();
+        let solution = Grid::parse("T-T\nC-C").unwrap();
+        assert_unique_solution(&board, &solution);
+    }
+
+    #[test]
+    fn to_dimacs_range_clues() {
+        let rows = vec![Clue::exactly(0), Clue::at_least(2)];
+        let columns = vec![Clue::at_most(1), Clue::any(), Clue::between(1, 2)];
+        let board = Board::new_parse_ranged(rows, columns, "T T\n   ").unwrap();
+        let solution = Grid::parse("T-T\nC-C").unwrap();
+        assert_unique_solution(&board, &solution);
+    }
+
+    #[test]
+    fn to_dimacs_unsatisfiable() {
+        let board = Board::new_parse(vec![2, 0], vec![1, 1], "  \nTT").unwrap();
+        let (variables, clauses) = clauses(&board.to_dimacs().cnf);
+        assert!(!satisfiable(&clauses, &mut vec![None; variables + 1]));
+    }
+
+    #[test]
+    fn to_dimacs_variants() {
+        // Diagonal camps, and camps with no tree, are both allowed.
+        let mut grid = Grid::parse(" T\n  ").unwrap();
+        grid.set_rules(RuleSet {
+            orthogonal_only: true,
+            no_matching: true,
+            ..RuleSet::default()
+        });
+        let board = Board::try_new(vec![1, 1], vec![1, 1], grid).unwrap();
+        let dimacs = board.to_dimacs();
+        assert_eq!(dimacs.associations, vec![]);
+        assert_unique_solution(&board, &Grid::parse("CT\n-C").unwrap());
+    }
+
+    #[test]
+    fn to_dimacs_torus() {
+        // The only classic solution has camps touching across the edge.
+        let mut board = Board::new_parse_clue_free(2, "T-T\n   ").unwrap();
+        board.set_rules(RuleSet::torus());
+        let (variables, clauses) = clauses(&board.to_dimacs().cnf);
+        assert!(!satisfiable(&clauses, &mut vec![None; variables + 1]));
+    }
+}
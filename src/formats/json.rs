@@ -0,0 +1,243 @@
+use board::*;
+use clue::*;
+use error::*;
+use grid::*;
+use rule_set::*;
+use serde_json::{self, Value};
+use std::str::FromStr;
+
+/// Make an [`Error::Format`] for JSON.
+///
+/// [`Error::Format`]: ../enum.Error.html#variant.Format
+fn json_error(message: String) -> Error {
+    Error::Format {
+        format: "JSON",
+        message,
+    }
+}
+
+/// Get `value` as a list of clues, which are numbers, `null` for
+/// those omitted, or strings parsed as [`Clue`]s.
+///
+/// [`Clue`]: ../struct.Clue.html
+fn clues(value: &Value, field: &str) -> Result<Vec<Clue>, Error> {
+    let error = || json_error(format!("\"{}\" must be a list of numbers", field));
+    let list = value.as_array().ok_or_else(error)?;
+    list.iter()
+        .map(|x| match x {
+            Value::Null => Ok(Clue::any()),
+            Value::String(x) => x.parse(),
+            x => x
+                .as_u64()
+                .map(|x| Clue::exactly(x as usize))
+                .ok_or_else(error),
+        })
+        .collect()
+}
+
+/// Convert `clues` to JSON: numbers, `null` for those omitted, and
+/// strings for the rest.
+fn clues_value(clues: Vec<Clue>) -> Value {
+    let clues = clues.into_iter().map(|clue| match clue.exact() {
+        Some(clue) => Value::from(clue),
+        None if clue.is_any() => Value::Null,
+        None => Value::from(clue.to_string()),
+    });
+    Value::Array(clues.collect())
+}
+
+/// Get `value` as the lines of a [`Grid`] joined by `\n`.
+///
+/// [`Grid`]: ../struct.Grid.html
+fn grid_lines(value: &Value) -> Result<String, Error> {
+    let error = || json_error("\"grid\" must be a list of strings".to_string());
+    let list = value.as_array().ok_or_else(error)?;
+    let lines: Result<Vec<_>, _> = list.iter().map(|x| x.as_str().ok_or_else(error)).collect();
+    Ok(lines?.join("\n"))
+}
+
+/// Build a `Board` from a JSON `value`.
+///
+/// See [`Board::from_json`].
+///
+/// [`Board::from_json`]: ../struct.Board.html#method.from_json
+pub fn from_value(value: &Value) -> Result<Board, Error> {
+    let field = |name: &str| {
+        value
+            .get(name)
+            .ok_or_else(|| json_error(format!("Missing field \"{}\"", name)))
+    };
+    let mut grid = Grid::parse(&grid_lines(field("grid")?)?)?;
+    if let Some(rules) = value.get("rules") {
+        let rules = rules
+            .as_str()
+            .ok_or_else(|| json_error("\"rules\" must be a string".to_string()))?;
+        let rules = RuleSet::from_str(rules).map_err(|x| json_error(x.to_string()))?;
+        grid.set_rules(rules);
+    }
+    if let Some(total) = value.get("total") {
+        let total = total
+            .as_u64()
+            .ok_or_else(|| json_error("\"total\" must be a number".to_string()))?;
+        return Ok(Board::new_clue_free(total as usize, grid));
+    }
+    let rows = clues(field("rows")?, "rows")?;
+    let columns = clues(field("columns")?, "columns")?;
+    Board::try_new_ranged(rows, columns, grid)
+}
+
+/// Convert `board` to a JSON object.
+///
+/// See [`Board::to_json`].
+///
+/// [`Board::to_json`]: ../struct.Board.html#method.to_json
+pub fn to_object(board: &Board) -> serde_json::Map<String, Value> {
+    let grid: Vec<_> = board.debug().split('\n').map(Value::from).collect();
+    let mut object = serde_json::Map::new();
+    if board.is_clue_free() {
+        object.insert("total".to_string(), Value::from(board.total()));
+    } else {
+        object.insert("rows".to_string(), clues_value(board.row_ranges()));
+        object.insert("columns".to_string(), clues_value(board.column_ranges()));
+    }
+    object.insert("grid".to_string(), Value::from(grid));
+    if board.rules() != RuleSet::default() {
+        object.insert("rules".to_string(), Value::from(board.rules().to_string()));
+    }
+    object
+}
+
+impl Board {
+    /// Parse a `Board` from JSON.
+    ///
+    /// The JSON is an object with the row clues in `"rows"`, the
+    /// column clues in `"columns"`, and the [`Grid`] as a list of
+    /// strings in `"grid"`.  Each string is a row parsed by
+    /// [`Grid::parse`].  An omitted clue is `null`, and a bound such as
+    /// `"<=2"` is a string parsed as a [`Clue`].  Clue-free `Board`s
+    /// give `"total"` instead of `"rows"` and `"columns"`.  `Board`s
+    /// played by other rules name their [`RuleSet`] in `"rules"`, such
+    /// as `"torus"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// let json = r#"{ "rows": [1, 0], "columns": [1, 0], "grid": [" T", "  "] }"#;
+    /// assert_eq!(
+    ///     Board::from_json(json),
+    ///     Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ")
+    /// );
+    /// let json = r#"{ "total": 1, "grid": [" T", "  "] }"#;
+    /// assert_eq!(
+    ///     Board::from_json(json),
+    ///     Board::new_parse_clue_free(1, " T\n  ")
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the JSON is malformed or doesn't match the schema above,
+    /// an [`Error::Format`] is returned.  Errors from parsing the
+    /// [`Grid`] or building the `Board` are passed through.
+    ///
+    /// [`Grid`]: struct.Grid.html
+    /// [`Grid::parse`]: struct.Grid.html#method.parse
+    /// [`Clue`]: struct.Clue.html
+    /// [`RuleSet`]: struct.RuleSet.html
+    /// [`Error::Format`]: enum.Error.html#variant.Format
+    pub fn from_json(s: &str) -> Result<Board, Error> {
+        let value: Value = serde_json::from_str(s).map_err(|x| json_error(x.to_string()))?;
+        from_value(&value)
+    }
+
+    /// Format the `Board` as JSON.
+    ///
+    /// See [`Board::from_json`] for the schema.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+    /// assert_eq!(
+    ///     board.to_json(),
+    ///     r#"{"columns":[1,0],"grid":[" T","  "],"rows":[1,0]}"#
+    /// );
+    /// ```
+    ///
+    /// [`Board::from_json`]: struct.Board.html#method.from_json
+    pub fn to_json(&self) -> String {
+        Value::from(to_object(self)).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pos::*;
+
+    #[test]
+    fn json_round_trip() {
+        let board = Board::new_parse(
+            vec![1, 1, 0, 2, 1],
+            vec![2, 0, 1, 1, 1],
+            "     \n T T \n     \nTT   \n    T",
+        )
+        .unwrap();
+        assert_eq!(Board::from_json(&board.to_json()), Ok(board));
+        let board = Board::new_parse_clue_free(2, "T-T\n   ").unwrap();
+        assert_eq!(Board::from_json(&board.to_json()), Ok(board));
+        let board =
+            Board::new_parse_partial(vec![Some(1), None], vec![None, Some(0)], " T\n  ").unwrap();
+        assert_eq!(
+            board.to_json(),
+            r#"{"columns":[null,0],"grid":[" T","  "],"rows":[1,null]}"#
+        );
+        assert_eq!(Board::from_json(&board.to_json()), Ok(board));
+        let rows = vec![Clue::at_most(1), Clue::exactly(0)];
+        let columns = vec![Clue::between(1, 2), Clue::any()];
+        let board = Board::new_parse_ranged(rows, columns, " T\n  ").unwrap();
+        assert_eq!(
+            board.to_json(),
+            r#"{"columns":["1..2",null],"grid":[" T","  "],"rows":["<=1",0]}"#
+        );
+        assert_eq!(Board::from_json(&board.to_json()), Ok(board));
+        let board = Board::from_json(r#"{"grid":["C T"],"rules":"torus","total":1}"#).unwrap();
+        assert_eq!(board.rules(), RuleSet::torus());
+        assert_eq!(
+            board.to_json(),
+            r#"{"grid":["C T"],"rules":"torus","total":1}"#
+        );
+    }
+
+    #[test]
+    fn from_json_errors() {
+        assert!(Board::from_json(r#"{ "total": 1, "grid": ["T"], "rules": 1 }"#).is_err());
+        assert!(Board::from_json(r#"{ "total": 1, "grid": ["T"], "rules": "x" }"#).is_err());
+        assert!(Board::from_json("{").is_err());
+        assert!(Board::from_json("[]").is_err());
+        assert!(Board::from_json(r#"{ "rows": [1], "columns": [1] }"#).is_err());
+        assert!(Board::from_json(r#"{ "rows": [1], "grid": ["T"] }"#).is_err());
+        assert!(Board::from_json(r#"{ "rows": [-1], "columns": [1], "grid": ["T"] }"#).is_err());
+        assert!(Board::from_json(r#"{ "rows": ["x"], "columns": [1], "grid": ["T"] }"#).is_err());
+        assert!(Board::from_json(r#"{ "total": "1", "grid": ["T"] }"#).is_err());
+        assert!(Board::from_json(r#"{ "total": 1, "grid": [1] }"#).is_err());
+        assert_eq!(
+            Board::from_json(r#"{ "rows": [1], "columns": [1], "grid": ["x"] }"#),
+            Err(Error::Parse {
+                character: 'x',
+                position: Some(Pos::new(0, 0)),
+            })
+        );
+        assert_eq!(
+            Board::from_json(r#"{ "rows": [1, 0], "columns": [1], "grid": ["T"] }"#),
+            Err(Error::DimensionMismatch {
+                rows: 2,
+                columns: 1,
+                grid_rows: 1,
+                grid_columns: 1,
+            })
+        );
+    }
+}
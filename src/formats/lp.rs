@@ -0,0 +1,299 @@
+use board::*;
+use clue::*;
+use pos::*;
+use std::fmt::Write;
+use tile::Tile::*;
+
+/// The variable that is 1 when there is a [`Camp`] at `(row, column)`.
+///
+/// [`Camp`]: enum.Tile.html#variant.Camp
+fn camp(row: usize, column: usize) -> String {
+    format!("x_{}_{}", row, column)
+}
+
+/// The variable that is 1 when the [`Tree`] at `tree` is associated
+/// with the [`Camp`] at `camp`.
+///
+/// [`Tree`]: enum.Tile.html#variant.Tree
+/// [`Camp`]: enum.Tile.html#variant.Camp
+fn association(tree: (usize, usize), camp: (usize, usize)) -> String {
+    format!("a_{}_{}_{}_{}", tree.0, tree.1, camp.0, camp.1)
+}
+
+/// Write the constraint `name: terms = rhs`, wrapping long sums.
+fn constraint(s: &mut String, name: &str, terms: &[String], relation: &str, rhs: isize) {
+    write!(s, " {}:", name).unwrap();
+    for (i, term) in terms.iter().enumerate() {
+        if i > 0 && i % 8 == 0 {
+            write!(s, "\n   ").unwrap();
+        }
+        if let Some(term) = term.strip_prefix('-') {
+            write!(s, " - {}", term).unwrap();
+        } else if i == 0 {
+            write!(s, " {}", term).unwrap();
+        } else {
+            write!(s, " + {}", term).unwrap();
+        }
+    }
+    writeln!(s, " {} {}", relation, rhs).unwrap();
+}
+
+/// Write the constraints keeping the sum of `terms` within `clue`.
+///
+/// A bound gets `name_min` and `name_max` constraints for its ends.
+fn clue_constraints(s: &mut String, name: &str, terms: &[String], clue: Clue) {
+    if terms.is_empty() {
+        return;
+    }
+    if let Some(clue) = clue.exact() {
+        constraint(s, name, terms, "=", clue as isize);
+        return;
+    }
+    if clue.min > 0 {
+        let name = format!("{}_min", name);
+        constraint(s, &name, terms, ">=", clue.min as isize);
+    }
+    if let Some(max) = clue.max {
+        constraint(s, &format!("{}_max", name), terms, "<=", max as isize);
+    }
+}
+
+impl Board {
+    /// Encode the `Board` as a 0/1 integer program in LP file format.
+    ///
+    /// The binary `x_R_C` is 1 if there is a [`Camp`] at row `R` and
+    /// column `C`.  The binary `a_TR_TC_CR_CC` is 1 if the [`Tree`]
+    /// at `(TR, TC)` is associated with the cell at `(CR, CC)`.  The
+    /// constraints require that:
+    ///
+    /// * [`Tile`]s already assigned keep their values.
+    /// * Each row and column has as many [`Camp`]s as its clue
+    ///   allows, and the whole `Board` has `total` if the clues don't
+    ///   fix it.
+    /// * Each 2x2 block holds at most one [`Camp`], so no two
+    ///   [`Camp`]s touch.
+    /// * Each [`Tree`] is associated with exactly one neighbour and
+    ///   each cell with as many [`Tree`]s as it has [`Camp`]s.
+    ///
+    /// If the [`RuleSet`] lets [`Camp`]s touch diagonally, each pair of
+    /// cells beside each other holds at most one [`Camp`] instead of
+    /// each 2x2 block, and if it leaves out matching there are no
+    /// associations.
+    ///
+    /// There is no objective, so any feasible solution is a solution
+    /// of the puzzle.  An infeasible program proves the puzzle has no
+    /// solution.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Tile`]: enum.Tile.html
+    /// [`RuleSet`]: struct.RuleSet.html
+    pub fn to_lp(&self) -> String {
+        let (height, width) = (self.num_rows(), self.num_columns());
+        let mut variables = Vec::new();
+        let mut s = String::new();
+        writeln!(s, "\\ camps_and_trees {}x{}", height, width).unwrap();
+        writeln!(s, "Minimize").unwrap();
+        writeln!(s, " obj:").unwrap();
+        writeln!(s, "Subject To").unwrap();
+
+        if !self.has_exact_clues() {
+            let cells: Vec<_> = (0..height)
+                .flat_map(|row| (0..width).map(move |column| camp(row, column)))
+                .collect();
+            if !cells.is_empty() {
+                constraint(&mut s, "total", &cells, "=", self.total() as isize);
+            }
+        }
+        for (row, clue) in self.row_ranges().into_iter().enumerate() {
+            let cells: Vec<_> = (0..width).map(|column| camp(row, column)).collect();
+            clue_constraints(&mut s, &format!("row_{}", row), &cells, clue);
+        }
+        for (column, clue) in self.column_ranges().into_iter().enumerate() {
+            let cells: Vec<_> = (0..height).map(|row| camp(row, column)).collect();
+            clue_constraints(&mut s, &format!("column_{}", column), &cells, clue);
+        }
+
+        if self.rules().orthogonal_only {
+            // Camps may touch diagonally, so each pair of cells beside
+            // each other gets its own constraint.
+            for row in 0..height {
+                for column in 0..width {
+                    for Pos { row: r, column: c } in self.touching(Pos::new(row, column)) {
+                        if (r, c) > (row, column) {
+                            let name = format!("touch_{}_{}_{}_{}", row, column, r, c);
+                            let cells = [camp(row, column), camp(r, c)];
+                            constraint(&mut s, &name, &cells, "<=", 1);
+                        }
+                    }
+                }
+            }
+        } else {
+            // Every pair of touching cells lies in a common 2x2 block.
+            // Boards only one cell wide use 1x2 blocks instead, and
+            // wrapping boards also have blocks across their edges.
+            let wrap = self.rules().wrap;
+            let blocks = |len: usize| {
+                if wrap && len > 2 {
+                    len
+                } else {
+                    len.saturating_sub(1).max(1)
+                }
+            };
+            for row in 0..blocks(height) {
+                for column in 0..blocks(width) {
+                    let mut cells = Vec::new();
+                    for r in 0..height.min(2) {
+                        for c in 0..width.min(2) {
+                            cells.push(camp((row + r) % height, (column + c) % width));
+                        }
+                    }
+                    if cells.len() > 1 {
+                        let name = format!("block_{}_{}", row, column);
+                        constraint(&mut s, &name, &cells, "<=", 1);
+                    }
+                }
+            }
+        }
+
+        // Without matching, `Tree`s only take up room.
+        if !self.rules().no_matching {
+            let mut by_cell = vec![vec![Vec::new(); width]; height];
+            for row in 0..height {
+                for column in 0..width {
+                    if self[(row, column)] != Tree {
+                        continue;
+                    }
+                    let mut associations = Vec::new();
+                    for Pos { row: r, column: c } in self.surrounding_tiles(Pos::new(row, column)) {
+                        let variable = association((row, column), (r, c));
+                        by_cell[r][c].push(variable.clone());
+                        variables.push(variable.clone());
+                        associations.push(variable);
+                    }
+                    let name = format!("tree_{}_{}", row, column);
+                    if associations.is_empty() {
+                        // A Tree with nowhere to put its Camp.  Its own
+                        // cell is fixed to 0, so this is infeasible.
+                        associations.push(camp(row, column));
+                    }
+                    constraint(&mut s, &name, &associations, "=", 1);
+                }
+            }
+            for (row, by_cell) in by_cell.into_iter().enumerate() {
+                for (column, mut associations) in by_cell.into_iter().enumerate() {
+                    if self[(row, column)] == Tree {
+                        continue;
+                    }
+                    associations.push(format!("-{}", camp(row, column)));
+                    let name = format!("camp_{}_{}", row, column);
+                    constraint(&mut s, &name, &associations, "=", 0);
+                }
+            }
+        }
+
+        writeln!(s, "Bounds").unwrap();
+        for row in 0..height {
+            for column in 0..width {
+                match self[(row, column)] {
+                    Unassigned => {}
+                    Camp => writeln!(s, " {} = 1", camp(row, column)).unwrap(),
+                    Grass | Tree | Blocked => writeln!(s, " {} = 0", camp(row, column)).unwrap(),
+                }
+            }
+        }
+
+        writeln!(s, "Binaries").unwrap();
+        for row in 0..height {
+            for column in 0..width {
+                writeln!(s, " {}", camp(row, column)).unwrap();
+            }
+        }
+        for variable in &variables {
+            writeln!(s, " {}", variable).unwrap();
+        }
+        writeln!(s, "End").unwrap();
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grid::*;
+    use rule_set::*;
+
+    #[test]
+    fn test_to_lp() {
+        let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        assert_eq!(
+            board.to_lp(),
+            "\\ camps_and_trees 2x2\n\
+             Minimize\n obj:\n\
+             Subject To\n row_0: x_0_0 + x_0_1 = 1\n row_1: x_1_0 + x_1_1 = 0\n \
+             column_0: x_0_0 + x_1_0 = 1\n column_1: x_0_1 + x_1_1 = 0\n \
+             block_0_0: x_0_0 + x_0_1 + x_1_0 + x_1_1 <= 1\n \
+             tree_0_1: a_0_1_0_0 + a_0_1_1_1 = 1\n \
+             camp_0_0: a_0_1_0_0 - x_0_0 = 0\n \
+             camp_1_0: - x_1_0 = 0\n \
+             camp_1_1: a_0_1_1_1 - x_1_1 = 0\n\
+             Bounds\n x_0_1 = 0\n\
+             Binaries\n x_0_0\n x_0_1\n x_1_0\n x_1_1\n a_0_1_0_0\n a_0_1_1_1\n\
+             End\n"
+        );
+    }
+
+    #[test]
+    fn test_to_lp_clue_free() {
+        let lp = Board::new_parse_clue_free(2, "T-T\n C ").unwrap().to_lp();
+        assert!(lp.contains("\n total: x_0_0 + x_0_1 + x_0_2 + x_1_0 + x_1_1 + x_1_2 = 2\n"));
+        assert!(lp.contains("\n x_1_1 = 1\n"));
+        assert!(!lp.contains("row_"));
+    }
+
+    #[test]
+    fn test_to_lp_range_clues() {
+        let rows = vec![Clue::at_most(1), Clue::between(1, 2)];
+        let columns = vec![Clue::at_least(1), Clue::any()];
+        let lp = Board::new_parse_ranged(rows, columns, " T\n  ")
+            .unwrap()
+            .to_lp();
+        assert!(lp.contains("\n total: x_0_0 + x_0_1 + x_1_0 + x_1_1 = 1\n"));
+        assert!(lp.contains("\n row_0_max: x_0_0 + x_0_1 <= 1\n"));
+        assert!(lp.contains("\n row_1_min: x_1_0 + x_1_1 >= 1\n row_1_max: x_1_0 + x_1_1 <= 2\n"));
+        assert!(lp.contains("\n column_0_min: x_0_0 + x_1_0 >= 1\n"));
+        assert!(!lp.contains("row_0_min") && !lp.contains("column_1"));
+    }
+
+    #[test]
+    fn test_to_lp_isolated_tree() {
+        let lp = Board::new_parse_clue_free(0, "T").unwrap().to_lp();
+        assert!(lp.contains("\n tree_0_0: x_0_0 = 1\n"));
+        assert!(lp.contains("\nBounds\n x_0_0 = 0\n"));
+    }
+
+    #[test]
+    fn test_to_lp_variants() {
+        let mut grid = Grid::parse(" T\n  ").unwrap();
+        grid.set_rules(RuleSet {
+            orthogonal_only: true,
+            no_matching: true,
+            ..RuleSet::default()
+        });
+        let board = Board::try_new(vec![1, 1], vec![1, 1], grid).unwrap();
+        let lp = board.to_lp();
+        assert!(lp.contains("\n touch_0_0_0_1: x_0_0 + x_0_1 <= 1\n"));
+        assert!(lp.contains("\n touch_0_1_1_1: x_0_1 + x_1_1 <= 1\n"));
+        assert!(!lp.contains("block_") && !lp.contains("touch_0_0_1_1"));
+        assert!(!lp.contains("tree_") && !lp.contains("camp_"));
+    }
+
+    #[test]
+    fn test_to_lp_torus() {
+        let mut board = Board::new_parse_clue_free(2, "T-T\n   ").unwrap();
+        board.set_rules(RuleSet::torus());
+        let lp = board.to_lp();
+        assert!(lp.contains("\n block_0_2: x_0_2 + x_0_0 + x_1_2 + x_1_0 <= 1\n"));
+        assert!(!lp.contains("block_1_"));
+    }
+}
@@ -0,0 +1,268 @@
+use board::*;
+use clue::*;
+use std::fmt::Write;
+use tile::Tile::*;
+
+/// The MiniZinc model shared by every [`Board`].
+///
+/// [`Board`]: struct.Board.html
+const MODEL: &str = r#"% Camps and trees.
+%
+% Place camps so that every tree is associated with exactly one
+% orthogonally adjacent camp and every camp with exactly one tree, no
+% two camps touch (even diagonally), and the camps in each row and
+% column match the clues.  On a torus, rows and columns wrap around.
+% Variants can let camps touch diagonally or leave out the trees.
+
+int: height;
+int: width;
+% The fewest and most camps in each row and column, with -1 for no
+% most.
+array[1..height] of int: row_min;
+array[1..height] of int: row_max;
+array[1..width] of int: column_min;
+array[1..width] of int: column_max;
+% The number of camps on the whole board.
+int: total;
+% 0 = unassigned, 1 = grass, 2 = camp, 3 = tree, 4 = blocked.
+array[1..height, 1..width] of 0..4: grid;
+% Whether rows and columns wrap around.
+bool: wrap;
+% Whether camps may touch diagonally.
+bool: orthogonal_only;
+% Whether camps and trees go without being associated.
+bool: no_matching;
+
+% The offsets of the orthogonal neighbours: up, down, left, right.
+array[1..4] of int: dr = [-1, 1, 0, 0];
+array[1..4] of int: dc = [0, 0, -1, 1];
+
+% The index x, at most one past either end of 1..n, moved back inside
+% if the board wraps, or 0 if it is off the board.
+function int: at(int: x, int: n) =
+  if x in 1..n then x elseif wrap then (x - 1 + n) mod n + 1 else 0 endif;
+
+array[1..height, 1..width] of var bool: camp;
+% The direction from each tree to its camp, or 0 for other cells.
+array[1..height, 1..width] of var 0..4: direction;
+
+constraint forall(r in 1..height, c in 1..width)(
+  if grid[r, c] == 2 then camp[r, c]
+  elseif grid[r, c] != 0 then not camp[r, c]
+  else true endif
+);
+
+constraint forall(r in 1..height)(
+  let { var int: camps = sum(c in 1..width)(bool2int(camp[r, c])) } in
+  camps >= row_min[r] /\ (row_max[r] < 0 \/ camps <= row_max[r])
+);
+constraint forall(c in 1..width)(
+  let { var int: camps = sum(r in 1..height)(bool2int(camp[r, c])) } in
+  camps >= column_min[c] /\ (column_max[c] < 0 \/ camps <= column_max[c])
+);
+constraint sum(r in 1..height, c in 1..width)(bool2int(camp[r, c])) == total;
+
+constraint forall(r in 1..height, c in 1..width, i in 0..1, j in -1..1
+                  where (i > 0 \/ j > 0) /\ not (orthogonal_only /\ i != 0 /\ j != 0))(
+  let { int: r2 = at(r + i, height); int: c2 = at(c + j, width) } in
+  if r2 > 0 /\ c2 > 0 /\ (r2 != r \/ c2 != c) then
+    not (camp[r, c] /\ camp[r2, c2])
+  else
+    true
+  endif
+);
+
+constraint forall(r in 1..height, c in 1..width)(
+  if grid[r, c] == 3 /\ not no_matching then
+    exists(d in 1..4 where at(r + dr[d], height) > 0 /\ at(c + dc[d], width) > 0)(
+      direction[r, c] == d /\ camp[at(r + dr[d], height), at(c + dc[d], width)]
+    )
+  else
+    direction[r, c] == 0
+  endif
+);
+
+constraint forall(r in 1..height, c in 1..width)(
+  camp[r, c] /\ not no_matching ->
+    sum(d in 1..4 where at(r - dr[d], height) > 0 /\ at(c - dc[d], width) > 0)(
+      bool2int(direction[at(r - dr[d], height), at(c - dc[d], width)] == d)
+    ) == 1
+);
+
+solve satisfy;
+
+% The solution in the format read by Grid::parse.
+output [
+  (if c == 1 /\ r > 1 then "\n" else "" endif) ++
+  (if grid[r, c] == 3 then "T" elseif fix(camp[r, c]) then "C" else "-" endif)
+  | r in 1..height, c in 1..width
+] ++ ["\n"];
+"#;
+
+/// A MiniZinc model and the data for one [`Board`].
+///
+/// See [`Board::to_minizinc`].
+///
+/// [`Board`]: struct.Board.html
+/// [`Board::to_minizinc`]: struct.Board.html#method.to_minizinc
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MiniZinc {
+    /// The model, which is the same for every [`Board`].
+    ///
+    /// [`Board`]: struct.Board.html
+    pub model: String,
+    /// The parameters of the model for this [`Board`] in `.dzn`
+    /// format.
+    ///
+    /// [`Board`]: struct.Board.html
+    pub data: String,
+}
+
+/// Format `numbers` as a MiniZinc array literal.
+fn array<I: Iterator<Item = isize>>(numbers: I) -> String {
+    let numbers: Vec<_> = numbers.map(|x| x.to_string()).collect();
+    format!("[{}]", numbers.join(", "))
+}
+
+impl Board {
+    /// Encode the `Board` as a MiniZinc model and data file.
+    ///
+    /// The model is parameterized by the size of the `Board`, the
+    /// clues, and the [`Tile`]s already assigned, so it can be
+    /// reused across puzzles.  Each row and column has the fewest and
+    /// most [`Camp`]s its clue allows, with `-1` where there is no most,
+    /// as for omitted clues and every clue of a clue-free `Board`.
+    /// `wrap`, `orthogonal_only` and `no_matching` are the fields of
+    /// the [`RuleSet`].  The solution is printed in the format read by
+    /// [`Grid::parse`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+    /// let minizinc = board.to_minizinc();
+    /// assert_eq!(
+    ///     minizinc.data,
+    ///     "height = 2;\n\
+    ///      width = 2;\n\
+    ///      row_min = [1, 0];\n\
+    ///      row_max = [1, 0];\n\
+    ///      column_min = [1, 0];\n\
+    ///      column_max = [1, 0];\n\
+    ///      total = 1;\n\
+    ///      grid = [| 0, 3\n       | 0, 0 |];\n\
+    ///      wrap = false;\n\
+    ///      orthogonal_only = false;\n\
+    ///      no_matching = false;\n"
+    /// );
+    /// ```
+    ///
+    /// [`Tile`]: enum.Tile.html
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`RuleSet`]: struct.RuleSet.html
+    /// [`Grid::parse`]: struct.Grid.html#method.parse
+    pub fn to_minizinc(&self) -> MiniZinc {
+        let bounds = |clues: Vec<Clue>, len: usize| {
+            let clues = if self.is_clue_free() {
+                vec![Clue::any(); len]
+            } else {
+                clues
+            };
+            (
+                array(clues.iter().map(|x| x.min as isize)),
+                array(clues.iter().map(|x| x.max.map_or(-1, |x| x as isize))),
+            )
+        };
+
+        let (row_min, row_max) = bounds(self.row_ranges(), self.num_rows());
+        let (column_min, column_max) = bounds(self.column_ranges(), self.num_columns());
+
+        let mut data = String::new();
+        writeln!(data, "height = {};", self.num_rows()).unwrap();
+        writeln!(data, "width = {};", self.num_columns()).unwrap();
+        writeln!(data, "row_min = {};", row_min).unwrap();
+        writeln!(data, "row_max = {};", row_max).unwrap();
+        writeln!(data, "column_min = {};", column_min).unwrap();
+        writeln!(data, "column_max = {};", column_max).unwrap();
+        writeln!(data, "total = {};", self.total()).unwrap();
+        let lines: Vec<_> = self
+            .rows()
+            .map(|row| {
+                let tiles: Vec<_> = row
+                    .iter()
+                    .map(|tile| match *tile {
+                        Unassigned => "0",
+                        Grass => "1",
+                        Camp => "2",
+                        Tree => "3",
+                        Blocked => "4",
+                    })
+                    .collect();
+                tiles.join(", ")
+            })
+            .collect();
+        writeln!(data, "grid = [| {} |];", lines.join("\n       | ")).unwrap();
+        let rules = self.rules();
+        writeln!(data, "wrap = {};", rules.wrap).unwrap();
+        writeln!(data, "orthogonal_only = {};", rules.orthogonal_only).unwrap();
+        writeln!(data, "no_matching = {};", rules.no_matching).unwrap();
+
+        MiniZinc {
+            model: MODEL.to_string(),
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rule_set::*;
+
+    #[test]
+    fn test_to_minizinc_clue_free() {
+        let board = Board::new_parse_clue_free(2, "T-T\n C ").unwrap();
+        let minizinc = board.to_minizinc();
+        assert_eq!(
+            minizinc.data,
+            "height = 2;\n\
+             width = 3;\n\
+             row_min = [0, 0];\n\
+             row_max = [-1, -1];\n\
+             column_min = [0, 0, 0];\n\
+             column_max = [-1, -1, -1];\n\
+             total = 2;\n\
+             grid = [| 3, 1, 3\n       | 0, 2, 0 |];\n\
+             wrap = false;\n\
+             orthogonal_only = false;\n\
+             no_matching = false;\n"
+        );
+    }
+
+    #[test]
+    fn test_to_minizinc_torus() {
+        let mut board = Board::new_parse_clue_free(2, "T-T\n C ").unwrap();
+        board.set_rules(RuleSet::torus());
+        let minizinc = board.to_minizinc();
+        assert!(minizinc.data.contains("\nwrap = true;\n"));
+        assert_eq!(minizinc.model, MODEL);
+    }
+
+    #[test]
+    fn test_to_minizinc_range_clues() {
+        let rows = vec![Clue::at_most(1), Clue::between(1, 2)];
+        let columns = vec![Clue::at_least(1), Clue::any()];
+        let board = Board::new_parse_ranged(rows, columns, " T\n  ").unwrap();
+        let data = board.to_minizinc().data;
+        assert!(data.contains("row_min = [0, 1];\nrow_max = [1, 2];\n"));
+        assert!(data.contains("column_min = [1, 0];\ncolumn_max = [-1, -1];\n"));
+    }
+
+    #[test]
+    fn test_to_minizinc_model_is_shared() {
+        let a = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        let b = Board::new_parse_clue_free(2, "T-T\n C ").unwrap();
+        assert_eq!(a.to_minizinc().model, b.to_minizinc().model);
+    }
+}
@@ -0,0 +1,72 @@
+//! Interchange formats for [`Board`]s.
+//!
+//! Each format adds methods to [`Board`] for reading and writing
+//! it.  Some formats, like DIMACS, can only be written.
+//!
+//! [`Board`]: ../struct.Board.html
+
+mod camps;
+mod combined;
+mod corpus;
+pub use self::corpus::*;
+mod csv;
+pub use self::csv::*;
+mod dimacs;
+pub use self::dimacs::*;
+mod json;
+mod lp;
+mod minizinc;
+pub use self::minizinc::*;
+mod puzz_link;
+mod state;
+mod tatham;
+mod text;
+mod tikz;
+
+use board::*;
+use error::*;
+use rule_set::*;
+use tile::Tile::*;
+
+impl Board {
+    /// Check that the `Board` can be written in `format`, which only
+    /// holds classic puzzles with a number for every clue, and return
+    /// those numbers as `(rows, columns)`.
+    ///
+    /// [`Blocked`] `Tile`s are only allowed if `blocked` is set.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error::Format`] for clue-free `Board`s, `Board`s with
+    /// omitted or range clues, `Board`s played by other than the
+    /// classic [`RuleSet`] and, unless `blocked` is set, `Board`s with
+    /// [`Blocked`] `Tile`s.
+    ///
+    /// [`Blocked`]: ../enum.Tile.html#variant.Blocked
+    /// [`RuleSet`]: ../struct.RuleSet.html
+    /// [`Error::Format`]: ../enum.Error.html#variant.Format
+    fn require_classic_exact(
+        &self,
+        format: &'static str,
+        blocked: bool,
+    ) -> Result<(Vec<usize>, Vec<usize>), Error> {
+        let error = |message: &str| Error::Format {
+            format,
+            message: message.to_string(),
+        };
+        if self.is_clue_free() {
+            Err(error("clue-free boards have no clues to write"))?
+        }
+        if !self.has_exact_clues() {
+            Err(error("omitted or range clues can't be written"))?
+        }
+        if self.rules() != RuleSet::default() {
+            Err(error("only the classic rules can be written"))?
+        }
+        if !blocked && self.count(Blocked) > 0 {
+            Err(error("blocked cells can't be written"))?
+        }
+        let exact = |clues: Vec<Option<usize>>| clues.into_iter().flatten().collect();
+        Ok((exact(self.row_clues()), exact(self.column_clues())))
+    }
+}
@@ -0,0 +1,243 @@
+use board::*;
+use error::*;
+use grid::*;
+use tile::Tile::*;
+
+/// Make an [`Error::Format`] for puzz.link URLs.
+///
+/// [`Error::Format`]: ../enum.Error.html#variant.Format
+fn puzz_link_error(message: &str) -> Error {
+    Error::Format {
+        format: "puzz.link URL",
+        message: message.to_string(),
+    }
+}
+
+/// Decode `count` clues written in the pzprjs "number16" encoding.
+///
+/// Returns the clues and the rest of `s`.
+fn decode_clues(s: &str, count: usize) -> Result<(Vec<usize>, &str), Error> {
+    let mut clues = Vec::new();
+    let mut chars = s.char_indices();
+    while clues.len() < count {
+        let (_, c) = chars
+            .next()
+            .ok_or_else(|| puzz_link_error("not enough clues"))?;
+        let digits = match c {
+            '0'..='9' | 'a'..='f' => {
+                clues.push(c.to_digit(16).unwrap() as usize);
+                continue;
+            }
+            '-' => 2,
+            '+' => 3,
+            _ => Err(puzz_link_error("unsupported clue"))?,
+        };
+        let mut clue = 0;
+        for _ in 0..digits {
+            let (_, c) = chars
+                .next()
+                .ok_or_else(|| puzz_link_error("truncated clue"))?;
+            let digit = c
+                .to_digit(16)
+                .ok_or_else(|| puzz_link_error("invalid clue"))?;
+            clue = clue * 16 + digit as usize;
+        }
+        clues.push(clue);
+    }
+    let rest = chars.next().map(|(i, _)| &s[i..]).unwrap_or("");
+    Ok((clues, rest))
+}
+
+/// Encode a clue in the pzprjs "number16" encoding.
+fn encode_clue(clue: usize) -> String {
+    if clue < 16 {
+        format!("{:x}", clue)
+    } else if clue < 256 {
+        format!("-{:02x}", clue)
+    } else {
+        format!("+{:03x}", clue)
+    }
+}
+
+impl Board {
+    /// Parse a puzz.link or pzv.jp URL for a Tents puzzle.
+    ///
+    /// URLs look like `https://puzz.link/p?tents/W/H/data`.  Anything
+    /// before the `?` is ignored.  `data` holds the column clues
+    /// followed by the row clues, then the [`Tree`]s:
+    ///
+    /// * Each clue is a hex digit, `-` and two hex digits, or `+`
+    ///   and three hex digits.
+    /// * The [`Tree`]s are a bitmap in reading order.  Every 5 tiles
+    ///   are written as one base 32 digit (`0`-`9`, `a`-`v`), with
+    ///   the first tile as the highest bit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// assert_eq!(
+    ///     Board::from_puzz_link("https://puzz.link/p?tents/3/2/10111c0"),
+    ///     Board::new_parse(vec![1, 1], vec![1, 0, 1], " TT\n   ")
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the URL is malformed, an [`Error::Format`] is returned.
+    ///
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Error::Format`]: enum.Error.html#variant.Format
+    pub fn from_puzz_link(url: &str) -> Result<Board, Error> {
+        let url = url.trim();
+        let query = match url.find('?') {
+            Some(i) => &url[i + 1..],
+            None => url,
+        };
+        let mut parts = query.splitn(4, '/');
+        if parts.next() != Some("tents") {
+            Err(puzz_link_error("not a tents puzzle"))?
+        }
+        let mut dimension = || -> Result<usize, Error> {
+            parts
+                .next()
+                .and_then(|x| x.parse().ok())
+                .ok_or_else(|| puzz_link_error("missing dimensions"))
+        };
+        let width = dimension()?;
+        let height = dimension()?;
+        let data = parts.next().unwrap_or("");
+
+        let (mut columns, data) = decode_clues(data, width + height)?;
+        let rows = columns.split_off(width);
+
+        let mut grid = Grid::blank(height, width);
+        let mut chars = data.chars();
+        for chunk in 0..(width * height).div_ceil(5) {
+            let bits = chars
+                .next()
+                .and_then(|c| c.to_digit(32))
+                .ok_or_else(|| puzz_link_error("invalid tree bitmap"))?;
+            for bit in 0..5 {
+                let i = chunk * 5 + bit;
+                if bits & (16 >> bit) != 0 {
+                    if i >= width * height {
+                        Err(puzz_link_error("tree outside the grid"))?
+                    }
+                    grid[(i / width, i % width)] = Tree;
+                }
+            }
+        }
+        if chars.next().is_some() {
+            Err(puzz_link_error("unexpected data after the trees"))?
+        }
+        Board::try_new(rows, columns, grid)
+    }
+
+    /// Format the `Board` as a puzz.link URL.
+    ///
+    /// Only the [`Tree`]s and clues are encoded.  See
+    /// [`Board::from_puzz_link`] for the format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// let board = Board::new_parse(vec![1, 1], vec![1, 0, 1], " TT\n C ").unwrap();
+    /// assert_eq!(
+    ///     board.to_puzz_link(),
+    ///     Ok("https://puzz.link/p?tents/3/2/10111c0".to_string())
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Clue-free `Board`s, `Board`s with omitted or range clues,
+    /// `Board`s with [`Blocked`] `Tile`s and `Board`s played by other
+    /// than the classic [`RuleSet`] can't be represented, so an
+    /// [`Error::Format`] is returned for them.
+    ///
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Blocked`]: enum.Tile.html#variant.Blocked
+    /// [`RuleSet`]: struct.RuleSet.html
+    /// [`Board::from_puzz_link`]: struct.Board.html#method.from_puzz_link
+    /// [`Error::Format`]: enum.Error.html#variant.Format
+    pub fn to_puzz_link(&self) -> Result<String, Error> {
+        let (rows, columns) = self.require_classic_exact("puzz.link URL", false)?;
+        let (width, height) = (self.num_columns(), self.num_rows());
+        let mut url = format!("https://puzz.link/p?tents/{}/{}/", width, height);
+        for &clue in columns.iter().chain(&rows) {
+            url.push_str(&encode_clue(clue));
+        }
+        for chunk in 0..(width * height).div_ceil(5) {
+            let mut bits = 0;
+            for bit in 0..5 {
+                let i = chunk * 5 + bit;
+                if i < width * height && self[(i / width, i % width)] == Tree {
+                    bits |= 16 >> bit;
+                }
+            }
+            url.push(::std::char::from_digit(bits, 32).unwrap());
+        }
+        Ok(url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rule_set::*;
+
+    #[test]
+    fn puzz_link_round_trip() {
+        let board = Board::new_parse(
+            vec![1, 1, 0, 2, 1],
+            vec![2, 0, 1, 1, 1],
+            "     \n T T \n     \nTT   \n    T",
+        ).unwrap();
+        let url = board.to_puzz_link().unwrap();
+        assert_eq!(url, "https://puzz.link/p?tents/5/5/20111110210a0o1");
+        assert_eq!(Board::from_puzz_link(&url), Ok(board));
+    }
+
+    #[test]
+    fn puzz_link_large_clues() {
+        let board = Board::new(vec![300, 17], vec![1, 2], Grid::blank(2, 2));
+        let url = board.to_puzz_link().unwrap();
+        assert_eq!(url, "https://puzz.link/p?tents/2/2/12+12c-110");
+        assert_eq!(Board::from_puzz_link(&url), Ok(board));
+    }
+
+    #[test]
+    fn from_puzz_link_other_hosts() {
+        let board = Board::new_parse(vec![1, 1], vec![1, 0, 1], " TT\n   ").unwrap();
+        assert_eq!(
+            Board::from_puzz_link("http://pzv.jp/p.html?tents/3/2/10111c0"),
+            Ok(board.clone())
+        );
+        assert_eq!(Board::from_puzz_link("tents/3/2/10111c0"), Ok(board));
+    }
+
+    #[test]
+    fn from_puzz_link_errors() {
+        assert!(Board::from_puzz_link("https://puzz.link/p?lits/3/2/10111c0").is_err());
+        assert!(Board::from_puzz_link("https://puzz.link/p?tents/3").is_err());
+        assert!(Board::from_puzz_link("https://puzz.link/p?tents/3/2/1011").is_err());
+        assert!(Board::from_puzz_link("https://puzz.link/p?tents/3/2/10111").is_err());
+        assert!(Board::from_puzz_link("https://puzz.link/p?tents/3/2/10111c").is_err());
+        assert!(Board::from_puzz_link("https://puzz.link/p?tents/3/2/10111c1").is_err());
+        assert!(Board::from_puzz_link("https://puzz.link/p?tents/3/2/10111c00").is_err());
+        assert!(Board::from_puzz_link("https://puzz.link/p?tents/3/2/1.111c0").is_err());
+    }
+
+    #[test]
+    fn to_puzz_link_clue_free() {
+        let board = Board::new_parse_clue_free(1, " T").unwrap();
+        assert!(board.to_puzz_link().is_err());
+        let mut board = Board::new_parse(vec![1], vec![1, 0], " T").unwrap();
+        board.set_rules(RuleSet::torus());
+        assert!(board.to_puzz_link().is_err());
+        let board = Board::new_parse(vec![1], vec![1, 0, 0], " T#").unwrap();
+        assert!(board.to_puzz_link().is_err());
+    }
+}
@@ -0,0 +1,224 @@
+use super::json::*;
+use board::*;
+use board_move::*;
+use error::*;
+use pos::*;
+use serde_json::{self, Value};
+use std::io::{self, Read, Write};
+use tile::*;
+
+/// The version of the format written by [`Board::save_state`].
+///
+/// [`Board::save_state`]: ../struct.Board.html#method.save_state
+const STATE_VERSION: u64 = 1;
+
+/// Make an [`Error::Format`] for saved states.
+///
+/// [`Error::Format`]: ../enum.Error.html#variant.Format
+fn state_error(message: String) -> Error {
+    Error::Format {
+        format: "state",
+        message,
+    }
+}
+
+/// Convert `value` to a [`Move`] on a `Board` of the given size.
+///
+/// [`Move`]: ../struct.Move.html
+fn parse_move(value: &Value, num_rows: usize, num_columns: usize) -> Result<Move, Error> {
+    let error = || {
+        state_error(
+            "\"history\" must be a list of {\"cell\": [row, column], \"old\": tile, \"new\": tile}"
+                .to_string(),
+        )
+    };
+    let cell = value
+        .get("cell")
+        .and_then(Value::as_array)
+        .ok_or_else(error)?;
+    let index = |i: usize| cell.get(i).and_then(Value::as_u64).ok_or_else(error);
+    if cell.len() != 2 {
+        return Err(error());
+    }
+    let pos = Pos::new(index(0)? as usize, index(1)? as usize);
+    if pos.row >= num_rows || pos.column >= num_columns {
+        return Err(state_error(format!(
+            "move at row {}, column {} is outside the board",
+            pos.row, pos.column
+        )));
+    }
+    let tile = |name: &str| -> Result<Tile, Error> {
+        value
+            .get(name)
+            .and_then(Value::as_str)
+            .ok_or_else(error)?
+            .parse()
+    };
+    Ok(Move {
+        pos,
+        old: tile("old")?,
+        new: tile("new")?,
+    })
+}
+
+impl Board {
+    /// Write the `Board` and its [`Board::history`] to `writer` so
+    /// the game or solve can be picked up again with
+    /// [`Board::load_state`].
+    ///
+    /// The state is a line of JSON holding the fields of
+    /// [`Board::to_json`], a `"version"`, currently `1`, and a
+    /// `"history"` of moves like
+    /// `{"cell":[0,0],"new":"C","old":" "}`.  The `Tile`s the solver
+    /// has filled in are part of the grid, so solving a loaded
+    /// `Board` carries on from where it was saved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, Tile::*};
+    /// let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+    /// board.place(0, 0, Camp).unwrap();
+    /// let mut state = Vec::new();
+    /// board.save_state(&mut state).unwrap();
+    /// let mut loaded = Board::load_state(&state[..]).unwrap();
+    /// assert_eq!(loaded, board);
+    /// assert_eq!(loaded.history, board.history);
+    /// loaded.undo();
+    /// assert_eq!(loaded.debug(), " T\n  ");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Errors from `writer` are passed through.
+    ///
+    /// [`Board::history`]: struct.Board.html#structfield.history
+    /// [`Board::load_state`]: struct.Board.html#method.load_state
+    /// [`Board::to_json`]: struct.Board.html#method.to_json
+    pub fn save_state<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let history: Vec<Value> = self
+            .history
+            .iter()
+            .map(|x| {
+                let mut object = serde_json::Map::new();
+                object.insert(
+                    "cell".to_string(),
+                    Value::from(vec![x.pos.row, x.pos.column]),
+                );
+                object.insert("old".to_string(), Value::from(format!("{:?}", x.old)));
+                object.insert("new".to_string(), Value::from(format!("{:?}", x.new)));
+                Value::from(object)
+            })
+            .collect();
+        let mut object = to_object(self);
+        object.insert("version".to_string(), Value::from(STATE_VERSION));
+        object.insert("history".to_string(), Value::from(history));
+        writeln!(writer, "{}", Value::from(object))
+    }
+
+    /// Read a `Board` written by [`Board::save_state`] from `reader`.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, the state is malformed, or its version isn't
+    /// supported, an [`Error::Format`] is returned.  Errors from
+    /// [`Board::from_json`] are passed through.
+    ///
+    /// [`Board::save_state`]: struct.Board.html#method.save_state
+    /// [`Board::from_json`]: struct.Board.html#method.from_json
+    /// [`Error::Format`]: enum.Error.html#variant.Format
+    pub fn load_state<R: Read>(mut reader: R) -> Result<Board, Error> {
+        let mut s = String::new();
+        reader
+            .read_to_string(&mut s)
+            .map_err(|x| state_error(x.to_string()))?;
+        let value: Value = serde_json::from_str(&s).map_err(|x| state_error(x.to_string()))?;
+        match value.get("version").map(Value::as_u64) {
+            Some(Some(STATE_VERSION)) => (),
+            Some(Some(version)) => {
+                return Err(state_error(format!("unsupported version {}", version)))
+            }
+            _ => return Err(state_error("Missing field \"version\"".to_string())),
+        }
+        let mut board = from_value(&value)?;
+        let history = value
+            .get("history")
+            .and_then(Value::as_array)
+            .ok_or_else(|| state_error("\"history\" must be a list".to_string()))?;
+        board.history = history
+            .iter()
+            .map(|x| parse_move(x, board.num_rows(), board.num_columns()))
+            .collect::<Result<_, _>>()?;
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tile::Tile::*;
+
+    #[test]
+    fn state_round_trip() {
+        let mut board = Board::new_parse(vec![0, 2, 0], vec![1, 0, 1], "T T\n   \n   ").unwrap();
+        board.place(1, 0, Camp).unwrap();
+        board.place(1, 1, Grass).unwrap();
+        board.clear(1, 1).unwrap();
+        let mut state = Vec::new();
+        board.save_state(&mut state).unwrap();
+        assert_eq!(
+            String::from_utf8(state.clone()).unwrap(),
+            concat!(
+                r#"{"columns":[1,0,1],"grid":["T T","C  ","   "],"history":["#,
+                r#"{"cell":[1,0],"new":"C","old":" "},"#,
+                r#"{"cell":[1,1],"new":"-","old":" "},"#,
+                r#"{"cell":[1,1],"new":" ","old":"-"}],"#,
+                r#""rows":[0,2,0],"version":1}"#,
+                "\n"
+            )
+        );
+        let loaded = Board::load_state(&state[..]).unwrap();
+        assert_eq!(loaded, board);
+        assert_eq!(loaded.history, board.history);
+
+        let board = Board::new_parse_clue_free(1, "T \n  ").unwrap();
+        let mut state = Vec::new();
+        board.save_state(&mut state).unwrap();
+        assert_eq!(Board::load_state(&state[..]), Ok(board));
+    }
+
+    #[test]
+    fn load_state_errors() {
+        let load = |s: &str| Board::load_state(s.as_bytes());
+        assert!(load("{").is_err());
+        assert_eq!(
+            load(r#"{"total": 1, "grid": ["T "], "history": []}"#),
+            Err(state_error("Missing field \"version\"".to_string()))
+        );
+        assert_eq!(
+            load(r#"{"version": 2, "total": 1, "grid": ["T "], "history": []}"#),
+            Err(state_error("unsupported version 2".to_string()))
+        );
+        assert!(load(r#"{"version": 1, "total": 1, "grid": ["T "]}"#).is_err());
+        assert!(load(r#"{"version": 1, "total": 1, "grid": ["T "], "history": [1]}"#).is_err());
+        assert_eq!(
+            load(concat!(
+                r#"{"version": 1, "total": 1, "grid": ["T "], "#,
+                r#""history": [{"cell": [1, 0], "old": " ", "new": "C"}]}"#
+            )),
+            Err(state_error(
+                "move at row 1, column 0 is outside the board".to_string()
+            ))
+        );
+        assert_eq!(
+            load(concat!(
+                r#"{"version": 1, "total": 1, "grid": ["T "], "#,
+                r#""history": [{"cell": [0, 1], "old": " ", "new": "x"}]}"#
+            )),
+            Err(Error::Parse {
+                character: 'x',
+                position: None,
+            })
+        );
+    }
+}
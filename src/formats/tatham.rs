@@ -0,0 +1,209 @@
+use board::*;
+use error::*;
+use grid::*;
+use tile::Tile::*;
+
+/// Make an [`Error::Format`] for Tatham game IDs.
+///
+/// [`Error::Format`]: ../enum.Error.html#variant.Format
+fn tatham_error(message: &str) -> Error {
+    Error::Format {
+        format: "Tatham game ID",
+        message: message.to_string(),
+    }
+}
+
+/// Parse a number in a Tatham game ID.
+fn number(s: &str) -> Result<usize, Error> {
+    s.parse().map_err(|_| tatham_error("expected a number"))
+}
+
+impl Board {
+    /// Parse a game ID from Simon Tatham's Portable Puzzle Collection.
+    ///
+    /// Game IDs look like `WxH:trees,c1,..,cW,r1,..,rH`.  The [`Tree`]s
+    /// are run length encoded in reading order: `_` is a [`Tree`],
+    /// `a` through `y` are 1 through 25 empty tiles followed by a
+    /// [`Tree`], and `z` is 25 empty tiles.  The encoding ends with an
+    /// extra [`Tree`] just past the end of the [`Grid`].  The column
+    /// clues come before the row clues.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// assert_eq!(
+    ///     Board::from_tatham_id("3x2:a_c,1,0,1,1,1"),
+    ///     Board::new_parse(vec![1, 1], vec![1, 0, 1], " TT\n   ")
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the game ID is malformed, an [`Error::Format`] is returned.
+    ///
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Grid`]: struct.Grid.html
+    /// [`Error::Format`]: enum.Error.html#variant.Format
+    pub fn from_tatham_id(id: &str) -> Result<Board, Error> {
+        let mut parts = id.trim().splitn(2, ':');
+        let params = parts.next().unwrap();
+        let desc = parts
+            .next()
+            .ok_or_else(|| tatham_error("missing ':' after the dimensions"))?;
+        let mut dimensions = params.splitn(2, 'x');
+        let width = number(dimensions.next().unwrap())?;
+        let height = number(
+            dimensions
+                .next()
+                .ok_or_else(|| tatham_error("dimensions must look like WxH"))?,
+        )?;
+
+        let mut sections = desc.split(',');
+        let trees = sections.next().unwrap();
+        let mut grid = Grid::blank(height, width);
+        let mut i = 0;
+        let mut finished = false;
+        for c in trees.chars() {
+            if finished {
+                Err(tatham_error("too much data to fill the grid"))?
+            }
+            match c {
+                '_' => (),
+                'a'..='y' => i += c as usize - 'a' as usize + 1,
+                'z' => {
+                    i += 25;
+                    continue;
+                }
+                _ => Err(tatham_error("invalid character in the grid"))?,
+            }
+            if i == width * height {
+                finished = true;
+            } else if i < width * height {
+                grid[(i / width, i % width)] = Tree;
+                i += 1;
+            } else {
+                Err(tatham_error("too much data to fill the grid"))?
+            }
+        }
+        if !finished {
+            Err(tatham_error("not enough data to fill the grid"))?
+        }
+
+        let numbers: Result<Vec<_>, _> = sections.map(number).collect();
+        let mut numbers = numbers?;
+        if numbers.len() != width + height {
+            Err(tatham_error("wrong number of clues"))?
+        }
+        let rows = numbers.split_off(width);
+        Board::try_new(rows, numbers, grid)
+    }
+
+    /// Format the `Board` as a game ID for Simon Tatham's Portable
+    /// Puzzle Collection.
+    ///
+    /// Only the [`Tree`]s and clues are encoded.  See
+    /// [`Board::from_tatham_id`] for the format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// let board = Board::new_parse(vec![1, 1], vec![1, 0, 1], " TT\n C ").unwrap();
+    /// assert_eq!(board.to_tatham_id(), Ok("3x2:a_c,1,0,1,1,1".to_string()));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Clue-free `Board`s, `Board`s with omitted or range clues,
+    /// `Board`s with [`Blocked`] `Tile`s and `Board`s played by other
+    /// than the classic [`RuleSet`] can't be represented, so an
+    /// [`Error::Format`] is returned for them.
+    ///
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Blocked`]: enum.Tile.html#variant.Blocked
+    /// [`RuleSet`]: struct.RuleSet.html
+    /// [`Board::from_tatham_id`]: struct.Board.html#method.from_tatham_id
+    /// [`Error::Format`]: enum.Error.html#variant.Format
+    pub fn to_tatham_id(&self) -> Result<String, Error> {
+        let (rows, columns) = self.require_classic_exact("Tatham game ID", false)?;
+        let (width, height) = (self.num_columns(), self.num_rows());
+        let mut id = format!("{}x{}:", width, height);
+        let mut run = 0;
+        for i in 0..=width * height {
+            if i == width * height || self[(i / width, i % width)] == Tree {
+                while run > 25 {
+                    id.push('z');
+                    run -= 25;
+                }
+                id.push(if run == 0 {
+                    '_'
+                } else {
+                    (b'a' + run as u8 - 1) as char
+                });
+                run = 0;
+            } else {
+                run += 1;
+            }
+        }
+        for clue in columns.iter().chain(&rows) {
+            id.push_str(&format!(",{}", clue));
+        }
+        Ok(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rule_set::*;
+
+    #[test]
+    fn tatham_round_trip() {
+        let board = Board::new_parse(
+            vec![1, 1, 0, 2, 1],
+            vec![2, 0, 1, 1, 1],
+            "     \n T T \n     \nTT   \n    T",
+        ).unwrap();
+        let id = board.to_tatham_id().unwrap();
+        assert_eq!(id, "5x5:faf_g_,2,0,1,1,1,1,1,0,2,1");
+        assert_eq!(Board::from_tatham_id(&id), Ok(board));
+    }
+
+    #[test]
+    fn tatham_long_runs() {
+        let mut grid = Grid::blank(6, 10);
+        grid[(0, 0)] = Tree;
+        grid[(2, 6)] = Tree;
+        grid[(5, 9)] = Tree;
+        let board = Board::new(vec![0; 6], vec![0; 10], grid);
+        let id = board.to_tatham_id().unwrap();
+        assert_eq!(id, "10x6:_yzg_,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0");
+        assert_eq!(Board::from_tatham_id(&id), Ok(board));
+    }
+
+    #[test]
+    fn from_tatham_id_errors() {
+        assert!(Board::from_tatham_id("3x2").is_err());
+        assert!(Board::from_tatham_id("3:a_c,1,0,1,1,1").is_err());
+        assert!(Board::from_tatham_id("3x2:a_,1,0,1,1,1").is_err());
+        assert!(Board::from_tatham_id("3x2:a_c_,1,0,1,1,1").is_err());
+        assert!(Board::from_tatham_id("3x2:a_f,1,0,1,1,1").is_err());
+        assert!(Board::from_tatham_id("3x2:a!d,1,0,1,1,1").is_err());
+        assert!(Board::from_tatham_id("3x2:a_c,1,0,1,1").is_err());
+        assert!(Board::from_tatham_id("3x2:a_c,1,0,1,1,x").is_err());
+    }
+
+    #[test]
+    fn to_tatham_id_clue_free() {
+        let board = Board::new_parse_clue_free(1, " T").unwrap();
+        assert!(board.to_tatham_id().is_err());
+        let board = Board::new_parse_partial(vec![None], vec![Some(1), Some(0)], " T").unwrap();
+        assert!(board.to_tatham_id().is_err());
+        let mut board = Board::new_parse(vec![1], vec![1, 0], " T").unwrap();
+        board.set_rules(RuleSet::torus());
+        assert!(board.to_tatham_id().is_err());
+        let board = Board::new_parse(vec![1], vec![1, 0, 0], " T#").unwrap();
+        assert!(board.to_tatham_id().is_err());
+    }
+}
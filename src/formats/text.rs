@@ -0,0 +1,292 @@
+use board::*;
+use clue::*;
+use error::*;
+use grid::*;
+use rule_set::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// Make an [`Error::Format`] for the text format.
+///
+/// [`Error::Format`]: ../enum.Error.html#variant.Format
+fn text_error<S: Into<String>>(message: S) -> Error {
+    Error::Format {
+        format: "puzzle text",
+        message: message.into(),
+    }
+}
+
+/// Split a line of clues by `,` and parse the pieces as [`Clue`]s.
+///
+/// [`Clue`]: ../struct.Clue.html
+fn read_clues(s: &str) -> Result<Vec<Clue>, Error> {
+    if s.trim().is_empty() {
+        Err(text_error("Row or column descriptors must not be empty"))?
+    }
+    s.split(',')
+        .map(|x| {
+            x.parse()
+                .map_err(|_| text_error(format!("Invalid clue {:?}", x.trim())))
+        })
+        .collect()
+}
+
+/// Parse a clue-free header of the form `total: N`.
+///
+/// Returns `None` if `s` isn't such a header.
+fn read_total(s: &str) -> Option<Result<usize, Error>> {
+    let total = s.trim().strip_prefix("total:")?.trim();
+    Some(
+        total
+            .parse()
+            .map_err(|x: std::num::ParseIntError| text_error(x.to_string())),
+    )
+}
+
+/// Parse a header of the form `rules: NAME`.
+///
+/// Returns `None` if `s` isn't such a header.
+fn read_rules(s: &str) -> Option<Result<RuleSet, Error>> {
+    Some(s.trim().strip_prefix("rules:")?.parse())
+}
+
+/// Join `clues` with `, `.
+fn write_clues(clues: &[Clue]) -> String {
+    let clues: Vec<_> = clues.iter().map(Clue::to_string).collect();
+    clues.join(", ")
+}
+
+impl Board {
+    /// Parse a `Board` from the plain text format read by the command
+    /// line program.
+    ///
+    /// The first line holds the row clues and the second line the
+    /// column clues, each separated by `,`.  The remaining lines are
+    /// the [`Grid`], parsed by [`Grid::parse`].  A clue of `?` is
+    /// omitted, and `<=N`, `>=N` and `A..B` are bounds, parsed as
+    /// [`Clue`]s.  Clue-free `Board`s start with a single `total: N`
+    /// line instead.  `Board`s played by other rules start with a
+    /// `rules: NAME` line naming their [`RuleSet`], such as
+    /// `rules: torus`.
+    ///
+    /// This is the canonical text representation of a `Board`, also
+    /// used by its `FromStr` and `Display` implementations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, Clue, RuleSet};
+    /// assert_eq!(
+    ///     Board::from_text("1, 0\n1, 0\n T\n  "),
+    ///     Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ")
+    /// );
+    /// assert_eq!(
+    ///     Board::from_text("total: 1\n T\n  "),
+    ///     Board::new_parse_clue_free(1, " T\n  ")
+    /// );
+    /// assert_eq!(
+    ///     Board::from_text("1, ?\n?, 0\n T\n  "),
+    ///     Board::new_parse_partial(vec![Some(1), None], vec![None, Some(0)], " T\n  ")
+    /// );
+    /// assert_eq!(
+    ///     Board::from_text("<=1, 0\n1..2, ≥0\n T\n  "),
+    ///     Board::new_parse_ranged(
+    ///         vec![Clue::at_most(1), Clue::exactly(0)],
+    ///         vec![Clue::between(1, 2), Clue::at_least(0)],
+    ///         " T\n  ",
+    ///     )
+    /// );
+    /// let torus = Board::from_text("rules: torus\n1\n1, 0, 0\nC T").unwrap();
+    /// assert_eq!(torus.rules(), RuleSet::torus());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If there are too few lines or the clues can't be parsed, an
+    /// [`Error::Format`] is returned.  Errors from parsing the
+    /// [`Grid`] or building the `Board` are passed through.
+    ///
+    /// [`Grid`]: struct.Grid.html
+    /// [`Grid::parse`]: struct.Grid.html#method.parse
+    /// [`Clue`]: struct.Clue.html
+    /// [`RuleSet`]: struct.RuleSet.html
+    /// [`Error::Format`]: enum.Error.html#variant.Format
+    pub fn from_text(s: &str) -> Result<Board, Error> {
+        let mut lines: Vec<_> = s.lines().collect();
+        let rules = match lines.first().and_then(|x| read_rules(x)) {
+            Some(rules) => {
+                lines.remove(0);
+                rules.map_err(|x| text_error(x.to_string()))?
+            }
+            None => RuleSet::default(),
+        };
+        let grid = |lines: &[&str]| -> Result<Grid, Error> {
+            let mut grid = Grid::parse(&lines.join("\n"))?;
+            grid.set_rules(rules);
+            Ok(grid)
+        };
+        if let Some(total) = lines.first().and_then(|x| read_total(x)) {
+            if lines.len() < 2 {
+                Err(text_error("Too few lines.  There must be at least 2."))?
+            }
+            return Ok(Board::new_clue_free(total?, grid(&lines[1..])?));
+        }
+        if lines.len() < 3 {
+            Err(text_error("Too few lines.  There must be at least 3."))?
+        }
+        let rows = read_clues(lines[0])?;
+        let columns = read_clues(lines[1])?;
+        Board::try_new_ranged(rows, columns, grid(&lines[2..])?)
+    }
+
+    /// Write the `Board` in the format read by [`Board::from_text`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+    /// assert_eq!(board.to_text(), "1, 0\n1, 0\n T\n  ");
+    /// let board = Board::new_parse_clue_free(1, " T\n  ").unwrap();
+    /// assert_eq!(board.to_text(), "total: 1\n T\n  ");
+    /// ```
+    ///
+    /// [`Board::from_text`]: struct.Board.html#method.from_text
+    pub fn to_text(&self) -> String {
+        let rules = if self.rules() == RuleSet::default() {
+            String::new()
+        } else {
+            format!("rules: {}\n", self.rules())
+        };
+        if self.is_clue_free() {
+            format!("{}total: {}\n{}", rules, self.total(), self.debug())
+        } else {
+            format!(
+                "{}{}\n{}\n{}",
+                rules,
+                write_clues(&self.row_ranges()),
+                write_clues(&self.column_ranges()),
+                self.debug()
+            )
+        }
+    }
+}
+
+impl FromStr for Board {
+    type Err = Error;
+
+    /// See [`Board::from_text`].
+    ///
+    /// [`Board::from_text`]: struct.Board.html#method.from_text
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Board::from_text(s)
+    }
+}
+
+impl fmt::Display for Board {
+    /// See [`Board::to_text`].
+    ///
+    /// [`Board::to_text`]: struct.Board.html#method.to_text
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_text())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_clues_empty() {
+        assert!(read_clues("").is_err());
+    }
+
+    #[test]
+    fn read_clues_one_element() {
+        assert_eq!(read_clues("1"), Ok(vec![Clue::exactly(1)]));
+    }
+
+    #[test]
+    fn read_clues_three_elements() {
+        assert_eq!(
+            read_clues("1, 2, 3"),
+            Ok(vec![Clue::exactly(1), Clue::exactly(2), Clue::exactly(3)])
+        );
+    }
+
+    #[test]
+    fn read_clues_omitted() {
+        assert_eq!(
+            read_clues("1, ?,?"),
+            Ok(vec![Clue::exactly(1), Clue::any(), Clue::any()])
+        );
+        assert!(read_clues("1, ??").is_err());
+    }
+
+    #[test]
+    fn read_clues_ranges() {
+        assert_eq!(
+            read_clues("<=2, ≥1,1..3"),
+            Ok(vec![
+                Clue::at_most(2),
+                Clue::at_least(1),
+                Clue::between(1, 3)
+            ])
+        );
+        assert!(read_clues("1..").is_err());
+    }
+
+    #[test]
+    fn from_text_too_little_input() {
+        assert!(Board::from_text("").is_err());
+        assert!(Board::from_text("0\n0").is_err());
+        assert!(Board::from_text("total: 1").is_err());
+        assert!(Board::from_text("total: x\n T").is_err());
+    }
+
+    #[test]
+    fn text_round_trip() {
+        let board = Board::new_parse(vec![1, 1], vec![1, 0, 1], "CTT\n  -").unwrap();
+        assert_eq!(Board::from_text(&board.to_text()), Ok(board));
+    }
+
+    #[test]
+    fn text_round_trip_omitted_clues() {
+        let board =
+            Board::new_parse_partial(vec![None, Some(1)], vec![None, None, Some(1)], "CTT\n  -")
+                .unwrap();
+        assert_eq!(board.to_text(), "?, 1\n?, ?, 1\nCTT\n  -");
+        assert_eq!(Board::from_text(&board.to_text()), Ok(board));
+    }
+
+    #[test]
+    fn text_round_trip_range_clues() {
+        let rows = vec![Clue::at_most(1), Clue::exactly(1)];
+        let columns = vec![Clue::at_least(1), Clue::any(), Clue::between(0, 1)];
+        let board = Board::new_parse_ranged(rows, columns, "CTT\n  -").unwrap();
+        assert_eq!(board.to_text(), "<=1, 1\n>=1, ?, <=1\nCTT\n  -");
+        assert_eq!(Board::from_text(&board.to_text()), Ok(board));
+    }
+
+    #[test]
+    fn text_round_trip_rules() {
+        // The `Camp` and `Tree` only touch across the edge.
+        let board = Board::from_text("rules: torus\ntotal: 1\nC T").unwrap();
+        assert_eq!(board.rules(), RuleSet::torus());
+        assert_eq!(board.to_text(), "rules: torus\ntotal: 1\nC T");
+        assert_eq!(Board::from_text(&board.to_text()), Ok(board));
+        assert!(Board::from_text("rules: donut\ntotal: 1\nC T").is_err());
+        assert!(Board::from_text("rules: torus\n1").is_err());
+    }
+
+    #[test]
+    fn from_str_and_display() {
+        let board: Board = "1,0\n1,0\n T\n  ".parse().unwrap();
+        assert_eq!(
+            board,
+            Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap()
+        );
+        assert_eq!(board.to_string(), "1, 0\n1, 0\n T\n  ");
+        assert_eq!(board.to_string().parse(), Ok(board));
+    }
+}
@@ -0,0 +1,135 @@
+use board::*;
+use clue::*;
+use std::fmt::Write;
+use tile::Tile::*;
+
+/// Format `clue` as LaTeX, or `None` for an omitted clue.
+fn clue_text(clue: Clue) -> Option<String> {
+    match (clue.min, clue.max) {
+        (0, None) => None,
+        (min, None) => Some(format!("$\\ge {}$", min)),
+        (min, Some(max)) if min == max => Some(min.to_string()),
+        (0, Some(max)) => Some(format!("$\\le {}$", max)),
+        (min, Some(max)) => Some(format!("{}--{}", min, max)),
+    }
+}
+
+impl Board {
+    /// Draw the `Board` as a TikZ picture for LaTeX documents.
+    ///
+    /// Each cell is a 1cm square, with the column clues across the
+    /// top and the row clues down the left side.  [`Tree`]s and
+    /// [`Blocked`] cells are drawn, and so are any [`Camp`]s and
+    /// [`Grass`] already placed, so calling this on a solved copy of
+    /// the `Board` draws its solution.  Scale the picture with
+    /// `\resizebox` or the `scale` option of `tikzpicture`.
+    ///
+    /// The colors need `\usepackage{xcolor}`, which TikZ loads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+    /// let tikz = board.to_tikz();
+    /// assert!(tikz.starts_with("\\begin{tikzpicture}"));
+    /// assert!(tikz.contains("\\draw (0,0) grid (2,2);"));
+    /// ```
+    ///
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Blocked`]: enum.Tile.html#variant.Blocked
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Grass`]: enum.Tile.html#variant.Grass
+    pub fn to_tikz(&self) -> String {
+        let (height, width) = (self.num_rows(), self.num_columns());
+        let mut s = String::new();
+        // Rows go down the page.
+        writeln!(s, "\\begin{{tikzpicture}}[x=1cm, y=-1cm]").unwrap();
+        for row in 0..height {
+            for column in 0..width {
+                let (x, y) = (column, row);
+                match self[(row, column)] {
+                    Unassigned => {}
+                    Grass => writeln!(
+                        s,
+                        "\\fill[green!20] ({},{}) rectangle +(1,1);",
+                        x, y
+                    )
+                    .unwrap(),
+                    Camp => writeln!(
+                        s,
+                        "\\fill[orange] ({},{}) ++(0.5,0.15) -- ++(-0.35,0.7) -- ++(0.7,0) -- cycle;",
+                        x, y
+                    )
+                    .unwrap(),
+                    Tree => {
+                        writeln!(
+                            s,
+                            "\\fill[brown] ({},{}) ++(0.43,0.5) rectangle +(0.14,0.35);",
+                            x, y
+                        )
+                        .unwrap();
+                        writeln!(
+                            s,
+                            "\\fill[green!50!black] ({},{}) ++(0.5,0.4) circle (0.27);",
+                            x, y
+                        )
+                        .unwrap();
+                    }
+                    Blocked => writeln!(
+                        s,
+                        "\\fill[gray!50] ({},{}) rectangle +(1,1);",
+                        x, y
+                    )
+                    .unwrap(),
+                }
+            }
+        }
+        writeln!(s, "\\draw (0,0) grid ({},{});", width, height).unwrap();
+        if !self.is_clue_free() {
+            for (column, clue) in self.column_ranges().into_iter().enumerate() {
+                if let Some(text) = clue_text(clue) {
+                    writeln!(s, "\\node at ({}.5,-0.5) {{{}}};", column, text).unwrap();
+                }
+            }
+            for (row, clue) in self.row_ranges().into_iter().enumerate() {
+                if let Some(text) = clue_text(clue) {
+                    writeln!(s, "\\node at (-0.5,{}.5) {{{}}};", row, text).unwrap();
+                }
+            }
+        }
+        writeln!(s, "\\end{{tikzpicture}}").unwrap();
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tikz_tiles() {
+        let board = Board::new_parse(vec![1, 0], vec![1, 0], "CT\n-#").unwrap();
+        let tikz = board.to_tikz();
+        assert!(tikz.contains("\\fill[orange] (0,0) ++(0.5,0.15) -- ++(-0.35,0.7)"));
+        assert!(tikz.contains("\\fill[green!50!black] (1,0) ++(0.5,0.4) circle (0.27);"));
+        assert!(tikz.contains("\\fill[green!20] (0,1) rectangle +(1,1);"));
+        assert!(tikz.contains("\\fill[gray!50] (1,1) rectangle +(1,1);"));
+        assert!(tikz.contains("\\node at (0.5,-0.5) {1};"));
+        assert!(tikz.contains("\\node at (-0.5,1.5) {0};"));
+        assert!(tikz.ends_with("\\end{tikzpicture}\n"));
+    }
+
+    #[test]
+    fn tikz_clues() {
+        let rows = vec![Clue::at_most(1), Clue::any()];
+        let columns = vec![Clue::between(1, 2), Clue::at_least(0)];
+        let board = Board::new_parse_ranged(rows, columns, " T\n  ").unwrap();
+        let tikz = board.to_tikz();
+        assert!(tikz.contains("\\node at (-0.5,0.5) {$\\le 1$};"));
+        assert!(tikz.contains("\\node at (0.5,-0.5) {1--2};"));
+        assert_eq!(tikz.matches("\\node").count(), 2);
+        let board = Board::new_parse_clue_free(1, " T").unwrap();
+        assert!(!board.to_tikz().contains("\\node"));
+    }
+}
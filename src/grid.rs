@@ -1,25 +1,62 @@
+use error::*;
+use parse_options::*;
+use pos::*;
+use rule_set::*;
 use std::fmt;
 use std::ops::{Index, IndexMut};
+use std::str::FromStr;
 use tile::Tile::{self, *};
+use tile_charset::*;
 
 /// A `Grid` of [`Tile`]s.
 ///
+/// The [`Tile`]s are stored in a single `Vec` in reading order.
+///
+/// `Grid`s are ordered by their `Tile`s in reading order, then by
+/// their size, then by their [`RuleSet`].
+///
 /// [`Tile`]: enum.Tile.html
-#[derive(Clone, PartialEq, Eq)]
+/// [`RuleSet`]: struct.RuleSet.html
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Grid {
-    pub array: Vec<Vec<Tile>>,
+    tiles: Vec<Tile>,
+    height: usize,
+    width: usize,
+    rules: RuleSet,
 }
 
 impl Grid {
     /// Create a new `Grid` from a table of `Tile`s.
+    ///
+    /// # Panics
+    ///
+    /// This will `panic` if the rows have different lengths.
     pub fn new(array: Vec<Vec<Tile>>) -> Grid {
-        Grid { array }
+        let height = array.len();
+        let width = array.first().map(|x| x.len()).unwrap_or(0);
+        assert!(
+            array.iter().all(|row| row.len() == width),
+            "Every row of a Grid must have the same length"
+        );
+        Grid {
+            tiles: array.into_iter().flatten().collect(),
+            height,
+            width,
+            rules: RuleSet::default(),
+        }
     }
 
     /// Create a new `Grid` by parsing the string.
     ///
     /// This parses characters via [`Tile::parse`] and `\n` as the
-    /// start of the next row.
+    /// start of the next row.  `\r` characters and a trailing newline
+    /// are ignored.
+    ///
+    /// # Errors
+    ///
+    /// If a character can't be parsed, an [`Error::Parse`] with its
+    /// position is returned.  If the rows have different lengths, an
+    /// [`Error::RaggedRow`] is returned.
     ///
     /// # Examples
     ///
@@ -33,19 +70,100 @@ impl Grid {
     ///        vec![Grass, Grass, Grass]
     ///    ].into())
     /// );
+    /// assert_eq!(Grid::parse("TC-\r\n - \r\n---\r\n"), Grid::parse("TC-\n - \n---"));
+    /// ```
+    ///
+    /// [`Tile::parse`]: enum.Tile.html#method.parse
+    /// [`Error::Parse`]: enum.Error.html#variant.Parse
+    /// [`Error::RaggedRow`]: enum.Error.html#variant.RaggedRow
+    pub fn parse(s: &str) -> Result<Grid, Error> {
+        Grid::parse_with_options(s, &ParseOptions::default())
+    }
+
+    /// Create a new `Grid` by parsing the string with the characters
+    /// in `charset`.
+    ///
+    /// This is like [`Grid::parse`] but parses characters via
+    /// [`TileCharset::parse`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Grid::parse`].
+    ///
+    /// [`Grid::parse`]: struct.Grid.html#method.parse
+    /// [`TileCharset::parse`]: struct.TileCharset.html#method.parse
+    pub fn parse_with(s: &str, charset: &TileCharset) -> Result<Grid, Error> {
+        let options = ParseOptions {
+            charset: *charset,
+            ..Default::default()
+        };
+        Grid::parse_with_options(s, &options)
+    }
+
+    /// Create a new `Grid` by parsing the string as described by
+    /// `options`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use camps_and_trees::{Grid, ParseOptions};
+    /// let options = ParseOptions {
+    ///     pad_rows: true,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     Grid::parse_with_options("T\n-C\n", &options),
+    ///     Grid::parse("T \n-C")
+    /// );
     /// ```
-    pub fn parse(s: &str) -> Result<Grid, String> {
+    ///
+    /// # Errors
+    ///
+    /// If a character can't be parsed, an [`Error::Parse`] with its
+    /// position is returned.  If the rows have different lengths and
+    /// can't be padded, an [`Error::RaggedRow`] is returned.
+    ///
+    /// [`Error::Parse`]: enum.Error.html#variant.Parse
+    /// [`Error::RaggedRow`]: enum.Error.html#variant.RaggedRow
+    pub fn parse_with_options(s: &str, options: &ParseOptions) -> Result<Grid, Error> {
         let mut grid = Vec::new();
         let mut row = Vec::new();
         for c in s.chars() {
+            if c == '\r' && !options.strict {
+                continue;
+            }
             if c == '\n' {
                 grid.push(row);
                 row = Vec::new();
             } else {
-                row.push(Tile::parse(c)?);
+                let tile = options.charset.parse(c).map_err(|_| Error::Parse {
+                    character: c,
+                    position: Some(Pos::new(grid.len(), row.len())),
+                })?;
+                row.push(tile);
+            }
+        }
+        if grid.is_empty() || !row.is_empty() || options.strict {
+            grid.push(row);
+        }
+
+        let pad = options.pad_rows && !options.strict;
+        let expected = if pad {
+            grid.iter().map(|row| row.len()).max().unwrap_or(0)
+        } else {
+            grid[0].len()
+        };
+        for (index, row) in grid.iter_mut().enumerate() {
+            if pad {
+                row.resize(expected, Unassigned);
+            } else if row.len() != expected {
+                Err(Error::RaggedRow {
+                    row: index,
+                    columns: row.len(),
+                    expected,
+                })?
             }
         }
-        grid.push(row);
         Ok(grid.into())
     }
 
@@ -55,7 +173,12 @@ impl Grid {
     ///
     /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
     pub fn blank(rows: usize, columns: usize) -> Grid {
-        vec![vec![Tile::Unassigned; columns]; rows].into()
+        Grid {
+            tiles: vec![Unassigned; rows * columns],
+            height: rows,
+            width: columns,
+            rules: RuleSet::default(),
+        }
     }
 
     /// Get the `Tile` at `(row, column)`.
@@ -67,52 +190,188 @@ impl Grid {
     /// If you are sure the coordinates are in bounds, use the `Index`
     /// operator: `grid[(row, column)]`.
     pub fn get(&self, row: usize, column: usize) -> Option<Tile> {
-        self.array.get(row).and_then(|r| r.get(column).cloned())
+        if row < self.height && column < self.width {
+            Some(self.tiles[row * self.width + column])
+        } else {
+            None
+        }
     }
 
-    /// Set the [`Tile`] at `(row, column)` to a [`Camp`].
+    /// Set the [`Tile`] at `pos` to a [`Camp`].
     ///
-    /// This will fill the surrounding and diagonal tiles with [`Grass`]
+    /// This will fill the surrounding and diagonal tiles with [`Grass`],
+    /// or only the surrounding ones if the [`RuleSet`] lets [`Camp`]s
+    /// touch diagonally.  See [`Grid::touching`].
     ///
     /// # Errors
     ///
-    /// If a [`Camp`] is already at a surrounding or diagonal tile,
-    /// then an error is produced.  The `Grid` is not modified on an
-    /// error.
+    /// If a [`Camp`] is already at `pos` or one of those tiles, then an
+    /// [`Error::CampConflict`] holding that [`Camp`] is produced.  The
+    /// `Grid` is not modified on an error.
     ///
     /// [`Tile`]: enum.Tile.html
     /// [`Camp`]: enum.Tile.html#variant.Camp
     /// [`Grass`]: enum.Tile.html#variant.Grass
-    pub fn set_camp(&mut self, row: usize, column: usize) -> Result<(), String> {
-        for r in row.saturating_sub(1)..=row + 1 {
-            for c in column.saturating_sub(1)..=column + 1 {
-                if self.get(r, c) == Some(Camp) {
-                    Err(format!(
-                        "Camps next to each other at row {}, column {}",
-                        row, column
-                    ))?;
-                }
-            }
+    /// [`Error::CampConflict`]: enum.Error.html#variant.CampConflict
+    /// [`RuleSet`]: struct.RuleSet.html
+    /// [`Grid::touching`]: struct.Grid.html#method.touching
+    pub fn set_camp(&mut self, pos: Pos) -> Result<(), Error> {
+        if let Some(conflict) = self.camp_conflict(pos) {
+            Err(Error::CampConflict { pos, conflict })?;
         }
-        self[(row, column)] = Camp;
-        for r in row.saturating_sub(1)..=row + 1 {
-            for c in column.saturating_sub(1)..=column + 1 {
-                if self.get(r, c) == Some(Unassigned) {
-                    self[(r, c)] = Grass;
-                }
+        self[pos] = Camp;
+        for x in self.touching(pos) {
+            if self[x] == Unassigned {
+                self[x] = Grass;
             }
         }
         Ok(())
     }
 
+    /// Can a [`Camp`] be placed at `pos`?
+    ///
+    /// This is `true` exactly when [`Grid::set_camp`] would succeed,
+    /// but doesn't modify the `Grid`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Grid, Pos};
+    /// let grid = Grid::parse("C  \n   ").unwrap();
+    /// assert!(!grid.can_place_camp(Pos::new(1, 1)));
+    /// assert!(grid.can_place_camp(Pos::new(1, 2)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `pos` is outside the `Grid`.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Grid::set_camp`]: struct.Grid.html#method.set_camp
+    pub fn can_place_camp(&self, pos: Pos) -> bool {
+        self.camp_conflict(pos).is_none()
+    }
+
+    /// Find a [`Camp`] at `pos` or touching it, possibly diagonally.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    fn camp_conflict(&self, pos: Pos) -> Option<Pos> {
+        if self[pos] == Camp {
+            return Some(pos);
+        }
+        self.touching(pos).into_iter().find(|&x| self[x] == Camp)
+    }
+
+    /// Find a [`Camp`] that touches another [`Camp`], possibly
+    /// diagonally.
+    ///
+    /// Returns the position of the first such [`Camp`] in reading
+    /// order.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub fn find_adjacent_camps(&self) -> Option<Pos> {
+        self.positions_of(Camp)
+            .find(|&pos| self.touching(pos).into_iter().any(|x| self[x] == Camp))
+    }
+
+    /// Get the [`RuleSet`] the `Grid` is played by.
+    ///
+    /// [`RuleSet`]: struct.RuleSet.html
+    pub fn rules(&self) -> RuleSet {
+        self.rules
+    }
+
+    /// Play the `Grid` by `rules` from now on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Grid, Pos, RuleSet};
+    /// let mut grid = Grid::parse("C  ").unwrap();
+    /// assert!(grid.can_place_camp(Pos::new(0, 2)));
+    /// grid.set_rules(RuleSet::torus());
+    /// assert!(!grid.can_place_camp(Pos::new(0, 2)));
+    /// ```
+    pub fn set_rules(&mut self, rules: RuleSet) {
+        self.rules = rules;
+    }
+
     /// Get the number of rows in the `Grid`.
     pub fn num_rows(&self) -> usize {
-        self.array.len()
+        self.height
     }
 
     /// Get the number of columns in the `Grid`.
     pub fn num_columns(&self) -> usize {
-        self.array.get(0).map(|x| x.len()).unwrap_or(0)
+        self.width
+    }
+
+    /// Iterate over every `Tile` with its position in reading order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use camps_and_trees::{Grid, Pos, Tile::*};
+    /// let grid = Grid::parse("TC\n -").unwrap();
+    /// assert_eq!(
+    ///     grid.cells().collect::<Vec<_>>(),
+    ///     vec![
+    ///         (Pos::new(0, 0), Tree),
+    ///         (Pos::new(0, 1), Camp),
+    ///         (Pos::new(1, 0), Unassigned),
+    ///         (Pos::new(1, 1), Grass)
+    ///     ]
+    /// );
+    /// ```
+    pub fn cells<'a>(&'a self) -> impl Iterator<Item = (Pos, Tile)> + 'a {
+        self.rows().enumerate().flat_map(|(row, tiles)| {
+            tiles
+                .iter()
+                .enumerate()
+                .map(move |(column, &tile)| (Pos::new(row, column), tile))
+        })
+    }
+
+    /// Iterate over the rows of the `Grid` from top to bottom.
+    pub fn rows<'a>(&'a self) -> impl Iterator<Item = &'a [Tile]> + 'a {
+        (0..self.height).map(move |row| self.row_tiles(row))
+    }
+
+    /// Iterate over the columns of the `Grid` from left to right.
+    ///
+    /// Each column iterates over its `Tile`s from top to bottom.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use camps_and_trees::{Grid, Tile::*};
+    /// let grid = Grid::parse("TC\n -").unwrap();
+    /// let columns: Vec<Vec<_>> = grid.columns().map(|x| x.collect()).collect();
+    /// assert_eq!(columns, vec![vec![Tree, Unassigned], vec![Camp, Grass]]);
+    /// ```
+    pub fn columns<'a>(&'a self) -> impl Iterator<Item = impl Iterator<Item = Tile> + 'a> + 'a {
+        (0..self.width).map(move |column| self.rows().map(move |row| row[column]))
+    }
+
+    /// Iterate over the position of every `Tile` equal to `tile` in
+    /// reading order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use camps_and_trees::{Grid, Tile::*};
+    /// let grid = Grid::parse("TC\nT-").unwrap();
+    /// assert_eq!(grid.positions_of(Tree).collect::<Vec<_>>(), vec![(0, 0), (1, 0)]);
+    /// ```
+    pub fn positions_of<'a>(&'a self, tile: Tile) -> impl Iterator<Item = Pos> + 'a {
+        self.cells()
+            .filter(move |&(_, x)| x == tile)
+            .map(|(position, _)| position)
+    }
+
+    /// Get the number of `Tile`s equal to `tile` in the whole `Grid`.
+    pub fn count(&self, tile: Tile) -> usize {
+        self.positions_of(tile).count()
     }
 
     /// Get the number of `Tile`s equal to `tile` in the given row.
@@ -124,13 +383,7 @@ impl Grid {
         // because of the strong guarantees of Vec, this check isn't
         // necessary, but it does make it easier to debug.
         debug_assert!(row < self.num_rows());
-        let mut count = 0;
-        for column in 0..self.num_columns() {
-            if self[(row, column)] == tile {
-                count += 1;
-            }
-        }
-        count
+        self.row_tiles(row).iter().filter(|&&x| x == tile).count()
     }
 
     /// Get the number of `Tile`s equal to `tile` in the given column.
@@ -142,21 +395,16 @@ impl Grid {
         // because of the strong guarantees of Vec, this check isn't
         // necessary, but it does make it easier to debug.
         debug_assert!(column < self.num_columns());
-        let mut count = 0;
-        for row in 0..self.num_rows() {
-            if self[(row, column)] == tile {
-                count += 1;
-            }
-        }
-        count
+        self.rows().filter(|row| row[column] == tile).count()
     }
 
-    /// Get the [`Tile`]s that surround the [`Tile`] at `(row, column)`.
+    /// Get the [`Tile`]s that surround the [`Tile`] at `pos`.
     ///
     /// This will return the points inside the `Grid` with `row +- 1`
-    /// *or* `column +- 1`.
+    /// *or* `column +- 1`.  See [`Pos::neighbors4`].  If the `Grid`
+    /// wraps, points past an edge come back in from the opposite one.
     ///
-    /// If a [`Camp`] is at `(row, column)`, this will return all
+    /// If a [`Camp`] is at `pos`, this will return all
     /// coordinates an associated [`Forest`] could be at.
     ///
     /// # Examples
@@ -164,9 +412,9 @@ impl Grid {
     /// Corners will return the two coordinates inside the `Grid`:
     ///
     /// ```
-    /// # use camps_and_trees::Grid;
+    /// # use camps_and_trees::{Grid, Pos};
     /// assert_eq!(
-    ///     Grid::blank(3, 3).surrounding_tiles(0, 0),
+    ///     Grid::blank(3, 3).surrounding_tiles(Pos::new(0, 0)),
     ///     vec![(0, 1), (1, 0)]
     /// );
     /// ```
@@ -175,9 +423,9 @@ impl Grid {
     /// `(-1, 1)`):
     ///
     /// ```
-    /// # use camps_and_trees::Grid;
+    /// # use camps_and_trees::{Grid, Pos};
     /// assert_eq!(
-    ///     Grid::blank(3, 3).surrounding_tiles(0, 1),
+    ///     Grid::blank(3, 3).surrounding_tiles(Pos::new(0, 1)),
     ///     vec![(0, 0), (0, 2), (1, 1)]
     /// );
     /// ```
@@ -185,37 +433,109 @@ impl Grid {
     /// Coordinates in the middle will return all four:
     ///
     /// ```
-    /// # use camps_and_trees::Grid;
+    /// # use camps_and_trees::{Grid, Pos};
     /// assert_eq!(
-    ///     Grid::blank(3, 3).surrounding_tiles(1, 1),
+    ///     Grid::blank(3, 3).surrounding_tiles(Pos::new(1, 1)),
     ///     vec![(0, 1), (1, 0), (1, 2), (2, 1)]
     /// );
     /// ```
     ///
     /// # Panics
     ///
-    /// This function will panic if `(row, column)` is outside the
-    /// `Grid`.
+    /// This function will panic if `pos` is outside the `Grid`.
     ///
+    /// [`Pos::neighbors4`]: struct.Pos.html#method.neighbors4
     /// [`Tile`]: enum.Tile.html
     /// [`Forest`]: enum.Tile.html#variant.Forest
     /// [`Camp`]: enum.Tile.html#variant.Camp
-    pub fn surrounding_tiles(&self, row: usize, column: usize) -> Vec<(usize, usize)> {
-        assert!(self.get(row, column).is_some());
-        let mut vec = Vec::new();
-        if row != 0 {
-            vec.push((row - 1, column));
-        }
-        if column != 0 {
-            vec.push((row, column - 1));
-        }
-        if column + 1 != self.num_columns() {
-            vec.push((row, column + 1));
-        }
-        if row + 1 != self.num_rows() {
-            vec.push((row + 1, column));
-        }
-        vec
+    pub fn surrounding_tiles(&self, pos: Pos) -> Vec<Pos> {
+        self.neighbors4(pos)
+    }
+
+    /// Get the positions directly above, left, right, and below `pos`
+    /// that are inside the `Grid`, wrapping around its edges if its
+    /// [`RuleSet`] says to.
+    ///
+    /// This is the same as [`Grid::surrounding_tiles`].
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `pos` is outside the `Grid`.
+    ///
+    /// [`RuleSet`]: struct.RuleSet.html
+    /// [`Grid::surrounding_tiles`]: struct.Grid.html#method.surrounding_tiles
+    pub fn neighbors4(&self, pos: Pos) -> Vec<Pos> {
+        assert!(self.get(pos.row, pos.column).is_some());
+        let (rows, columns) = (self.num_rows(), self.num_columns());
+        self.rules.neighbors4(pos, rows, columns)
+    }
+
+    /// Get the positions touching `pos`, including diagonally, that
+    /// are inside the `Grid`, wrapping around like
+    /// [`Grid::neighbors4`].
+    ///
+    /// In the classic game these are the [`Tile`]s that must be
+    /// [`Grass`] if a [`Camp`] is at `pos`, which are given by
+    /// [`Grid::touching`].  They are returned in reading order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Grid, Pos};
+    /// assert_eq!(
+    ///     Grid::blank(3, 3).neighbors8(Pos::new(2, 0)),
+    ///     vec![(1, 0), (1, 1), (2, 1)]
+    /// );
+    /// assert_eq!(Grid::blank(3, 3).neighbors8(Pos::new(1, 1)).len(), 8);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `pos` is outside the `Grid`.
+    ///
+    /// [`Tile`]: enum.Tile.html
+    /// [`Grass`]: enum.Tile.html#variant.Grass
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Grid::neighbors4`]: struct.Grid.html#method.neighbors4
+    /// [`Grid::touching`]: struct.Grid.html#method.touching
+    pub fn neighbors8(&self, pos: Pos) -> Vec<Pos> {
+        assert!(self.get(pos.row, pos.column).is_some());
+        let (rows, columns) = (self.num_rows(), self.num_columns());
+        self.rules.neighbors8(pos, rows, columns)
+    }
+
+    /// Get the positions that must be [`Grass`] if a [`Camp`] is at
+    /// `pos`.
+    ///
+    /// These are [`Grid::neighbors8`], or only [`Grid::neighbors4`] if
+    /// the [`RuleSet`] lets [`Camp`]s touch diagonally.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Grid, Pos, RuleSet};
+    /// let mut grid = Grid::blank(2, 2);
+    /// assert_eq!(grid.touching(Pos::new(0, 0)).len(), 3);
+    /// grid.set_rules(RuleSet {
+    ///     orthogonal_only: true,
+    ///     ..RuleSet::default()
+    /// });
+    /// assert_eq!(grid.touching(Pos::new(0, 0)), vec![(0, 1), (1, 0)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `pos` is outside the `Grid`.
+    ///
+    /// [`Grass`]: enum.Tile.html#variant.Grass
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Grid::neighbors8`]: struct.Grid.html#method.neighbors8
+    /// [`Grid::neighbors4`]: struct.Grid.html#method.neighbors4
+    /// [`RuleSet`]: struct.RuleSet.html
+    pub fn touching(&self, pos: Pos) -> Vec<Pos> {
+        assert!(self.get(pos.row, pos.column).is_some());
+        let (rows, columns) = (self.num_rows(), self.num_columns());
+        self.rules.touching(pos, rows, columns)
     }
 
     /// Format the `Grid` in debug mode.
@@ -225,6 +545,22 @@ impl Grid {
         format!("{:?}", self)
     }
 
+    /// Format the `Grid` with the characters in `charset`.
+    ///
+    /// This is like [`Grid::debug`] but formats [`Tile`]s via
+    /// [`TileCharset::format`].
+    ///
+    /// [`Grid::debug`]: struct.Grid.html#method.debug
+    /// [`Tile`]: enum.Tile.html
+    /// [`TileCharset::format`]: struct.TileCharset.html#method.format
+    pub fn format_with(&self, charset: &TileCharset) -> String {
+        let rows: Vec<String> = self
+            .rows()
+            .map(|row| row.iter().map(|&tile| charset.format(tile)).collect())
+            .collect();
+        rows.join("\n")
+    }
+
     /// Is every [`Tile`] not [`Unassigned`]?
     ///
     /// # Remarks
@@ -241,9 +577,25 @@ impl Grid {
     /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
     /// [`Board::solve`]: struct.Board.html#method.solve
     pub fn is_solved(&self) -> bool {
-        self.array
-            .iter()
-            .all(|row| row.iter().all(|x| *x != Unassigned))
+        self.positions_of(Unassigned).next().is_none()
+    }
+
+    /// Get the `Tile`s in the given row.
+    fn row_tiles(&self, row: usize) -> &[Tile] {
+        &self.tiles[row * self.width..(row + 1) * self.width]
+    }
+
+    /// Get the position of `(row, column)` in `tiles`.
+    fn offset(&self, row: usize, column: usize) -> usize {
+        assert!(
+            row < self.height && column < self.width,
+            "({}, {}) is outside the {}x{} Grid",
+            row,
+            column,
+            self.height,
+            self.width
+        );
+        row * self.width + column
     }
 }
 
@@ -253,26 +605,51 @@ impl From<Vec<Vec<Tile>>> for Grid {
     }
 }
 
+impl FromStr for Grid {
+    type Err = Error;
+
+    /// See [`Grid::parse`].
+    ///
+    /// [`Grid::parse`]: struct.Grid.html#method.parse
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Grid::parse(s)
+    }
+}
+
 impl Index<(usize, usize)> for Grid {
     type Output = Tile;
     fn index(&self, index: (usize, usize)) -> &Tile {
-        &self.array[index.0][index.1]
+        &self.tiles[self.offset(index.0, index.1)]
     }
 }
 
 impl IndexMut<(usize, usize)> for Grid {
     fn index_mut(&mut self, index: (usize, usize)) -> &mut Tile {
-        &mut self.array[index.0][index.1]
+        let offset = self.offset(index.0, index.1);
+        &mut self.tiles[offset]
+    }
+}
+
+impl Index<Pos> for Grid {
+    type Output = Tile;
+    fn index(&self, pos: Pos) -> &Tile {
+        &self[(pos.row, pos.column)]
+    }
+}
+
+impl IndexMut<Pos> for Grid {
+    fn index_mut(&mut self, pos: Pos) -> &mut Tile {
+        &mut self[(pos.row, pos.column)]
     }
 }
 
 impl fmt::Debug for Grid {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for row in 0..self.array.len() {
-            if row != 0 {
-                write!(f, "\n")?;
+        for (index, row) in self.rows().enumerate() {
+            if index != 0 {
+                writeln!(f)?;
             }
-            for x in &self.array[row] {
+            for x in row {
                 write!(f, "{:?}", x)?;
             }
         }
@@ -296,6 +673,151 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_grid_error_position() {
+        assert_eq!(
+            Grid::parse("TC-\n x \n---"),
+            Err(Error::Parse {
+                character: 'x',
+                position: Some(Pos::new(1, 1)),
+            })
+        );
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("TC-\n - ".parse::<Grid>(), Grid::parse("TC-\n - "));
+        assert_eq!("C".parse::<Tile>(), Ok(Camp));
+        assert!("".parse::<Tile>().is_err());
+        assert!("CT".parse::<Tile>().is_err());
+        assert!("x".parse::<Tile>().is_err());
+    }
+
+    #[test]
+    fn new_flattens_rows() {
+        let grid = Grid::new(vec![vec![Tree, Camp, Grass], vec![Unassigned, Grass, Tree]]);
+        assert_eq!((grid.num_rows(), grid.num_columns()), (2, 3));
+        assert_eq!(grid[(1, 2)], Tree);
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid, Grid::parse("TC-\n -T").unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_ragged() {
+        Grid::new(vec![vec![Tree, Camp], vec![Grass]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_past_row_end() {
+        let grid = Grid::blank(2, 2);
+        let _ = grid[(0, 2)];
+    }
+
+    #[test]
+    fn iterators() {
+        let grid = Grid::parse("TC-\n - ").unwrap();
+        assert_eq!(grid.cells().count(), 6);
+        assert_eq!(grid.cells().nth(4), Some((Pos::new(1, 1), Grass)));
+        let rows: Vec<_> = grid.rows().collect();
+        assert_eq!(rows[1], &[Unassigned, Grass, Unassigned][..]);
+        let columns: Vec<Vec<_>> = grid.columns().map(|x| x.collect()).collect();
+        assert_eq!(columns[2], vec![Grass, Unassigned]);
+        let grass: Vec<_> = grid.positions_of(Grass).collect();
+        assert_eq!(grass, vec![(0, 2), (1, 1)]);
+        assert_eq!(Grid::blank(0, 0).columns().count(), 0);
+    }
+
+    #[test]
+    fn parse_tolerant() {
+        let expected = Grid::parse("TC\n -");
+        assert!(expected.is_ok());
+        assert_eq!(Grid::parse("TC\r\n -\r\n"), expected);
+        assert_eq!(Grid::parse("TC\n -\n"), expected);
+        assert_eq!(
+            Grid::parse("TC\n -\n\n"),
+            Err(Error::RaggedRow {
+                row: 2,
+                columns: 0,
+                expected: 2,
+            })
+        );
+        assert_eq!(
+            Grid::parse("TC\n-"),
+            Err(Error::RaggedRow {
+                row: 1,
+                columns: 1,
+                expected: 2,
+            })
+        );
+        assert_eq!(Grid::parse(""), Ok(vec![vec![]].into()));
+    }
+
+    #[test]
+    fn parse_padded() {
+        let options = ParseOptions {
+            pad_rows: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            Grid::parse_with_options("T\n-C-\r\n\nC\n", &options),
+            Grid::parse("T  \n-C-\n   \nC  ")
+        );
+    }
+
+    #[test]
+    fn parse_strict() {
+        let options = ParseOptions {
+            pad_rows: true,
+            strict: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            Grid::parse_with_options("TC\n -", &options),
+            Grid::parse("TC\n -")
+        );
+        assert_eq!(
+            Grid::parse_with_options("TC\r\n -", &options),
+            Err(Error::Parse {
+                character: '\r',
+                position: Some(Pos::new(0, 2)),
+            })
+        );
+        assert_eq!(
+            Grid::parse_with_options("TC\n -\n", &options),
+            Err(Error::RaggedRow {
+                row: 2,
+                columns: 0,
+                expected: 2,
+            })
+        );
+        assert!(Grid::parse_with_options("TC\n-", &options).is_err());
+    }
+
+    #[test]
+    fn parse_with_charset() {
+        let charset = TileCharset {
+            unassigned: '.',
+            grass: '*',
+            camp: 'x',
+            tree: 'T',
+            blocked: '#',
+        };
+        let grid = Grid::parse_with("xT*\n.*.", &charset).unwrap();
+        assert_eq!(grid, Grid::parse("CT-\n - ").unwrap());
+        assert_eq!(grid.format_with(&charset), "xT*\n.*.");
+        assert_eq!(grid.format_with(&TileCharset::default()), grid.debug());
+        assert_eq!(
+            Grid::parse_with("xT*\n.C.", &charset),
+            Err(Error::Parse {
+                character: 'C',
+                position: Some(Pos::new(1, 1)),
+            })
+        );
+    }
+
     #[test]
     fn blank_grid_test() {
         assert_eq!(
@@ -335,6 +857,15 @@ mod tests {
         assert!(Grid::parse("-CT\n---\n-T-").unwrap().is_solved());
     }
 
+    #[test]
+    fn count_test() {
+        let grid = Grid::parse("C T\nC- \n   ").unwrap();
+        assert_eq!(grid.count(Unassigned), 5);
+        assert_eq!(grid.count(Grass), 1);
+        assert_eq!(grid.count(Camp), 2);
+        assert_eq!(grid.count(Tree), 1);
+    }
+
     #[test]
     fn count_in_row_test() {
         let grid = Grid::parse("C  \nC  \n   ").unwrap();
@@ -360,19 +891,19 @@ mod tests {
     #[test]
     fn surrounding_tiles_corner() {
         assert_eq!(
-            Grid::blank(3, 3).surrounding_tiles(0, 0),
+            Grid::blank(3, 3).surrounding_tiles(Pos::new(0, 0)),
             vec![(0, 1), (1, 0)]
         );
         assert_eq!(
-            Grid::blank(3, 3).surrounding_tiles(0, 2),
+            Grid::blank(3, 3).surrounding_tiles(Pos::new(0, 2)),
             vec![(0, 1), (1, 2)]
         );
         assert_eq!(
-            Grid::blank(3, 3).surrounding_tiles(2, 0),
+            Grid::blank(3, 3).surrounding_tiles(Pos::new(2, 0)),
             vec![(1, 0), (2, 1)]
         );
         assert_eq!(
-            Grid::blank(3, 3).surrounding_tiles(2, 2),
+            Grid::blank(3, 3).surrounding_tiles(Pos::new(2, 2)),
             vec![(1, 2), (2, 1)]
         );
     }
@@ -380,19 +911,19 @@ mod tests {
     #[test]
     fn surrounding_tiles_edge() {
         assert_eq!(
-            Grid::blank(3, 3).surrounding_tiles(0, 1),
+            Grid::blank(3, 3).surrounding_tiles(Pos::new(0, 1)),
             vec![(0, 0), (0, 2), (1, 1)]
         );
         assert_eq!(
-            Grid::blank(3, 3).surrounding_tiles(1, 0),
+            Grid::blank(3, 3).surrounding_tiles(Pos::new(1, 0)),
             vec![(0, 0), (1, 1), (2, 0)]
         );
         assert_eq!(
-            Grid::blank(3, 3).surrounding_tiles(1, 2),
+            Grid::blank(3, 3).surrounding_tiles(Pos::new(1, 2)),
             vec![(0, 2), (1, 1), (2, 2)]
         );
         assert_eq!(
-            Grid::blank(3, 3).surrounding_tiles(2, 1),
+            Grid::blank(3, 3).surrounding_tiles(Pos::new(2, 1)),
             vec![(1, 1), (2, 0), (2, 2)]
         );
     }
@@ -400,28 +931,93 @@ mod tests {
     #[test]
     fn surrounding_tiles_middle() {
         assert_eq!(
-            Grid::blank(3, 3).surrounding_tiles(1, 1),
+            Grid::blank(3, 3).surrounding_tiles(Pos::new(1, 1)),
             vec![(0, 1), (1, 0), (1, 2), (2, 1)]
         );
     }
 
+    #[test]
+    fn neighbors() {
+        let grid = Grid::blank(2, 3);
+        assert_eq!(grid.neighbors4(Pos::new(0, 1)), grid.surrounding_tiles(Pos::new(0, 1)));
+        assert_eq!(
+            grid.neighbors8(Pos::new(0, 1)),
+            vec![(0, 0), (0, 2), (1, 0), (1, 1), (1, 2)]
+        );
+        assert_eq!(Grid::blank(1, 1).neighbors8(Pos::new(0, 0)), Vec::<Pos>::new());
+    }
+
+    #[test]
+    #[should_panic]
+    fn neighbors8_out_of_bounds() {
+        Grid::blank(2, 2).neighbors8(Pos::new(2, 0));
+    }
+
+    #[test]
+    fn find_adjacent_camps_test() {
+        let find = |s| Grid::parse(s).unwrap().find_adjacent_camps();
+        assert_eq!(find("C C\n   \nC C"), None);
+        assert_eq!(find("C C\n C \n   "), Some(Pos::new(0, 0)));
+        assert_eq!(find("  C\n C \n   "), Some(Pos::new(0, 2)));
+        assert_eq!(find("   \n  C\n  C"), Some(Pos::new(1, 2)));
+    }
+
     #[test]
     fn set_camp_test() {
         let mut grid = Grid::parse(" T \nT T\n T ").unwrap();
         assert_eq!(grid.debug(), " T \nT T\n T ");
-        assert!(grid.set_camp(0, 0).is_ok());
+        assert!(grid.set_camp(Pos::new(0, 0)).is_ok());
         assert_eq!(grid.debug(), "CT \nT-T\n T ");
-        assert!(grid.set_camp(1, 1).is_err());
+        assert_eq!(
+            grid.set_camp(Pos::new(1, 1)),
+            Err(Error::CampConflict {
+                pos: Pos::new(1, 1),
+                conflict: Pos::new(0, 0),
+            })
+        );
         assert_eq!(grid.debug(), "CT \nT-T\n T ");
-        assert!(grid.set_camp(0, 2).is_ok());
+        assert!(grid.set_camp(Pos::new(0, 2)).is_ok());
         assert_eq!(grid.debug(), "CTC\nT-T\n T ");
-        assert!(grid.set_camp(1, 1).is_err());
+        assert!(grid.set_camp(Pos::new(1, 1)).is_err());
         assert_eq!(grid.debug(), "CTC\nT-T\n T ");
-        assert!(grid.set_camp(2, 0).is_ok());
+        assert!(grid.set_camp(Pos::new(2, 0)).is_ok());
         assert_eq!(grid.debug(), "CTC\nT-T\nCT ");
-        assert!(grid.set_camp(2, 0).is_err());
+        assert_eq!(
+            grid.set_camp(Pos::new(2, 0)),
+            Err(Error::CampConflict {
+                pos: Pos::new(2, 0),
+                conflict: Pos::new(2, 0),
+            })
+        );
         assert_eq!(grid.debug(), "CTC\nT-T\nCT ");
-        assert!(grid.set_camp(2, 2).is_ok());
+        assert!(grid.set_camp(Pos::new(2, 2)).is_ok());
         assert_eq!(grid.debug(), "CTC\nT-T\nCTC");
     }
+
+    #[test]
+    fn can_place_camp_test() {
+        let grid = Grid::parse("C T\nT  ").unwrap();
+        assert!(!grid.can_place_camp(Pos::new(0, 0)));
+        assert!(!grid.can_place_camp(Pos::new(1, 1)));
+        assert!(grid.can_place_camp(Pos::new(1, 2)));
+        assert_eq!(grid, Grid::parse("C T\nT  ").unwrap());
+    }
+
+    #[test]
+    fn hash_and_ord_test() {
+        use std::collections::{BTreeSet, HashSet};
+        let grids = [
+            Grid::parse("T-C\n   \n- T").unwrap(),
+            Grid::parse("T-C\n   \n- T").unwrap(),
+            Grid::parse("T-C   \n- T   ").unwrap(),
+            Grid::parse("T-C\n   \n-CT").unwrap(),
+            Grid::parse("   \n   ").unwrap(),
+        ];
+        let hashed: HashSet<Grid> = grids.iter().cloned().collect();
+        assert_eq!(hashed.len(), 4);
+        let ordered: BTreeSet<&Grid> = grids.iter().collect();
+        assert!(ordered
+            .into_iter()
+            .eq(vec![&grids[4], &grids[0], &grids[2], &grids[3]]));
+    }
 }
@@ -3,6 +3,9 @@ use tile::Tile::*;
 
 /// Fill [`Unassigned`] slots that can't possibly be [`Camp`]s with [`Grass`].
 ///
+/// These are the slots with no [`Tree`] beside them, so nothing is
+/// filled if the [`RuleSet`] has `no_matching` set.
+///
 /// # Examples
 ///
 /// ```
@@ -15,32 +18,25 @@ use tile::Tile::*;
 /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
 /// [`Camp`]: enum.Tile.html#variant.Camp
 /// [`Grass`]: enum.Tile.html#variant.Grass
+/// [`Tree`]: enum.Tile.html#variant.Tree
+/// [`RuleSet`]: struct.RuleSet.html
 pub fn initialize_grass(board: &mut Board) -> bool {
-    let mut changed = false;
-    for row in 0..board.rows.len() {
-        for column in 0..board.columns.len() {
-            if board.grid[(row, column)] == Unassigned {
-                let mut tiles = Vec::new();
-                if row + 1 != board.rows.len() {
-                    tiles.push(board.grid[(row + 1, column)]);
-                }
-                if column + 1 != board.columns.len() {
-                    tiles.push(board.grid[(row, column + 1)]);
-                }
-                if row != 0 {
-                    tiles.push(board.grid[(row - 1, column)]);
-                }
-                if column != 0 {
-                    tiles.push(board.grid[(row, column - 1)]);
-                }
-                if tiles.into_iter().all(|x| x != Tree) {
-                    board.grid[(row, column)] = Grass;
-                    changed = true;
-                }
-            }
-        }
+    if board.rules().no_matching {
+        return false;
+    }
+    let grass: Vec<_> = board
+        .positions_of(Unassigned)
+        .filter(|&pos| {
+            board
+                .surrounding_tiles(pos)
+                .into_iter()
+                .all(|position| board[position] != Tree)
+        })
+        .collect();
+    for &position in &grass {
+        board.set(position, Grass);
     }
-    changed
+    !grass.is_empty()
 }
 
 #[cfg(test)]
@@ -55,4 +51,11 @@ mod tests {
         assert!(!initialize_grass(&mut board));
         assert_eq!(board.debug(), " T \nT -\n --");
     }
+
+    #[test]
+    fn initialize_grass_rectangular() {
+        let mut board = Board::new_parse(vec![1, 0], vec![0, 0, 0, 1], "   T\n    ").unwrap();
+        assert!(initialize_grass(&mut board));
+        assert_eq!(board.debug(), "-- T\n--- ");
+    }
 }
@@ -1,116 +1,594 @@
 use board::*;
+use clue::*;
+use compact_grid::*;
+use contradiction::*;
 use grid::*;
-use tile::Tile::*;
-
-/// Process a single row of the `Grid`.
-///
-/// # Steps
-///
-/// 1. If `count == 0` then push `grid` onto `possibilities` and
-///    return.
-/// 2. If `column == grid.num_columns()` then simply return.  This is
-///    because there weren't enough [`Camp`]s placed for this
-///    possibility to be valid.
-/// 3. Otherwise, try putting a [`Camp`] at `(row, column)`.  If that
-///    succeeds, recurse into `(row, column + 1)` with that grid.
-/// 4. Recurse into `(row, column + 1)` without placing a [`Camp`] at
-///    `(row, column)`.
-fn process_row(possibilities: &mut Vec<Grid>, grid: Grid, count: usize, row: usize, column: usize) {
-    if count == 0 {
-        possibilities.push(grid);
-        return;
-    } else if column == grid.num_columns() {
-        return;
-    } else if grid[(row, column)] == Unassigned {
-        // try assigning here
-        let mut b = grid.clone();
-        if b.set_camp(row, column).is_ok() {
-            process_row(possibilities, b, count - 1, row, column + 1);
-        }
-    }
-    // don't assign here
-    process_row(possibilities, grid, count, row, column + 1)
-}
-
-/// See documentation for `process_row`.
-fn process_column(
-    possibilities: &mut Vec<Grid>,
-    grid: Grid,
-    count: usize,
-    row: usize,
-    column: usize,
-) {
-    if count == 0 {
-        possibilities.push(grid);
-        return;
-    } else if row == grid.num_rows() {
-        return;
-    } else if grid[(row, column)] == Unassigned {
-        // try assigning here
-        let mut b = grid.clone();
-        if b.set_camp(row, column).is_ok() {
-            process_column(possibilities, b, count - 1, row + 1, column);
+use line_masks::*;
+use pos::*;
+use std::ops::RangeInclusive;
+use tile::Tile::{self, *};
+
+/// A search through every way to place [`Camp`]s along one line.
+///
+/// The search works on `grid` in place.  Each [`Camp`] placed is
+/// recorded in `log` and undone before trying the next option.  Each
+/// complete possibility is folded into `intersection` as soon as it is
+/// found, so memory use doesn't grow with the number of possibilities.
+///
+/// The search stops once it finds more than `limit` possibilities, or
+/// once [`Board::should_stop`] for `board`.
+///
+/// [`Camp`]: enum.Tile.html#variant.Camp
+/// [`Board::should_stop`]: struct.Board.html#method.should_stop
+struct LineSearch<'a> {
+    board: &'a Board,
+    grid: CompactGrid,
+    log: Vec<(Pos, Tile)>,
+    intersection: Option<CompactGrid>,
+    possibilities: usize,
+    limit: usize,
+    stopped: bool,
+}
+
+impl<'a> LineSearch<'a> {
+    fn new(board: &'a Board, limit: usize) -> Self {
+        LineSearch {
+            board,
+            grid: CompactGrid::from(&**board),
+            log: Vec::new(),
+            intersection: None,
+            possibilities: 0,
+            limit,
+            stopped: false,
+        }
+    }
+
+    /// Whether more than `limit` possibilities have been found, or the
+    /// search was stopped.
+    fn exceeded(&self) -> bool {
+        self.stopped || self.possibilities > self.limit
+    }
+
+    /// Count a complete possibility and add it to the intersection.
+    fn add(&mut self) {
+        self.possibilities += 1;
+        if self.possibilities.is_multiple_of(STOP_CHECK_INTERVAL) && self.board.should_stop() {
+            self.stopped = true;
+        }
+        if !self.exceeded() {
+            add_possibility(&mut self.intersection, &self.grid);
         }
     }
-    // don't assign here
-    process_column(possibilities, grid, count, row + 1, column)
+
+    /// Process a single row of the `Grid`.
+    ///
+    /// # Steps
+    ///
+    /// 1. If more than `limit` possibilities were found, give up.
+    /// 2. If `count == 0` then add `grid` to `intersection` and
+    ///    return.
+    /// 3. If `column == grid.num_columns()` then simply return.  This
+    ///    is because there weren't enough [`Camp`]s placed for this
+    ///    possibility to be valid.
+    /// 4. Otherwise, if a [`Camp`] can be put at `(row, column)`, put
+    ///    it there, recurse into `(row, column + 1)`, and undo it.
+    /// 5. Recurse into `(row, column + 1)` without placing a [`Camp`]
+    ///    at `(row, column)`.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    fn row(&mut self, count: usize, row: usize, column: usize) {
+        if self.exceeded() {
+            return;
+        } else if count == 0 {
+            self.add();
+            return;
+        } else if column == self.grid.num_columns() {
+            return;
+        } else if self.grid.tile(Pos::new(row, column)) == Unassigned {
+            // try assigning here
+            let len = self.log.len();
+            if self
+                .grid
+                .set_camp_logged(Pos::new(row, column), &mut self.log)
+                .is_ok()
+            {
+                self.row(count - 1, row, column + 1);
+                self.grid.undo(&mut self.log, len);
+            }
+        }
+        // don't assign here
+        self.row(count, row, column + 1)
+    }
+
+    /// See documentation for `row`.
+    fn column(&mut self, count: usize, row: usize, column: usize) {
+        if self.exceeded() {
+            return;
+        } else if count == 0 {
+            self.add();
+            return;
+        } else if row == self.grid.num_rows() {
+            return;
+        } else if self.grid.tile(Pos::new(row, column)) == Unassigned {
+            // try assigning here
+            let len = self.log.len();
+            if self
+                .grid
+                .set_camp_logged(Pos::new(row, column), &mut self.log)
+                .is_ok()
+            {
+                self.column(count - 1, row + 1, column);
+                self.grid.undo(&mut self.log, len);
+            }
+        }
+        // don't assign here
+        self.column(count, row + 1, column)
+    }
+
+    /// Get the intersection of every possibility found.
+    ///
+    /// Returns `None` if there were no possibilities, or the `Grid`
+    /// searched through if there were too many.
+    fn finish(self) -> Option<Grid> {
+        debug_assert!(self.log.is_empty());
+        self.board.record_possibilities(self.possibilities);
+        if self.exceeded() {
+            return Some(Grid::from(&self.grid));
+        }
+        self.intersection.map(|grid| Grid::from(&grid))
+    }
 }
 
-/// Find the intersection of all possibilities.
+/// How many possibilities to go through between checks of
+/// [`Board::should_stop`].
+///
+/// [`Board::should_stop`]: struct.Board.html#method.should_stop
+const STOP_CHECK_INTERVAL: usize = 1024;
+
+/// Add a possibility to the intersection of all possibilities.
 ///
 /// If a [`Tile`] has the same value throughout each possibility, then
-/// that [`Tile`] is yielded the same way in the resulting [`Grid`].
-/// If it varies, then it is [`Unassigned`].
+/// that [`Tile`] is yielded the same way in the resulting grid.  If
+/// it varies, then it is [`Unassigned`].
+///
+/// `intersection` is `None` until the first possibility is added.
 ///
 /// [`Tile`]: enum.Tile.html
-/// [`Grid`]: struct.Grid.html
 /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
-fn intersection(possibilities: Vec<Grid>) -> Grid {
-    let mut possibilities = possibilities.into_iter();
-    let mut grid = possibilities.next().unwrap();
-    for ngrid in possibilities {
-        for row in 0..grid.num_rows() {
-            for column in 0..grid.num_columns() {
-                if grid[(row, column)] != ngrid[(row, column)] {
-                    grid[(row, column)] = Unassigned;
-                }
+fn add_possibility(intersection: &mut Option<CompactGrid>, grid: &CompactGrid) {
+    match *intersection {
+        Some(ref mut intersection) => intersection.intersect(grid),
+        None => *intersection = Some(grid.clone()),
+    }
+}
+
+/// What every way of placing [`Camp`]s along a line has in common.
+///
+/// Bit `i` of each mask is the `i`th `Tile` of the line.
+///
+/// [`Camp`]: enum.Tile.html#variant.Camp
+struct Placements {
+    /// `Tile`s that are always a [`Camp`].
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    camps: u64,
+    /// `Tile`s in the line that are always next to a [`Camp`].
+    beside: u64,
+    /// `Tile`s in the neighboring lines that always touch a [`Camp`].
+    around: u64,
+    /// Whether any placement was found.
+    found: bool,
+}
+
+impl Placements {
+    fn new(full: u64) -> Self {
+        Placements {
+            camps: full,
+            beside: full,
+            around: full,
+            found: false,
+        }
+    }
+
+    /// Add the placement with [`Camp`]s at `chosen`.
+    ///
+    /// Returns whether anything is still common to every placement.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    fn add(&mut self, chosen: u64, full: u64) -> bool {
+        let around = spread(chosen, full);
+        self.camps &= chosen;
+        self.beside &= around & !chosen;
+        self.around &= around;
+        self.found = true;
+        self.around != 0
+    }
+}
+
+/// Place each of `counts` more [`Camp`]s along line `line` in every
+/// possible way and keep what all of the placements agree on.
+///
+/// `pos(line, index)` gives the position of the `index`th `Tile` of a
+/// line.  There are `lines` lines of `length` `Tile`s, and `length`
+/// must be at most [`MAX_MASK_LENGTH`].
+///
+/// `clues` are the clues of the crossing lines, as in
+/// [`LineMasks::new`].  Without `clues` this gives the same result as a
+/// [`LineSearch`], but works on bitmasks instead of a copy of the
+/// `Grid`.
+///
+/// Returns the `Tile`s to set, or `None` if there is no way to place
+/// the [`Camp`]s.  Nothing is set if there are more placements than
+/// [`SolverLimits::max_possibilities_per_line`], or if solving should
+/// stop before they have all been gone through.
+///
+/// [`Camp`]: enum.Tile.html#variant.Camp
+/// [`MAX_MASK_LENGTH`]: constant.MAX_MASK_LENGTH.html
+/// [`LineSearch`]: struct.LineSearch.html
+/// [`LineMasks::new`]: struct.LineMasks.html#method.new
+/// [`SolverLimits::max_possibilities_per_line`]: struct.SolverLimits.html#structfield.max_possibilities_per_line
+fn line_changes<F: Fn(usize, usize) -> Pos>(
+    board: &Board,
+    pos: F,
+    line: usize,
+    lines: usize,
+    length: usize,
+    counts: RangeInclusive<usize>,
+    clues: &[Clue],
+) -> Option<Vec<(Pos, Tile)>> {
+    let masks = LineMasks::new(board, &pos, line, lines, length, clues);
+    let full = masks.full;
+    let limit = possibility_limit(board);
+    let mut placements = Placements::new(full);
+    let mut possibilities: usize = 0;
+    let mut stopped = false;
+    let mut common = true;
+    for count in counts {
+        masks.enumerate(count, &mut |chosen| {
+            possibilities += 1;
+            if possibilities.is_multiple_of(STOP_CHECK_INTERVAL) && board.should_stop() {
+                stopped = true;
+                return false;
+            }
+            common = possibilities <= limit && placements.add(chosen, full);
+            common
+        });
+        if stopped || !common {
+            break;
+        }
+    }
+    board.record_possibilities(possibilities);
+    if stopped || possibilities > limit {
+        return Some(Vec::new());
+    } else if !placements.found {
+        return None;
+    }
+
+    let neighbors: Vec<usize> = (line.saturating_sub(1)..lines.min(line + 2))
+        .filter(|&x| x != line)
+        .collect();
+    let mut changes = Vec::new();
+    for index in 0..length {
+        if placements.camps & 1 << index != 0 {
+            changes.push((pos(line, index), Camp));
+        } else if placements.beside & masks.unassigned & 1 << index != 0 {
+            changes.push((pos(line, index), Grass));
+        }
+    }
+    for &x in &neighbors {
+        for index in 0..length {
+            if placements.around & 1 << index != 0 && board[pos(x, index)] == Unassigned {
+                changes.push((pos(x, index), Grass));
             }
         }
     }
-    grid
+    Some(changes)
+}
+
+/// The most possibilities to go through for a line of `board`.
+///
+/// See [`SolverLimits::max_possibilities_per_line`].
+///
+/// [`SolverLimits::max_possibilities_per_line`]: struct.SolverLimits.html#structfield.max_possibilities_per_line
+fn possibility_limit(board: &Board) -> usize {
+    board
+        .limits
+        .max_possibilities_per_line
+        .unwrap_or(usize::MAX)
+}
+
+/// Find every `Tile` of `grid` that differs from `board`.
+fn grid_changes(board: &Board, grid: &Grid) -> Vec<(Pos, Tile)> {
+    grid.cells()
+        .filter(|&(pos, tile)| board[pos] != tile)
+        .collect()
+}
+
+/// Set each `Tile` in `changes` that is still [`Unassigned`].
+///
+/// Returns whether the `Board` changed.
+///
+/// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+fn apply(board: &mut Board, changes: &[(Pos, Tile)]) -> bool {
+    let mut changed = false;
+    for &(pos, tile) in changes {
+        if board[pos] == Unassigned {
+            board.set(pos, tile);
+            changed = true;
+        }
+    }
+    changed
 }
 
 /// Loop through every possibility for each column and row and process
 /// their intersections.
-pub fn process_intersections(board: &mut Board) -> bool {
+///
+/// Lines of up to 64 `Tile`s are handled with bitmasks, skipping
+/// placements that break the clues of the lines crossing them.  Longer
+/// lines, and every line of a `Board` whose [`RuleSet`] changes which
+/// `Tile`s touch, fall back to searching through copies of the `Grid`.
+///
+/// Return whether any values were changed.
+///
+/// # Errors
+///
+/// If a row or column already has more [`Camp`]s than its clue, or
+/// there is no way to place the rest of its [`Camp`]s, the offending
+/// cell and [`Contradiction`] are returned.  The `Board` may have
+/// been partially updated.
+///
+/// [`Camp`]: enum.Tile.html#variant.Camp
+/// [`Contradiction`]: enum.Contradiction.html
+/// [`RuleSet`]: struct.RuleSet.html
+pub fn process_intersections(board: &mut Board) -> Result<bool, (Pos, Contradiction)> {
+    let rows = process_rows(board)?;
+    let columns = process_columns(board)?;
+    Ok(rows || columns)
+}
+
+/// Process the intersections of every row in turn.
+#[cfg(not(feature = "parallel"))]
+fn process_rows(board: &mut Board) -> Result<bool, (Pos, Contradiction)> {
     let mut changed = false;
-    for row in 0..board.rows.len() {
-        let mut possibilities = Vec::new();
-        let count = board.rows[row] - board.count_in_row(row, Camp);
-        process_row(&mut possibilities, board.grid.clone(), count, row, 0);
-        let new_grid = intersection(possibilities);
-        changed = changed || board.grid != new_grid;
-        board.grid = new_grid;
-    }
-    for column in 0..board.columns.len() {
-        let mut possibilities = Vec::new();
-        let count = board.columns[column] - board.count_in_column(column, Camp);
-        process_column(&mut possibilities, board.grid.clone(), count, 0, column);
-        let new_grid = intersection(possibilities);
-        changed = changed || board.grid != new_grid;
-        board.grid = new_grid;
+    for row in 0..board.num_rows() {
+        changed |= process_row_intersections(board, row)?;
     }
-    changed
+    Ok(changed)
+}
+
+/// Process the intersections of every column in turn.
+#[cfg(not(feature = "parallel"))]
+fn process_columns(board: &mut Board) -> Result<bool, (Pos, Contradiction)> {
+    let mut changed = false;
+    for column in 0..board.num_columns() {
+        changed |= process_column_intersections(board, column)?;
+    }
+    Ok(changed)
+}
+
+/// Process the intersections of every row at once, then apply them in
+/// order.
+///
+/// Each row only sees the `Board` as it was before any of them were
+/// applied, so a pass can deduce less than processing them in turn.
+#[cfg(feature = "parallel")]
+fn process_rows(board: &mut Board) -> Result<bool, (Pos, Contradiction)> {
+    use rayon::prelude::*;
+    check_columns(board)?;
+    let rows: Vec<_> = {
+        let board = &*board;
+        (0..board.num_rows())
+            .into_par_iter()
+            .map(|row| row_changes(board, row).map_err(|reason| (Pos::new(row, 0), reason)))
+            .collect()
+    };
+    let mut changed = false;
+    for changes in rows {
+        changed |= apply(board, &changes?);
+    }
+    Ok(changed)
+}
+
+/// Process the intersections of every column at once, then apply them
+/// in order.
+///
+/// See [`process_rows`].
+///
+/// [`process_rows`]: fn.process_rows.html
+#[cfg(feature = "parallel")]
+fn process_columns(board: &mut Board) -> Result<bool, (Pos, Contradiction)> {
+    use rayon::prelude::*;
+    check_rows(board)?;
+    let columns: Vec<_> = {
+        let board = &*board;
+        (0..board.num_columns())
+            .into_par_iter()
+            .map(|column| {
+                column_changes(board, column).map_err(|reason| (Pos::new(0, column), reason))
+            })
+            .collect()
+    };
+    let mut changed = false;
+    for changes in columns {
+        changed |= apply(board, &changes?);
+    }
+    Ok(changed)
+}
+
+/// Loop through every possibility for one row and process their
+/// intersection.
+///
+/// This is the part of [`process_intersections`] for a single row.
+/// Rows whose clue is omitted, and so every row of a clue-free `Board`,
+/// are left alone.
+///
+/// # Errors
+///
+/// If a column already has more [`Camp`]s than its clue, this is
+/// reported first, since the row's placements are checked against the
+/// column clues.  Otherwise see [`process_intersections`].
+///
+/// [`process_intersections`]: fn.process_intersections.html
+/// [`Camp`]: enum.Tile.html#variant.Camp
+pub fn process_row_intersections(
+    board: &mut Board,
+    row: usize,
+) -> Result<bool, (Pos, Contradiction)> {
+    check_columns(board)?;
+    let changes = row_changes(board, row).map_err(|reason| (Pos::new(row, 0), reason))?;
+    Ok(apply(board, &changes))
+}
+
+/// Loop through every possibility for one column and process their
+/// intersection.
+///
+/// See [`process_row_intersections`].
+///
+/// [`process_row_intersections`]: fn.process_row_intersections.html
+pub fn process_column_intersections(
+    board: &mut Board,
+    column: usize,
+) -> Result<bool, (Pos, Contradiction)> {
+    check_rows(board)?;
+    let changes = column_changes(board, column).map_err(|reason| (Pos::new(0, column), reason))?;
+    Ok(apply(board, &changes))
+}
+
+/// Check that no row has more [`Camp`]s than its clue.
+///
+/// [`Camp`]: enum.Tile.html#variant.Camp
+fn check_rows(board: &Board) -> Result<(), (Pos, Contradiction)> {
+    for row in 0..board.num_rows() {
+        if board.row_range(row).is_over(board.count_in_row(row, Camp)) {
+            return Err((Pos::new(row, 0), Contradiction::RowOverfilled));
+        }
+    }
+    Ok(())
+}
+
+/// Check that no column has more [`Camp`]s than its clue.
+///
+/// [`Camp`]: enum.Tile.html#variant.Camp
+fn check_columns(board: &Board) -> Result<(), (Pos, Contradiction)> {
+    for column in 0..board.num_columns() {
+        if board
+            .column_range(column)
+            .is_over(board.count_in_column(column, Camp))
+        {
+            return Err((Pos::new(0, column), Contradiction::ColumnOverfilled));
+        }
+    }
+    Ok(())
+}
+
+/// How many more [`Camp`]s a line of `length` `Tile`s with `camps`
+/// already can take to meet `clue`.
+///
+/// [`Camp`]: enum.Tile.html#variant.Camp
+fn remaining(clue: Clue, camps: usize, length: usize) -> RangeInclusive<usize> {
+    let most = clue.max.map_or(length, |max| max - camps).min(length);
+    clue.min.saturating_sub(camps)..=most
+}
+
+/// Find the `Tile`s that every possibility for `row` agrees on.
+///
+/// A [`Contradiction`] is at the start of the row.
+///
+/// [`Contradiction`]: enum.Contradiction.html
+fn row_changes(board: &Board, row: usize) -> Result<Vec<(Pos, Tile)>, Contradiction> {
+    let clue = board.row_range(row);
+    if clue.is_any() {
+        return Ok(Vec::new());
+    }
+    let (height, width) = (board.num_rows(), board.num_columns());
+    let camps = board.count_in_row(row, Camp);
+    if clue.is_over(camps) {
+        return Err(Contradiction::RowOverfilled);
+    }
+    let counts = remaining(clue, camps, width);
+    let changes = if width <= MAX_MASK_LENGTH && board.rules().has_classic_touching() {
+        let clues = board.column_ranges();
+        line_changes(board, Pos::new, row, height, width, counts, &clues)
+    } else {
+        let mut search = LineSearch::new(board, possibility_limit(board));
+        for count in counts {
+            search.row(count, row, 0);
+        }
+        search.finish().map(|grid| grid_changes(board, &grid))
+    };
+    changes.ok_or(Contradiction::RowUnderfilled)
+}
+
+/// Find the `Tile`s that every possibility for `column` agrees on.
+///
+/// A [`Contradiction`] is at the start of the column.
+///
+/// [`Contradiction`]: enum.Contradiction.html
+fn column_changes(board: &Board, column: usize) -> Result<Vec<(Pos, Tile)>, Contradiction> {
+    let clue = board.column_range(column);
+    if clue.is_any() {
+        return Ok(Vec::new());
+    }
+    let (height, width) = (board.num_rows(), board.num_columns());
+    let camps = board.count_in_column(column, Camp);
+    if clue.is_over(camps) {
+        return Err(Contradiction::ColumnOverfilled);
+    }
+    let counts = remaining(clue, camps, height);
+    let changes = if height <= MAX_MASK_LENGTH && board.rules().has_classic_touching() {
+        let pos = |column, row| Pos::new(row, column);
+        let clues = board.row_ranges();
+        line_changes(board, pos, column, width, height, counts, &clues)
+    } else {
+        let mut search = LineSearch::new(board, possibility_limit(board));
+        for count in counts {
+            search.column(count, 0, column);
+        }
+        search.finish().map(|grid| grid_changes(board, &grid))
+    };
+    changes.ok_or(Contradiction::ColumnUnderfilled)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Apply [`line_changes`] to `board`.
+    ///
+    /// [`line_changes`]: fn.line_changes.html
+    fn process_line<F: Fn(usize, usize) -> Pos>(
+        board: &mut Board,
+        pos: F,
+        line: usize,
+        lines: usize,
+        length: usize,
+        count: usize,
+        clues: &[Clue],
+    ) -> Option<bool> {
+        let changes = line_changes(board, pos, line, lines, length, count..=count, clues)?;
+        Some(apply(board, &changes))
+    }
+    use initialize_grass::*;
+
+    /// Find the intersection of all possibilities via
+    /// `add_possibility`.
+    fn intersection(possibilities: Vec<Grid>) -> Option<Grid> {
+        let mut intersection = None;
+        for grid in &possibilities {
+            add_possibility(&mut intersection, &CompactGrid::from(grid));
+        }
+        intersection.map(|grid| Grid::from(&grid))
+    }
+
     #[test]
     fn intersection_one_possibility_is_the_possibility() {
         let grid = Grid::blank(3, 3);
-        assert_eq!(intersection(vec![grid.clone()]), grid);
+        assert_eq!(intersection(vec![grid.clone()]), Some(grid));
+    }
+
+    #[test]
+    fn intersection_no_possibilities() {
+        assert_eq!(intersection(vec![]), None);
     }
 
     #[test]
@@ -119,29 +597,251 @@ mod tests {
         let grid2 = Grid::parse("CT \n C-\n   ").unwrap();
         assert_eq!(
             intersection(vec![grid1, grid2]),
-            Grid::parse(" T \n C-\n   ").unwrap()
+            Some(Grid::parse(" T \n C-\n   ").unwrap())
+        );
+    }
+
+    #[test]
+    fn process_line_matches_grid_search() {
+        // A small linear congruential generator so the boards are the
+        // same every run.
+        let mut seed = 12345u32;
+        let mut random = |n: u32| {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            (seed >> 16) % n
+        };
+        for _ in 0..200 {
+            let (height, width) = (1 + random(6) as usize, 1 + random(6) as usize);
+            let mut grid = Grid::blank(height, width);
+            for row in 0..height {
+                for column in 0..width {
+                    grid[(row, column)] = match random(8) {
+                        0 => Tree,
+                        1 => Grass,
+                        2 => Camp,
+                        _ => Unassigned,
+                    };
+                }
+            }
+            let board = Board::new_clue_free(0, grid);
+            for row in 0..height {
+                let count = random(4) as usize;
+                let mut masks = board.clone();
+                let mut search = board.clone();
+                let mut line_search = LineSearch::new(&board, usize::MAX);
+                line_search.row(count, row, 0);
+                assert_eq!(line_search.grid, CompactGrid::from(&*search));
+                let expected = line_search.finish().map(|grid| {
+                    let changes = grid_changes(&search, &grid);
+                    apply(&mut search, &changes)
+                });
+                let actual = process_line(&mut masks, Pos::new, row, height, width, count, &[]);
+                assert_eq!(actual, expected, "row {} of {:?}", row, board);
+                assert_eq!(masks, search, "row {} of {:?}", row, board);
+            }
+            for column in 0..width {
+                let count = random(4) as usize;
+                let mut masks = board.clone();
+                let mut search = board.clone();
+                let mut line_search = LineSearch::new(&board, usize::MAX);
+                line_search.column(count, 0, column);
+                assert_eq!(line_search.grid, CompactGrid::from(&*search));
+                let expected = line_search.finish().map(|grid| {
+                    let changes = grid_changes(&search, &grid);
+                    apply(&mut search, &changes)
+                });
+                let pos = |column, row| Pos::new(row, column);
+                let actual = process_line(&mut masks, pos, column, width, height, count, &[]);
+                assert_eq!(actual, expected, "column {} of {:?}", column, board);
+                assert_eq!(masks, search, "column {} of {:?}", column, board);
+            }
+        }
+    }
+
+    #[test]
+    fn process_line_full_width() {
+        let mut board = Board::new_clue_free(0, Grid::blank(1, 64));
+        assert_eq!(
+            process_line(&mut board, Pos::new, 0, 1, 64, 32, &[]),
+            Some(false)
+        );
+        assert_eq!(process_line(&mut board, Pos::new, 0, 1, 64, 33, &[]), None);
+        board.set(Pos::new(0, 63), Tree);
+        assert_eq!(
+            process_line(&mut board, Pos::new, 0, 1, 64, 32, &[]),
+            Some(true)
+        );
+        assert_eq!(board.count(Camp), 32);
+        assert_eq!(board.count(Grass), 31);
+        assert_eq!(board[(0, 0)], Camp);
+        assert_eq!(board[(0, 62)], Camp);
+    }
+
+    #[test]
+    fn process_line_over_limit() {
+        let mut board = Board::new_clue_free(0, Grid::blank(1, 64));
+        board.set(Pos::new(0, 63), Tree);
+        board.limits.max_possibilities_per_line = Some(0);
+        assert_eq!(
+            process_line(&mut board, Pos::new, 0, 1, 64, 32, &[]),
+            Some(false)
+        );
+        assert_eq!(board.count(Unassigned), 63);
+        board.limits.max_possibilities_per_line = Some(1);
+        assert_eq!(
+            process_line(&mut board, Pos::new, 0, 1, 64, 32, &[]),
+            Some(true)
         );
+        assert_eq!(board.count(Camp), 32);
+    }
+
+    #[test]
+    fn process_row_intersections_long_line_over_limit() {
+        let columns = (0..65)
+            .map(|column| (column >= 60 && column % 2 == 0) as usize)
+            .collect();
+        let grid = "-".repeat(60) + "     ";
+        let mut board = Board::new_parse(vec![3], columns, &grid).unwrap();
+        board.limits.max_possibilities_per_line = Some(0);
+        assert_eq!(process_row_intersections(&mut board, 0), Ok(false));
+        assert_eq!(board.count(Unassigned), 5);
+        board.limits.max_possibilities_per_line = None;
+        assert_eq!(process_row_intersections(&mut board, 0), Ok(true));
+        assert_eq!(board.count(Camp), 3);
     }
 
     #[test]
     fn process_intersections_row_deduce_grass_next_row() {
         let mut board = Board::new_parse(
-            vec![1, 0, 0, 0, 0],
+            vec![1, 0, 0, 0, 1],
             vec![1, 0, 1, 0, 0],
-            " - --\nT T  \n-    \n     \n     ",
+            " - --\nT T  \n-----\nT T  \n - --",
         ).unwrap();
-        assert!(process_intersections(&mut board));
-        assert_eq!(board.debug(), " - --\nT-T  \n-    \n     \n     ");
+        assert_eq!(process_intersections(&mut board), Ok(true));
+        assert_eq!(board.debug(), " - --\nT-T  \n-----\nT-T  \n - --");
     }
 
     #[test]
     fn process_intersections_column_deduce_grass_next_column() {
         let mut board = Board::new_parse(
             vec![1, 0, 1, 0, 0],
-            vec![1, 0, 0, 0, 0],
-            " T   \n-    \n T   \n-    \n-    ",
+            vec![1, 0, 0, 0, 1],
+            " T-T \n- - -\n T-T \n- - -\n- - -",
+        ).unwrap();
+        assert_eq!(process_intersections(&mut board), Ok(true));
+        assert_eq!(board.debug(), " T-T \n-----\n T-T \n- - -\n- - -");
+    }
+
+    #[test]
+    fn process_intersections_row_overfilled() {
+        let mut board = Board::new_parse(vec![1, 0, 0], vec![1, 0, 1], "C C\nT T\n   ").unwrap();
+        assert_eq!(
+            process_intersections(&mut board),
+            Err((Pos::new(0, 0), Contradiction::RowOverfilled))
+        );
+    }
+
+    #[test]
+    fn process_intersections_column_overfilled() {
+        let mut board = Board::new_parse(vec![1, 0, 1], vec![1, 0, 0], "CT \n   \nCT ").unwrap();
+        assert_eq!(
+            process_intersections(&mut board),
+            Err((Pos::new(0, 0), Contradiction::ColumnOverfilled))
+        );
+    }
+
+    #[test]
+    fn process_intersections_no_placement() {
+        let mut board = Board::new_parse(vec![2, 0], vec![1, 1], "  \nTT").unwrap();
+        assert_eq!(
+            process_intersections(&mut board),
+            Err((Pos::new(0, 0), Contradiction::RowUnderfilled))
+        );
+    }
+
+    #[test]
+    fn process_row_intersections_one_row() {
+        let mut board = Board::new_parse(vec![2, 0], vec![1, 0, 0, 0, 1], " T T 
+     ").unwrap();
+        assert_eq!(process_row_intersections(&mut board, 1), Ok(false));
+        assert_eq!(process_row_intersections(&mut board, 0), Ok(true));
+        assert_eq!(board.debug(), "CT TC
+-- --");
+    }
+
+    #[test]
+    fn process_column_intersections_one_column() {
+        let mut board = Board::new_parse(vec![1, 0, 0, 0, 1], vec![2, 0], "  \nT \n  \nT \n  ").unwrap();
+        assert_eq!(process_column_intersections(&mut board, 1), Ok(false));
+        assert_eq!(process_column_intersections(&mut board, 0), Ok(true));
+        assert_eq!(board.debug(), "C-\nT-\n  \nT-\nC-");
+    }
+
+    #[test]
+    fn process_intersections_rectangular() {
+        let mut board = Board::new_parse(vec![2], vec![1, 0, 0, 0, 1], " T T ").unwrap();
+        assert_eq!(process_intersections(&mut board), Ok(true));
+        assert_eq!(board.debug(), "CT TC");
+    }
+
+    #[test]
+    fn process_line_crossing_clue_filled() {
+        let board = Board::new_clue_free(0, Grid::parse(" T \n---\nC--").unwrap());
+        let mut without = board.clone();
+        assert_eq!(
+            process_line(&mut without, Pos::new, 0, 3, 3, 1, &[]),
+            Some(false)
+        );
+        let mut with = board.clone();
+        let clues: Vec<_> = vec![1, 0, 1].into_iter().map(Clue::exactly).collect();
+        assert_eq!(
+            process_line(&mut with, Pos::new, 0, 3, 3, 1, &clues),
+            Some(true)
+        );
+        assert_eq!(with.debug(), " TC\n---\nC--");
+    }
+
+    #[test]
+    fn process_line_crossing_clue_unreachable() {
+        // A camp anywhere else would leave column 3 without room for
+        // its camp.
+        let board = Board::new_clue_free(0, Grid::parse("  - \n T-T\n---T").unwrap());
+        let mut with = board.clone();
+        let clues: Vec<_> = vec![1, 0, 0, 1].into_iter().map(Clue::exactly).collect();
+        assert_eq!(
+            process_line(&mut with, Pos::new, 0, 3, 4, 1, &clues),
+            Some(true)
+        );
+        assert_eq!(with.debug(), "  -C\n T-T\n---T");
+    }
+
+    #[test]
+    fn process_row_intersections_range_clue() {
+        // Only one way to place at least two camps fits on the row.
+        let rows = vec![Clue::at_least(2), Clue::any()];
+        let columns = vec![Clue::any(); 3];
+        let mut board = Board::new_parse_ranged(rows, columns, "   \nT T").unwrap();
+        assert_eq!(process_row_intersections(&mut board, 0), Ok(true));
+        assert_eq!(board.debug(), "C-C\nT-T");
+        assert_eq!(process_row_intersections(&mut board, 1), Ok(false));
+    }
+
+    /// Run `process_intersections` until it stops changing the `Board`.
+    fn process_intersections_until_stuck(board: &mut Board) {
+        initialize_grass(board);
+        while process_intersections(board) == Ok(true) {}
+    }
+
+    #[test]
+    fn process_intersections_solves_with_crossing_clues() {
+        // Without checking the crossing clues this board gets stuck
+        // partway through.
+        let mut board = Board::new_parse(
+            vec![1, 1, 1, 1, 1],
+            vec![1, 1, 1, 1, 1],
+            " T   \n     \n   TT\n TT  \n     ",
         ).unwrap();
-        assert!(process_intersections(&mut board));
-        assert_eq!(board.debug(), " T   \n--   \n T   \n-    \n-    ");
+        process_intersections_until_stuck(&mut board);
+        assert_eq!(board.debug(), "CT---\n---C-\n-C-TT\n-TT-C\n--C--");
     }
 }
@@ -0,0 +1,161 @@
+use board_observer::*;
+use pos::*;
+use serde_json::Value;
+use std::io::{self, Write};
+use std::sync::Mutex;
+use tile::*;
+
+/// A [`BoardObserver`] that writes every change as a line of JSON.
+///
+/// Each line is an object like
+/// `{"step":12,"strategy":"fill_zeros","cell":[3,4],"tile":"-"}`.
+/// `step` counts the changes from `1`, `strategy` is the
+/// [`Strategy::name`] that made the change or `null`, `cell` is the
+/// `[row, column]` and `tile` is the new [`Tile`] as written by
+/// [`Grid::parse`].  Replaying the lines in order over the starting
+/// `Board` gives the `Board` at the end.
+///
+/// Write errors can't be reported while solving, so writing stops at
+/// the first one and [`JsonTrace::flush`] returns it.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Board, JsonTrace};
+/// # use std::sync::Arc;
+/// let trace = Arc::new(JsonTrace::new(Vec::new()));
+/// let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n--").unwrap();
+/// board.observer = Some(trace.clone());
+/// board.solve().unwrap();
+/// board.observer = None;
+/// let output = Arc::try_unwrap(trace).ok().unwrap().into_inner().unwrap();
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "{\"step\":1,\"strategy\":\"fill_camps\",\"cell\":[0,0],\"tile\":\"C\"}\n"
+/// );
+/// ```
+///
+/// [`BoardObserver`]: trait.BoardObserver.html
+/// [`Strategy::name`]: trait.Strategy.html#tymethod.name
+/// [`Tile`]: enum.Tile.html
+/// [`Grid::parse`]: struct.Grid.html#method.parse
+/// [`JsonTrace::flush`]: struct.JsonTrace.html#method.flush
+pub struct JsonTrace<W> {
+    state: Mutex<State<W>>,
+}
+
+/// The parts of a [`JsonTrace`] that change as it writes.
+///
+/// [`JsonTrace`]: struct.JsonTrace.html
+struct State<W> {
+    writer: W,
+    step: usize,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> JsonTrace<W> {
+    /// Write the trace to `writer`.
+    pub fn new(writer: W) -> Self {
+        JsonTrace {
+            state: Mutex::new(State {
+                writer,
+                step: 0,
+                error: None,
+            }),
+        }
+    }
+
+    /// Flush the writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from writing a line, if there was one,
+    /// or else any error from flushing.
+    pub fn flush(&self) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        match state.error.take() {
+            Some(error) => Err(error),
+            None => state.writer.flush(),
+        }
+    }
+
+    /// Flush the writer and get it back.
+    ///
+    /// # Errors
+    ///
+    /// See [`JsonTrace::flush`].
+    ///
+    /// [`JsonTrace::flush`]: struct.JsonTrace.html#method.flush
+    pub fn into_inner(self) -> io::Result<W> {
+        self.flush()?;
+        Ok(self.state.into_inner().unwrap().writer)
+    }
+}
+
+impl<W: Write + Send> BoardObserver for JsonTrace<W> {
+    fn on_tile_set(&self, pos: Pos, _old: Tile, new: Tile, strategy: Option<&'static str>) {
+        let mut state = self.state.lock().unwrap();
+        if state.error.is_some() {
+            return;
+        }
+        state.step += 1;
+        let line = format!(
+            "{{\"step\":{},\"strategy\":{},\"cell\":[{},{}],\"tile\":{}}}",
+            state.step,
+            Value::from(strategy),
+            pos.row,
+            pos.column,
+            Value::from(format!("{:?}", new))
+        );
+        if let Err(error) = writeln!(state.writer, "{}", line) {
+            state.error = Some(error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use board::*;
+    use std::sync::Arc;
+    use tile::Tile::*;
+
+    /// A writer that always fails.
+    struct Broken;
+
+    impl Write for Broken {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("broken"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn set_outside_solving() {
+        let trace = Arc::new(JsonTrace::new(Vec::new()));
+        let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        board.observer = Some(trace.clone());
+        board.set(Pos::new(1, 0), Grass);
+        board.set(Pos::new(1, 0), Grass);
+        board.set(Pos::new(1, 1), Grass);
+        board.observer = None;
+        let output = Arc::try_unwrap(trace).ok().unwrap().into_inner().unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "{\"step\":1,\"strategy\":null,\"cell\":[1,0],\"tile\":\"-\"}\n\
+             {\"step\":2,\"strategy\":null,\"cell\":[1,1],\"tile\":\"-\"}\n"
+        );
+    }
+
+    #[test]
+    fn write_error() {
+        let trace = JsonTrace::new(Broken);
+        trace.on_tile_set(Pos::new(0, 0), Unassigned, Camp, None);
+        trace.on_tile_set(Pos::new(0, 1), Unassigned, Grass, None);
+        assert_eq!(trace.flush().unwrap_err().to_string(), "broken");
+        assert!(trace.flush().is_ok());
+    }
+}
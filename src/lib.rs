@@ -1,9 +1,60 @@
+#[cfg(feature = "logging")]
+#[macro_use]
+extern crate log;
+#[cfg(feature = "image")]
+extern crate png as png_crate;
+#[cfg(feature = "arbitrary")]
+extern crate quickcheck;
+#[cfg(feature = "parallel")]
+extern crate rayon;
+extern crate serde_json;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(feature = "wasm")]
+extern crate wasm_bindgen;
+
+mod annotations;
+pub use annotations::*;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "arbitrary")]
+pub use arbitrary::*;
 mod associate_trees;
 pub use associate_trees::*;
 mod board;
 pub use board::*;
+mod board_move;
+pub use board_move::*;
+mod board_observer;
+pub use board_observer::*;
+mod canonical_form;
+pub use canonical_form::*;
+mod cancel_token;
+pub use cancel_token::*;
+mod clue;
+pub use clue::*;
+mod compact_grid;
+pub use compact_grid::*;
+mod contradiction;
+pub use contradiction::*;
+mod counter;
+mod counts;
+mod error;
+pub use error::*;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod explain;
+pub use explain::*;
+mod formats;
+pub use formats::*;
 mod fill_camps;
 pub use fill_camps::*;
+mod fill_segments;
+pub use fill_segments::*;
+mod fill_total;
+pub use fill_total::*;
+mod fill_tree_camps;
+pub use fill_tree_camps::*;
 mod fill_zeros;
 pub use fill_zeros::*;
 mod grid;
@@ -12,5 +63,58 @@ mod initialize_grass;
 pub use initialize_grass::*;
 mod intersection;
 pub use intersection::*;
+mod json_trace;
+pub use json_trace::*;
+mod line_masks;
+mod line_view;
+pub use line_view::*;
+mod match_trees;
+pub use match_trees::*;
+mod matching;
+mod minimize;
+mod orphans;
+mod pair_intersection;
+pub use pair_intersection::*;
+mod parse_options;
+pub use parse_options::*;
+mod pos;
+pub use pos::*;
+mod rating;
+pub use rating::*;
+pub mod render;
+mod redundant_clues;
+mod regions;
+pub use redundant_clues::*;
+mod repl;
+pub use repl::*;
+mod resize;
+pub use resize::*;
+mod rule_set;
+pub use rule_set::*;
+pub mod samples;
+mod solution_count;
+mod solve_outcome;
+pub use solve_outcome::*;
+mod solve_stats;
+pub use solve_stats::*;
+mod solver_config;
+pub use solver_config::*;
+mod solver_limits;
+pub use solver_limits::*;
+mod strategy;
+pub use strategy::*;
+mod technique_analysis;
+pub use technique_analysis::*;
 mod tile;
 pub use tile::*;
+mod tile_charset;
+pub use tile_charset::*;
+mod transform;
+pub use transform::*;
+mod trial;
+pub use trial::*;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod violation;
+pub use violation::*;
+mod work_queue;
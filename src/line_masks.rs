@@ -0,0 +1,257 @@
+use board::*;
+use clue::*;
+use pos::*;
+use tile::Tile::{self, *};
+
+/// The longest line that can be handled as a bitmask.
+pub const MAX_MASK_LENGTH: usize = 64;
+
+/// Get a mask with a bit set for each of `length` `Tile`s.
+pub fn full_mask(length: usize) -> u64 {
+    if length == MAX_MASK_LENGTH {
+        !0
+    } else {
+        (1 << length) - 1
+    }
+}
+
+/// Add the `Tile`s at and on either side of each bit of `mask`.
+///
+/// `full` has a bit set for each `Tile` in the line.
+pub fn spread(mask: u64, full: u64) -> u64 {
+    (mask | mask << 1 | mask >> 1) & full
+}
+
+/// Get the most non-adjacent bits that can be chosen from `mask`.
+///
+/// Taking the lowest remaining bit each time is optimal.
+pub fn capacity(mut mask: u64) -> usize {
+    let mut count = 0;
+    while mask != 0 {
+        let lowest = mask & mask.wrapping_neg();
+        mask &= !(lowest | lowest << 1);
+        count += 1;
+    }
+    count
+}
+
+/// Whether a line with `camps` and room for more in `open` can still
+/// meet `clue`.
+pub fn fits(camps: u64, open: u64, full: u64, clue: Clue) -> bool {
+    let placed = camps.count_ones() as usize;
+    clue.allows_any(placed, capacity(open & !spread(camps, full)) + placed)
+}
+
+/// Which lines crossing a line can still meet their clues, depending
+/// on what becomes of the `Tile` they share with it.
+///
+/// Bit `i` of each mask is the `i`th crossing line.
+struct Cross {
+    /// Crossing lines that can take a [`Camp`] on the shared `Tile`.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    camp: u64,
+    /// Crossing lines that can lose the shared `Tile` and its neighbors
+    /// to a [`Camp`] next to it.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    beside: u64,
+    /// Crossing lines that can lose just the shared `Tile`.
+    grass: u64,
+}
+
+impl Cross {
+    /// Check the `lines` lines crossing line `line` against their
+    /// `clues`.
+    ///
+    /// Only the crossing lines in `touched` are checked; the rest are
+    /// left out of every mask.  `pos` is as in [`LineMasks::new`].
+    ///
+    /// [`LineMasks::new`]: struct.LineMasks.html#method.new
+    fn new<F: Fn(usize, usize) -> Pos>(
+        board: &Board,
+        pos: &F,
+        line: usize,
+        lines: usize,
+        clues: &[Clue],
+        mut touched: u64,
+    ) -> Self {
+        let full = full_mask(lines);
+        let shared = 1 << line;
+        let around = spread(shared, full);
+        let mut cross = Cross {
+            camp: 0,
+            beside: 0,
+            grass: 0,
+        };
+        while touched != 0 {
+            let index = touched.trailing_zeros() as usize;
+            touched &= touched - 1;
+            let camps = crossing_mask(board, pos, index, lines, Camp);
+            let unassigned = crossing_mask(board, pos, index, lines, Unassigned);
+            let clue = clues[index];
+            if fits(camps | shared, unassigned & !around, full, clue) {
+                cross.camp |= 1 << index;
+            }
+            if fits(camps, unassigned & !around, full, clue) {
+                cross.beside |= 1 << index;
+            }
+            if fits(camps, unassigned & !shared, full, clue) {
+                cross.grass |= 1 << index;
+            }
+        }
+        cross
+    }
+}
+
+/// Get the `tile`s of the `index`th crossing line as a mask over the
+/// `lines` lines.
+///
+/// `pos` is as in [`LineMasks::new`].
+///
+/// [`LineMasks::new`]: struct.LineMasks.html#method.new
+pub fn crossing_mask<F: Fn(usize, usize) -> Pos>(
+    board: &Board,
+    pos: &F,
+    index: usize,
+    lines: usize,
+    tile: Tile,
+) -> u64 {
+    (0..lines)
+        .filter(|&x| board[pos(x, index)] == tile)
+        .fold(0, |mask, x| mask | 1 << x)
+}
+
+/// One line of a `Board` as bitmasks, ready to enumerate the ways to
+/// place [`Camp`]s along it.
+///
+/// Bit `i` of each mask is the `i`th `Tile` of the line.
+///
+/// [`Camp`]: enum.Tile.html#variant.Camp
+pub struct LineMasks {
+    /// A bit for each `Tile` in the line.
+    pub full: u64,
+    /// The [`Unassigned`] `Tile`s of the line.
+    ///
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    pub unassigned: u64,
+    /// [`Unassigned`] `Tile`s that could take a [`Camp`].
+    ///
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    candidates: u64,
+    cross: Option<Cross>,
+}
+
+impl LineMasks {
+    /// Read line `line` of `board`.
+    ///
+    /// `pos(line, index)` gives the position of the `index`th `Tile` of
+    /// a line.  There are `lines` lines of `length` `Tile`s, and
+    /// `length` must be at most [`MAX_MASK_LENGTH`].
+    ///
+    /// `clues` are the clues of the crossing lines, or empty if there
+    /// are none.  Placements that would leave a crossing line with too
+    /// many [`Camp`]s, or without room for enough of them, are skipped.
+    ///
+    /// [`MAX_MASK_LENGTH`]: constant.MAX_MASK_LENGTH.html
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub fn new<F: Fn(usize, usize) -> Pos>(
+        board: &Board,
+        pos: &F,
+        line: usize,
+        lines: usize,
+        length: usize,
+        clues: &[Clue],
+    ) -> Self {
+        debug_assert!(length <= MAX_MASK_LENGTH);
+        let full = full_mask(length);
+        let mask = |line: usize, tile| {
+            (0..length)
+                .filter(|&index| board[pos(line, index)] == tile)
+                .fold(0u64, |mask, index| mask | 1 << index)
+        };
+        let camps =
+            (line.saturating_sub(1)..lines.min(line + 2)).fold(0, |camps, x| camps | mask(x, Camp));
+        let unassigned = mask(line, Unassigned);
+        let cross = if clues.len() == length && lines <= MAX_MASK_LENGTH {
+            let touched = spread(unassigned, full);
+            Some(Cross::new(board, pos, line, lines, clues, touched))
+        } else {
+            None
+        };
+        let allowed = cross.as_ref().map_or(full, |cross| cross.camp);
+        LineMasks {
+            full,
+            unassigned,
+            candidates: unassigned & !spread(camps, full) & allowed,
+            cross,
+        }
+    }
+
+    /// Call `visit` with each way to place `count` more [`Camp`]s along
+    /// the line, given as the mask of [`Camp`]s placed.
+    ///
+    /// Stops as soon as `visit` returns `false`.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub fn enumerate<V: FnMut(u64) -> bool>(&self, count: usize, visit: &mut V) {
+        enumerate(self, self.candidates, count, 0, 0, visit);
+    }
+
+    /// Check whether the crossing lines in `crossing` can still meet
+    /// their clues once the [`Unassigned`] `Tile`s of the line are
+    /// filled with [`Camp`]s at `chosen` and [`Grass`] everywhere else.
+    ///
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Grass`]: enum.Tile.html#variant.Grass
+    fn allows(&self, chosen: u64, crossing: u64) -> bool {
+        let cross = match self.cross {
+            Some(ref cross) => cross,
+            None => return true,
+        };
+        let around = spread(chosen, self.full);
+        let broken = chosen & !cross.camp
+            | around & !chosen & !cross.beside
+            | self.unassigned & !around & !cross.grass;
+        broken & crossing == 0
+    }
+}
+
+/// Enumerate every way to choose `count` non-adjacent bits of
+/// `candidates` that `line` allows, passing each to `visit`.
+///
+/// The lowest candidate is either taken, which removes it and its
+/// neighbor from the candidates, or skipped.  Crossing lines are
+/// checked once every bit up to and including their neighbors has been
+/// decided; `settled` has the crossing lines already checked.
+///
+/// Returns `false` once `visit` asks to stop.
+fn enumerate<V: FnMut(u64) -> bool>(
+    line: &LineMasks,
+    candidates: u64,
+    count: usize,
+    chosen: u64,
+    settled: u64,
+    visit: &mut V,
+) -> bool {
+    let lowest = candidates & candidates.wrapping_neg();
+    let decided = if count == 0 {
+        line.full
+    } else {
+        (lowest >> 1).saturating_sub(1)
+    };
+    if !line.allows(chosen, decided & !settled) {
+        return true;
+    }
+    if count == 0 {
+        return visit(chosen);
+    }
+    if capacity(candidates) < count {
+        return true;
+    }
+    let rest = candidates & !(lowest | lowest << 1);
+    enumerate(line, rest, count - 1, chosen | lowest, decided, visit)
+        && enumerate(line, candidates & !lowest, count, chosen, decided, visit)
+}
@@ -0,0 +1,269 @@
+use grid::*;
+use pos::*;
+use std::ops::Index;
+use tile::Tile::{self, *};
+
+/// A row of a [`Grid`], from [`Grid::row`].
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Grid, Tile::*};
+/// let grid = Grid::parse("  T \n-C  ").unwrap();
+/// let row = grid.row(0);
+/// assert_eq!(row[2], Tree);
+/// assert_eq!(row.count(Unassigned), 3);
+/// assert_eq!(row.segments(), vec![vec![(0, 0), (0, 1)], vec![(0, 3)]]);
+/// ```
+///
+/// [`Grid`]: struct.Grid.html
+/// [`Grid::row`]: struct.Grid.html#method.row
+#[derive(Copy, Clone, Debug)]
+pub struct RowView<'a> {
+    grid: &'a Grid,
+    row: usize,
+}
+
+/// A column of a [`Grid`], from [`Grid::column`].
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Grid, Tile::*};
+/// let grid = Grid::parse("  T \n-C  ").unwrap();
+/// let column = grid.column(1);
+/// assert_eq!(column.iter().collect::<Vec<_>>(), vec![Unassigned, Camp]);
+/// assert_eq!(column.segments(), vec![vec![(0, 1)]]);
+/// ```
+///
+/// [`Grid`]: struct.Grid.html
+/// [`Grid::column`]: struct.Grid.html#method.column
+#[derive(Copy, Clone, Debug)]
+pub struct ColumnView<'a> {
+    grid: &'a Grid,
+    column: usize,
+}
+
+impl Grid {
+    /// Get a view of the `Tile`s in the given row.
+    ///
+    /// # Panics
+    ///
+    /// This will `panic` if `row >= num_rows()`.
+    pub fn row<'a>(&'a self, row: usize) -> RowView<'a> {
+        assert!(
+            row < self.num_rows(),
+            "row {} is outside the {}x{} Grid",
+            row,
+            self.num_rows(),
+            self.num_columns()
+        );
+        RowView { grid: self, row }
+    }
+
+    /// Get a view of the `Tile`s in the given column.
+    ///
+    /// # Panics
+    ///
+    /// This will `panic` if `column >= num_columns()`.
+    pub fn column<'a>(&'a self, column: usize) -> ColumnView<'a> {
+        assert!(
+            column < self.num_columns(),
+            "column {} is outside the {}x{} Grid",
+            column,
+            self.num_rows(),
+            self.num_columns()
+        );
+        ColumnView { grid: self, column }
+    }
+}
+
+impl<'a> RowView<'a> {
+    /// Get the index of the row in the [`Grid`].
+    ///
+    /// [`Grid`]: struct.Grid.html
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    /// Get the number of `Tile`s in the row.
+    pub fn len(&self) -> usize {
+        self.grid.num_columns()
+    }
+
+    /// Does the row have no `Tile`s?
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the position of the `Tile` in `column`.
+    pub fn pos(&self, column: usize) -> Pos {
+        Pos::new(self.row, column)
+    }
+
+    /// Iterate over the `Tile`s from left to right.
+    pub fn iter(&self) -> impl Iterator<Item = Tile> + 'a {
+        self.cells().map(|(_, tile)| tile)
+    }
+
+    /// Iterate over the position of every `Tile` along with the
+    /// `Tile`, from left to right.
+    pub fn cells(&self) -> impl Iterator<Item = (Pos, Tile)> + 'a {
+        let (grid, row) = (self.grid, self.row);
+        (0..grid.num_columns()).map(move |column| {
+            let pos = Pos::new(row, column);
+            (pos, grid[pos])
+        })
+    }
+
+    /// Get the number of `Tile`s equal to `tile`.
+    pub fn count(&self, tile: Tile) -> usize {
+        self.iter().filter(|&x| x == tile).count()
+    }
+
+    /// Split the [`Unassigned`] `Tile`s into runs of neighboring ones,
+    /// from left to right.
+    ///
+    /// If the [`Grid`] wraps, the runs at the two ends are one run,
+    /// starting with the one on the right.
+    ///
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    /// [`Grid`]: struct.Grid.html
+    pub fn segments(&self) -> Vec<Vec<Pos>> {
+        segments(self.cells(), self.grid.rules().wrap)
+    }
+}
+
+impl<'a> ColumnView<'a> {
+    /// Get the index of the column in the [`Grid`].
+    ///
+    /// [`Grid`]: struct.Grid.html
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Get the number of `Tile`s in the column.
+    pub fn len(&self) -> usize {
+        self.grid.num_rows()
+    }
+
+    /// Does the column have no `Tile`s?
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the position of the `Tile` in `row`.
+    pub fn pos(&self, row: usize) -> Pos {
+        Pos::new(row, self.column)
+    }
+
+    /// Iterate over the `Tile`s from top to bottom.
+    pub fn iter(&self) -> impl Iterator<Item = Tile> + 'a {
+        self.cells().map(|(_, tile)| tile)
+    }
+
+    /// Iterate over the position of every `Tile` along with the
+    /// `Tile`, from top to bottom.
+    pub fn cells(&self) -> impl Iterator<Item = (Pos, Tile)> + 'a {
+        let (grid, column) = (self.grid, self.column);
+        (0..grid.num_rows()).map(move |row| {
+            let pos = Pos::new(row, column);
+            (pos, grid[pos])
+        })
+    }
+
+    /// Get the number of `Tile`s equal to `tile`.
+    pub fn count(&self, tile: Tile) -> usize {
+        self.iter().filter(|&x| x == tile).count()
+    }
+
+    /// Split the [`Unassigned`] `Tile`s into runs of neighboring ones,
+    /// from top to bottom.
+    ///
+    /// If the [`Grid`] wraps, the runs at the two ends are one run,
+    /// starting with the one at the bottom.
+    ///
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    /// [`Grid`]: struct.Grid.html
+    pub fn segments(&self) -> Vec<Vec<Pos>> {
+        segments(self.cells(), self.grid.rules().wrap)
+    }
+}
+
+impl<'a> Index<usize> for RowView<'a> {
+    type Output = Tile;
+    fn index(&self, column: usize) -> &Tile {
+        &self.grid[(self.row, column)]
+    }
+}
+
+impl<'a> Index<usize> for ColumnView<'a> {
+    type Output = Tile;
+    fn index(&self, row: usize) -> &Tile {
+        &self.grid[(row, self.column)]
+    }
+}
+
+/// Split the [`Unassigned`] `Tile`s of a line into runs, joining the
+/// runs at either end if `wrap` is set.
+///
+/// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+fn segments<I: Iterator<Item = (Pos, Tile)>>(cells: I, wrap: bool) -> Vec<Vec<Pos>> {
+    let mut runs = Vec::new();
+    let mut run = Vec::new();
+    let mut starts_open = false;
+    for (i, (pos, tile)) in cells.enumerate() {
+        if tile == Unassigned {
+            starts_open |= i == 0;
+            run.push(pos);
+        } else if !run.is_empty() {
+            runs.push(run);
+            run = Vec::new();
+        }
+    }
+    if !run.is_empty() {
+        if wrap && starts_open && !runs.is_empty() {
+            run.extend(runs.remove(0));
+        }
+        runs.push(run);
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rule_set::*;
+
+    #[test]
+    fn line_view_index() {
+        let grid = Grid::parse("TC\n -").unwrap();
+        assert_eq!(grid.row(1)[1], Grass);
+        assert_eq!(grid.column(0)[0], Tree);
+        assert_eq!(grid.row(0).len(), 2);
+        assert_eq!(grid.column(1).pos(1), (1, 1));
+        assert_eq!(
+            grid.column(1).cells().collect::<Vec<_>>(),
+            vec![(Pos::new(0, 1), Camp), (Pos::new(1, 1), Grass)]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn line_view_outside() {
+        let _ = Grid::parse("TC\n -").unwrap().column(2);
+    }
+
+    #[test]
+    fn segments_wrap() {
+        let mut grid = Grid::parse("  T  -  ").unwrap();
+        assert_eq!(grid.row(0).segments().len(), 3);
+        grid.set_rules(RuleSet::torus());
+        assert_eq!(
+            grid.row(0).segments(),
+            vec![vec![(0, 3), (0, 4)], vec![(0, 6), (0, 7), (0, 0), (0, 1)]]
+        );
+        let grid = Grid::parse("    ").unwrap();
+        assert_eq!(grid.row(0).segments().len(), 1);
+    }
+}
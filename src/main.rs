@@ -1,49 +1,901 @@
 extern crate camps_and_trees;
-use camps_and_trees::Board;
+extern crate serde_json;
+use camps_and_trees::{
+    Board, BoardObserver, Corpus, Csv, Error, Explainer, JsonTrace, Pos, Rating, Repl,
+    SolveOutcome, SolverConfig, SolverLimits, Tile, CSV_BOARD_COLUMNS,
+};
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufRead, BufWriter, Write};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-/// Split the input by `,` and parse the pieces as numbers.
-fn read_camps(s: &str) -> Result<Vec<usize>, String> {
-    if s.is_empty() {
-        Err("Row or column descriptors must not be empty")?
+/// The exit code for I/O errors and anything else not covered below.
+const EXIT_IO: i32 = 1;
+/// The exit code for arguments or puzzles that can't be parsed.
+const EXIT_PARSE: i32 = 2;
+/// The exit code for puzzles that break the rules.
+const EXIT_INCONSISTENT: i32 = 3;
+/// The exit code for puzzles the solver can't finish, because it got
+/// stuck or hit a `SolverLimits`.
+const EXIT_STUCK: i32 = 4;
+
+/// How many times `bench` solves each puzzle if `--runs` isn't given.
+const DEFAULT_RUNS: usize = 10;
+
+/// An error that stops the program, with the code to exit with.
+#[derive(Debug, PartialEq)]
+pub struct Failure {
+    pub code: i32,
+    pub message: String,
+}
+
+impl Failure {
+    fn new(code: i32, message: String) -> Self {
+        Failure { code, message }
+    }
+}
+
+impl From<String> for Failure {
+    /// Errors without a category are I/O errors.
+    fn from(message: String) -> Self {
+        Failure::new(EXIT_IO, message)
+    }
+}
+
+/// The exit code for `outcome`, or `0` if it is solved.
+fn outcome_code(outcome: &SolveOutcome) -> i32 {
+    match *outcome {
+        SolveOutcome::Solved => 0,
+        SolveOutcome::Stuck { .. } | SolveOutcome::Stopped { .. } => EXIT_STUCK,
+        SolveOutcome::Contradiction { .. } => EXIT_INCONSISTENT,
+    }
+}
+
+/// The exit code for an `error` from solving.
+fn solve_error_code(error: &Error) -> i32 {
+    match *error {
+        Error::Unsolved { .. } | Error::Stopped { .. } => EXIT_STUCK,
+        // The solver broke a rule itself, so the puzzle may be fine.
+        Error::InvalidSolution { .. } => EXIT_IO,
+        _ => EXIT_INCONSISTENT,
     }
-    let camps: Result<_, _> = s.split(',').map(|x| x.trim()).map(|x| x.parse()).collect();
-    camps.map_err(|x: std::num::ParseIntError| x.to_string())
 }
 
 /// Analyze the lines of `stdin`.
 ///
-/// `lines` should look like `vec![rows, columns, board..]`.
+/// `lines` should look like `vec![rows, columns, board..]`.  For
+/// clue-free puzzles, `lines` should look like `vec!["total: N",
+/// board..]` instead.  If the first non-space character is `{`, the
+/// lines are parsed as JSON via `Board::from_json`.  Otherwise they
+/// are parsed by `Board::from_text`, falling back to
+/// `Board::parse_combined` for puzzles with the clues in the grid.
 pub fn analyze_stdin(lines: Vec<String>) -> Result<Board, String> {
-    if lines.len() < 3 {
-        Err("Too few lines.  There must be at least 3.")?
+    let text = lines.join("\n");
+    if text.trim_start().starts_with('{') {
+        return Board::from_json(&text).map_err(|x| x.to_string());
+    }
+    Board::from_text(&text)
+        .or_else(|error| {
+            // Report the error for whichever format the input looks
+            // like.  Text starts with a total or comma separated clues.
+            let is_text = lines
+                .first()
+                .is_none_or(|x| x.contains(',') || x.trim().starts_with("total:"));
+            Board::parse_combined(&text).map_err(|x| if is_text { error } else { x })
+        })
+        .map_err(|x| x.to_string())
+}
+
+/// What the program was asked to do.
+#[derive(Debug, Default, PartialEq)]
+pub enum Command {
+    /// Solve the puzzle on `stdin`, or each file in `Args::files` and
+    /// print a summary table.
+    #[default]
+    Solve,
+    /// Print a JSON line rating each puzzle with `Board::rate`.
+    Rate,
+    /// Print a JSON line with the `Board::minimal_techniques` of each
+    /// puzzle.
+    Techniques,
+    /// Solve each puzzle in CSV files, printing the solutions and
+    /// their `Rating`s as CSV.
+    Csv,
+    /// Solve each puzzle `Args::runs` times and print a table of how
+    /// long it took and how much work it was.
+    Bench,
+}
+
+/// How much the program prints besides its results.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Print only the results, without tables or puzzles in errors.
+    Quiet,
+    /// Print the results, tables and errors.
+    #[default]
+    Normal,
+    /// Also print a line to `stderr` as each puzzle is finished.
+    Verbose,
+    /// Also print each step of every strategy to `stderr`.
+    Detailed,
+}
+
+/// The settings given on the command line.
+#[derive(Debug, Default, PartialEq)]
+pub struct Args {
+    pub command: Command,
+    /// The puzzle files given after a command such as `solve`.
+    /// Directories and patterns with `*` or `?` are expanded.
+    pub files: Vec<String>,
+    pub limits: SolverLimits,
+    pub config: SolverConfig,
+    /// The file to write a `JsonTrace` of the solve to.
+    pub json_trace: Option<String>,
+    /// Print the `Board` after each step of the solve.
+    pub steps: bool,
+    /// Explain each step of the solve in sentences.
+    pub explain: bool,
+    /// Read `Repl` commands from `stdin` instead of solving.
+    pub repl: bool,
+    /// Print the camps of the solution as `row,column` lines.
+    pub camps: bool,
+    /// Also print the tree each camp serves after its coordinates.
+    pub camp_trees: bool,
+    /// How many times `bench` solves each puzzle.
+    pub runs: usize,
+    pub verbosity: Verbosity,
+}
+
+/// Prints the `Board` to `stdout` after each step of the solve, below
+/// the name of the strategy that made it.
+#[derive(Default)]
+struct StepPrinter {
+    steps: AtomicUsize,
+}
+
+impl BoardObserver for StepPrinter {
+    fn on_tile_set(&self, _: Pos, _: Tile, _: Tile, _: Option<&'static str>) {}
+
+    fn on_step(&self, board: &Board, strategy: &'static str) {
+        let step = self.steps.fetch_add(1, Ordering::Relaxed) + 1;
+        println!("Step {}: {}", step, strategy);
+        println!("{}\n", board.display_with_clues());
     }
-    let rows = read_camps(&lines[0])?;
-    let columns = read_camps(&lines[1])?;
-    Board::new_parse(rows, columns, &lines[2..].join("\n"))
+}
+
+/// Prints each step of the solve to `stderr`: the strategy, how many
+/// `Tile`s it set and how many are left.
+#[derive(Default)]
+struct StrategyPrinter {
+    tiles: AtomicUsize,
+}
+
+impl BoardObserver for StrategyPrinter {
+    fn on_tile_set(&self, _: Pos, _: Tile, _: Tile, _: Option<&'static str>) {
+        self.tiles.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_step(&self, board: &Board, strategy: &'static str) {
+        let tiles = self.tiles.swap(0, Ordering::Relaxed);
+        eprintln!(
+            "{}: set {} tiles, {} left",
+            strategy,
+            tiles,
+            board.count(Tile::Unassigned)
+        );
+    }
+}
+
+/// Parse the command line arguments, not including the program name.
+///
+/// `--max-possibilities-per-line N` sets
+/// `SolverLimits::max_possibilities_per_line`, `--max-duration-ms N`
+/// sets `SolverLimits::max_duration` to `N` milliseconds and
+/// `--max-iterations N` sets `SolverLimits::max_iterations`.
+///
+/// `--trial-depth N` sets `SolverConfig::trial_depth` and
+/// `--profile NAME` sets `SolverConfig::profile` to `standard`, `human`
+/// or `fast`.  `--no-guessing` sets `SolverConfig::no_guessing` and
+/// `--stats` sets `SolverConfig::collect_stats`.
+///
+/// `--json-trace FILE` writes every deduction to `FILE` as JSON lines
+/// and `--steps` prints the `Board` after each step of the solve.
+/// `--explain` prints a sentence explaining each deduction.
+///
+/// `--camps` prints the camps of the solution as `row,column` lines
+/// with `Board::to_camp_list`, and `--camps-with-trees` adds the tree
+/// each camp serves.
+///
+/// `-q` prints only the results, `-v` also prints a line as each
+/// puzzle is finished, and `-vv` or a second `-v` also prints each
+/// step of every strategy.
+///
+/// `--repl` reads the puzzle from `stdin` up to the first empty line,
+/// then runs each following line as a `Repl` command.
+///
+/// `solve FILE..` solves each puzzle file and prints a table of how
+/// each went.  `rate [FILE..]` rates each puzzle file, or the puzzle on `stdin`
+/// if there are none, and prints a line of JSON for each.
+/// `techniques [FILE..]` does the same with the minimal set of
+/// strategies that solves each puzzle.  `csv [FILE..]` solves every
+/// puzzle in each `Csv` file and prints the solutions as CSV.  The
+/// solver settings for these start from `Rating::default_config`
+/// instead.
+///
+/// `bench [FILE..]` solves each puzzle, or each puzzle of a `Corpus`,
+/// `--runs N` times, 10 by default, and prints a table of the times
+/// and work done.
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut args = args.into_iter().peekable();
+    let command = match args.peek().map(String::as_str) {
+        Some("rate") => Some(Command::Rate),
+        Some("techniques") => Some(Command::Techniques),
+        Some("csv") => Some(Command::Csv),
+        Some("solve") => Some(Command::Solve),
+        Some("bench") => Some(Command::Bench),
+        _ => None,
+    };
+    let takes_files = command.is_some();
+    if let Some(command) = command {
+        args.next();
+        match command {
+            Command::Solve => (),
+            Command::Bench => parsed.runs = DEFAULT_RUNS,
+            _ => parsed.config = Rating::default_config(),
+        }
+        parsed.command = command;
+    }
+    let limits = &mut parsed.limits;
+    let config = &mut parsed.config;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--max-possibilities-per-line" => {
+                limits.max_possibilities_per_line = Some(parse_value(&arg, args.next())?);
+            }
+            "--max-duration-ms" => {
+                limits.max_duration = Some(Duration::from_millis(parse_value(&arg, args.next())?));
+            }
+            "--max-iterations" => limits.max_iterations = Some(parse_value(&arg, args.next())?),
+            "--trial-depth" => config.trial_depth = parse_value(&arg, args.next())?,
+            "--profile" => config.profile = parse_value(&arg, args.next())?,
+            "--no-guessing" => config.no_guessing = true,
+            "--stats" => config.collect_stats = true,
+            "--json-trace" => parsed.json_trace = Some(parse_value(&arg, args.next())?),
+            "--steps" => parsed.steps = true,
+            "--explain" => parsed.explain = true,
+            "--repl" => parsed.repl = true,
+            "-q" | "--quiet" => parsed.verbosity = Verbosity::Quiet,
+            "-v" | "--verbose" => {
+                parsed.verbosity = match parsed.verbosity {
+                    Verbosity::Verbose | Verbosity::Detailed => Verbosity::Detailed,
+                    _ => Verbosity::Verbose,
+                }
+            }
+            "-vv" => parsed.verbosity = Verbosity::Detailed,
+            "--runs" => {
+                parsed.runs = parse_value(&arg, args.next())?;
+                if parsed.runs == 0 {
+                    return Err(format!("Invalid value for {}: \"0\"", arg));
+                }
+            }
+            "--camps" => parsed.camps = true,
+            "--camps-with-trees" => {
+                parsed.camps = true;
+                parsed.camp_trees = true;
+            }
+            _ if takes_files && !arg.starts_with('-') => parsed.files.push(arg),
+            _ => return Err(format!("Unknown argument: {:?}", arg)),
+        }
+    }
+    Ok(parsed)
+}
+
+/// Parse the `value` following the argument `arg`.
+fn parse_value<T: FromStr>(arg: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("Missing value for {}", arg))?;
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value for {}: {:?}", arg, value))
 }
 
 /// Get the lines of `stdin`.
 fn get_stdin_lines() -> Result<Vec<String>, String> {
-    use std::io::BufRead;
     let stdin = std::io::stdin();
     let lines: Result<_, _> = stdin.lock().lines().collect();
     lines.map_err(|x| x.to_string())
 }
 
+/// Load the puzzle from the lines of `stdin` before the first empty
+/// line, then run each remaining line as a `Repl` command.
+///
+/// The output of each command is printed to `stdout`.  Errors are
+/// printed as `Error: ...` and don't stop the `Repl`.
+fn run_repl(args: Args) -> Result<(), Failure> {
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut puzzle = Vec::new();
+    for line in &mut lines {
+        let line = line.map_err(|x| x.to_string())?;
+        if line.is_empty() {
+            break;
+        }
+        puzzle.push(line);
+    }
+    let mut board = analyze_stdin(puzzle).map_err(|x| Failure::new(EXIT_PARSE, x))?;
+    board.limits = args.limits;
+    board
+        .validate()
+        .map_err(|x| Failure::new(EXIT_INCONSISTENT, x.to_string()))?;
+    let mut repl = Repl::new(board);
+    repl.config = args.config;
+    let stdout = std::io::stdout();
+    for line in lines {
+        let line = line.map_err(|x| x.to_string())?;
+        let mut stdout = stdout.lock();
+        let result = match repl.run(&line) {
+            Ok(ref output) if output.is_empty() => Ok(()),
+            Ok(output) => writeln!(stdout, "{}", output),
+            Err(e) => writeln!(stdout, "Error: {}", e),
+        };
+        result
+            .and_then(|()| stdout.flush())
+            .map_err(|x| x.to_string())?;
+    }
+    Ok(())
+}
+
+/// A one word description of `outcome`.
+fn status_name(outcome: &SolveOutcome) -> &'static str {
+    match *outcome {
+        SolveOutcome::Solved => "solved",
+        SolveOutcome::Stuck { .. } => "stuck",
+        SolveOutcome::Stopped { .. } => "stopped",
+        SolveOutcome::Contradiction { .. } => "contradiction",
+    }
+}
+
+/// The JSON record printed by `rate` for the puzzle in `file`.
+fn rating_record(file: &str, rating: &Rating) -> Value {
+    let mut object = serde_json::Map::new();
+    object.insert("file".to_string(), Value::from(file));
+    object.insert(
+        "status".to_string(),
+        Value::from(status_name(&rating.outcome)),
+    );
+    object.insert(
+        "solved".to_string(),
+        Value::from(rating.outcome.is_solved()),
+    );
+    object.insert("difficulty".to_string(), Value::from(rating.difficulty));
+    object.insert(
+        "techniques".to_string(),
+        Value::from(rating.techniques.clone()),
+    );
+    object.insert("search".to_string(), Value::from(rating.search));
+    object.insert(
+        "time_ms".to_string(),
+        Value::from(rating.time.as_secs_f64() * 1000.0),
+    );
+    Value::from(object)
+}
+
+/// The JSON record printed by `rate` for a `file` that couldn't be
+/// read or parsed.
+fn error_record(file: &str, error: String) -> Value {
+    let mut object = serde_json::Map::new();
+    object.insert("file".to_string(), Value::from(file));
+    object.insert("error".to_string(), Value::from(error));
+    Value::from(object)
+}
+
+/// Whether `name` matches `pattern`, where `*` matches any run of
+/// characters and `?` any one character.
+fn wildcard_match(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((&'*', rest)) => (0..=name.len()).any(|i| wildcard_match(rest, &name[i..])),
+        Some((&'?', rest)) => !name.is_empty() && wildcard_match(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && wildcard_match(rest, &name[1..]),
+    }
+}
+
+/// Expand `path` into the files it names, sorted.
+///
+/// A directory names every file in it, and a file name with `*` or
+/// `?` names every file in its directory that matches.  Anything else
+/// names itself.
+fn expand_path(path: &str) -> Result<Vec<String>, String> {
+    let path = std::path::Path::new(path);
+    let (dir, pattern) = if path.is_dir() {
+        (path, vec!['*'])
+    } else {
+        match path.file_name().and_then(|x| x.to_str()) {
+            Some(name) if name.contains(['*', '?']) => (
+                path.parent()
+                    .filter(|x| !x.as_os_str().is_empty())
+                    .unwrap_or_else(|| std::path::Path::new(".")),
+                name.chars().collect(),
+            ),
+            _ => return Ok(vec![path.to_string_lossy().into_owned()]),
+        }
+    };
+    let entries = std::fs::read_dir(dir).map_err(|x| format!("{}: {}", dir.display(), x))?;
+    let mut files = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|x| format!("{}: {}", dir.display(), x))?;
+        let name: Vec<char> = entry.file_name().to_string_lossy().chars().collect();
+        if entry.path().is_file() && wildcard_match(&pattern, &name) {
+            files.push(entry.path().to_string_lossy().into_owned());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// The files to read puzzles from: `args.files` expanded with
+/// `expand_path`, or `-` for `stdin` if there are none.
+fn input_files(args: &Args) -> Result<Vec<String>, String> {
+    if args.files.is_empty() {
+        return Ok(vec!["-".to_string()]);
+    }
+    let mut files = Vec::new();
+    for file in &args.files {
+        files.extend(expand_path(file)?);
+    }
+    Ok(files)
+}
+
+/// Read the lines of `file`, or of `stdin` if `file` is `-`.
+fn read_lines(file: &str) -> Result<Vec<String>, String> {
+    if file == "-" {
+        get_stdin_lines()
+    } else {
+        let text = std::fs::read_to_string(file).map_err(|x| x.to_string())?;
+        Ok(text.lines().map(str::to_string).collect())
+    }
+}
+
+/// Read the puzzle in `file`, or on `stdin` if `file` is `-`.
+fn read_puzzle(file: &str, args: &Args) -> Result<Board, Failure> {
+    let mut board = analyze_stdin(read_lines(file)?).map_err(|x| Failure::new(EXIT_PARSE, x))?;
+    board.limits = args.limits.clone();
+    board
+        .validate()
+        .map_err(|x| Failure::new(EXIT_INCONSISTENT, x.to_string()))?;
+    Ok(board)
+}
+
+/// The JSON record printed by `techniques` for the puzzle in `file`.
+///
+/// `techniques` is `null` if the puzzle can't be solved.
+fn techniques_record(file: &str, techniques: Option<Vec<&'static str>>) -> Value {
+    let mut object = serde_json::Map::new();
+    object.insert("file".to_string(), Value::from(file));
+    object.insert("solved".to_string(), Value::from(techniques.is_some()));
+    object.insert("techniques".to_string(), Value::from(techniques));
+    Value::from(object)
+}
+
+/// Print that the `index`th of `count` puzzles, in `file`, finished
+/// with `status`, if `args` asks for it.
+fn report_progress(args: &Args, index: usize, count: usize, file: &str, status: &str) {
+    if args.verbosity >= Verbosity::Verbose {
+        eprintln!("[{}/{}] {}: {}", index + 1, count, file, status);
+    }
+}
+
+/// Set up `board` to print each step of the solve, if `args` asks for
+/// it.
+fn observe_steps(args: &Args, board: &mut Board) {
+    if args.verbosity >= Verbosity::Detailed {
+        board.observer = Some(Arc::new(StrategyPrinter::default()));
+    }
+}
+
+/// Rate or analyze each puzzle in `args.files`, or the one on `stdin`
+/// if there are none, printing a line of JSON for each.
+///
+/// Puzzles that can't be read or parsed get a record with an `error`
+/// instead of failing the run.
+fn run_batch(args: Args) -> Result<(), String> {
+    let files = input_files(&args)?;
+    let stdout = std::io::stdout();
+    let mut stdout = stdout.lock();
+    for (i, file) in files.iter().enumerate() {
+        let (record, status) = match read_puzzle(file, &args) {
+            Ok(board) if args.command == Command::Rate => {
+                let rating = board.rate_with(&args.config);
+                let status = status_name(&rating.outcome);
+                (rating_record(file, &rating), status)
+            }
+            Ok(board) => {
+                let techniques = board.minimal_techniques(&args.config);
+                let status = if techniques.is_some() {
+                    "solved"
+                } else {
+                    "unsolved"
+                };
+                (techniques_record(file, techniques), status)
+            }
+            Err(e) => (error_record(file, e.message), "invalid"),
+        };
+        writeln!(stdout, "{}", record).map_err(|x| x.to_string())?;
+        report_progress(&args, i, files.len(), file, status);
+    }
+    Ok(())
+}
+
+/// The CSV record printed by `csv` for the puzzle `name` in `file`.
+///
+/// `board` is the puzzle after solving, and `rating` is `None` if it
+/// couldn't be solved because it is invalid.
+fn csv_record(file: &str, name: &str, board: &Board, rating: Option<&Rating>) -> String {
+    let mut fields = vec![file.to_string(), name.to_string()];
+    match rating {
+        Some(rating) => fields.extend(vec![
+            status_name(&rating.outcome).to_string(),
+            rating.difficulty.to_string(),
+            rating.search.to_string(),
+            format!("{:.3}", rating.time.as_secs_f64() * 1000.0),
+        ]),
+        None => fields.extend(vec![
+            "invalid".to_string(),
+            String::new(),
+            String::new(),
+            String::new(),
+        ]),
+    }
+    fields.extend(Csv::board_fields(board));
+    Csv::record(&fields)
+}
+
+/// Solve every puzzle in the `Csv` files in `args.files`, or on
+/// `stdin` if there are none, and print a CSV of the solutions with
+/// their `Rating`s.
+///
+/// Puzzles that are invalid are printed unsolved with the status
+/// `invalid`.  Files that can't be read or parsed stop the run.
+fn run_csv(args: Args) -> Result<(), Failure> {
+    let files = input_files(&args)?;
+    let stdout = std::io::stdout();
+    let mut stdout = stdout.lock();
+    let mut header: Vec<String> = ["file", "name", "status", "difficulty", "search", "time_ms"]
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+    header.extend(CSV_BOARD_COLUMNS.iter().map(|x| x.to_string()));
+    write!(stdout, "{}", Csv::record(&header)).map_err(|x| x.to_string())?;
+    for file in &files {
+        let puzzles = Csv::parse(&read_lines(file)?.join("\n"))
+            .map_err(|x| Failure::new(EXIT_PARSE, format!("{}: {}", file, x)))?;
+        for (name, mut board) in puzzles {
+            board.limits = args.limits.clone();
+            let record = match board.validate() {
+                Ok(()) => {
+                    let rating = board.solve_rated(&args.config);
+                    csv_record(file, &name, &board, Some(&rating))
+                }
+                Err(_) => csv_record(file, &name, &board, None),
+            };
+            write!(stdout, "{}", record).map_err(|x| x.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// How solving one file went in `solve FILE..`.
+struct SolveRow {
+    file: String,
+    /// A `status_name`, or `invalid` if the puzzle couldn't be read.
+    status: &'static str,
+    /// How long solving took, if the puzzle was valid.
+    time: Option<Duration>,
+    /// The exit code for how solving went, or `0` if it was solved.
+    code: i32,
+}
+
+/// Format `rows` as a table of each file with its status and time,
+/// followed by a line counting each status.
+fn summary_table(rows: &[SolveRow]) -> String {
+    let width = rows
+        .iter()
+        .map(|x| x.file.len())
+        .chain(Some(4))
+        .max()
+        .unwrap_or(4);
+    let mut table = format!("{:width$}  {:13}  {:>10}\n", "file", "status", "time_ms");
+    for row in rows {
+        let time = match row.time {
+            Some(time) => format!("{:.3}", time.as_secs_f64() * 1000.0),
+            None => "-".to_string(),
+        };
+        table.push_str(&format!(
+            "{:width$}  {:13}  {:>10}\n",
+            row.file, row.status, time
+        ));
+    }
+    let counts: Vec<String> = ["solved", "stuck", "stopped", "contradiction", "invalid"]
+        .iter()
+        .map(|&status| {
+            let count = rows.iter().filter(|x| x.status == status).count();
+            format!("{} {}", count, status)
+        })
+        .collect();
+    table.push_str(&format!("{} puzzles: {}\n", rows.len(), counts.join(", ")));
+    table
+}
+
+/// Solve each puzzle file in `args.files` and print a `summary_table`.
+///
+/// Files that can't be read or parsed are reported on `stderr` and
+/// counted as `invalid`.  If any puzzle isn't solved, the run fails
+/// with the exit code of the first one.
+fn run_solve_files(args: Args) -> Result<(), Failure> {
+    let mut rows = Vec::new();
+    let files = input_files(&args)?;
+    let count = files.len();
+    for (i, file) in files.into_iter().enumerate() {
+        let row = match read_puzzle(&file, &args) {
+            Ok(mut board) => {
+                observe_steps(&args, &mut board);
+                let start = Instant::now();
+                let outcome = board.solve_outcome_with(&args.config);
+                SolveRow {
+                    status: status_name(&outcome),
+                    time: Some(start.elapsed()),
+                    code: outcome_code(&outcome),
+                    file,
+                }
+            }
+            Err(e) => {
+                if args.verbosity > Verbosity::Quiet {
+                    eprintln!("{}: {}", file, e.message);
+                }
+                SolveRow {
+                    file,
+                    status: "invalid",
+                    time: None,
+                    code: e.code,
+                }
+            }
+        };
+        report_progress(&args, i, count, &row.file, row.status);
+        rows.push(row);
+    }
+    let table = summary_table(&rows);
+    if args.verbosity > Verbosity::Quiet {
+        print!("{}", table);
+    } else if let Some(counts) = table.lines().last() {
+        println!("{}", counts);
+    }
+    let failed = rows.iter().filter(|x| x.code != 0).count();
+    match rows.iter().find(|x| x.code != 0) {
+        Some(row) => Err(Failure::new(
+            row.code,
+            format!("{} of {} puzzles weren't solved", failed, rows.len()),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// The puzzles in `file` to benchmark, with their names.
+///
+/// A file with a single puzzle in any format is named after the file.
+/// The puzzles of a `Corpus` with more than one are named `file: name`.
+fn bench_puzzles(file: &str, args: &Args) -> Result<Vec<(String, Board)>, Failure> {
+    let lines = read_lines(file)?;
+    let puzzles = match Corpus::parse(&lines.join("\n")) {
+        Ok(ref puzzles) if puzzles.len() > 1 => puzzles
+            .iter()
+            .map(|(name, board)| (format!("{}: {}", file, name), board.clone()))
+            .collect(),
+        _ => {
+            let board = analyze_stdin(lines)
+                .map_err(|x| Failure::new(EXIT_PARSE, format!("{}: {}", file, x)))?;
+            vec![(file.to_string(), board)]
+        }
+    };
+    let mut valid = Vec::new();
+    for (name, mut board) in puzzles {
+        board.limits = args.limits.clone();
+        board
+            .validate()
+            .map_err(|x| Failure::new(EXIT_INCONSISTENT, format!("{}: {}", name, x)))?;
+        valid.push((name, board));
+    }
+    Ok(valid)
+}
+
+/// How benchmarking one puzzle went in `bench`.
+struct BenchRow {
+    puzzle: String,
+    /// The `status_name` of the last run.
+    status: &'static str,
+    min: Duration,
+    median: Duration,
+    /// The `SolveStats::iterations` of the last run.
+    iterations: usize,
+    /// The `SolveStats::possibilities` of the last run.
+    possibilities: usize,
+}
+
+/// Solve a copy of `board` `runs` times with `config`, timing each.
+fn bench_puzzle(puzzle: String, board: &Board, config: &SolverConfig, runs: usize) -> BenchRow {
+    let config = SolverConfig {
+        collect_stats: true,
+        ..*config
+    };
+    let mut times = Vec::with_capacity(runs);
+    let mut last = None;
+    for _ in 0..runs {
+        let mut board = board.clone();
+        let start = Instant::now();
+        let outcome = board.solve_outcome_with(&config);
+        times.push(start.elapsed());
+        last = Some((outcome, board));
+    }
+    times.sort();
+    let (outcome, board) = last.unwrap();
+    let stats = board.stats().unwrap();
+    BenchRow {
+        puzzle,
+        status: status_name(&outcome),
+        min: times[0],
+        median: median(&times),
+        iterations: stats.iterations,
+        possibilities: stats.possibilities,
+    }
+}
+
+/// The median of `times`, which must be sorted and not empty.
+fn median(times: &[Duration]) -> Duration {
+    let middle = times.len() / 2;
+    if times.len().is_multiple_of(2) {
+        (times[middle - 1] + times[middle]) / 2
+    } else {
+        times[middle]
+    }
+}
+
+/// Format `rows` as a table of each puzzle with its status, times and
+/// work done.
+fn bench_table(rows: &[BenchRow]) -> String {
+    let width = rows
+        .iter()
+        .map(|x| x.puzzle.len())
+        .chain(Some(6))
+        .max()
+        .unwrap_or(6);
+    let mut table = format!(
+        "{:width$}  {:13}  {:>10}  {:>10}  {:>10}  {:>13}\n",
+        "puzzle", "status", "min_ms", "median_ms", "iterations", "possibilities"
+    );
+    for row in rows {
+        table.push_str(&format!(
+            "{:width$}  {:13}  {:>10.3}  {:>10.3}  {:>10}  {:>13}\n",
+            row.puzzle,
+            row.status,
+            row.min.as_secs_f64() * 1000.0,
+            row.median.as_secs_f64() * 1000.0,
+            row.iterations,
+            row.possibilities
+        ));
+    }
+    table
+}
+
+/// Benchmark each puzzle in `args.files`, or on `stdin` if there are
+/// none, and print a `bench_table`.
+///
+/// Puzzles that can't be read, parsed or are invalid stop the run.
+fn run_bench(args: Args) -> Result<(), Failure> {
+    let mut puzzles = Vec::new();
+    for file in &input_files(&args)? {
+        puzzles.extend(bench_puzzles(file, &args)?);
+    }
+    let count = puzzles.len();
+    let mut rows = Vec::new();
+    for (i, (puzzle, board)) in puzzles.into_iter().enumerate() {
+        let row = bench_puzzle(puzzle, &board, &args.config, args.runs);
+        report_progress(&args, i, count, &row.puzzle, row.status);
+        rows.push(row);
+    }
+    print!("{}", bench_table(&rows));
+    Ok(())
+}
+
 /// Attempt to run the application's main method.
-fn try_main() -> Result<(), String> {
-    let mut board = analyze_stdin(get_stdin_lines()?)?;
-    board.solve()?;
+fn try_main() -> Result<(), Failure> {
+    let args = parse_args(std::env::args().skip(1)).map_err(|x| Failure::new(EXIT_PARSE, x))?;
+    if args.command == Command::Csv {
+        return run_csv(args);
+    }
+    if args.command == Command::Bench {
+        return run_bench(args);
+    }
+    if args.command == Command::Solve && !args.files.is_empty() {
+        return run_solve_files(args);
+    }
+    if args.command != Command::Solve {
+        return Ok(run_batch(args)?);
+    }
+    if args.repl {
+        return run_repl(args);
+    }
+    let mut board = analyze_stdin(get_stdin_lines()?).map_err(|x| Failure::new(EXIT_PARSE, x))?;
+    let verbosity = args.verbosity;
+    board.limits = args.limits;
+    board
+        .validate()
+        .map_err(|x| Failure::new(EXIT_INCONSISTENT, x.to_string()))?;
+    let mut observers: Vec<Arc<dyn BoardObserver>> = Vec::new();
+    let trace = match args.json_trace {
+        Some(path) => {
+            let file = File::create(&path).map_err(|x| format!("{}: {}", path, x))?;
+            let trace = Arc::new(JsonTrace::new(BufWriter::new(file)));
+            observers.push(trace.clone());
+            Some(trace)
+        }
+        None => None,
+    };
+    if args.steps {
+        observers.push(Arc::new(StepPrinter::default()));
+    }
+    if args.explain {
+        observers.push(Arc::new(Explainer::new(std::io::stdout())));
+    }
+    if args.verbosity >= Verbosity::Detailed {
+        observers.push(Arc::new(StrategyPrinter::default()));
+    }
+    if !observers.is_empty() {
+        board.observer = Some(Arc::new(observers));
+    }
+    let mut config = args.config;
+    config.collect_stats |= args.verbosity >= Verbosity::Detailed;
+    let start = Instant::now();
+    let outcome = board.solve_outcome_with(&config);
+    if args.verbosity >= Verbosity::Verbose {
+        eprintln!(
+            "{} in {:.3} ms",
+            status_name(&outcome),
+            start.elapsed().as_secs_f64() * 1000.0
+        );
+    }
+    let result: Result<(), Error> = outcome.into();
+    if let Some(stats) = board.stats() {
+        eprint!("{}", stats);
+    }
+    if let Some(trace) = trace {
+        trace.flush().map_err(|x| x.to_string())?;
+    }
+    result.map_err(|x| {
+        let message = if verbosity > Verbosity::Quiet {
+            format!("{}\n{}", x, board.display_with_clues())
+        } else {
+            x.to_string()
+        };
+        Failure::new(solve_error_code(&x), message)
+    })?;
+    if args.camps {
+        print!("{}", board.to_camp_list(args.camp_trees));
+    }
     Ok(())
 }
 
-/// Wrap `try_main`.  If an error is encountered, print it to `stderr` and exit with code 1.
+/// Wrap `try_main`.  If an error is encountered, print it to `stderr`
+/// and exit with its code: `1` for I/O errors, `2` for arguments or
+/// puzzles that can't be parsed, `3` for puzzles that break the rules
+/// and `4` for puzzles the solver can't finish.
 fn main() {
     match try_main() {
         Ok(()) => (),
         Err(e) => {
-            eprintln!("Error: {}", e);
-            std::process::exit(1);
+            eprintln!("Error: {}", e.message);
+            std::process::exit(e.code);
         }
     }
 }
@@ -51,6 +903,7 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use camps_and_trees::Profile;
 
     #[test]
     fn analyze_stdin_too_little_input() {
@@ -72,17 +925,457 @@ mod tests {
     }
 
     #[test]
-    fn read_camps_empty() {
-        assert!(read_camps("").is_err());
+    fn analyze_stdin_clue_free() {
+        assert!(analyze_stdin(vec!["total: 1".to_string()]).is_err());
+        assert!(analyze_stdin(vec!["total: x".to_string(), " T".to_string()]).is_err());
+        assert_eq!(
+            analyze_stdin(vec![
+                "total: 1".to_string(),
+                " T".to_string(),
+                "  ".to_string()
+            ]),
+            Ok(Board::new_parse_clue_free(1, " T\n  ").unwrap())
+        );
     }
 
     #[test]
-    fn read_camps_one_element() {
-        assert_eq!(read_camps("1"), Ok(vec![1]));
+    fn analyze_stdin_json() {
+        assert_eq!(
+            analyze_stdin(vec![
+                "  {\"rows\": [1, 0], \"columns\": [1, 0],".to_string(),
+                "   \"grid\": [\" T\", \"  \"]}".to_string(),
+            ]),
+            Ok(Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap())
+        );
+        assert!(analyze_stdin(vec!["{".to_string()]).is_err());
+    }
+
+    #[test]
+    fn analyze_stdin_combined() {
+        assert_eq!(
+            analyze_stdin(vec![
+                "1 0".to_string(),
+                " T 1".to_string(),
+                "   0".to_string()
+            ]),
+            Ok(Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap())
+        );
+        assert_eq!(
+            analyze_stdin(vec![
+                "1 0".to_string(),
+                " T 1".to_string(),
+                "   x".to_string()
+            ]),
+            Err("Invalid combined puzzle: expected a clue but found \"x\"".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_args_empty() {
+        assert_eq!(parse_args(Vec::new()), Ok(Args::default()));
+    }
+
+    #[test]
+    fn parse_args_max_possibilities_per_line() {
+        let args = vec![
+            "--max-possibilities-per-line".to_string(),
+            "1000".to_string(),
+        ];
+        assert_eq!(
+            parse_args(args).map(|args| args.limits.max_possibilities_per_line),
+            Ok(Some(1000))
+        );
+        assert_eq!(
+            parse_args(vec!["--max-possibilities-per-line".to_string()]),
+            Err("Missing value for --max-possibilities-per-line".to_string())
+        );
+        assert_eq!(
+            parse_args(vec![
+                "--max-possibilities-per-line".to_string(),
+                "x".to_string()
+            ]),
+            Err("Invalid value for --max-possibilities-per-line: \"x\"".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_args_trial_depth() {
+        let args = vec!["--trial-depth".to_string(), "2".to_string()];
+        assert_eq!(parse_args(args).map(|args| args.config.trial_depth), Ok(2));
+        assert_eq!(
+            parse_args(vec!["--trial-depth".to_string(), "-1".to_string()]),
+            Err("Invalid value for --trial-depth: \"-1\"".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_args_profile() {
+        let args = vec!["--profile".to_string(), "human".to_string()];
+        assert_eq!(
+            parse_args(args).map(|args| args.config.profile),
+            Ok(Profile::HumanStyle)
+        );
+        assert_eq!(
+            parse_args(vec!["--profile".to_string(), "slow".to_string()]),
+            Err("Invalid value for --profile: \"slow\"".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_args_no_guessing() {
+        let args = vec![
+            "--trial-depth".to_string(),
+            "1".to_string(),
+            "--no-guessing".to_string(),
+        ];
+        assert_eq!(
+            parse_args(args).map(|args| (args.config.trial_depth, args.config.no_guessing)),
+            Ok((1, true))
+        );
+    }
+
+    #[test]
+    fn parse_args_stats() {
+        assert_eq!(
+            parse_args(vec!["--stats".to_string()]).map(|args| args.config.collect_stats),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn parse_args_max_duration_and_iterations() {
+        let args = vec![
+            "--max-duration-ms".to_string(),
+            "250".to_string(),
+            "--max-iterations".to_string(),
+            "10".to_string(),
+        ];
+        assert_eq!(
+            parse_args(args).map(|args| (args.limits.max_duration, args.limits.max_iterations)),
+            Ok((Some(Duration::from_millis(250)), Some(10)))
+        );
+        assert_eq!(
+            parse_args(vec!["--max-duration-ms".to_string(), "1.5".to_string()]),
+            Err("Invalid value for --max-duration-ms: \"1.5\"".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_args_json_trace() {
+        let args = vec!["--json-trace".to_string(), "trace.jsonl".to_string()];
+        assert_eq!(
+            parse_args(args).map(|args| args.json_trace),
+            Ok(Some("trace.jsonl".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_args_steps() {
+        assert_eq!(
+            parse_args(vec!["--steps".to_string()]).map(|args| args.steps),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn parse_args_explain() {
+        assert_eq!(
+            parse_args(vec!["--explain".to_string()]).map(|args| args.explain),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn parse_args_repl() {
+        assert_eq!(
+            parse_args(vec!["--repl".to_string()]).map(|args| args.repl),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn parse_args_rate() {
+        let args = vec![
+            "rate".to_string(),
+            "a.txt".to_string(),
+            "--max-iterations".to_string(),
+            "10".to_string(),
+            "b.txt".to_string(),
+        ];
+        assert_eq!(
+            parse_args(args).map(|args| (args.command, args.files, args.limits.max_iterations)),
+            Ok((
+                Command::Rate,
+                vec!["a.txt".to_string(), "b.txt".to_string()],
+                Some(10)
+            ))
+        );
+        assert_eq!(
+            parse_args(vec!["rate".to_string()]).map(|args| (
+                args.command,
+                args.files,
+                args.config
+            )),
+            Ok((Command::Rate, Vec::new(), Rating::default_config()))
+        );
+        assert_eq!(
+            parse_args(vec!["a.txt".to_string()]),
+            Err("Unknown argument: \"a.txt\"".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_args_techniques() {
+        let args = vec!["techniques".to_string(), "a.txt".to_string()];
+        assert_eq!(
+            parse_args(args).map(|args| (args.command, args.files, args.config)),
+            Ok((
+                Command::Techniques,
+                vec!["a.txt".to_string()],
+                Rating::default_config()
+            ))
+        );
+    }
+
+    #[test]
+    fn techniques_record_fields() {
+        let record = techniques_record("a.txt", Some(vec!["fill_zeros"]));
+        assert_eq!(
+            record.to_string(),
+            r#"{"file":"a.txt","solved":true,"techniques":["fill_zeros"]}"#
+        );
+        let record = techniques_record("a.txt", None);
+        assert_eq!(
+            record.to_string(),
+            r#"{"file":"a.txt","solved":false,"techniques":null}"#
+        );
+    }
+
+    #[test]
+    fn parse_args_solve_files() {
+        let args = vec![
+            "solve".to_string(),
+            "a.txt".to_string(),
+            "--trial-depth".to_string(),
+            "1".to_string(),
+            "b.txt".to_string(),
+        ];
+        assert_eq!(
+            parse_args(args).map(|args| (args.command, args.files, args.config.trial_depth)),
+            Ok((
+                Command::Solve,
+                vec!["a.txt".to_string(), "b.txt".to_string()],
+                1
+            ))
+        );
+        assert_eq!(
+            parse_args(vec!["solve".to_string()]).map(|args| args.config),
+            Ok(SolverConfig::default())
+        );
+    }
+
+    #[test]
+    fn wildcards() {
+        let matches = |pattern: &str, name: &str| {
+            let pattern: Vec<char> = pattern.chars().collect();
+            let name: Vec<char> = name.chars().collect();
+            wildcard_match(&pattern, &name)
+        };
+        assert!(matches("*.txt", "a.txt"));
+        assert!(matches("*.txt", ".txt"));
+        assert!(!matches("*.txt", "a.json"));
+        assert!(matches("a?c*", "abcdef"));
+        assert!(!matches("a?c", "ac"));
+        assert!(matches("abc", "abc"));
+    }
+
+    #[test]
+    fn expand_paths() {
+        let dir = std::env::temp_dir().join("camps_and_trees_expand_paths");
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in &["b.txt", "a.txt", "c.json"] {
+            File::create(dir.join(name)).unwrap();
+        }
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        assert_eq!(
+            expand_path(&path("*.txt")),
+            Ok(vec![path("a.txt"), path("b.txt")])
+        );
+        assert_eq!(
+            expand_path(dir.to_str().unwrap()),
+            Ok(vec![path("a.txt"), path("b.txt"), path("c.json")])
+        );
+        assert_eq!(expand_path(&path("d.txt")), Ok(vec![path("d.txt")]));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn summary_table_counts() {
+        let rows = vec![
+            SolveRow {
+                file: "puzzles/a.txt".to_string(),
+                status: "solved",
+                time: Some(Duration::from_micros(1500)),
+                code: 0,
+            },
+            SolveRow {
+                file: "b.txt".to_string(),
+                status: "invalid",
+                time: None,
+                code: EXIT_PARSE,
+            },
+        ];
+        assert_eq!(
+            summary_table(&rows),
+            "file           status            time_ms\n\
+             puzzles/a.txt  solved              1.500\n\
+             b.txt          invalid                 -\n\
+             2 puzzles: 1 solved, 0 stuck, 0 stopped, 0 contradiction, 1 invalid\n"
+        );
+    }
+
+    #[test]
+    fn parse_args_csv() {
+        let args = vec!["csv".to_string(), "a.csv".to_string()];
+        assert_eq!(
+            parse_args(args).map(|args| (args.command, args.files)),
+            Ok((Command::Csv, vec!["a.csv".to_string()]))
+        );
+    }
+
+    #[test]
+    fn csv_record_fields() {
+        let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        let rating = board.solve_rated(&Rating::default_config());
+        let record = csv_record("a.csv", "easy", &board, Some(&rating));
+        assert!(record.starts_with("a.csv,easy,solved,2,false,"));
+        assert!(record.ends_with(",2,2,1,1 0,1 0,CT--\n"));
+        let board = Board::new_parse(vec![1, 0], vec![0, 1], " T\n  ").unwrap();
+        assert_eq!(
+            csv_record("a.csv", "bad", &board, None),
+            "a.csv,bad,invalid,,,,2,2,1,1 0,0 1,.T..\n"
+        );
+    }
+
+    #[test]
+    fn rating_record_fields() {
+        let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        let record = rating_record("a.txt", &board.rate());
+        assert_eq!(record["file"], "a.txt");
+        assert_eq!(record["status"], "solved");
+        assert_eq!(record["solved"], true);
+        assert_eq!(record["difficulty"], 2);
+        assert_eq!(
+            record["techniques"],
+            Value::from(vec!["fill_zeros", "fill_camps"])
+        );
+        assert_eq!(record["search"], false);
+        assert!(record["time_ms"].is_f64());
+    }
+
+    #[test]
+    fn parse_args_camps() {
+        assert_eq!(
+            parse_args(vec!["--camps".to_string()]).map(|args| (args.camps, args.camp_trees)),
+            Ok((true, false))
+        );
+        assert_eq!(
+            parse_args(vec!["--camps-with-trees".to_string()])
+                .map(|args| (args.camps, args.camp_trees)),
+            Ok((true, true))
+        );
+    }
+
+    #[test]
+    fn exit_codes() {
+        let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        assert_eq!(outcome_code(&board.clone().solve_outcome()), 0);
+        board.place(0, 0, Tile::Grass).unwrap();
+        let outcome = board.clone().solve_outcome();
+        assert_eq!(outcome_code(&outcome), EXIT_INCONSISTENT);
+        let error: Result<(), Error> = outcome.into();
+        assert_eq!(solve_error_code(&error.unwrap_err()), EXIT_INCONSISTENT);
+        let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        let outcome = board.clone().solve_outcome_using(&[]);
+        assert_eq!(outcome_code(&outcome), EXIT_STUCK);
+        let error: Result<(), Error> = outcome.into();
+        assert_eq!(solve_error_code(&error.unwrap_err()), EXIT_STUCK);
+        let error = Error::InvalidSolution { violations: vec![] };
+        assert_eq!(solve_error_code(&error), EXIT_IO);
+        assert_eq!(Failure::from("x".to_string()).code, EXIT_IO);
     }
 
     #[test]
-    fn read_camps_three_elements() {
-        assert_eq!(read_camps("1, 2, 3"), Ok(vec![1, 2, 3]));
+    fn parse_args_verbosity() {
+        let verbosity = |args: &[&str]| {
+            parse_args(args.iter().map(|x| x.to_string())).map(|args| args.verbosity)
+        };
+        assert_eq!(verbosity(&[]), Ok(Verbosity::Normal));
+        assert_eq!(verbosity(&["-q"]), Ok(Verbosity::Quiet));
+        assert_eq!(verbosity(&["solve", "a.txt", "-v"]), Ok(Verbosity::Verbose));
+        assert_eq!(verbosity(&["-vv"]), Ok(Verbosity::Detailed));
+        assert_eq!(verbosity(&["-v", "--verbose"]), Ok(Verbosity::Detailed));
+        assert_eq!(verbosity(&["-v", "-q"]), Ok(Verbosity::Quiet));
+    }
+
+    #[test]
+    fn parse_args_unknown() {
+        assert_eq!(
+            parse_args(vec!["-x".to_string()]),
+            Err("Unknown argument: \"-x\"".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_args_bench() {
+        let args = |args: &[&str]| parse_args(args.iter().map(|x| x.to_string()));
+        assert_eq!(
+            args(&["bench", "a.txt"]).map(|args| (
+                args.command,
+                args.files,
+                args.runs,
+                args.config
+            )),
+            Ok((
+                Command::Bench,
+                vec!["a.txt".to_string()],
+                DEFAULT_RUNS,
+                SolverConfig::default()
+            ))
+        );
+        assert_eq!(args(&["bench", "--runs", "3"]).map(|args| args.runs), Ok(3));
+        assert_eq!(
+            args(&["bench", "--runs", "0"]),
+            Err("Invalid value for --runs: \"0\"".to_string())
+        );
+    }
+
+    #[test]
+    fn bench_medians() {
+        let ms = Duration::from_millis;
+        assert_eq!(median(&[ms(1), ms(2), ms(9)]), ms(2));
+        assert_eq!(median(&[ms(1), ms(2), ms(4), ms(9)]), ms(3));
+        let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        let row = bench_puzzle("a.txt".to_string(), &board, &SolverConfig::default(), 3);
+        assert_eq!(row.status, "solved");
+        assert!(row.min <= row.median);
+        assert!(row.iterations > 0);
+    }
+
+    #[test]
+    fn bench_table_columns() {
+        let rows = vec![BenchRow {
+            puzzle: "pack.txt: easy".to_string(),
+            status: "solved",
+            min: Duration::from_micros(1250),
+            median: Duration::from_micros(1500),
+            iterations: 3,
+            possibilities: 42,
+        }];
+        assert_eq!(
+            bench_table(&rows),
+            "puzzle          status             min_ms   median_ms  iterations  possibilities\n\
+             pack.txt: easy  solved              1.250       1.500           3             42\n"
+        );
     }
 }
@@ -0,0 +1,317 @@
+use board::*;
+use contradiction::*;
+use matching::*;
+use pos::*;
+use tile::Tile::{self, *};
+
+/// Match every [`Tree`] with a [`Camp`] of its own, and every
+/// [`Camp`] with a [`Tree`] of its own.
+///
+/// If `k` [`Tree`]s are next to only `k` [`Tile`]s that could be
+/// [`Camp`]s, those [`Tile`]s must all be [`Camp`]s.  Likewise, if
+/// `k` [`Camp`]s are next to only `k` [`Tree`]s, those [`Tree`]s are
+/// taken, so [`Unassigned`] [`Tile`]s next to only taken [`Tree`]s
+/// become [`Grass`].  Nothing changes if the [`RuleSet`] has
+/// `no_matching` set.
+///
+/// Return whether any values were changed.
+///
+/// # Examples
+///
+/// Each [`Tree`] could use two corners, but the four [`Tree`]s only
+/// have four corners between them:
+///
+/// ```
+/// # use camps_and_trees::{Board, match_trees};
+/// let mut board = Board::new_parse_clue_free(4, " T \nT-T\n T ").unwrap();
+/// assert_eq!(match_trees(&mut board), Ok(true));
+/// assert_eq!(board.debug(), "CTC\nT-T\nCTC");
+/// ```
+///
+/// # Errors
+///
+/// If some [`Tree`]s are next to fewer [`Tile`]s that could be
+/// [`Camp`]s than there are [`Tree`]s, an unmatched [`Tree`] and
+/// [`Contradiction::TreeWithoutCamp`] are returned.  If some [`Camp`]s
+/// are next to fewer [`Tree`]s than there are [`Camp`]s, an unmatched
+/// [`Camp`] and [`Contradiction::SharedTree`] are returned, or
+/// [`Contradiction::LonelyCamp`] if it has no [`Tree`] at all.
+///
+/// [`Tile`]: enum.Tile.html
+/// [`Tree`]: enum.Tile.html#variant.Tree
+/// [`Camp`]: enum.Tile.html#variant.Camp
+/// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+/// [`Grass`]: enum.Tile.html#variant.Grass
+/// [`Contradiction::TreeWithoutCamp`]: enum.Contradiction.html#variant.TreeWithoutCamp
+/// [`Contradiction::SharedTree`]: enum.Contradiction.html#variant.SharedTree
+/// [`Contradiction::LonelyCamp`]: enum.Contradiction.html#variant.LonelyCamp
+/// [`RuleSet`]: struct.RuleSet.html
+pub fn match_trees(board: &mut Board) -> Result<bool, (Pos, Contradiction)> {
+    if board.rules().no_matching {
+        return Ok(false);
+    }
+    let camps = match_camps(board)?;
+    let grass = match_placed_camps(board)?;
+    for &pos in &camps {
+        board.set(pos, Camp);
+    }
+    for &pos in &grass {
+        board.set(pos, Grass);
+    }
+    Ok(!camps.is_empty() || !grass.is_empty())
+}
+
+/// Set the [`Unassigned`] [`Tile`]s that no way of matching every
+/// [`Tree`] and every [`Camp`] could use to [`Grass`].
+///
+/// In a solution each [`Camp`] is matched with its own [`Tree`], so
+/// a [`Tile`] that no such matching uses can't be a [`Camp`].  This
+/// doesn't depend on the clues, and does nothing if the [`RuleSet`]
+/// has `no_matching` set.
+///
+/// Return whether any values were changed.
+///
+/// # Examples
+///
+/// The right [`Camp`] needs the middle [`Tree`], so the left [`Camp`]
+/// needs the left [`Tree`], leaving nothing for the left end:
+///
+/// ```
+/// # use camps_and_trees::{Board, grass_unmatched};
+/// let mut board = Board::new_parse_clue_free(2, " TCTC").unwrap();
+/// assert_eq!(grass_unmatched(&mut board), Ok(true));
+/// assert_eq!(board.debug(), "-TCTC");
+/// ```
+///
+/// # Errors
+///
+/// If the [`Tree`]s and [`Camp`]s can't all be matched, an unmatched
+/// [`Tree`] and [`Contradiction::TreeWithoutCamp`] or an unmatched
+/// [`Camp`] and [`Contradiction::SharedTree`] are returned.
+///
+/// [`Tile`]: enum.Tile.html
+/// [`Tree`]: enum.Tile.html#variant.Tree
+/// [`Camp`]: enum.Tile.html#variant.Camp
+/// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+/// [`Grass`]: enum.Tile.html#variant.Grass
+/// [`Contradiction::TreeWithoutCamp`]: enum.Contradiction.html#variant.TreeWithoutCamp
+/// [`Contradiction::SharedTree`]: enum.Contradiction.html#variant.SharedTree
+/// [`RuleSet`]: struct.RuleSet.html
+pub fn grass_unmatched(board: &mut Board) -> Result<bool, (Pos, Contradiction)> {
+    if board.rules().no_matching {
+        return Ok(false);
+    }
+    let trees: Vec<Pos> = board.positions_of(Tree).collect();
+    let (edges, cells) = graph(board, &trees, |tile| tile == Camp || tile == Unassigned);
+    let required: Vec<bool> = cells.iter().map(|&pos| board[pos] == Camp).collect();
+    let camps: Vec<usize> = (0..cells.len()).filter(|&i| required[i]).collect();
+    let matching = Matching::with_priority(edges, cells.len(), &camps);
+    if let Some(tree) = matching.unmatched_left() {
+        return Err((trees[tree], Contradiction::TreeWithoutCamp));
+    }
+    if let Some(&camp) = camps.iter().find(|&&camp| !matching.is_matched_right(camp)) {
+        return Err((cells[camp], Contradiction::SharedTree));
+    }
+    let mut usable = vec![false; board.num_rows() * board.num_columns()];
+    for (used, pos) in matching.usable_right(&required).into_iter().zip(cells) {
+        usable[index(board, pos)] = used;
+    }
+    let grass: Vec<Pos> = board
+        .positions_of(Unassigned)
+        .filter(|&pos| !usable[index(board, pos)])
+        .collect();
+    for &pos in &grass {
+        board.set(pos, Grass);
+    }
+    Ok(!grass.is_empty())
+}
+
+/// Find the [`Unassigned`] [`Tile`]s that every way of giving each
+/// [`Tree`] its own [`Camp`] uses.
+///
+/// [`Tile`]: enum.Tile.html
+/// [`Tree`]: enum.Tile.html#variant.Tree
+/// [`Camp`]: enum.Tile.html#variant.Camp
+/// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+fn match_camps(board: &Board) -> Result<Vec<Pos>, (Pos, Contradiction)> {
+    let trees: Vec<Pos> = board.positions_of(Tree).collect();
+    let (edges, cells) = graph(board, &trees, |tile| tile == Camp || tile == Unassigned);
+    let matching = Matching::new(edges, cells.len());
+    if let Some(tree) = matching.unmatched_left() {
+        return Err((trees[tree], Contradiction::TreeWithoutCamp));
+    }
+    Ok(matching
+        .forced_right()
+        .into_iter()
+        .zip(cells)
+        .filter(|&(forced, pos)| forced && board[pos] == Unassigned)
+        .map(|(_, pos)| pos)
+        .collect())
+}
+
+/// Find the [`Unassigned`] [`Tile`]s whose [`Tree`]s are all needed
+/// by the [`Camp`]s already placed.
+///
+/// [`Tile`]: enum.Tile.html
+/// [`Tree`]: enum.Tile.html#variant.Tree
+/// [`Camp`]: enum.Tile.html#variant.Camp
+/// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+fn match_placed_camps(board: &Board) -> Result<Vec<Pos>, (Pos, Contradiction)> {
+    let camps: Vec<Pos> = board.positions_of(Camp).collect();
+    let (edges, trees) = graph(board, &camps, |tile| tile == Tree);
+    let matching = Matching::new(edges, trees.len());
+    if let Some(camp) = matching.unmatched_left() {
+        let reason = if board
+            .surrounding_tiles(camps[camp])
+            .into_iter()
+            .any(|pos| board[pos] == Tree)
+        {
+            Contradiction::SharedTree
+        } else {
+            Contradiction::LonelyCamp
+        };
+        return Err((camps[camp], reason));
+    }
+    let mut taken = vec![false; board.num_rows() * board.num_columns()];
+    for (forced, pos) in matching.forced_right().into_iter().zip(trees) {
+        taken[index(board, pos)] = forced;
+    }
+    Ok(board
+        .positions_of(Unassigned)
+        .filter(|&pos| {
+            board
+                .surrounding_tiles(pos)
+                .into_iter()
+                .all(|x| board[x] != Tree || taken[index(board, x)])
+        })
+        .collect())
+}
+
+/// Build the graph joining each of `from` to the `Tile`s around it
+/// that `to` accepts.
+///
+/// Returns the edges of each of `from` and the position of each
+/// `Tile` they lead to.
+fn graph<F: Fn(Tile) -> bool>(board: &Board, from: &[Pos], to: F) -> (Vec<Vec<usize>>, Vec<Pos>) {
+    let mut positions = Vec::new();
+    let mut indices = vec![None; board.num_rows() * board.num_columns()];
+    let edges = from
+        .iter()
+        .map(|&x| {
+            board
+                .surrounding_tiles(x)
+                .into_iter()
+                .filter(|&pos| to(board[pos]))
+                .map(|pos| {
+                    *indices[index(board, pos)].get_or_insert_with(|| {
+                        positions.push(pos);
+                        positions.len() - 1
+                    })
+                })
+                .collect()
+        })
+        .collect();
+    (edges, positions)
+}
+
+/// The index of `pos` when the `Tile`s of `board` are laid out in
+/// reading order.
+fn index(board: &Board, pos: Pos) -> usize {
+    pos.row * board.num_columns() + pos.column
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_trees_tight_trees() {
+        let mut board = Board::new_parse_clue_free(4, " T \nT-T\n T ").unwrap();
+        assert_eq!(match_trees(&mut board), Ok(true));
+        assert_eq!(board.debug(), "CTC\nT-T\nCTC");
+        assert_eq!(match_trees(&mut board), Ok(false));
+    }
+
+    #[test]
+    fn match_trees_chain_of_trees() {
+        // The right `Tree` only has the middle, so the left `Tree`
+        // needs the left end.
+        let mut board = Board::new_parse_clue_free(2, " T T-\n-----").unwrap();
+        assert_eq!(match_trees(&mut board), Ok(true));
+        assert_eq!(board.debug(), "CTCT-\n-----");
+    }
+
+    #[test]
+    fn match_trees_tight_camps() {
+        // The right `Camp` needs the middle `Tree`, so the left `Camp`
+        // needs the left `Tree`, leaving nothing for the left end.
+        let mut board = Board::new_parse_clue_free(2, " TCTC").unwrap();
+        assert_eq!(match_trees(&mut board), Ok(true));
+        assert_eq!(board.debug(), "-TCTC");
+    }
+
+    #[test]
+    fn match_trees_ambiguous() {
+        let mut board = Board::new_parse_clue_free(1, " T ").unwrap();
+        assert_eq!(match_trees(&mut board), Ok(false));
+        assert_eq!(board.debug(), " T ");
+    }
+
+    #[test]
+    fn match_trees_tree_without_camp() {
+        let mut board = Board::new_parse_clue_free(2, "T T").unwrap();
+        assert_eq!(
+            match_trees(&mut board),
+            Err((Pos::new(0, 2), Contradiction::TreeWithoutCamp))
+        );
+    }
+
+    #[test]
+    fn match_trees_shared_tree() {
+        let mut board = Board::new_parse_clue_free(2, "CTC\n---").unwrap();
+        assert_eq!(
+            match_trees(&mut board),
+            Err((Pos::new(0, 2), Contradiction::SharedTree))
+        );
+    }
+
+    #[test]
+    fn grass_unmatched_tight_camps() {
+        let mut board = Board::new_parse_clue_free(2, " TCTC").unwrap();
+        assert_eq!(grass_unmatched(&mut board), Ok(true));
+        assert_eq!(board.debug(), "-TCTC");
+        assert_eq!(grass_unmatched(&mut board), Ok(false));
+    }
+
+    #[test]
+    fn grass_unmatched_ambiguous() {
+        let mut board = Board::new_parse_clue_free(1, " T ").unwrap();
+        assert_eq!(grass_unmatched(&mut board), Ok(false));
+        assert_eq!(board.debug(), " T ");
+    }
+
+    #[test]
+    fn grass_unmatched_away_from_trees() {
+        let mut board = Board::new_parse_clue_free(1, "CT-\n---\n   ").unwrap();
+        assert_eq!(grass_unmatched(&mut board), Ok(true));
+        assert_eq!(board.debug(), "CT-\n---\n---");
+    }
+
+    #[test]
+    fn grass_unmatched_shared_tree() {
+        let mut board = Board::new_parse_clue_free(2, "CTC\n---").unwrap();
+        assert_eq!(
+            grass_unmatched(&mut board),
+            Err((Pos::new(0, 2), Contradiction::SharedTree))
+        );
+    }
+
+    #[test]
+    fn match_trees_lonely_camp() {
+        let mut board = Board::new_parse_clue_free(1, "C-T\n-- ").unwrap();
+        assert_eq!(
+            match_trees(&mut board),
+            Err((Pos::new(0, 0), Contradiction::LonelyCamp))
+        );
+    }
+}
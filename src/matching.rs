@@ -0,0 +1,311 @@
+/// A maximum matching of a bipartite graph.
+///
+/// The graph has `left` and `right` vertices numbered from `0`.  Every
+/// edge joins a left vertex to a right vertex.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Matching {
+    /// The right vertices next to each left vertex.
+    edges: Vec<Vec<usize>>,
+    /// The left vertices next to each right vertex, one after another.
+    /// See [`Matching::back`].
+    ///
+    /// [`Matching::back`]: struct.Matching.html#method.back
+    back: Vec<usize>,
+    /// Where the left vertices next to each right vertex start in
+    /// `back`, followed by the length of `back`.
+    back_starts: Vec<usize>,
+    /// The right vertex matched with each left vertex.
+    left: Vec<Option<usize>>,
+    /// The left vertex matched with each right vertex.
+    right: Vec<Option<usize>>,
+}
+
+impl Matching {
+    /// Find a maximum matching of the graph where left vertex `l` is
+    /// next to each right vertex in `edges[l]`.
+    ///
+    /// Every right vertex must be less than `num_right`.
+    pub fn new(edges: Vec<Vec<usize>>, num_right: usize) -> Self {
+        Matching::with_priority(edges, num_right, &[])
+    }
+
+    /// Find a maximum matching like [`Matching::new`], but match the
+    /// right vertices in `first` before any others.
+    ///
+    /// Matching more vertices never unmatches one, so the result
+    /// covers as many of `first` as any matching can.
+    ///
+    /// [`Matching::new`]: struct.Matching.html#method.new
+    pub fn with_priority(edges: Vec<Vec<usize>>, num_right: usize, first: &[usize]) -> Self {
+        let mut back_starts = vec![0; num_right + 1];
+        for &r in edges.iter().flatten() {
+            back_starts[r + 1] += 1;
+        }
+        for r in 0..num_right {
+            back_starts[r + 1] += back_starts[r];
+        }
+        let mut next = back_starts.clone();
+        let mut back = vec![0; back_starts[num_right]];
+        for (l, rights) in edges.iter().enumerate() {
+            for &r in rights {
+                back[next[r]] = l;
+                next[r] += 1;
+            }
+        }
+        let mut matching = Matching {
+            left: vec![None; edges.len()],
+            right: vec![None; num_right],
+            edges,
+            back,
+            back_starts,
+        };
+        // A search that fails leaves the matching as it was, so the
+        // vertices it saw are still dead ends for the next search.  Only
+        // a search that succeeds needs a new `stamp`.
+        let mut seen = vec![0; matching.left.len()];
+        let mut stamp = 1;
+        for &r in first {
+            if matching.augment_right(r, &mut seen, stamp) {
+                stamp += 1;
+            }
+        }
+        let mut seen = vec![0; num_right];
+        let mut stamp = 1;
+        for l in 0..matching.left.len() {
+            if matching.left[l].is_none() && matching.augment(l, &mut seen, stamp) {
+                stamp += 1;
+            }
+        }
+        matching
+    }
+
+    /// Try to match `l` by moving along an alternating path through
+    /// right vertices not yet seen, whose mark in `seen` isn't `stamp`.
+    ///
+    /// A free right vertex next to `l` is taken before trying any path.
+    ///
+    /// Returns whether `l` was matched.
+    fn augment(&mut self, l: usize, seen: &mut [usize], stamp: usize) -> bool {
+        if let Some(&r) = self.edges[l]
+            .iter()
+            .find(|&&r| seen[r] != stamp && self.right[r].is_none())
+        {
+            seen[r] = stamp;
+            self.left[l] = Some(r);
+            self.right[r] = Some(l);
+            return true;
+        }
+        for i in 0..self.edges[l].len() {
+            let r = self.edges[l][i];
+            if seen[r] == stamp {
+                continue;
+            }
+            seen[r] = stamp;
+            let free = match self.right[r] {
+                Some(other) => self.augment(other, seen, stamp),
+                None => true,
+            };
+            if free {
+                self.left[l] = Some(r);
+                self.right[r] = Some(l);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Try to match `r` by moving along an alternating path through
+    /// left vertices not yet seen, like [`Matching::augment`].
+    ///
+    /// Returns whether `r` was matched.
+    ///
+    /// [`Matching::augment`]: struct.Matching.html#method.augment
+    fn augment_right(&mut self, r: usize, seen: &mut [usize], stamp: usize) -> bool {
+        if self.right[r].is_some() {
+            return true;
+        }
+        for i in self.back_starts[r]..self.back_starts[r + 1] {
+            let l = self.back[i];
+            if seen[l] == stamp {
+                continue;
+            }
+            seen[l] = stamp;
+            let free = match self.left[l] {
+                Some(other) => {
+                    // Give `other` up for `r`, then match it again.
+                    self.right[other] = None;
+                    self.left[l] = None;
+                    let rematched = self.augment_right(other, seen, stamp);
+                    if !rematched {
+                        self.right[other] = Some(l);
+                        self.left[l] = Some(other);
+                    }
+                    rematched
+                }
+                None => true,
+            };
+            if free {
+                self.left[l] = Some(r);
+                self.right[r] = Some(l);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// The left vertices next to right vertex `r`.
+    fn back(&self, r: usize) -> &[usize] {
+        &self.back[self.back_starts[r]..self.back_starts[r + 1]]
+    }
+
+    /// The first left vertex that isn't matched.
+    ///
+    /// If there is one, no matching covers every left vertex.
+    pub fn unmatched_left(&self) -> Option<usize> {
+        self.left.iter().position(Option::is_none)
+    }
+
+    /// Whether left vertex `l` is matched.
+    pub fn is_matched_left(&self, l: usize) -> bool {
+        self.left[l].is_some()
+    }
+
+    /// The right vertex matched with left vertex `l`, if any.
+    pub fn matched_left(&self, l: usize) -> Option<usize> {
+        self.left[l]
+    }
+
+    /// Whether right vertex `r` is matched.
+    pub fn is_matched_right(&self, r: usize) -> bool {
+        self.right[r].is_some()
+    }
+
+    /// Find the right vertices that every maximum matching uses.
+    ///
+    /// A matched right vertex can be avoided if there is an
+    /// alternating path from it to an unmatched right vertex.  Every
+    /// other right vertex is forced.
+    pub fn forced_right(&self) -> Vec<bool> {
+        let mut avoidable: Vec<bool> = self.right.iter().map(Option::is_none).collect();
+        let mut queue: Vec<usize> = (0..self.right.len()).filter(|&r| avoidable[r]).collect();
+        let mut visited = vec![false; self.left.len()];
+        while let Some(r) = queue.pop() {
+            // Any left vertex next to `r` can switch to it, freeing
+            // its own match.
+            for &l in self.back(r) {
+                if visited[l] {
+                    continue;
+                }
+                visited[l] = true;
+                if let Some(matched) = self.left[l] {
+                    if !avoidable[matched] {
+                        avoidable[matched] = true;
+                        queue.push(matched);
+                    }
+                }
+            }
+        }
+        avoidable.into_iter().map(|avoidable| !avoidable).collect()
+    }
+
+    /// Find the right vertices that some matching covering every left
+    /// vertex and every `required` right vertex uses.
+    ///
+    /// This matching must already cover all of them.  An unmatched
+    /// right vertex is usable if a left vertex next to it can switch
+    /// to it, passing its old match along an alternating path until a
+    /// right vertex that isn't required is given up.
+    pub fn usable_right(&self, required: &[bool]) -> Vec<bool> {
+        // The matched right vertices that can be given up.
+        let mut spare: Vec<bool> = (0..self.right.len())
+            .map(|r| self.right[r].is_some() && !required[r])
+            .collect();
+        let mut queue: Vec<usize> = (0..self.right.len()).filter(|&r| spare[r]).collect();
+        while let Some(r) = queue.pop() {
+            // Any required right vertex next to the match of `r` can
+            // move to it, so its own match can be given up too.
+            if let Some(l) = self.right[r] {
+                for &other in &self.edges[l] {
+                    if !spare[other] && required[other] {
+                        spare[other] = true;
+                        queue.push(other);
+                    }
+                }
+            }
+        }
+        (0..self.right.len())
+            .map(|r| {
+                self.right[r].is_some()
+                    || self
+                        .back(r)
+                        .iter()
+                        .any(|&l| self.left[l].is_some_and(|matched| spare[matched]))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matching_augments() {
+        // Left 0 takes right 0 first, then moves to right 1 so left 1
+        // can have right 0.
+        let matching = Matching::new(vec![vec![0, 1], vec![0]], 2);
+        assert_eq!(matching.left, vec![Some(1), Some(0)]);
+        assert_eq!(matching.unmatched_left(), None);
+    }
+
+    #[test]
+    fn matching_unmatched_left() {
+        let matching = Matching::new(vec![vec![0], vec![0], vec![1]], 2);
+        assert_eq!(matching.unmatched_left(), Some(1));
+    }
+
+    #[test]
+    fn forced_right_tight_set() {
+        // Lefts 0 and 1 only reach rights 0 and 1, so both are forced.
+        // Left 2 can use either of rights 2 and 3.
+        let matching = Matching::new(vec![vec![0, 1], vec![1, 0], vec![2, 3]], 4);
+        assert_eq!(matching.forced_right(), vec![true, true, false, false]);
+    }
+
+    #[test]
+    fn forced_right_alternating_path() {
+        // Left 0 can move from right 0 to right 1 if left 1 moves to
+        // right 2, so nothing is forced.
+        let matching = Matching::new(vec![vec![0, 1], vec![1, 2]], 3);
+        assert_eq!(matching.forced_right(), vec![false, false, false]);
+        let matching = Matching::new(vec![vec![0, 1], vec![1]], 3);
+        assert_eq!(matching.forced_right(), vec![true, true, false]);
+    }
+
+    #[test]
+    fn with_priority_matches_first() {
+        let matching = Matching::with_priority(vec![vec![0, 1]], 2, &[1]);
+        assert_eq!(matching.left, vec![Some(1)]);
+        let matching = Matching::with_priority(vec![vec![0, 1], vec![0]], 2, &[0, 1]);
+        assert_eq!(matching.left, vec![Some(1), Some(0)]);
+    }
+
+    #[test]
+    fn usable_right_keeps_required() {
+        // Right 0 is required, so left 0 can't give it up for right 1.
+        let matching = Matching::with_priority(vec![vec![0, 1]], 2, &[0]);
+        assert_eq!(matching.usable_right(&[true, false]), vec![true, false]);
+        assert_eq!(matching.usable_right(&[false, false]), vec![true, true]);
+    }
+
+    #[test]
+    fn usable_right_alternating_path() {
+        // Left 1 can take right 2 if the required right 1 moves to left
+        // 0, giving up right 0.
+        let matching = Matching::with_priority(vec![vec![0, 1], vec![1, 2]], 3, &[1]);
+        assert_eq!(
+            matching.usable_right(&[false, true, false]),
+            vec![true, true, true]
+        );
+    }
+}
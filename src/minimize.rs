@@ -0,0 +1,211 @@
+use board::*;
+use clue::*;
+use error::*;
+use grid::*;
+use matching::*;
+use pos::*;
+use tile::Tile::*;
+
+impl Board {
+    /// Make a sparser puzzle with the same kind of clues by taking out
+    /// [`Tree`]s along with their [`Camp`]s while it stays uniquely
+    /// solvable, leaving at least `min_trees` [`Tree`]s.
+    ///
+    /// Each [`Tree`] is tried in reading order, removing it and the
+    /// [`Camp`] it is paired with in the solution.  Exact clues are
+    /// counted again from what is left of the solution, omitted and
+    /// range clues are kept, and a clue-free `Board` gets a new
+    /// `total`.  The removal is kept if the result still has exactly
+    /// one solution, and this repeats until no [`Tree`] can be
+    /// removed.  Under [`RuleSet::no_matching`], [`Tree`]s and
+    /// [`Camp`]s are removed one at a time instead.
+    ///
+    /// Fewer [`Tree`]s rarely make a puzzle ambiguous, so with a small
+    /// `min_trees` little of the puzzle is left.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{samples, Tile};
+    /// let board = samples::by_name("6x6_a5").unwrap();
+    /// let minimized = board.minimize(4).unwrap();
+    /// assert_eq!(minimized.count(Tile::Tree), 4);
+    /// assert_eq!(minimized.has_unique_solution(), Ok(true));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the `Board` doesn't have exactly one solution,
+    /// [`Error::NotUnique`] is returned.  See also
+    /// [`Board::find_solutions`].
+    ///
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`RuleSet::no_matching`]: struct.RuleSet.html#structfield.no_matching
+    /// [`Error::NotUnique`]: enum.Error.html#variant.NotUnique
+    /// [`Board::find_solutions`]: struct.Board.html#method.find_solutions
+    pub fn minimize(&self, min_trees: usize) -> Result<Board, Error> {
+        let mut solutions = self.find_solutions(2)?;
+        if solutions.len() != 1 {
+            return Err(Error::NotUnique {
+                solutions: solutions.len(),
+            });
+        }
+        let mut solution = solutions.pop().unwrap();
+        let mut puzzle = self.clone();
+        puzzle.observer = None;
+        puzzle.history.clear();
+        loop {
+            let mut removed = false;
+            for group in groups(&solution) {
+                let trees = group.iter().filter(|&&pos| puzzle[pos] == Tree).count();
+                if puzzle.count(Tree) < min_trees + trees {
+                    continue;
+                }
+                let mut grid: Grid = (*puzzle).clone();
+                let mut solved = solution.clone();
+                for &pos in &group {
+                    grid[pos] = Unassigned;
+                    solved[pos] = Grass;
+                }
+                if let Some(candidate) = puzzle.with_solution(grid, &solved) {
+                    if candidate.has_unique_solution()? {
+                        puzzle = candidate;
+                        solution = solved;
+                        removed = true;
+                    }
+                }
+            }
+            if !removed {
+                return Ok(puzzle);
+            }
+        }
+    }
+
+    /// Make a `Board` of `grid` with the clues of this `Board` fitted
+    /// to `solution`, or `None` if a range clue doesn't allow it.
+    fn with_solution(&self, grid: Grid, solution: &Grid) -> Option<Board> {
+        let camps = solution.count(Camp);
+        let mut board = if self.is_clue_free() {
+            Board::new_clue_free(camps, grid)
+        } else {
+            let fit = |clue: Clue, count: usize| match clue.exact() {
+                Some(_) => Some(Clue::exactly(count)),
+                None if clue.allows(count) => Some(clue),
+                None => None,
+            };
+            let rows = (0..self.num_rows())
+                .map(|row| fit(self.row_range(row), solution.count_in_row(row, Camp)))
+                .collect::<Option<Vec<Clue>>>()?;
+            let columns = (0..self.num_columns())
+                .map(|column| {
+                    fit(
+                        self.column_range(column),
+                        solution.count_in_column(column, Camp),
+                    )
+                })
+                .collect::<Option<Vec<Clue>>>()?;
+            Board::try_new_ranged(rows, columns, grid).ok()?
+        };
+        board.set_total(camps);
+        board.limits = self.limits.clone();
+        Some(board)
+    }
+}
+
+/// The cells to remove together from `solution`: each [`Tree`] with
+/// the [`Camp`] it is paired with, in reading order.
+///
+/// [`Tree`]: enum.Tile.html#variant.Tree
+/// [`Camp`]: enum.Tile.html#variant.Camp
+fn groups(solution: &Grid) -> Vec<Vec<Pos>> {
+    let trees: Vec<Pos> = solution.positions_of(Tree).collect();
+    if solution.rules().no_matching {
+        return trees
+            .into_iter()
+            .chain(solution.positions_of(Camp))
+            .map(|pos| vec![pos])
+            .collect();
+    }
+    let camps: Vec<Pos> = solution.positions_of(Camp).collect();
+    let edges = trees
+        .iter()
+        .map(|&tree| {
+            solution
+                .neighbors4(tree)
+                .into_iter()
+                .filter_map(|pos| camps.iter().position(|&camp| camp == pos))
+                .collect()
+        })
+        .collect();
+    let matching = Matching::new(edges, camps.len());
+    trees
+        .iter()
+        .enumerate()
+        .map(|(l, &tree)| match matching.matched_left(l) {
+            Some(r) => vec![tree, camps[r]],
+            None => vec![tree],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rule_set::*;
+    use samples;
+
+    #[test]
+    fn minimize_removes_pairs() {
+        let board = samples::by_name("6x6_a5").unwrap();
+        let minimized = board.minimize(3).unwrap();
+        assert_eq!(minimized.count(Tree), 3);
+        assert_eq!(minimized.total(), 3);
+        let sum = |clues: Vec<Option<usize>>| clues.into_iter().flatten().sum::<usize>();
+        assert_eq!(sum(minimized.row_clues()), 3);
+        assert_eq!(sum(minimized.column_clues()), 3);
+        assert_eq!(minimized.has_unique_solution(), Ok(true));
+        let trees = board.count(Tree);
+        assert_eq!(board.minimize(trees).unwrap(), board);
+    }
+
+    #[test]
+    fn minimize_keeps_omitted_clues() {
+        let board = samples::by_name("6x6_a5").unwrap();
+        let mut rows = board.row_ranges();
+        rows[0] = Clue::any();
+        let board = Board::try_new_ranged(rows, board.column_ranges(), (*board).clone()).unwrap();
+        let minimized = board.minimize(2).unwrap();
+        assert!(minimized.row_range(0).is_any());
+        assert_eq!(minimized.count(Tree), 2);
+        assert_eq!(minimized.has_unique_solution(), Ok(true));
+    }
+
+    #[test]
+    fn minimize_not_unique() {
+        let board = Board::new_parse_clue_free(1, " T ").unwrap();
+        assert_eq!(
+            board.minimize(0).err(),
+            Some(Error::NotUnique { solutions: 2 })
+        );
+        let board = Board::new_parse(vec![1, 0], vec![0, 1], " T\n  ").unwrap();
+        assert_eq!(
+            board.minimize(0).err(),
+            Some(Error::NotUnique { solutions: 0 })
+        );
+    }
+
+    #[test]
+    fn minimize_no_matching() {
+        let mut grid = Grid::parse("T  \n   \n  T").unwrap();
+        grid.set_rules(RuleSet {
+            no_matching: true,
+            ..RuleSet::default()
+        });
+        let mut board = Board::try_new(vec![0, 0, 1], vec![1, 0, 0], grid).unwrap();
+        board.set_total(1);
+        let minimized = board.minimize(0).unwrap();
+        assert_eq!(minimized.debug(), "   \n   \n   ");
+        assert_eq!(minimized.total(), 0);
+    }
+}
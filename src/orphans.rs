@@ -0,0 +1,93 @@
+use grid::*;
+use pos::*;
+use tile::Tile::{self, *};
+
+impl Grid {
+    /// Find the [`Tree`]s that can never get a [`Camp`]: every cell
+    /// beside them is [`Grass`], a [`Tree`], [`Blocked`] or off the
+    /// `Grid`.
+    ///
+    /// [`Tree`]s next to a [`Camp`] aren't included, even if that
+    /// [`Camp`] belongs to another [`Tree`].  Under
+    /// [`RuleSet::no_matching`] no [`Tree`] needs a [`Camp`], so there
+    /// are none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Grid;
+    /// let grid = Grid::parse("-T \nTT-").unwrap();
+    /// assert_eq!(grid.orphaned_trees(), vec![(1, 0), (1, 1)]);
+    /// ```
+    ///
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Grass`]: enum.Tile.html#variant.Grass
+    /// [`Blocked`]: enum.Tile.html#variant.Blocked
+    /// [`RuleSet::no_matching`]: struct.RuleSet.html#structfield.no_matching
+    pub fn orphaned_trees(&self) -> Vec<Pos> {
+        self.isolated(Tree, &[Camp, Unassigned])
+    }
+
+    /// Find the [`Camp`]s with no [`Tree`] beside them.
+    ///
+    /// Under [`RuleSet::no_matching`] [`Camp`]s don't need a [`Tree`],
+    /// so there are none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Grid;
+    /// let grid = Grid::parse("C-C\n--T").unwrap();
+    /// assert_eq!(grid.treeless_camps(), vec![(0, 0)]);
+    /// ```
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`RuleSet::no_matching`]: struct.RuleSet.html#structfield.no_matching
+    pub fn treeless_camps(&self) -> Vec<Pos> {
+        self.isolated(Camp, &[Tree])
+    }
+
+    /// Find each `tile` with none of `partners` beside it.
+    fn isolated(&self, tile: Tile, partners: &[Tile]) -> Vec<Pos> {
+        if self.rules().no_matching {
+            return Vec::new();
+        }
+        self.positions_of(tile)
+            .filter(|&pos| {
+                self.neighbors4(pos)
+                    .into_iter()
+                    .all(|x| !partners.contains(&self[x]))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rule_set::*;
+
+    #[test]
+    fn orphans_no_matching() {
+        let mut grid = Grid::parse("C-T").unwrap();
+        assert_eq!(grid.orphaned_trees(), vec![(0, 2)]);
+        assert_eq!(grid.treeless_camps(), vec![(0, 0)]);
+        grid.set_rules(RuleSet {
+            no_matching: true,
+            ..RuleSet::default()
+        });
+        assert!(grid.orphaned_trees().is_empty());
+        assert!(grid.treeless_camps().is_empty());
+    }
+
+    #[test]
+    fn orphans_wrap() {
+        let mut grid = Grid::parse("T-C").unwrap();
+        assert_eq!(grid.orphaned_trees(), vec![(0, 0)]);
+        grid.set_rules(RuleSet::torus());
+        assert!(grid.orphaned_trees().is_empty());
+        assert!(grid.treeless_camps().is_empty());
+    }
+}
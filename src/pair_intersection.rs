@@ -0,0 +1,338 @@
+use board::*;
+use clue::*;
+use contradiction::*;
+use line_masks::*;
+use pos::*;
+use tile::Tile::*;
+
+/// The most placements along one line that [`process_pair`] will
+/// pair up.
+///
+/// Pairing takes time quadratic in the number of placements, so busier
+/// lines are left for later.
+///
+/// [`process_pair`]: fn.process_pair.html
+const MAX_PAIR_PLACEMENTS: usize = 64;
+
+/// What every way of placing [`Camp`]s along two neighboring lines has
+/// in common.
+///
+/// Bit `i` of each mask is the `i`th `Tile` of a line.
+///
+/// [`Camp`]: enum.Tile.html#variant.Camp
+struct PairPlacements {
+    /// `Tile`s of the two lines that are always a [`Camp`].
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    camps: [u64; 2],
+    /// `Tile`s of the line before, the two lines, and the line after
+    /// that are never a [`Camp`] but always touch one.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    around: [u64; 4],
+    /// Whether any placement was found.
+    found: bool,
+}
+
+impl PairPlacements {
+    fn new(full: u64) -> Self {
+        PairPlacements {
+            camps: [full; 2],
+            around: [full; 4],
+            found: false,
+        }
+    }
+
+    /// Add the placement with [`Camp`]s at `first` and `second`.
+    ///
+    /// Returns whether anything is still common to every placement.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    fn add(&mut self, first: u64, second: u64, full: u64) -> bool {
+        let (first_around, second_around) = (spread(first, full), spread(second, full));
+        self.camps[0] &= first;
+        self.camps[1] &= second;
+        self.around[0] &= first_around;
+        self.around[1] &= first_around & !first | second_around;
+        self.around[2] &= second_around & !second | first_around;
+        self.around[3] &= second_around;
+        self.found = true;
+        self.camps.iter().chain(&self.around).any(|&mask| mask != 0)
+    }
+}
+
+/// Place the rest of the [`Camp`]s along lines `line` and `line + 1`
+/// together and keep what all of the placements agree on.
+///
+/// Placements of one line that fit on their own can still clash with
+/// every placement of its neighbor, or together leave a crossing line
+/// unable to meet its clue.
+///
+/// `pos`, `lines`, `length` and `clues` are as in
+/// [`LineMasks::new`], except that `lines` must also be at most
+/// [`MAX_MASK_LENGTH`] and `clues` can't be empty.  `counts` are the
+/// [`Camp`]s left to place along each of the two lines.
+///
+/// Returns whether the `Board` changed, or `None` if there is no way
+/// to place the [`Camp`]s.  Lines with more than
+/// [`MAX_PAIR_PLACEMENTS`] placements are left unchanged.
+///
+/// [`Camp`]: enum.Tile.html#variant.Camp
+/// [`LineMasks::new`]: struct.LineMasks.html#method.new
+/// [`MAX_MASK_LENGTH`]: constant.MAX_MASK_LENGTH.html
+/// [`MAX_PAIR_PLACEMENTS`]: constant.MAX_PAIR_PLACEMENTS.html
+fn process_pair<F: Fn(usize, usize) -> Pos>(
+    board: &mut Board,
+    pos: F,
+    line: usize,
+    lines: usize,
+    length: usize,
+    counts: [usize; 2],
+    clues: &[Clue],
+) -> Option<bool> {
+    debug_assert!(lines <= MAX_MASK_LENGTH && clues.len() == length);
+    let masks = [
+        LineMasks::new(board, &pos, line, lines, length, clues),
+        LineMasks::new(board, &pos, line + 1, lines, length, clues),
+    ];
+    let full = masks[0].full;
+    let mut placements = [Vec::new(), Vec::new()];
+    for ((masks, &count), placements) in masks.iter().zip(&counts).zip(&mut placements) {
+        masks.enumerate(count, &mut |chosen| {
+            placements.push(chosen);
+            placements.len() <= MAX_PAIR_PLACEMENTS
+        });
+        board.record_possibilities(placements.len());
+        if placements.is_empty() {
+            return None;
+        } else if placements.len() > MAX_PAIR_PLACEMENTS {
+            return Some(false);
+        }
+    }
+
+    // The crossing lines with clues either line could change, as
+    // `(index, clue, camps, unassigned)`.
+    let cross_full = full_mask(lines);
+    let mut touched = spread(masks[0].unassigned | masks[1].unassigned, full);
+    let mut crossing = Vec::new();
+    while touched != 0 {
+        let index = touched.trailing_zeros() as usize;
+        touched &= touched - 1;
+        let clue = clues[index];
+        if !clue.is_any() {
+            crossing.push((
+                index,
+                clue,
+                crossing_mask(board, &pos, index, lines, Camp),
+                crossing_mask(board, &pos, index, lines, Unassigned),
+            ));
+        }
+    }
+    let allowed = |chosen: [u64; 2]| {
+        crossing.iter().all(|&(index, clue, mut camps, mut open)| {
+            for (offset, (masks, &chosen)) in masks.iter().zip(&chosen).enumerate() {
+                let shared = 1 << (line + offset);
+                if chosen & 1 << index != 0 {
+                    camps |= shared;
+                }
+                if spread(chosen, full) & 1 << index != 0 {
+                    open &= !spread(shared, cross_full);
+                } else if masks.unassigned & 1 << index != 0 {
+                    open &= !shared;
+                }
+            }
+            fits(camps, open, cross_full, clue)
+        })
+    };
+
+    let mut common = PairPlacements::new(full);
+    'first: for &first in &placements[0] {
+        for &second in &placements[1] {
+            if spread(first, full) & second != 0 || !allowed([first, second]) {
+                continue;
+            }
+            if !common.add(first, second, full) {
+                break 'first;
+            }
+        }
+    }
+    if !common.found {
+        return None;
+    }
+
+    let mut changes = Vec::new();
+    for (offset, &camps) in common.camps.iter().enumerate() {
+        for index in 0..length {
+            if camps & 1 << index != 0 {
+                changes.push((pos(line + offset, index), Camp));
+            }
+        }
+    }
+    for (offset, &around) in common.around.iter().enumerate() {
+        if line + offset == 0 || line + offset > lines {
+            continue;
+        }
+        let x = line + offset - 1;
+        for index in 0..length {
+            if around & 1 << index != 0 && board[pos(x, index)] == Unassigned {
+                changes.push((pos(x, index), Grass));
+            }
+        }
+    }
+    for &(position, tile) in &changes {
+        board.set(position, tile);
+    }
+    Some(!changes.is_empty())
+}
+
+/// Process every pair of neighboring rows, then every pair of
+/// neighboring columns, placing their [`Camp`]s together.
+///
+/// This catches deductions that [`process_intersections`] misses by
+/// looking at one line at a time, such as a placement that only fails
+/// because of where its neighbor's [`Camp`]s have to go.  It only runs
+/// on `Board`s with clues whose sides are at most 64 `Tile`s long and
+/// whose `Tile`s touch as in the classic game, and skips pairs where
+/// either clue isn't a single number.
+///
+/// Return whether any values were changed.
+///
+/// # Errors
+///
+/// If a row or column already has more [`Camp`]s than its clue, or
+/// there is no way to place the rest of the [`Camp`]s of a pair of
+/// lines, the offending cell and [`Contradiction`] are returned.  The
+/// `Board` may have been partially updated.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Board, process_pair_intersections};
+/// let mut board = Board::new_parse(
+///     vec![1, 1, 1, 1],
+///     vec![2, 0, 1, 0, 1],
+///     " T  -\n - T \nT- T \nC----",
+/// ).unwrap();
+/// assert_eq!(process_pair_intersections(&mut board), Ok(true));
+/// assert_eq!(board.debug(), " T --\n - T \nT- T \nC----");
+/// ```
+///
+/// [`Camp`]: enum.Tile.html#variant.Camp
+/// [`process_intersections`]: fn.process_intersections.html
+/// [`Contradiction`]: enum.Contradiction.html
+pub fn process_pair_intersections(board: &mut Board) -> Result<bool, (Pos, Contradiction)> {
+    let (height, width) = (board.num_rows(), board.num_columns());
+    if board.is_clue_free()
+        || !board.rules().has_classic_touching()
+        || height > MAX_MASK_LENGTH
+        || width > MAX_MASK_LENGTH
+    {
+        return Ok(false);
+    }
+    let (rows, columns) = (board.row_ranges(), board.column_ranges());
+    // Row placements are checked against the column clues, so an
+    // overfilled column would otherwise look like an impossible pair of
+    // rows.
+    for (column, &clue) in columns.iter().enumerate() {
+        if clue.is_over(board.count_in_column(column, Camp)) {
+            return Err((Pos::new(0, column), Contradiction::ColumnOverfilled));
+        }
+    }
+    let mut changed = false;
+    'rows: for row in 0..height.saturating_sub(1) {
+        let mut counts = [0; 2];
+        for (offset, count) in counts.iter_mut().enumerate() {
+            let clue = match rows[row + offset].exact() {
+                Some(clue) => clue,
+                None => continue 'rows,
+            };
+            *count = match clue.checked_sub(board.count_in_row(row + offset, Camp)) {
+                Some(count) => count,
+                None => return Err((Pos::new(row + offset, 0), Contradiction::RowOverfilled)),
+            };
+        }
+        match process_pair(board, Pos::new, row, height, width, counts, &columns) {
+            Some(row_changed) => changed |= row_changed,
+            None => return Err((Pos::new(row, 0), Contradiction::RowUnderfilled)),
+        }
+    }
+    'columns: for column in 0..width.saturating_sub(1) {
+        let mut counts = [0; 2];
+        for (offset, count) in counts.iter_mut().enumerate() {
+            let clue = match columns[column + offset].exact() {
+                Some(clue) => clue,
+                None => continue 'columns,
+            };
+            let placed = board.count_in_column(column + offset, Camp);
+            *count = match clue.checked_sub(placed) {
+                Some(count) => count,
+                None => {
+                    return Err((
+                        Pos::new(0, column + offset),
+                        Contradiction::ColumnOverfilled,
+                    ))
+                }
+            };
+        }
+        let pos = |column, row| Pos::new(row, column);
+        match process_pair(board, pos, column, width, height, counts, &rows) {
+            Some(column_changed) => changed |= column_changed,
+            None => return Err((Pos::new(0, column), Contradiction::ColumnUnderfilled)),
+        }
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use intersection::*;
+
+    #[test]
+    fn process_pair_intersections_after_intersections() {
+        let mut board = Board::new_parse(
+            vec![1, 1, 1, 1],
+            vec![2, 0, 1, 0, 1],
+            " T  -\n - T \nT- T \nC----",
+        ).unwrap();
+        assert_eq!(process_intersections(&mut board), Ok(false));
+        assert_eq!(process_pair_intersections(&mut board), Ok(true));
+        assert_eq!(board.debug(), " T --\n - T \nT- T \nC----");
+    }
+
+    #[test]
+    fn process_pair_intersections_columns() {
+        let mut board = Board::new_parse(
+            vec![2, 0, 1, 0, 1],
+            vec![1, 1, 1, 1],
+            "  TC\nT---\n   -\n TT-\n-  -",
+        ).unwrap();
+        assert_eq!(process_pair_intersections(&mut board), Ok(true));
+        assert_eq!(board.debug(), "  TC\nT---\n   -\n-TT-\n-  -");
+    }
+
+    #[test]
+    fn process_pair_intersections_no_placement() {
+        let mut board = Board::new_parse(vec![1, 1], vec![1, 1, 0], "T T\n   ").unwrap();
+        assert_eq!(
+            process_pair_intersections(&mut board),
+            Err((Pos::new(0, 0), Contradiction::RowUnderfilled))
+        );
+    }
+
+    #[test]
+    fn process_pair_intersections_column_overfilled() {
+        let mut board = Board::new_parse(vec![1, 0], vec![0, 1], "CT\n  ").unwrap();
+        assert_eq!(
+            process_pair_intersections(&mut board),
+            Err((Pos::new(0, 0), Contradiction::ColumnOverfilled))
+        );
+    }
+
+    #[test]
+    fn process_pair_intersections_clue_free() {
+        let mut board = Board::new_parse_clue_free(1, "T T\n   ").unwrap();
+        assert_eq!(process_pair_intersections(&mut board), Ok(false));
+        assert_eq!(board.debug(), "T T\n   ");
+    }
+}
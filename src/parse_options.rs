@@ -0,0 +1,26 @@
+use tile_charset::*;
+
+/// Options controlling how [`Grid::parse_with_options`] reads text.
+///
+/// By default, `\r` characters are ignored so files with Windows line
+/// endings parse, and a single trailing newline doesn't start an empty
+/// row.
+///
+/// [`Grid::parse_with_options`]: struct.Grid.html#method.parse_with_options
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// The characters for each [`Tile`].
+    ///
+    /// [`Tile`]: enum.Tile.html
+    pub charset: TileCharset,
+    /// Pad rows shorter than the longest row with [`Unassigned`]
+    /// [`Tile`]s instead of rejecting them.
+    ///
+    /// [`Tile`]: enum.Tile.html
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    pub pad_rows: bool,
+    /// Treat `\r`, a trailing newline, and short rows as errors.
+    ///
+    /// This overrides `pad_rows`.
+    pub strict: bool,
+}
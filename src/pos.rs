@@ -0,0 +1,168 @@
+use std::cmp::Ordering;
+
+/// The position of a [`Tile`] on a [`Grid`].
+///
+/// Positions are ordered in reading order: by `row`, then by
+/// `column`.  They convert to and from `(row, column)` tuples and
+/// compare equal to them.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::Pos;
+/// let pos = Pos::new(1, 2);
+/// assert_eq!(pos, (1, 2));
+/// assert_eq!(Pos::from((1, 2)), pos);
+/// assert_eq!(pos.offset(-1, 1, 3, 4), Some(Pos::new(0, 3)));
+/// assert_eq!(pos.offset(-2, 0, 3, 4), None);
+/// ```
+///
+/// [`Tile`]: enum.Tile.html
+/// [`Grid`]: struct.Grid.html
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pos {
+    pub row: usize,
+    pub column: usize,
+}
+
+impl Pos {
+    /// Create a new `Pos`.
+    pub fn new(row: usize, column: usize) -> Self {
+        Pos { row, column }
+    }
+
+    /// Move by `rows` and `columns`, staying inside a [`Grid`] with
+    /// `num_rows` rows and `num_columns` columns.
+    ///
+    /// Returns `None` if the result is outside the [`Grid`].
+    ///
+    /// [`Grid`]: struct.Grid.html
+    pub fn offset(
+        self,
+        rows: isize,
+        columns: isize,
+        num_rows: usize,
+        num_columns: usize,
+    ) -> Option<Pos> {
+        let row = offset(self.row, rows, num_rows)?;
+        let column = offset(self.column, columns, num_columns)?;
+        Some(Pos { row, column })
+    }
+
+    /// Get the positions directly above, left, right, and below, in
+    /// that order, that are inside a [`Grid`] with `num_rows` rows and
+    /// `num_columns` columns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Pos;
+    /// assert_eq!(Pos::new(0, 1).neighbors4(3, 3), vec![(0, 0), (0, 2), (1, 1)]);
+    /// ```
+    ///
+    /// [`Grid`]: struct.Grid.html
+    pub fn neighbors4(self, num_rows: usize, num_columns: usize) -> Vec<Pos> {
+        [(-1, 0), (0, -1), (0, 1), (1, 0)]
+            .iter()
+            .filter_map(|&(r, c)| self.offset(r, c, num_rows, num_columns))
+            .collect()
+    }
+
+    /// Get the positions touching this one, including diagonally, in
+    /// reading order, that are inside a [`Grid`] with `num_rows` rows
+    /// and `num_columns` columns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Pos;
+    /// assert_eq!(
+    ///     Pos::new(0, 1).neighbors8(3, 3),
+    ///     vec![(0, 0), (0, 2), (1, 0), (1, 1), (1, 2)]
+    /// );
+    /// ```
+    ///
+    /// [`Grid`]: struct.Grid.html
+    pub fn neighbors8(self, num_rows: usize, num_columns: usize) -> Vec<Pos> {
+        let mut neighbors = Vec::new();
+        for r in -1..=1 {
+            for c in -1..=1 {
+                if r != 0 || c != 0 {
+                    neighbors.extend(self.offset(r, c, num_rows, num_columns));
+                }
+            }
+        }
+        neighbors
+    }
+}
+
+/// Add `delta` to `x`, returning `None` unless the result is in
+/// `0..len`.
+fn offset(x: usize, delta: isize, len: usize) -> Option<usize> {
+    let x = if delta < 0 {
+        x.checked_sub(delta.unsigned_abs())?
+    } else {
+        x.checked_add(delta as usize)?
+    };
+    if x < len {
+        Some(x)
+    } else {
+        None
+    }
+}
+
+impl From<(usize, usize)> for Pos {
+    fn from((row, column): (usize, usize)) -> Self {
+        Pos { row, column }
+    }
+}
+
+impl From<Pos> for (usize, usize) {
+    fn from(pos: Pos) -> Self {
+        (pos.row, pos.column)
+    }
+}
+
+impl PartialEq<(usize, usize)> for Pos {
+    fn eq(&self, other: &(usize, usize)) -> bool {
+        (self.row, self.column) == *other
+    }
+}
+
+impl PartialOrd<(usize, usize)> for Pos {
+    fn partial_cmp(&self, other: &(usize, usize)) -> Option<Ordering> {
+        (self.row, self.column).partial_cmp(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_bounds() {
+        let pos = Pos::new(1, 1);
+        assert_eq!(pos.offset(1, 1, 3, 3), Some(Pos::new(2, 2)));
+        assert_eq!(pos.offset(2, 0, 3, 3), None);
+        assert_eq!(pos.offset(0, -2, 3, 3), None);
+        assert_eq!(Pos::new(0, 0).offset(0, 0, 0, 0), None);
+    }
+
+    #[test]
+    fn neighbors() {
+        assert_eq!(Pos::new(0, 0).neighbors4(3, 3), vec![(0, 1), (1, 0)]);
+        assert_eq!(Pos::new(1, 1).neighbors4(3, 3).len(), 4);
+        assert_eq!(
+            Pos::new(0, 0).neighbors8(3, 3),
+            vec![(0, 1), (1, 0), (1, 1)]
+        );
+        assert_eq!(Pos::new(1, 1).neighbors8(3, 3).len(), 8);
+        assert_eq!(Pos::new(0, 0).neighbors8(1, 1), Vec::<Pos>::new());
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(Pos::new(0, 5) < Pos::new(1, 0));
+        assert!(Pos::new(1, 0) < Pos::new(1, 1));
+    }
+}
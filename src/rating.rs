@@ -0,0 +1,178 @@
+use board::*;
+use solve_outcome::*;
+use solver_config::*;
+use std::time::{Duration, Instant};
+use strategy::*;
+
+/// How hard a `Board` is to solve, from [`Board::rate`].
+///
+/// [`Board::rate`]: struct.Board.html#method.rate
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rating {
+    /// How solving went.
+    pub outcome: SolveOutcome,
+    /// The sum of the [`Strategy::difficulty`] of each technique in
+    /// `techniques`.
+    ///
+    /// [`Strategy::difficulty`]: trait.Strategy.html#method.difficulty
+    pub difficulty: usize,
+    /// The [`Strategy::name`] of each strategy that deduced at least
+    /// one `Tile`, in the order the solver tries them.
+    ///
+    /// [`Strategy::name`]: trait.Strategy.html#tymethod.name
+    pub techniques: Vec<&'static str>,
+    /// Whether a strategy that guesses was needed.  See
+    /// [`Strategy::guesses`].
+    ///
+    /// [`Strategy::guesses`]: trait.Strategy.html#method.guesses
+    pub search: bool,
+    /// The wall time spent solving.
+    pub time: Duration,
+}
+
+impl Rating {
+    /// The settings [`Board::rate`] solves with: the
+    /// [`Profile::HumanStyle`] techniques, with trials two deep behind
+    /// them.
+    ///
+    /// [`Board::rate`]: struct.Board.html#method.rate
+    /// [`Profile::HumanStyle`]: enum.Profile.html#variant.HumanStyle
+    pub fn default_config() -> SolverConfig {
+        SolverConfig {
+            profile: Profile::HumanStyle,
+            trial_depth: 2,
+            ..Default::default()
+        }
+    }
+}
+
+impl Board {
+    /// Rate how hard the `Board` is by solving a copy of it with
+    /// [`Rating::default_config`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+    /// let rating = board.rate();
+    /// assert!(rating.outcome.is_solved());
+    /// assert_eq!(rating.techniques, vec!["fill_zeros", "fill_camps"]);
+    /// assert_eq!(rating.difficulty, 2);
+    /// assert!(!rating.search);
+    /// ```
+    ///
+    /// [`Rating::default_config`]: struct.Rating.html#method.default_config
+    pub fn rate(&self) -> Rating {
+        self.rate_with(&Rating::default_config())
+    }
+
+    /// Rate how hard the `Board` is by solving a copy of it with
+    /// `config`.
+    ///
+    /// [`SolverConfig::collect_stats`] is always turned on.
+    ///
+    /// [`SolverConfig::collect_stats`]: struct.SolverConfig.html#structfield.collect_stats
+    pub fn rate_with(&self, config: &SolverConfig) -> Rating {
+        let mut board = self.clone();
+        board.observer = None;
+        board.solve_rated(config)
+    }
+
+    /// Solve the `Board` in place with `config` like
+    /// [`Board::solve_outcome_with`], rating how hard it was like
+    /// [`Board::rate_with`].
+    ///
+    /// [`Board::solve_outcome_with`]: struct.Board.html#method.solve_outcome_with
+    /// [`Board::rate_with`]: struct.Board.html#method.rate_with
+    pub fn solve_rated(&mut self, config: &SolverConfig) -> Rating {
+        let config = SolverConfig {
+            collect_stats: true,
+            ..*config
+        };
+        let start = Instant::now();
+        let outcome = self.solve_outcome_with(&config);
+        let time = start.elapsed();
+        let strategies = default_strategies(&config);
+        let used: Vec<&Box<dyn Strategy>> = match self.stats() {
+            Some(stats) => strategies
+                .iter()
+                .zip(&stats.strategies)
+                .filter(|&(_, stats)| stats.tiles_changed > 0)
+                .map(|(strategy, _)| strategy)
+                .collect(),
+            None => Vec::new(),
+        };
+        Rating {
+            outcome,
+            difficulty: used.iter().map(|x| x.difficulty()).sum(),
+            techniques: used.iter().map(|x| x.name()).collect(),
+            search: used.iter().any(|x| x.guesses()),
+            time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tile::Tile::*;
+
+    #[test]
+    fn rate_needs_intersections() {
+        let board = Board::new_parse(
+            vec![1, 1, 0, 2, 1],
+            vec![2, 0, 1, 1, 1],
+            "     \n T T \n     \nTT   \n    T",
+        )
+        .unwrap();
+        let rating = board.rate();
+        assert!(rating.outcome.is_solved());
+        assert!(!rating.search);
+        assert_eq!(
+            rating.difficulty,
+            rating
+                .techniques
+                .iter()
+                .map(|&name| default_strategies(&Rating::default_config())
+                    .into_iter()
+                    .find(|x| x.name() == name)
+                    .unwrap()
+                    .difficulty())
+                .sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn rate_with_trials() {
+        let board = Board::new_parse(
+            vec![2, 0, 2, 0, 2],
+            vec![1, 2, 0, 3, 0],
+            "  T  \nT  T \n    T\n T   \n  T  ",
+        )
+        .unwrap();
+        let rating = board.rate();
+        assert!(rating.outcome.is_solved());
+        assert!(rating.search);
+        assert_eq!(
+            rating.techniques,
+            vec!["fill_zeros", "fill_camps", "trials"]
+        );
+        assert_eq!(rating.difficulty, 22);
+        // The `Board` itself isn't solved.
+        assert_eq!(board.count(Unassigned), 19);
+        let mut solved = board.clone();
+        assert_eq!(
+            solved.solve_rated(&Rating::default_config()).outcome,
+            rating.outcome
+        );
+        assert_eq!(solved.count(Unassigned), 0);
+
+        let rating = board.rate_with(&SolverConfig {
+            profile: Profile::HumanStyle,
+            ..Default::default()
+        });
+        assert!(!rating.outcome.is_solved());
+        assert!(!rating.search);
+    }
+}
@@ -0,0 +1,262 @@
+use board::*;
+use clue::*;
+use error::*;
+use std::mem;
+
+/// The row and column clues of a `Board` that could each be left out
+/// without making the solution ambiguous.
+///
+/// See [`Board::redundant_clues`].
+///
+/// [`Board::redundant_clues`]: struct.Board.html#method.redundant_clues
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RedundantClues {
+    /// The rows whose clues are redundant, in order.
+    pub rows: Vec<usize>,
+    /// The columns whose clues are redundant, in order.
+    pub columns: Vec<usize>,
+}
+
+impl RedundantClues {
+    /// Get the number of redundant clues.
+    pub fn len(&self) -> usize {
+        self.rows.len() + self.columns.len()
+    }
+
+    /// Is no clue redundant?
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty() && self.columns.is_empty()
+    }
+}
+
+impl Board {
+    /// Find the row and column clues that could each be replaced by
+    /// [`Clue::any`] while the `Board` still has exactly one solution.
+    ///
+    /// Every clue is tried on its own, keeping the others and the
+    /// `total`, so leaving out two redundant clues together may still
+    /// make the puzzle ambiguous.  Clues that are already omitted
+    /// aren't listed, so a clue-free `Board` has none.  Use
+    /// [`Board::minimize_clues`] to leave out as many as possible at
+    /// once.
+    ///
+    /// # Examples
+    ///
+    /// The [`Grass`] leaves the `Tree` only one cell for its `Camp`, so
+    /// no clue is needed:
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, RedundantClues};
+    /// let board = Board::new_parse(vec![0, 1], vec![1, 0], "T-\n  ").unwrap();
+    /// assert_eq!(
+    ///     board.redundant_clues(),
+    ///     Ok(RedundantClues {
+    ///         rows: vec![0, 1],
+    ///         columns: vec![0, 1],
+    ///     })
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the `Board` doesn't have exactly one solution,
+    /// [`Error::NotUnique`] is returned.  See also
+    /// [`Board::find_solutions`].
+    ///
+    /// [`Clue::any`]: struct.Clue.html#method.any
+    /// [`Board::minimize_clues`]: struct.Board.html#method.minimize_clues
+    /// [`Grass`]: enum.Tile.html#variant.Grass
+    /// [`Error::NotUnique`]: enum.Error.html#variant.NotUnique
+    /// [`Board::find_solutions`]: struct.Board.html#method.find_solutions
+    pub fn redundant_clues(&self) -> Result<RedundantClues, Error> {
+        let solutions = self.count_solutions(2)?;
+        if solutions != 1 {
+            return Err(Error::NotUnique { solutions });
+        }
+        let mut redundant = RedundantClues::default();
+        if self.is_clue_free() {
+            return Ok(redundant);
+        }
+        let (rows, columns) = (self.row_ranges(), self.column_ranges());
+        for row in 0..rows.len() {
+            if !rows[row].is_any() {
+                let mut rows = rows.clone();
+                rows[row] = Clue::any();
+                if self.is_unique_with(rows, columns.clone())? {
+                    redundant.rows.push(row);
+                }
+            }
+        }
+        for column in 0..columns.len() {
+            if !columns[column].is_any() {
+                let mut columns = columns.clone();
+                columns[column] = Clue::any();
+                if self.is_unique_with(rows.clone(), columns)? {
+                    redundant.columns.push(column);
+                }
+            }
+        }
+        Ok(redundant)
+    }
+
+    /// Make a sparser puzzle by replacing row and column clues with
+    /// [`Clue::any`] while it stays uniquely solvable.
+    ///
+    /// The row clues are tried in order, then the column clues.  Each
+    /// removal is kept if the `Board`, without every clue removed so
+    /// far, still has exactly one solution.  Unlike
+    /// [`Board::redundant_clues`], no clue of the result can be left
+    /// out on top of the others.  The `total` and the [`Grid`] are
+    /// kept, and a clue-free `Board` is returned as it is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, Clue};
+    /// let board = Board::new_parse(vec![1], vec![1, 0, 0], " T ").unwrap();
+    /// let minimized = board.minimize_clues().unwrap();
+    /// assert_eq!(minimized.row_ranges(), vec![Clue::any()]);
+    /// assert_eq!(
+    ///     minimized.column_ranges(),
+    ///     vec![Clue::any(), Clue::any(), Clue::exactly(0)]
+    /// );
+    /// assert_eq!(minimized.has_unique_solution(), Ok(true));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the `Board` doesn't have exactly one solution,
+    /// [`Error::NotUnique`] is returned.
+    ///
+    /// [`Clue::any`]: struct.Clue.html#method.any
+    /// [`Board::redundant_clues`]: struct.Board.html#method.redundant_clues
+    /// [`Grid`]: struct.Grid.html
+    /// [`Error::NotUnique`]: enum.Error.html#variant.NotUnique
+    pub fn minimize_clues(&self) -> Result<Board, Error> {
+        let solutions = self.count_solutions(2)?;
+        if solutions != 1 {
+            return Err(Error::NotUnique { solutions });
+        }
+        if self.is_clue_free() {
+            let mut board = self.clone();
+            board.observer = None;
+            board.history.clear();
+            return Ok(board);
+        }
+        let (mut rows, mut columns) = (self.row_ranges(), self.column_ranges());
+        for row in 0..rows.len() {
+            if !rows[row].is_any() {
+                let clue = mem::replace(&mut rows[row], Clue::any());
+                if !self.is_unique_with(rows.clone(), columns.clone())? {
+                    rows[row] = clue;
+                }
+            }
+        }
+        for column in 0..columns.len() {
+            if !columns[column].is_any() {
+                let clue = mem::replace(&mut columns[column], Clue::any());
+                if !self.is_unique_with(rows.clone(), columns.clone())? {
+                    columns[column] = clue;
+                }
+            }
+        }
+        self.with_clues(rows, columns)
+    }
+
+    /// Is the `Board` with `rows` and `columns` as its clues uniquely
+    /// solvable?
+    fn is_unique_with(&self, rows: Vec<Clue>, columns: Vec<Clue>) -> Result<bool, Error> {
+        self.with_clues(rows, columns)?.has_unique_solution()
+    }
+
+    /// A copy of the `Board` with `rows` and `columns` as its clues,
+    /// keeping the `total`.
+    fn with_clues(&self, rows: Vec<Clue>, columns: Vec<Clue>) -> Result<Board, Error> {
+        let mut board = Board::try_new_ranged(rows, columns, (**self).clone())?;
+        board.set_total(self.total());
+        board.limits = self.limits.clone();
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use samples;
+
+    #[test]
+    fn redundant_clues_each_unique() {
+        let board = samples::by_name("6x6_a5").unwrap();
+        let redundant = board.redundant_clues().unwrap();
+        assert!(!redundant.is_empty());
+        for &row in &redundant.rows {
+            let mut rows = board.row_ranges();
+            rows[row] = Clue::any();
+            let mut partial =
+                Board::try_new_ranged(rows, board.column_ranges(), (*board).clone()).unwrap();
+            partial.set_total(board.total());
+            assert_eq!(partial.has_unique_solution(), Ok(true));
+        }
+    }
+
+    #[test]
+    fn redundant_clues_needed() {
+        let board = Board::new_parse(vec![1], vec![1, 0, 0], " T ").unwrap();
+        assert_eq!(
+            board.redundant_clues(),
+            Ok(RedundantClues {
+                rows: vec![0],
+                columns: vec![0, 1, 2],
+            })
+        );
+        // Without its clue the `Camp` could go on either side.
+        let rows = vec![Clue::any()];
+        let columns = vec![Clue::exactly(1), Clue::any(), Clue::any()];
+        let board = Board::new_parse_ranged(rows, columns, " T ").unwrap();
+        assert_eq!(board.redundant_clues(), Ok(RedundantClues::default()));
+        let board = Board::new_parse_clue_free(1, " T").unwrap();
+        assert_eq!(board.redundant_clues(), Ok(RedundantClues::default()));
+        let board = Board::new_parse_clue_free(1, " T ").unwrap();
+        assert_eq!(
+            board.redundant_clues(),
+            Err(Error::NotUnique { solutions: 2 })
+        );
+    }
+
+    #[test]
+    fn minimize_clues_sample() {
+        let board = samples::by_name("6x6_a5").unwrap();
+        let minimized = board.minimize_clues().unwrap();
+        assert_eq!(minimized.has_unique_solution(), Ok(true));
+        assert_eq!(*minimized, *board);
+        assert_eq!(minimized.total(), board.total());
+        let omitted = |board: &Board| {
+            let ranges = board.row_ranges().into_iter().chain(board.column_ranges());
+            ranges.filter(|clue| clue.is_any()).count()
+        };
+        assert!(omitted(&minimized) >= 2);
+        // No clue that is left can be removed on top of the others.
+        assert_eq!(minimized.redundant_clues(), Ok(RedundantClues::default()));
+    }
+
+    #[test]
+    fn minimize_clues_edge_cases() {
+        let board = Board::new_parse_clue_free(1, " T").unwrap();
+        assert_eq!(board.minimize_clues(), Ok(board.clone()));
+        let board = Board::new_parse_clue_free(1, " T ").unwrap();
+        assert_eq!(
+            board.minimize_clues(),
+            Err(Error::NotUnique { solutions: 2 })
+        );
+        // Each clue can be left out on its own, but not all of them.
+        let board = Board::new_parse(vec![1], vec![1, 0, 0], " T ").unwrap();
+        assert_eq!(board.redundant_clues().map(|x| x.len()), Ok(4));
+        let minimized = board.minimize_clues().unwrap();
+        assert_eq!(minimized.row_ranges(), vec![Clue::any()]);
+        assert_eq!(
+            minimized.column_ranges(),
+            vec![Clue::any(), Clue::any(), Clue::exactly(0)]
+        );
+        assert_eq!(minimized.total(), 1);
+    }
+}
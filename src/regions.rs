@@ -0,0 +1,146 @@
+use grid::*;
+use std::ops::Range;
+use tile::Tile::{self, *};
+
+impl Grid {
+    /// Split the `Grid` into rectangles that can be solved on their
+    /// own, as `(rows, columns)`.
+    ///
+    /// A row or column of only [`Grass`] and [`Blocked`] `Tile`s
+    /// separates the parts on either side of it, since no [`Camp`] can
+    /// touch or share a [`Tree`] across it.  It is only used if no
+    /// line crossing it has [`Unassigned`] `Tile`s on both sides, so
+    /// each clue is settled by a single part.  The parts are split
+    /// again the same way, and those with no [`Unassigned`] `Tile`s
+    /// are left out.  A `Grid` that wraps around is never split.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Grid;
+    /// let grid = Grid::parse(" T---\n---T \n T---").unwrap();
+    /// assert_eq!(
+    ///     grid.independent_regions(),
+    ///     vec![(0..3, 0..2), (1..2, 3..5)]
+    /// );
+    /// ```
+    ///
+    /// [`Grass`]: enum.Tile.html#variant.Grass
+    /// [`Blocked`]: enum.Tile.html#variant.Blocked
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    pub fn independent_regions(&self) -> Vec<(Range<usize>, Range<usize>)> {
+        let whole = (0..self.num_rows(), 0..self.num_columns());
+        if self.rules().wrap {
+            return if self.count(Unassigned) > 0 {
+                vec![whole]
+            } else {
+                Vec::new()
+            };
+        }
+        let mut regions = Vec::new();
+        self.split_regions(whole.0, whole.1, &mut regions);
+        regions
+    }
+
+    /// Split the rectangle `rows` by `columns` like
+    /// [`Grid::independent_regions`], adding the parts to `regions`.
+    ///
+    /// [`Grid::independent_regions`]: struct.Grid.html#method.independent_regions
+    fn split_regions(
+        &self,
+        rows: Range<usize>,
+        columns: Range<usize>,
+        regions: &mut Vec<(Range<usize>, Range<usize>)>,
+    ) {
+        let unassigned = |row: usize, column: usize| self[(row, column)] == Unassigned;
+        if !rows
+            .clone()
+            .any(|row| columns.clone().any(|column| unassigned(row, column)))
+        {
+            return;
+        }
+        let row = separator(rows.clone(), columns.clone(), |row, column| {
+            self[(row, column)]
+        });
+        if let Some(row) = row {
+            self.split_regions(rows.start..row, columns.clone(), regions);
+            self.split_regions(row + 1..rows.end, columns, regions);
+            return;
+        }
+        let column = separator(columns.clone(), rows.clone(), |column, row| {
+            self[(row, column)]
+        });
+        if let Some(column) = column {
+            self.split_regions(rows.clone(), columns.start..column, regions);
+            self.split_regions(rows, column + 1..columns.end, regions);
+            return;
+        }
+        regions.push((rows, columns));
+    }
+}
+
+/// Find the first line in `lines` that separates the rectangle of
+/// `lines` by `across`, where `tile(line, i)` is the `Tile` at `i`
+/// along `line`.
+///
+/// The line has to be all [`Grass`] or [`Blocked`], and none of the
+/// lines in `across` may have [`Unassigned`] `Tile`s on both sides of
+/// it.
+///
+/// [`Grass`]: enum.Tile.html#variant.Grass
+/// [`Blocked`]: enum.Tile.html#variant.Blocked
+/// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+fn separator<F: Fn(usize, usize) -> Tile>(
+    lines: Range<usize>,
+    across: Range<usize>,
+    tile: F,
+) -> Option<usize> {
+    let spans: Vec<(usize, usize)> = across
+        .clone()
+        .filter_map(|i| {
+            let mut found = lines.clone().filter(|&line| tile(line, i) == Unassigned);
+            let first = found.next()?;
+            Some((first, found.last().unwrap_or(first)))
+        })
+        .collect();
+    lines.clone().find(|&line| {
+        across
+            .clone()
+            .all(|i| tile(line, i) == Grass || tile(line, i) == Blocked)
+            && spans
+                .iter()
+                .all(|&(first, last)| last < line || first > line)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rule_set::*;
+
+    #[test]
+    fn independent_regions_shared_line() {
+        // The middle column has cells on both sides of the grass row.
+        let grid = Grid::parse(" T \n---\n T ").unwrap();
+        assert_eq!(grid.independent_regions(), vec![(0..3, 0..3)]);
+        let grid = Grid::parse("T  \n---\n  T").unwrap();
+        assert_eq!(grid.independent_regions(), vec![(0..3, 0..3)]);
+        let grid = Grid::parse("T- \n---\n -T").unwrap();
+        assert_eq!(grid.independent_regions(), vec![(0..1, 2..3), (2..3, 0..1)]);
+    }
+
+    #[test]
+    fn independent_regions_solved() {
+        let grid = Grid::parse("CT\n--").unwrap();
+        assert!(grid.independent_regions().is_empty());
+    }
+
+    #[test]
+    fn independent_regions_wrap() {
+        let mut grid = Grid::parse("T- \n---\n -T").unwrap();
+        grid.set_rules(RuleSet::torus());
+        assert_eq!(grid.independent_regions(), vec![(0..3, 0..3)]);
+    }
+}
@@ -0,0 +1,262 @@
+use super::*;
+use board::*;
+use clue::*;
+use grid::*;
+use std::fmt::Write;
+use tile::Tile::{self, *};
+
+/// Render the `Board` as a standalone HTML page the puzzle can be
+/// played on.
+///
+/// The `Board` is drawn as a table with the clues across the top and
+/// down the left side.  Clicking a cell that isn't a [`Tree`] or
+/// [`Blocked`] cycles it through empty, [`Camp`] and [`Grass`].  If
+/// `solution` is given, a button toggles between the player's cells and
+/// the solution, which is otherwise hidden.  The page needs no server and no files beside
+/// it, so it can be published as is.
+///
+/// If `options.labels` is set, the column and row labels are drawn
+/// outside the clues.  Cells are `options.cell_size` pixels wide and
+/// drawn in `options.colors`.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Board, render::{html, RenderOptions}};
+/// let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+/// let mut solved = board.clone();
+/// solved.solve().unwrap();
+/// let page = html(&board, Some(&solved), &RenderOptions::default());
+/// assert!(page.starts_with("<!DOCTYPE html>"));
+/// assert!(page.contains("data-solution=\"camp\""));
+/// ```
+///
+/// # Panics
+///
+/// This will `panic` if `solution` is a different size than the
+/// `Board`.
+///
+/// [`Tree`]: ../enum.Tile.html#variant.Tree
+/// [`Blocked`]: ../enum.Tile.html#variant.Blocked
+/// [`Camp`]: ../enum.Tile.html#variant.Camp
+/// [`Grass`]: ../enum.Tile.html#variant.Grass
+pub fn html(board: &Board, solution: Option<&Grid>, options: &RenderOptions) -> String {
+    if let Some(solution) = solution {
+        assert!(
+            solution.num_rows() == board.num_rows()
+                && solution.num_columns() == board.num_columns(),
+            "the solution doesn't fit the board"
+        );
+    }
+    let colors = &options.colors;
+    let mut s = String::new();
+    writeln!(s, "<!DOCTYPE html>").unwrap();
+    writeln!(s, "<html>").unwrap();
+    writeln!(s, "<head>").unwrap();
+    writeln!(s, "<meta charset=\"utf-8\">").unwrap();
+    writeln!(s, "<title>Camps and Trees</title>").unwrap();
+    writeln!(s, "<style>").unwrap();
+    writeln!(
+        s,
+        "body {{ background: {}; color: {}; font-family: sans-serif; }}",
+        colors.background.hex(),
+        colors.text.hex()
+    )
+    .unwrap();
+    writeln!(s, "table {{ border-collapse: collapse; }}").unwrap();
+    writeln!(
+        s,
+        "th, td {{ width: {0}px; height: {0}px; padding: 0; text-align: center; \
+         font-size: {1}px; }}",
+        options.cell_size,
+        options.cell_size / 2
+    )
+    .unwrap();
+    writeln!(s, "th {{ font-weight: normal; }}").unwrap();
+    writeln!(s, "td {{ border: 1px solid {}; }}", colors.lines.hex()).unwrap();
+    writeln!(s, "td.open {{ cursor: pointer; }}").unwrap();
+    writeln!(
+        s,
+        "td[data-tile=grass] {{ background: {}; }}",
+        colors.grass.hex()
+    )
+    .unwrap();
+    writeln!(
+        s,
+        "td[data-tile=camp]::after {{ content: \"\\25B2\"; color: {}; }}",
+        colors.camp.hex()
+    )
+    .unwrap();
+    writeln!(
+        s,
+        "td.tree::after {{ content: \"\\2663\"; color: {}; }}",
+        colors.tree.hex()
+    )
+    .unwrap();
+    writeln!(s, "td.blocked {{ background: {}; }}", colors.blocked.hex()).unwrap();
+    writeln!(s, "</style>").unwrap();
+    writeln!(s, "</head>").unwrap();
+    writeln!(s, "<body>").unwrap();
+    writeln!(s, "<table id=\"puzzle\">").unwrap();
+
+    // The header cells before the Grid on each row, and above it.
+    let clues = !board.is_clue_free();
+    let corner = clues as usize + options.labels as usize;
+    let header = |s: &mut String, text: &str| {
+        writeln!(s, "<th>{}</th>", text).unwrap();
+    };
+    let escape = |clue: Clue| {
+        if clue.is_any() {
+            String::new()
+        } else {
+            clue.to_string().replace('<', "&lt;").replace('>', "&gt;")
+        }
+    };
+    if options.labels {
+        writeln!(s, "<tr>").unwrap();
+        for _ in 0..corner {
+            header(&mut s, "");
+        }
+        for column in 0..board.num_columns() {
+            header(&mut s, &column_name(column));
+        }
+        writeln!(s, "</tr>").unwrap();
+    }
+    if clues {
+        writeln!(s, "<tr>").unwrap();
+        for _ in 0..corner {
+            header(&mut s, "");
+        }
+        for clue in board.column_ranges() {
+            header(&mut s, &escape(clue));
+        }
+        writeln!(s, "</tr>").unwrap();
+    }
+    let row_ranges = board.row_ranges();
+    for row in 0..board.num_rows() {
+        writeln!(s, "<tr>").unwrap();
+        if options.labels {
+            header(&mut s, &row_name(row));
+        }
+        if clues {
+            header(&mut s, &escape(row_ranges[row]));
+        }
+        for column in 0..board.num_columns() {
+            let solved = solution.map(|x| x[(row, column)]);
+            match board[(row, column)] {
+                Tree => writeln!(s, "<td class=\"tree\"></td>").unwrap(),
+                Blocked => writeln!(s, "<td class=\"blocked\"></td>").unwrap(),
+                tile => {
+                    let solved = match solved {
+                        Some(solved) => format!(" data-solution=\"{}\"", tile_name(solved)),
+                        None => String::new(),
+                    };
+                    writeln!(
+                        s,
+                        "<td class=\"open\" data-tile=\"{}\"{}></td>",
+                        tile_name(tile),
+                        solved
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        writeln!(s, "</tr>").unwrap();
+    }
+    writeln!(s, "</table>").unwrap();
+    if solution.is_some() {
+        writeln!(s, "<p><button id=\"toggle\">Show solution</button></p>").unwrap();
+    }
+    s.push_str(SCRIPT);
+    writeln!(s, "</body>").unwrap();
+    writeln!(s, "</html>").unwrap();
+    s
+}
+
+/// The name of `tile` in the page's `data-tile` attributes.
+fn tile_name(tile: Tile) -> &'static str {
+    match tile {
+        Camp => "camp",
+        Grass => "grass",
+        _ => "",
+    }
+}
+
+/// Cycles cells when clicked and toggles the solution.
+const SCRIPT: &str = r#"<script>
+var table = document.getElementById("puzzle");
+var toggle = document.getElementById("toggle");
+var tiles = ["", "camp", "grass"];
+var solved = false;
+table.addEventListener("click", function (event) {
+  var cell = event.target.closest("td.open");
+  if (!cell || solved) {
+    return;
+  }
+  var next = (tiles.indexOf(cell.dataset.tile) + 1) % tiles.length;
+  cell.dataset.tile = tiles[next];
+});
+if (toggle) {
+  toggle.addEventListener("click", function () {
+    solved = !solved;
+    table.querySelectorAll("td.open").forEach(function (cell) {
+      if (solved) {
+        cell.dataset.player = cell.dataset.tile;
+        cell.dataset.tile = cell.dataset.solution;
+      } else {
+        cell.dataset.tile = cell.dataset.player;
+      }
+    });
+    toggle.textContent = solved ? "Hide solution" : "Show solution";
+  });
+}
+</script>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn html_cells() {
+        let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n-#").unwrap();
+        let page = html(&board, None, &RenderOptions::default());
+        assert_eq!(page.matches("<td class=\"open\"").count(), 2);
+        assert!(page.contains("<td class=\"open\" data-tile=\"grass\"></td>"));
+        assert!(page.contains("<td class=\"tree\"></td>"));
+        assert!(page.contains("<td class=\"blocked\"></td>"));
+        assert!(!page.contains("data-solution"));
+        assert!(!page.contains("<button"));
+        // A row of column clues and a clue beside each row.
+        assert_eq!(page.matches("<th>1</th>").count(), 2);
+        assert_eq!(page.matches("<th>0</th>").count(), 2);
+    }
+
+    #[test]
+    fn html_solution() {
+        let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        let mut solved = board.clone();
+        solved.solve().unwrap();
+        let page = html(&board, Some(&solved), &RenderOptions::default());
+        assert!(page.contains("<td class=\"open\" data-tile=\"\" data-solution=\"camp\"></td>"));
+        assert_eq!(page.matches("data-solution=\"grass\"").count(), 2);
+        assert!(page.contains("<button id=\"toggle\">"));
+    }
+
+    #[test]
+    fn html_labels_and_ranges() {
+        let rows = vec![Clue::at_most(1), Clue::any()];
+        let columns = vec![Clue::exactly(1), Clue::exactly(0)];
+        let board = Board::new_parse_ranged(rows, columns, " T\n  ").unwrap();
+        let options = RenderOptions {
+            labels: true,
+            ..Default::default()
+        };
+        let page = html(&board, None, &options);
+        assert!(page.contains("<th>&lt;=1</th>"));
+        assert!(page.contains("<th>B</th>"));
+        assert!(page.contains("<th>2</th>"));
+        // The corner above the labels and clues of the rows.
+        assert_eq!(page.matches("<th></th>").count(), 5);
+    }
+}
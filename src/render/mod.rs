@@ -0,0 +1,220 @@
+//! Render [`Grid`]s and [`Board`]s for display.
+//!
+//! Every renderer takes a [`RenderOptions`] controlling the
+//! presentation shared between output formats.
+//!
+//! [`Grid`]: ../struct.Grid.html
+//! [`Board`]: ../struct.Board.html
+//! [`RenderOptions`]: struct.RenderOptions.html
+
+mod html;
+pub use self::html::*;
+#[cfg(feature = "image")]
+mod png;
+#[cfg(feature = "image")]
+pub use self::png::*;
+mod svg;
+pub use self::svg::*;
+mod text;
+pub use self::text::*;
+
+use annotations::*;
+use grid::*;
+use pos::*;
+use tile::Tile;
+
+/// Options shared by every renderer.
+///
+/// Renderers ignore the options that don't apply to them.  For
+/// example, [`text`] has no colors.
+///
+/// [`text`]: fn.text.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Draw coordinate labels along the edges of the `Grid`.
+    ///
+    /// Columns are labeled by [`column_name`] and rows are labeled
+    /// by [`row_name`].
+    ///
+    /// [`column_name`]: fn.column_name.html
+    /// [`row_name`]: fn.row_name.html
+    pub labels: bool,
+    /// The width and height of each cell in pixels.
+    pub cell_size: usize,
+    /// The colors to draw with.
+    pub colors: Colors,
+    /// How long each frame of an animation is shown, in milliseconds.
+    pub frame_delay: u16,
+    /// Pencil marks to draw on [`Unassigned`] cells.  They must be
+    /// the same size as the `Grid`.
+    ///
+    /// [`Unassigned`]: ../enum.Tile.html#variant.Unassigned
+    pub annotations: Option<Annotations>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            labels: false,
+            cell_size: 40,
+            colors: Colors::default(),
+            frame_delay: 500,
+            annotations: None,
+        }
+    }
+}
+
+/// A color in RGB.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    /// Format the `Color` as `#rrggbb`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::render::Color;
+    /// assert_eq!(Color(255, 0, 16).hex(), "#ff0010");
+    /// ```
+    pub fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// The colors used by graphical renderers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Colors {
+    /// The background, including [`Unassigned`] cells.
+    ///
+    /// [`Unassigned`]: ../enum.Tile.html#variant.Unassigned
+    pub background: Color,
+    /// The lines between cells.
+    pub lines: Color,
+    /// Clues and labels.
+    pub text: Color,
+    /// The fill of [`Grass`] cells.
+    ///
+    /// [`Grass`]: ../enum.Tile.html#variant.Grass
+    pub grass: Color,
+    /// The tent drawn for [`Camp`]s.
+    ///
+    /// [`Camp`]: ../enum.Tile.html#variant.Camp
+    pub camp: Color,
+    /// The leaves of [`Tree`]s.
+    ///
+    /// [`Tree`]: ../enum.Tile.html#variant.Tree
+    pub tree: Color,
+    /// The trunks of [`Tree`]s.
+    ///
+    /// [`Tree`]: ../enum.Tile.html#variant.Tree
+    pub trunk: Color,
+    /// The fill of [`Blocked`] cells.
+    ///
+    /// [`Blocked`]: ../enum.Tile.html#variant.Blocked
+    pub blocked: Color,
+}
+
+impl Default for Colors {
+    fn default() -> Self {
+        Colors {
+            background: Color(0xff, 0xff, 0xff),
+            lines: Color(0x42, 0x42, 0x42),
+            text: Color(0x00, 0x00, 0x00),
+            grass: Color(0xc5, 0xe1, 0xa5),
+            camp: Color(0xef, 0x6c, 0x00),
+            tree: Color(0x2e, 0x7d, 0x32),
+            trunk: Color(0x6d, 0x4c, 0x41),
+            blocked: Color(0x90, 0xa4, 0xae),
+        }
+    }
+}
+
+/// Get the [`Mark`] to draw at `pos`, if there is one and the cell is
+/// still [`Unassigned`].
+///
+/// [`Mark`]: ../enum.Mark.html
+/// [`Unassigned`]: ../enum.Tile.html#variant.Unassigned
+fn mark_at(grid: &Grid, annotations: Option<&Annotations>, pos: Pos) -> Option<Mark> {
+    annotations
+        .and_then(|x| x[pos])
+        .filter(|_| grid[pos] == Tile::Unassigned)
+}
+
+/// Get the label of a column.
+///
+/// Columns are labeled `A` through `Z`, then `AA`, `AB`, and so on.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::render::column_name;
+/// assert_eq!(column_name(0), "A");
+/// assert_eq!(column_name(25), "Z");
+/// assert_eq!(column_name(26), "AA");
+/// ```
+pub fn column_name(column: usize) -> String {
+    let mut name = Vec::new();
+    let mut column = column + 1;
+    while column != 0 {
+        column -= 1;
+        name.push((b'A' + (column % 26) as u8) as char);
+        column /= 26;
+    }
+    name.into_iter().rev().collect()
+}
+
+/// Get the label of a row.
+///
+/// Rows are labeled starting at `1`.
+pub fn row_name(row: usize) -> String {
+    (row + 1).to_string()
+}
+
+/// Get the label of the cell at `(row, column)`.
+///
+/// This is the [`column_name`] followed by the [`row_name`].
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::render::cell_name;
+/// assert_eq!(cell_name(3, 2), "C4");
+/// ```
+///
+/// [`column_name`]: fn.column_name.html
+/// [`row_name`]: fn.row_name.html
+pub fn cell_name(row: usize, column: usize) -> String {
+    format!("{}{}", column_name(column), row_name(row))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_name_test() {
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(2), "C");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(column_name(27), "AB");
+        assert_eq!(column_name(51), "AZ");
+        assert_eq!(column_name(52), "BA");
+        assert_eq!(column_name(701), "ZZ");
+        assert_eq!(column_name(702), "AAA");
+    }
+
+    #[test]
+    fn row_name_test() {
+        assert_eq!(row_name(0), "1");
+        assert_eq!(row_name(9), "10");
+    }
+
+    #[test]
+    fn cell_name_test() {
+        assert_eq!(cell_name(0, 0), "A1");
+        assert_eq!(cell_name(3, 2), "C4");
+        assert_eq!(cell_name(11, 26), "AA12");
+    }
+}
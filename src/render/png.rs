@@ -0,0 +1,575 @@
+use super::*;
+use annotations::*;
+use board::*;
+use board_observer::*;
+use png_crate;
+use pos::*;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::mem;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tile::Tile::{self, *};
+
+/// Glyphs for `0`-`9` and `A`-`Z`, 5 pixels wide and 7 tall.  Each
+/// byte is a row with the leftmost pixel in bit 4.
+const FONT: [[u8; 7]; 36] = [
+    [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+    [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+    [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+    [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+    [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+    [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+    [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+    [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+    [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+    [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E],
+    [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E],
+    [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C],
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+    [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F],
+    [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+    [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+    [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C],
+    [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+    [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11],
+    [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+    [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+    [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+    [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D],
+    [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+    [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+    [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04],
+    [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+    [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11],
+    [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04],
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F],
+];
+
+/// Glyphs for the symbols in range clues, like `FONT`.
+const SYMBOLS: [(char, [u8; 7]); 4] = [
+    ('<', [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02]),
+    ('>', [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08]),
+    ('=', [0x00, 0x00, 0x1F, 0x00, 0x1F, 0x00, 0x00]),
+    ('.', [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C]),
+];
+
+/// An RGB image being drawn.
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize, background: Color) -> Self {
+        let mut pixels = Vec::with_capacity(width * height * 3);
+        for _ in 0..width * height {
+            pixels.extend_from_slice(&[background.0, background.1, background.2]);
+        }
+        Canvas {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Color the pixel at `(x, y)`, ignoring points outside the image.
+    fn set(&mut self, x: usize, y: usize, color: Color) {
+        if x < self.width && y < self.height {
+            let i = (y * self.width + x) * 3;
+            self.pixels[i..i + 3].copy_from_slice(&[color.0, color.1, color.2]);
+        }
+    }
+
+    fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, color: Color) {
+        for y in y..y + height {
+            for x in x..x + width {
+                self.set(x, y, color);
+            }
+        }
+    }
+
+    fn fill_circle(&mut self, cx: usize, cy: usize, r: usize, color: Color) {
+        for y in cy.saturating_sub(r)..cy + r + 1 {
+            for x in cx.saturating_sub(r)..cx + r + 1 {
+                let (dx, dy) = (x as isize - cx as isize, y as isize - cy as isize);
+                if dx * dx + dy * dy <= (r * r) as isize {
+                    self.set(x, y, color);
+                }
+            }
+        }
+    }
+
+    /// Fill a triangle pointing up with its apex at `(cx, top)` and
+    /// its base from `left` to `right` at `bottom`.
+    fn fill_tent(
+        &mut self,
+        cx: usize,
+        top: usize,
+        left: usize,
+        right: usize,
+        bottom: usize,
+        color: Color,
+    ) {
+        let half = (right - left) / 2;
+        for y in top..bottom + 1 {
+            let w = half * (y - top) / (bottom - top).max(1);
+            self.fill_rect(cx - w, y, 2 * w + 1, 1, color);
+        }
+    }
+
+    /// Draw `text` centered at `(cx, cy)` with each font pixel
+    /// `scale` pixels wide.  Characters missing from the font are
+    /// skipped.
+    fn text(&mut self, cx: usize, cy: usize, text: &str, scale: usize, color: Color) {
+        if text.is_empty() {
+            return;
+        }
+        let width = (text.len() * 6 - 1) * scale;
+        let x = cx.saturating_sub(width / 2);
+        let y = cy.saturating_sub(7 * scale / 2);
+        for (i, c) in text.chars().enumerate() {
+            let glyph = match c {
+                '0'..='9' => FONT[c as usize - '0' as usize],
+                'A'..='Z' => FONT[c as usize - 'A' as usize + 10],
+                _ => match SYMBOLS.iter().find(|&&(symbol, _)| symbol == c) {
+                    Some(&(_, glyph)) => glyph,
+                    None => continue,
+                },
+            };
+            for (row, bits) in glyph.iter().enumerate() {
+                for column in 0..5 {
+                    if bits & (0x10 >> column) != 0 {
+                        let x = x + (i * 6 + column) * scale;
+                        self.fill_rect(x, y + row * scale, scale, scale, color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Render the `Board` as a PNG image.
+///
+/// The layout matches [`svg`]: clues across the top and down the
+/// left side, with labels outside them if `options.labels` is set.
+/// Each cell is `options.cell_size` pixels wide and drawn in
+/// `options.colors`, with the marks in `options.annotations` drawn
+/// as in [`svg`].
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Board, render::{png, RenderOptions}};
+/// let board = Board::new_parse(vec![1, 0], vec![1, 0], "CT\n--").unwrap();
+/// let png = png(&board, &RenderOptions::default());
+/// assert_eq!(&png[1..4], b"PNG");
+/// ```
+///
+/// [`svg`]: fn.svg.html
+pub fn png(board: &Board, options: &RenderOptions) -> Vec<u8> {
+    let mut data = Vec::new();
+    // Writing to a Vec can't fail.
+    write_png(board, options, &mut data).unwrap();
+    data
+}
+
+/// Encode the `Board` as a PNG image into `writer`.
+fn write_png<W: Write>(board: &Board, options: &RenderOptions, writer: W) -> io::Result<()> {
+    let canvas = draw(board, options);
+    let mut encoder = png_crate::Encoder::new(writer, canvas.width as u32, canvas.height as u32);
+    encoder.set_color(png_crate::ColorType::Rgb);
+    encoder.set_depth(png_crate::BitDepth::Eight);
+    let to_io = io::Error::other;
+    let mut writer = encoder.write_header().map_err(to_io)?;
+    writer.write_image_data(&canvas.pixels).map_err(to_io)?;
+    writer.finish().map_err(to_io)
+}
+
+/// Render solving the `Board` as an animated PNG.
+///
+/// The first frame is the `Board` as given, and each strategy that
+/// makes progress adds a frame with its deductions filled in, drawn
+/// as by [`png`].  Each frame is shown for `options.frame_delay`
+/// milliseconds, and the animation plays once.  The `Board` itself
+/// isn't changed.  If the solver gets stuck or finds a contradiction,
+/// the animation ends where it stopped.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Board, render::{solve_animation, RenderOptions}};
+/// let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+/// let apng = solve_animation(&board, &RenderOptions::default());
+/// assert_eq!(&apng[1..4], b"PNG");
+/// ```
+///
+/// [`png`]: fn.png.html
+pub fn solve_animation(board: &Board, options: &RenderOptions) -> Vec<u8> {
+    let mut data = Vec::new();
+    // Writing to a Vec can't fail.
+    write_solve_animation(board, options, &mut data).unwrap();
+    data
+}
+
+/// Draws a frame of an animation after each step of a solve.
+struct FrameRecorder {
+    options: RenderOptions,
+    frames: Mutex<Vec<Canvas>>,
+}
+
+impl BoardObserver for FrameRecorder {
+    fn on_tile_set(&self, _: Pos, _: Tile, _: Tile, _: Option<&'static str>) {}
+
+    fn on_step(&self, board: &Board, _: &'static str) {
+        let frame = draw(board, &self.options);
+        self.frames.lock().unwrap().push(frame);
+    }
+}
+
+/// Encode the animation of solving the `Board` into `writer`.
+fn write_solve_animation<W: Write>(
+    board: &Board,
+    options: &RenderOptions,
+    writer: W,
+) -> io::Result<()> {
+    let recorder = Arc::new(FrameRecorder {
+        options: options.clone(),
+        frames: Mutex::new(vec![draw(board, options)]),
+    });
+    let mut solved = board.clone();
+    solved.observer = Some(recorder.clone());
+    solved.solve_outcome();
+    let frames = mem::take(&mut *recorder.frames.lock().unwrap());
+
+    let (width, height) = (frames[0].width as u32, frames[0].height as u32);
+    let mut encoder = png_crate::Encoder::new(writer, width, height);
+    encoder.set_color(png_crate::ColorType::Rgb);
+    encoder.set_depth(png_crate::BitDepth::Eight);
+    let to_io = io::Error::other;
+    encoder
+        .set_animated(frames.len() as u32, 1)
+        .map_err(to_io)?;
+    encoder
+        .set_frame_delay(options.frame_delay, 1000)
+        .map_err(to_io)?;
+    let mut writer = encoder.write_header().map_err(to_io)?;
+    for frame in &frames {
+        writer.write_image_data(&frame.pixels).map_err(to_io)?;
+    }
+    writer.finish().map_err(to_io)
+}
+
+/// Draw the `Board` onto a new [`Canvas`].
+///
+/// [`Canvas`]: struct.Canvas.html
+fn draw(board: &Board, options: &RenderOptions) -> Canvas {
+    let (height, width) = (board.num_rows(), board.num_columns());
+    let colors = &options.colors;
+    let cell = options.cell_size;
+    let mut margin = 0;
+    if !board.is_clue_free() {
+        margin += cell;
+    }
+    if options.labels {
+        margin += cell;
+    }
+    // PNG images must have at least one pixel.
+    let image_width = (margin + width * cell).max(1);
+    let image_height = (margin + height * cell).max(1);
+    let mut canvas = Canvas::new(image_width, image_height, colors.background);
+
+    // The centers of the cells `i` cells above and left of the Grid.
+    let before = |i: usize| margin - cell / 2 - i * cell;
+    let center = |i: usize| margin + i * cell + cell / 2;
+    let scale = (cell / 14).max(1);
+    if !board.is_clue_free() {
+        // Omitted clues are left blank.
+        for (column, clue) in board.column_ranges().into_iter().enumerate() {
+            if clue.is_any() {
+                continue;
+            }
+            canvas.text(
+                center(column),
+                before(0),
+                &clue.to_string(),
+                scale,
+                colors.text,
+            );
+        }
+        for (row, clue) in board.row_ranges().into_iter().enumerate() {
+            if clue.is_any() {
+                continue;
+            }
+            canvas.text(
+                before(0),
+                center(row),
+                &clue.to_string(),
+                scale,
+                colors.text,
+            );
+        }
+    }
+    if options.labels {
+        let offset = if board.is_clue_free() { 0 } else { 1 };
+        for column in 0..width {
+            canvas.text(
+                center(column),
+                before(offset),
+                &column_name(column),
+                scale,
+                colors.text,
+            );
+        }
+        for row in 0..height {
+            canvas.text(
+                before(offset),
+                center(row),
+                &row_name(row),
+                scale,
+                colors.text,
+            );
+        }
+    }
+
+    for row in 0..height {
+        for column in 0..width {
+            let (x, y) = (margin + column * cell, margin + row * cell);
+            let mark = mark_at(board, options.annotations.as_ref(), Pos::new(row, column));
+            match board[(row, column)] {
+                Unassigned => match mark {
+                    None => {}
+                    Some(Mark::MaybeCamp) => {
+                        canvas.fill_circle(x + cell / 2, y + cell / 2, cell / 8, colors.camp)
+                    }
+                    Some(Mark::Camp) => canvas.fill_tent(
+                        x + cell / 2,
+                        y + cell * 5 / 16,
+                        x + cell * 5 / 16,
+                        x + cell * 11 / 16,
+                        y + cell * 11 / 16,
+                        colors.camp,
+                    ),
+                    Some(Mark::Grass) => canvas.fill_rect(
+                        x + cell / 4,
+                        y + cell / 4,
+                        cell / 2,
+                        cell / 2,
+                        colors.grass,
+                    ),
+                },
+                Grass => canvas.fill_rect(x, y, cell, cell, colors.grass),
+                Camp => canvas.fill_tent(
+                    x + cell / 2,
+                    y + cell / 8,
+                    x + cell / 8,
+                    x + cell * 7 / 8,
+                    y + cell * 7 / 8,
+                    colors.camp,
+                ),
+                Tree => {
+                    canvas.fill_rect(
+                        x + cell * 7 / 16,
+                        y + cell / 2,
+                        cell / 8,
+                        cell * 3 / 8,
+                        colors.trunk,
+                    );
+                    canvas.fill_circle(x + cell / 2, y + cell * 3 / 8, cell / 4, colors.tree);
+                }
+                Blocked => canvas.fill_rect(x, y, cell, cell, colors.blocked),
+            }
+        }
+    }
+
+    for row in 0..=height {
+        let y = margin + row * cell;
+        canvas.fill_rect(
+            margin,
+            y.min(image_height - 1),
+            width * cell,
+            1,
+            colors.lines,
+        );
+    }
+    for column in 0..=width {
+        let x = margin + column * cell;
+        canvas.fill_rect(
+            x.min(image_width - 1),
+            margin,
+            1,
+            height * cell,
+            colors.lines,
+        );
+    }
+    canvas
+}
+
+impl Board {
+    /// Render the `Board` as a PNG image and save it to `path`.
+    ///
+    /// See [`render::png`].
+    ///
+    /// # Errors
+    ///
+    /// Errors creating or writing the file are returned.
+    ///
+    /// [`render::png`]: render/fn.png.html
+    pub fn render_png<P: AsRef<Path>>(&self, path: P, options: &RenderOptions) -> io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        write_png(self, options, &mut file)?;
+        file.flush()
+    }
+
+    /// Render solving the `Board` as an animated PNG and save it to
+    /// `path`.
+    ///
+    /// See [`render::solve_animation`].
+    ///
+    /// # Errors
+    ///
+    /// Errors creating or writing the file are returned.
+    ///
+    /// [`render::solve_animation`]: render/fn.solve_animation.html
+    pub fn render_solve_animation<P: AsRef<Path>>(
+        &self,
+        path: P,
+        options: &RenderOptions,
+    ) -> io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        write_solve_animation(self, options, &mut file)?;
+        file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grid::*;
+
+    /// Decode a PNG into its width, height, and RGB pixels.
+    fn decode(data: &[u8]) -> (usize, usize, Vec<u8>) {
+        let decoder = png_crate::Decoder::new(data);
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        (info.width as usize, info.height as usize, pixels)
+    }
+
+    fn pixel(image: &(usize, usize, Vec<u8>), x: usize, y: usize) -> Color {
+        let i = (y * image.0 + x) * 3;
+        Color(image.2[i], image.2[i + 1], image.2[i + 2])
+    }
+
+    #[test]
+    fn png_tiles() {
+        let board = Board::new_parse(vec![1, 0], vec![1, 0], "CT\n- ").unwrap();
+        let options = RenderOptions::default();
+        let colors = options.colors;
+        let image = decode(&png(&board, &options));
+        assert_eq!((image.0, image.1), (120, 120));
+        // Camp at A1, Tree at B1, Grass at A2, Unassigned at B2.
+        assert_eq!(pixel(&image, 60, 70), colors.camp);
+        assert_eq!(pixel(&image, 100, 55), colors.tree);
+        assert_eq!(pixel(&image, 100, 72), colors.trunk);
+        assert_eq!(pixel(&image, 60, 100), colors.grass);
+        assert_eq!(pixel(&image, 100, 100), colors.background);
+        assert_eq!(pixel(&image, 40, 100), colors.lines);
+        assert_eq!(pixel(&image, 60, 80), colors.lines);
+    }
+
+    #[test]
+    fn png_options() {
+        let board = Board::new_parse_clue_free(1, "T\n-").unwrap();
+        let mut options = RenderOptions {
+            labels: true,
+            cell_size: 20,
+            ..Default::default()
+        };
+        options.colors.grass = Color(1, 2, 3);
+        let image = decode(&png(&board, &options));
+        assert_eq!((image.0, image.1), (40, 60));
+        assert_eq!(pixel(&image, 30, 50), Color(1, 2, 3));
+    }
+
+    #[test]
+    fn png_annotations() {
+        let board = Board::new_parse_clue_free(1, " T\n  ").unwrap();
+        let mut annotations = Annotations::for_grid(&board);
+        annotations[Pos::new(0, 0)] = Some(Mark::MaybeCamp);
+        annotations[Pos::new(1, 0)] = Some(Mark::Grass);
+        let options = RenderOptions {
+            annotations: Some(annotations),
+            ..Default::default()
+        };
+        let image = decode(&png(&board, &options));
+        let colors = Colors::default();
+        assert_eq!(pixel(&image, 20, 20), colors.camp);
+        assert_eq!(pixel(&image, 20, 60), colors.grass);
+        assert_eq!(pixel(&image, 5, 45), colors.background);
+    }
+
+    #[test]
+    fn solve_animation_frames() {
+        let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        let options = RenderOptions {
+            frame_delay: 250,
+            ..Default::default()
+        };
+        let data = solve_animation(&board, &options);
+        let decoder = png_crate::Decoder::new(&data[..]);
+        let mut reader = decoder.read_info().unwrap();
+        let frames = reader.info().animation_control.unwrap().num_frames;
+        assert_eq!(frames, 4);
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let mut last = None;
+        for _ in 0..frames {
+            reader.next_frame(&mut pixels).unwrap();
+            let control = reader.info().frame_control.unwrap();
+            assert_eq!((control.delay_num, control.delay_den), (250, 1000));
+            last = Some(pixels.clone());
+        }
+        let (width, height) = (board.num_columns() * 40 + 40, board.num_rows() * 40 + 40);
+        let last = (width, height, last.unwrap());
+        // The `Camp` appears in the last frame, and the `Board` itself
+        // is left alone.
+        assert_eq!(pixel(&last, 60, 70), Colors::default().camp);
+        assert_eq!(board.debug(), " T\n  ");
+    }
+
+    #[test]
+    fn png_text() {
+        let mut canvas = Canvas::new(20, 20, Color(0, 0, 0));
+        canvas.text(10, 10, "1", 1, Color(255, 255, 255));
+        let lit: Vec<_> = (0..20)
+            .filter(|&x| canvas.pixels[(13 * 20 + x) * 3] == 255)
+            .collect();
+        // The bottom row of `1` is 3 pixels wide and centered.
+        assert_eq!(lit, vec![9, 10, 11]);
+    }
+
+    #[test]
+    fn png_text_symbols() {
+        let mut canvas = Canvas::new(20, 20, Color(0, 0, 0));
+        canvas.text(10, 10, "<=", 1, Color(255, 255, 255));
+        let lit: Vec<_> = (0..20)
+            .filter(|&x| canvas.pixels[(9 * 20 + x) * 3] == 255)
+            .collect();
+        // The third row of `<` is one pixel, and of `=` its top bar.
+        assert_eq!(lit, vec![6, 11, 12, 13, 14, 15]);
+    }
+
+    #[test]
+    fn png_empty_board() {
+        let board = Board::new_clue_free(0, Grid::blank(0, 0));
+        let image = decode(&png(&board, &RenderOptions::default()));
+        assert_eq!((image.0, image.1), (1, 1));
+    }
+}
@@ -0,0 +1,318 @@
+use super::*;
+use annotations::*;
+use board::*;
+use clue::*;
+use pos::*;
+use std::fmt::Write;
+use tile::Tile::*;
+
+/// Render the `Board` as an SVG image.
+///
+/// [`Grass`] is shaded, [`Camp`]s are drawn as tents, and [`Tree`]s
+/// as trees.  Column clues are drawn across the top and row clues
+/// down the left side.  Omitted clues are left blank, and clue-free
+/// `Board`s have no clues drawn.  If `options.labels` is set, the
+/// column labels are drawn above the clues and the row labels to the
+/// left of them.  Each cell is `options.cell_size` pixels wide and
+/// drawn in `options.colors`.  The marks in `options.annotations`
+/// are drawn smaller on cells still [`Unassigned`]: a dot for
+/// [`Mark::MaybeCamp`], a small tent for [`Mark::Camp`] and a small
+/// square of grass for [`Mark::Grass`].
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Board, render::{svg, RenderOptions}};
+/// let board = Board::new_parse(vec![1, 0], vec![1, 0], "CT\n--").unwrap();
+/// let svg = svg(&board, &RenderOptions::default());
+/// assert!(svg.starts_with("<svg "));
+/// assert!(svg.ends_with("</svg>\n"));
+/// ```
+///
+/// [`Grass`]: ../enum.Tile.html#variant.Grass
+/// [`Camp`]: ../enum.Tile.html#variant.Camp
+/// [`Tree`]: ../enum.Tile.html#variant.Tree
+/// [`Unassigned`]: ../enum.Tile.html#variant.Unassigned
+/// [`Mark::MaybeCamp`]: ../enum.Mark.html#variant.MaybeCamp
+/// [`Mark::Camp`]: ../enum.Mark.html#variant.Camp
+/// [`Mark::Grass`]: ../enum.Mark.html#variant.Grass
+pub fn svg(board: &Board, options: &RenderOptions) -> String {
+    let (height, width) = (board.num_rows(), board.num_columns());
+    let colors = &options.colors;
+    let cell = options.cell_size;
+    let mut margin = 0;
+    if !board.is_clue_free() {
+        margin += cell;
+    }
+    if options.labels {
+        margin += cell;
+    }
+    let (image_width, image_height) = (margin + width * cell, margin + height * cell);
+
+    let mut s = String::new();
+    writeln!(
+        s,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" \
+         viewBox=\"0 0 {0} {1}\" font-family=\"sans-serif\" font-size=\"{2}\" \
+         fill=\"{3}\" text-anchor=\"middle\" dominant-baseline=\"central\">",
+        image_width,
+        image_height,
+        cell / 2,
+        colors.text.hex()
+    )
+    .unwrap();
+    writeln!(
+        s,
+        "<rect width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+        image_width,
+        image_height,
+        colors.background.hex()
+    )
+    .unwrap();
+
+    // The centers of the cells just above and left of the Grid.
+    let before = |i: usize| margin - cell / 2 - i * cell;
+    let center = |i: usize| margin + i * cell + cell / 2;
+    let label = |s: &mut String, x: usize, y: usize, text: &str| {
+        writeln!(s, "<text x=\"{}\" y=\"{}\">{}</text>", x, y, text).unwrap();
+    };
+    if !board.is_clue_free() {
+        // Omitted clues are left blank.
+        let text = |clue: Clue| clue.to_string().replace('<', "&lt;").replace('>', "&gt;");
+        for (column, clue) in board.column_ranges().into_iter().enumerate() {
+            if !clue.is_any() {
+                label(&mut s, center(column), before(0), &text(clue));
+            }
+        }
+        for (row, clue) in board.row_ranges().into_iter().enumerate() {
+            if !clue.is_any() {
+                label(&mut s, before(0), center(row), &text(clue));
+            }
+        }
+    }
+    if options.labels {
+        let offset = if board.is_clue_free() { 0 } else { 1 };
+        for column in 0..width {
+            label(&mut s, center(column), before(offset), &column_name(column));
+        }
+        for row in 0..height {
+            label(&mut s, before(offset), center(row), &row_name(row));
+        }
+    }
+
+    for row in 0..height {
+        for column in 0..width {
+            let (x, y) = (margin + column * cell, margin + row * cell);
+            let mark = mark_at(board, options.annotations.as_ref(), Pos::new(row, column));
+            match board[(row, column)] {
+                Unassigned => match mark {
+                    None => {}
+                    Some(Mark::MaybeCamp) => writeln!(
+                        s,
+                        "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>",
+                        x + cell / 2,
+                        y + cell / 2,
+                        cell / 8,
+                        colors.camp.hex()
+                    )
+                    .unwrap(),
+                    Some(Mark::Camp) => writeln!(
+                        s,
+                        "<polygon points=\"{},{} {},{} {},{}\" fill=\"{}\"/>",
+                        x + cell / 2,
+                        y + cell * 5 / 16,
+                        x + cell * 5 / 16,
+                        y + cell * 11 / 16,
+                        x + cell * 11 / 16,
+                        y + cell * 11 / 16,
+                        colors.camp.hex()
+                    )
+                    .unwrap(),
+                    Some(Mark::Grass) => writeln!(
+                        s,
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                        x + cell / 4,
+                        y + cell / 4,
+                        cell / 2,
+                        cell / 2,
+                        colors.grass.hex()
+                    )
+                    .unwrap(),
+                },
+                Grass => writeln!(
+                    s,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                    x,
+                    y,
+                    cell,
+                    cell,
+                    colors.grass.hex()
+                )
+                .unwrap(),
+                Camp => writeln!(
+                    s,
+                    "<polygon points=\"{},{} {},{} {},{}\" fill=\"{}\"/>",
+                    x + cell / 2,
+                    y + cell / 8,
+                    x + cell / 8,
+                    y + cell * 7 / 8,
+                    x + cell * 7 / 8,
+                    y + cell * 7 / 8,
+                    colors.camp.hex()
+                )
+                .unwrap(),
+                Tree => {
+                    writeln!(
+                        s,
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                        x + cell * 7 / 16,
+                        y + cell / 2,
+                        cell / 8,
+                        cell * 3 / 8,
+                        colors.trunk.hex()
+                    )
+                    .unwrap();
+                    writeln!(
+                        s,
+                        "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>",
+                        x + cell / 2,
+                        y + cell * 3 / 8,
+                        cell / 4,
+                        colors.tree.hex()
+                    )
+                    .unwrap();
+                }
+                Blocked => writeln!(
+                    s,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                    x,
+                    y,
+                    cell,
+                    cell,
+                    colors.blocked.hex()
+                )
+                .unwrap(),
+            }
+        }
+    }
+
+    for row in 0..=height {
+        let y = margin + row * cell;
+        writeln!(
+            s,
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\"/>",
+            margin,
+            y,
+            image_width,
+            y,
+            colors.lines.hex()
+        )
+        .unwrap();
+    }
+    for column in 0..=width {
+        let x = margin + column * cell;
+        writeln!(
+            s,
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\"/>",
+            x,
+            margin,
+            x,
+            image_height,
+            colors.lines.hex()
+        )
+        .unwrap();
+    }
+    writeln!(s, "</svg>").unwrap();
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn svg_tiles() {
+        let board = Board::new_parse(vec![1, 0], vec![1, 0], "CT\n- ").unwrap();
+        let svg = svg(&board, &RenderOptions::default());
+        assert!(svg
+            .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"120\" height=\"120\""));
+        assert_eq!(svg.matches("<polygon ").count(), 1);
+        assert_eq!(svg.matches("<circle ").count(), 1);
+        assert_eq!(svg.matches("fill=\"#c5e1a5\"").count(), 1);
+        assert_eq!(svg.matches("<line ").count(), 6);
+    }
+
+    #[test]
+    fn svg_annotations() {
+        let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        let mut annotations = Annotations::for_grid(&board);
+        annotations[Pos::new(0, 0)] = Some(Mark::MaybeCamp);
+        annotations[Pos::new(1, 0)] = Some(Mark::Grass);
+        annotations[Pos::new(1, 1)] = Some(Mark::Camp);
+        let options = RenderOptions {
+            annotations: Some(annotations),
+            ..Default::default()
+        };
+        let svg = svg(&board, &options);
+        assert_eq!(svg.matches("<circle ").count(), 2);
+        assert_eq!(svg.matches("<polygon ").count(), 1);
+        assert!(svg.contains("<rect x=\"50\" y=\"90\" width=\"20\" height=\"20\""));
+    }
+
+    #[test]
+    fn svg_options() {
+        let board = Board::new_parse(vec![1, 0], vec![1, 0], "CT\n- ").unwrap();
+        let mut options = RenderOptions {
+            cell_size: 10,
+            ..Default::default()
+        };
+        options.colors.grass = Color(1, 2, 3);
+        let svg = svg(&board, &options);
+        assert!(svg.contains("width=\"30\" height=\"30\""));
+        assert!(
+            svg.contains("<rect x=\"10\" y=\"20\" width=\"10\" height=\"10\" fill=\"#010203\"/>")
+        );
+    }
+
+    #[test]
+    fn svg_clues() {
+        let board = Board::new_parse(vec![1, 0], vec![0, 1], " T\n  ").unwrap();
+        let svg = svg(&board, &RenderOptions::default());
+        assert!(svg.contains("<text x=\"60\" y=\"20\">0</text>"));
+        assert!(svg.contains("<text x=\"100\" y=\"20\">1</text>"));
+        assert!(svg.contains("<text x=\"20\" y=\"60\">1</text>"));
+        assert!(svg.contains("<text x=\"20\" y=\"100\">0</text>"));
+    }
+
+    #[test]
+    fn svg_range_clues() {
+        let rows = vec![Clue::at_most(1), Clue::any()];
+        let columns = vec![Clue::between(0, 1), Clue::at_least(1)];
+        let board = Board::new_parse_ranged(rows, columns, " T\n  ").unwrap();
+        let svg = svg(&board, &RenderOptions::default());
+        assert!(svg.contains("<text x=\"60\" y=\"20\">&lt;=1</text>"));
+        assert!(svg.contains("<text x=\"100\" y=\"20\">&gt;=1</text>"));
+        assert!(svg.contains("<text x=\"20\" y=\"60\">&lt;=1</text>"));
+        assert!(!svg.contains("y=\"100\">"));
+    }
+
+    #[test]
+    fn svg_labels() {
+        let board = Board::new_parse_clue_free(1, " T\n  ").unwrap();
+        let options = RenderOptions {
+            labels: true,
+            ..Default::default()
+        };
+        let svg = svg(&board, &options);
+        assert!(svg.contains("width=\"120\" height=\"120\""));
+        assert!(svg.contains("<text x=\"100\" y=\"20\">B</text>"));
+        assert!(svg.contains("<text x=\"20\" y=\"100\">2</text>"));
+
+        let board = Board::new_parse(vec![1, 0], vec![0, 1], " T\n  ").unwrap();
+        let svg = super::svg(&board, &options);
+        assert!(svg.contains("width=\"160\" height=\"160\""));
+        assert!(svg.contains("<text x=\"140\" y=\"20\">B</text>"));
+        assert!(svg.contains("<text x=\"60\" y=\"100\">1</text>"));
+        assert!(svg.contains("<text x=\"20\" y=\"140\">2</text>"));
+    }
+}
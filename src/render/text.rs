@@ -0,0 +1,199 @@
+use super::*;
+use annotations::*;
+use board::*;
+use clue::*;
+use grid::*;
+use pos::*;
+use std::fmt::Write;
+
+/// Render the `Grid` as text.
+///
+/// Each [`Tile`] is drawn as its `Debug` character, except that cells
+/// still [`Unassigned`] show their mark from `options.annotations` as
+/// its [`Mark::symbol`].  If `options.labels` is set, the column
+/// labels are drawn across the top and the row labels down the left
+/// side.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Grid, render::{text, RenderOptions}};
+/// let grid = Grid::parse("TC-\n - \n---").unwrap();
+/// let options = RenderOptions {
+///     labels: true,
+///     ..Default::default()
+/// };
+/// assert_eq!(text(&grid, &options), "  ABC\n1 TC-\n2  - \n3 ---");
+/// ```
+///
+/// [`Tile`]: ../enum.Tile.html
+/// [`Unassigned`]: ../enum.Tile.html#variant.Unassigned
+/// [`Mark::symbol`]: ../enum.Mark.html#method.symbol
+pub fn text(grid: &Grid, options: &RenderOptions) -> String {
+    let annotations = options.annotations.as_ref();
+    if !options.labels {
+        let rows: Vec<String> = (0..grid.num_rows())
+            .map(|row| {
+                (0..grid.num_columns())
+                    .map(|column| cell_text(grid, annotations, row, column))
+                    .collect()
+            })
+            .collect();
+        return rows.join("\n");
+    }
+    let columns: Vec<_> = (0..grid.num_columns()).map(column_name).collect();
+    let rows: Vec<_> = (0..grid.num_rows()).map(row_name).collect();
+    table(grid, annotations, &columns, &rows)
+}
+
+/// The text drawn for the cell at `(row, column)`.
+fn cell_text(grid: &Grid, annotations: Option<&Annotations>, row: usize, column: usize) -> String {
+    match mark_at(grid, annotations, Pos::new(row, column)) {
+        Some(mark) => mark.symbol().to_string(),
+        None => format!("{:?}", grid[(row, column)]),
+    }
+}
+
+/// Draw the `Grid` with a header above each column and beside each
+/// row.
+///
+/// Headers are right aligned.
+fn table(
+    grid: &Grid,
+    annotations: Option<&Annotations>,
+    columns: &[String],
+    rows: &[String],
+) -> String {
+    let row_width = rows.iter().map(|x| x.len()).max().unwrap_or(0);
+    let column_width = columns.iter().map(|x| x.len()).max().unwrap_or(1);
+    // Single character headers line up with the tiles on their own.
+    // Wider headers need spacing to stay readable.
+    let separator = if column_width > 1 { " " } else { "" };
+    let mut s = String::new();
+    write!(s, "{:1$} ", "", row_width).unwrap();
+    let columns: Vec<_> = columns
+        .iter()
+        .map(|header| format!("{:>1$}", header, column_width))
+        .collect();
+    s.push_str(&columns.join(separator));
+    for (row, header) in rows.iter().enumerate() {
+        write!(s, "\n{:>1$} ", header, row_width).unwrap();
+        let tiles: Vec<_> = (0..grid.num_columns())
+            .map(|column| {
+                let tile = cell_text(grid, annotations, row, column);
+                format!("{:>1$}", tile, column_width)
+            })
+            .collect();
+        s.push_str(&tiles.join(separator));
+    }
+    s
+}
+
+impl Board {
+    /// Format the `Board` with its clues.
+    ///
+    /// The column clues are drawn across the top and the row clues
+    /// down the left side, aligned for multi-digit and range clues,
+    /// with `?` for those omitted.  Clue-free `Board`s are drawn below a `total: N`
+    /// line instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// let board = Board::new_parse(vec![1, 0], vec![1, 0], "CT\n--").unwrap();
+    /// assert_eq!(board.display_with_clues(), "  10\n1 CT\n0 --");
+    /// let board = Board::new_parse_clue_free(1, "CT\n--").unwrap();
+    /// assert_eq!(board.display_with_clues(), "total: 1\nCT\n--");
+    /// ```
+    pub fn display_with_clues(&self) -> String {
+        if self.is_clue_free() {
+            return format!("total: {}\n{}", self.total(), self.debug());
+        }
+        let clues = |clues: Vec<Clue>| -> Vec<String> {
+            clues.into_iter().map(|x| x.to_string()).collect()
+        };
+        table(
+            self,
+            None,
+            &clues(self.column_ranges()),
+            &clues(self.row_ranges()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_without_labels() {
+        let grid = Grid::parse("TC-\n - \n---").unwrap();
+        assert_eq!(text(&grid, &RenderOptions::default()), "TC-\n - \n---");
+    }
+
+    #[test]
+    fn text_with_labels() {
+        let grid = Grid::parse("TC-\n - \n---").unwrap();
+        let options = RenderOptions {
+            labels: true,
+            ..Default::default()
+        };
+        assert_eq!(text(&grid, &options), "  ABC\n1 TC-\n2  - \n3 ---");
+    }
+
+    #[test]
+    fn text_with_annotations() {
+        let grid = Grid::parse("T  \n - ").unwrap();
+        let mut annotations = Annotations::for_grid(&grid);
+        annotations[Pos::new(0, 1)] = Some(Mark::MaybeCamp);
+        annotations[Pos::new(0, 2)] = Some(Mark::Camp);
+        annotations[Pos::new(1, 0)] = Some(Mark::Grass);
+        // Marks on cells that aren't `Unassigned` aren't drawn.
+        annotations[Pos::new(1, 1)] = Some(Mark::Camp);
+        let mut options = RenderOptions {
+            annotations: Some(annotations),
+            ..Default::default()
+        };
+        assert_eq!(text(&grid, &options), "T?c\n.- ");
+        options.labels = true;
+        assert_eq!(text(&grid, &options), "  ABC\n1 T?c\n2 .- ");
+    }
+
+    #[test]
+    fn display_with_wide_clues() {
+        let board = Board::new_blank(vec![0, 12, 0], vec![1, 0, 11]);
+        assert_eq!(
+            board.display_with_clues(),
+            "    1  0 11\n 0         \n12         \n 0         "
+        );
+    }
+
+    #[test]
+    fn display_with_range_clues() {
+        let rows = vec![Clue::at_most(1), Clue::any()];
+        let columns = vec![Clue::between(1, 2), Clue::exactly(0)];
+        let board = Board::new_parse_ranged(rows, columns, "CT\n--").unwrap();
+        assert_eq!(
+            board.display_with_clues(),
+            "    1..2    0\n<=1    C    T\n  ?    -    -"
+        );
+    }
+
+    #[test]
+    fn text_with_wide_labels() {
+        let grid = Grid::blank(10, 27);
+        let options = RenderOptions {
+            labels: true,
+            ..Default::default()
+        };
+        let text = text(&grid, &options);
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines.len(), 11);
+        assert!(lines[0].starts_with("    A  B"));
+        assert!(lines[0].ends_with(" Z AA"));
+        assert!(lines[1].starts_with(" 1         "));
+        assert!(lines[10].starts_with("10         "));
+        assert!(lines.iter().all(|l| l.len() == lines[0].len()));
+    }
+}
@@ -0,0 +1,440 @@
+use annotations::*;
+use board::*;
+use contradiction::*;
+use error::*;
+use pos::*;
+use render::{self, RenderOptions};
+use solver_config::*;
+use std::fs::File;
+use tile::Tile::*;
+use tile::*;
+
+/// A line-oriented command interpreter for playing a `Board`.
+///
+/// Each line passed to [`Repl::run`] is one command:
+///
+/// * `place ROW COLUMN TILE` sets a `Tile` with [`Board::place`],
+///   where `TILE` is `camp`, `grass` or `empty`.  Rows and columns
+///   start at `0`.
+/// * `mark ROW COLUMN MARK` pencils a [`Mark`] into
+///   [`Repl::annotations`], where `MARK` is `maybe`, `camp`, `grass`
+///   or `none`.  Marks don't change the `Board`.
+/// * `merge` places the confirmed marks with
+///   [`Board::merge_annotations`].
+/// * `marks` prints the `Board` with the marks on its empty cells.
+/// * `hint` prints a `place` command for a `Tile` that can be deduced.
+/// * `undo` reverts the last `place` with [`Board::undo`].
+/// * `check` lists the rules the `Board` breaks, found with
+///   [`Board::check_consistency`], or with [`Board::verify_solution`]
+///   once the `Board` is full.  Otherwise it reports whether the
+///   `Board` is solved.
+/// * `show` prints the `Board` with its clues.
+/// * `save FILE` writes the `Board` and its moves to `FILE` with
+///   [`Board::save_state`] and `load FILE` reads them back.
+/// * `help` lists the commands.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Board, Repl};
+/// let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+/// let mut repl = Repl::new(board);
+/// assert_eq!(repl.run("hint").unwrap(), "place 0 0 camp");
+/// repl.run("place 0 0 camp").unwrap();
+/// assert_eq!(repl.board.debug(), "CT\n  ");
+/// repl.run("undo").unwrap();
+/// assert_eq!(repl.board.debug(), " T\n  ");
+/// ```
+///
+/// [`Repl::run`]: struct.Repl.html#method.run
+/// [`Repl::annotations`]: struct.Repl.html#structfield.annotations
+/// [`Mark`]: enum.Mark.html
+/// [`Board::merge_annotations`]: struct.Board.html#method.merge_annotations
+/// [`Board::place`]: struct.Board.html#method.place
+/// [`Board::undo`]: struct.Board.html#method.undo
+/// [`Board::save_state`]: struct.Board.html#method.save_state
+/// [`Board::verify_solution`]: struct.Board.html#method.verify_solution
+/// [`Board::check_consistency`]: struct.Board.html#method.check_consistency
+#[derive(Clone, Debug)]
+pub struct Repl {
+    /// The `Board` being played.
+    pub board: Board,
+    /// The settings used to solve the `Board` for hints.
+    pub config: SolverConfig,
+    /// The pencil marks on the `Board`.
+    pub annotations: Annotations,
+}
+
+impl Repl {
+    /// Start playing `board`.
+    pub fn new(board: Board) -> Self {
+        Repl {
+            annotations: Annotations::for_grid(&board),
+            board,
+            config: SolverConfig::default(),
+        }
+    }
+
+    /// Run the command on `line`, returning the text to print.
+    ///
+    /// Blank lines do nothing and return an empty string.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error::Format`] is returned if the command is malformed or
+    /// can't be carried out.  `place` returns the errors of
+    /// [`Board::place`] for moves that break the rules, and `hint`
+    /// returns an [`Error::Contradiction`] if the `Board` breaks the
+    /// rules.
+    ///
+    /// [`Error::Format`]: enum.Error.html#variant.Format
+    /// [`Board::place`]: struct.Board.html#method.place
+    /// [`Error::Contradiction`]: enum.Error.html#variant.Contradiction
+    pub fn run(&mut self, line: &str) -> Result<String, Error> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] => Ok(String::new()),
+            ["place", row, column, tile] => self.place(row, column, tile),
+            ["mark", row, column, mark] => self.mark(row, column, mark),
+            ["merge"] => self.merge(),
+            ["marks"] => Ok(self.marks()),
+            ["hint"] => self.hint(),
+            ["undo"] => self.undo(),
+            ["check"] => self.check(),
+            ["show"] => Ok(self.board.display_with_clues()),
+            ["save", path] => self.save(path),
+            ["load", path] => self.load(path),
+            ["help"] => Ok(HELP.to_string()),
+            _ => Err(command_error(format!("unknown command {:?}", line.trim()))),
+        }
+    }
+
+    fn place(&mut self, row: &str, column: &str, tile: &str) -> Result<String, Error> {
+        let pos = self.parse_pos(row, column)?;
+        let tile = match tile {
+            "camp" => Camp,
+            "grass" => Grass,
+            "empty" => Unassigned,
+            _ => return Err(command_error(format!("unknown tile {:?}", tile))),
+        };
+        self.board.place(pos.row, pos.column, tile)?;
+        Ok(String::new())
+    }
+
+    fn mark(&mut self, row: &str, column: &str, mark: &str) -> Result<String, Error> {
+        let pos = self.parse_pos(row, column)?;
+        self.annotations[pos] = match mark {
+            "maybe" => Some(Mark::MaybeCamp),
+            "camp" => Some(Mark::Camp),
+            "grass" => Some(Mark::Grass),
+            "none" => None,
+            _ => return Err(command_error(format!("unknown mark {:?}", mark))),
+        };
+        Ok(String::new())
+    }
+
+    fn merge(&mut self) -> Result<String, Error> {
+        let merged = self.board.merge_annotations(&mut self.annotations)?;
+        Ok(format!("Merged {} marks", merged))
+    }
+
+    fn marks(&self) -> String {
+        let options = RenderOptions {
+            labels: true,
+            annotations: Some(self.annotations.clone()),
+            ..Default::default()
+        };
+        render::text(&self.board, &options)
+    }
+
+    /// Parse a row and column inside the `Board`.
+    fn parse_pos(&self, row: &str, column: &str) -> Result<Pos, Error> {
+        let pos = Pos::new(parse_index(row)?, parse_index(column)?);
+        if pos.row >= self.board.num_rows() || pos.column >= self.board.num_columns() {
+            return Err(command_error(format!(
+                "row {}, column {} is outside the board",
+                pos.row, pos.column
+            )));
+        }
+        Ok(pos)
+    }
+
+    fn hint(&self) -> Result<String, Error> {
+        self.check_rules()?;
+        let mut solved = self.board.clone();
+        if let Err(e @ Error::Contradiction { .. }) = solved.solve_with(&self.config) {
+            return Err(e);
+        }
+        let hint = self
+            .board
+            .positions_of(Unassigned)
+            .find(|&pos| solved[pos] != Unassigned);
+        Ok(match hint {
+            Some(pos) => format!(
+                "place {} {} {}",
+                pos.row,
+                pos.column,
+                tile_name(solved[pos])
+            ),
+            None => "No hint".to_string(),
+        })
+    }
+
+    fn undo(&mut self) -> Result<String, Error> {
+        match self.board.undo() {
+            Some(_) => Ok(String::new()),
+            None => Err(command_error("nothing to undo".to_string())),
+        }
+    }
+
+    fn save(&self, path: &str) -> Result<String, Error> {
+        File::create(path)
+            .and_then(|file| self.board.save_state(file))
+            .map_err(|x| command_error(format!("{}: {}", path, x)))?;
+        Ok(String::new())
+    }
+
+    fn load(&mut self, path: &str) -> Result<String, Error> {
+        let file = File::open(path).map_err(|x| command_error(format!("{}: {}", path, x)))?;
+        let limits = self.board.limits.clone();
+        self.board = Board::load_state(file)?;
+        self.board.limits = limits;
+        self.annotations = Annotations::for_grid(&self.board);
+        Ok(String::new())
+    }
+
+    fn check(&self) -> Result<String, Error> {
+        let remaining = self.board.count(Unassigned);
+        let violations = if remaining > 0 {
+            self.board.check_consistency()
+        } else {
+            self.board.verify_solution()
+        };
+        Ok(if !violations.is_empty() {
+            let lines: Vec<String> = violations.iter().map(ToString::to_string).collect();
+            lines.join("\n")
+        } else if remaining > 0 {
+            format!("OK with {} tiles remaining", remaining)
+        } else {
+            "Solved".to_string()
+        })
+    }
+
+    /// Return an [`Error::Contradiction`] if the `Board` breaks the
+    /// rules.
+    ///
+    /// [`Error::Contradiction`]: enum.Error.html#variant.Contradiction
+    fn check_rules(&self) -> Result<(), Error> {
+        match find_contradiction(&self.board) {
+            Some((cell, reason)) => Err(Error::Contradiction { cell, reason }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The text printed by `help`.
+const HELP: &str = "place ROW COLUMN camp|grass|empty
+mark ROW COLUMN maybe|camp|grass|none
+merge
+marks
+hint
+undo
+check
+show
+save FILE
+load FILE
+help";
+
+/// An [`Error::Format`] for a bad command.
+///
+/// [`Error::Format`]: enum.Error.html#variant.Format
+fn command_error(message: String) -> Error {
+    Error::Format {
+        format: "command",
+        message,
+    }
+}
+
+/// Parse a row or column number.
+fn parse_index(s: &str) -> Result<usize, Error> {
+    s.parse()
+        .map_err(|_| command_error(format!("expected a number but found {:?}", s)))
+}
+
+/// The name of `tile` used by `place`.
+fn tile_name(tile: Tile) -> &'static str {
+    match tile {
+        Unassigned => "empty",
+        Grass => "grass",
+        Camp => "camp",
+        Tree => "tree",
+        Blocked => "blocked",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repl() -> Repl {
+        Repl::new(Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap())
+    }
+
+    #[test]
+    fn place_and_undo() {
+        let mut repl = repl();
+        assert_eq!(repl.run("place 1 0 grass"), Ok(String::new()));
+        assert_eq!(repl.run("  place 0 0   camp "), Ok(String::new()));
+        assert_eq!(repl.board.debug(), "CT\n- ");
+        assert_eq!(repl.run("undo"), Ok(String::new()));
+        assert_eq!(repl.run("undo"), Ok(String::new()));
+        assert_eq!(repl.board.debug(), " T\n  ");
+        assert_eq!(
+            repl.run("undo"),
+            Err(command_error("nothing to undo".to_string()))
+        );
+    }
+
+    #[test]
+    fn place_errors() {
+        let mut repl = repl();
+        assert_eq!(
+            repl.run("place 0 1 grass"),
+            Err(Error::TreeChanged {
+                pos: Pos::new(0, 1)
+            })
+        );
+        assert_eq!(
+            repl.run("place 2 0 grass"),
+            Err(command_error(
+                "row 2, column 0 is outside the board".to_string()
+            ))
+        );
+        assert_eq!(
+            repl.run("place 0 0 tree"),
+            Err(command_error("unknown tile \"tree\"".to_string()))
+        );
+        assert_eq!(
+            repl.run("place x 0 camp"),
+            Err(command_error(
+                "expected a number but found \"x\"".to_string()
+            ))
+        );
+        assert_eq!(
+            repl.run("place 0 0"),
+            Err(command_error("unknown command \"place 0 0\"".to_string()))
+        );
+        assert_eq!(repl.board.debug(), " T\n  ");
+    }
+
+    #[test]
+    fn place_breaking_rules() {
+        let mut repl = repl();
+        assert_eq!(
+            repl.run("place 1 0 camp"),
+            Err(Error::ClueExceeded {
+                pos: Pos::new(1, 0),
+                reason: Contradiction::RowOverfilled,
+            })
+        );
+        assert_eq!(
+            repl.run("undo"),
+            Err(command_error("nothing to undo".to_string()))
+        );
+    }
+
+    #[test]
+    fn mark_and_merge() {
+        let mut repl = repl();
+        assert_eq!(repl.run("mark 0 0 maybe"), Ok(String::new()));
+        assert_eq!(repl.run("mark 1 0 grass"), Ok(String::new()));
+        assert_eq!(repl.run("mark 1 1 grass"), Ok(String::new()));
+        assert_eq!(repl.run("mark 1 1 none"), Ok(String::new()));
+        assert_eq!(repl.run("marks"), Ok("  AB\n1 ?T\n2 . ".to_string()));
+        assert_eq!(repl.board.debug(), " T\n  ");
+        assert_eq!(repl.run("merge"), Ok("Merged 1 marks".to_string()));
+        assert_eq!(repl.board.debug(), " T\n- ");
+        assert_eq!(repl.annotations.count(), 1);
+        assert_eq!(
+            repl.run("mark 0 0 tent"),
+            Err(command_error("unknown mark \"tent\"".to_string()))
+        );
+        assert_eq!(
+            repl.run("mark 0 2 camp"),
+            Err(command_error(
+                "row 0, column 2 is outside the board".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn hint_and_check() {
+        let mut repl = repl();
+        assert_eq!(
+            repl.run("check"),
+            Ok("OK with 3 tiles remaining".to_string())
+        );
+        assert_eq!(repl.run("hint"), Ok("place 0 0 camp".to_string()));
+        repl.run("place 0 0 camp").unwrap();
+        assert_eq!(repl.run("hint"), Ok("place 1 0 grass".to_string()));
+        repl.run("place 1 0 grass").unwrap();
+        repl.run("place 1 1 grass").unwrap();
+        assert_eq!(repl.run("check"), Ok("Solved".to_string()));
+        assert_eq!(repl.run("hint"), Ok("No hint".to_string()));
+    }
+
+    #[test]
+    fn check_full_board() {
+        let board = Board::new_parse(vec![2, 0], vec![1, 0, 1], " T \n-T-").unwrap();
+        let mut repl = Repl::new(board);
+        repl.run("place 0 0 camp").unwrap();
+        repl.run("place 0 2 camp").unwrap();
+        assert_eq!(
+            repl.run("check"),
+            Ok("Camp at row 0, column 2 has no tree of its own\n\
+                Tree at row 1, column 1 has no camp of its own"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn check_contradiction() {
+        let mut repl = repl();
+        repl.run("place 0 0 grass").unwrap();
+        assert_eq!(
+            repl.run("check"),
+            Ok("Row 0 has room for 0 camps but needs 1".to_string())
+        );
+        assert_eq!(
+            repl.run("hint"),
+            Err(Error::Contradiction {
+                cell: Pos::new(0, 0),
+                reason: Contradiction::RowUnderfilled,
+            })
+        );
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = std::env::temp_dir().join("camps_and_trees_repl_save_and_load.json");
+        let path = path.to_str().unwrap();
+        let mut repl = repl();
+        repl.run("place 0 0 camp").unwrap();
+        assert_eq!(repl.run(&format!("save {}", path)), Ok(String::new()));
+        let mut loaded = Repl::new(Board::new_parse_clue_free(0, " ").unwrap());
+        assert_eq!(loaded.run(&format!("load {}", path)), Ok(String::new()));
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(loaded.board, repl.board);
+        assert_eq!(loaded.run("undo"), Ok(String::new()));
+        assert_eq!(loaded.board.debug(), " T\n  ");
+        assert!(loaded.run(&format!("load {}", path)).is_err());
+    }
+
+    #[test]
+    fn show_and_blank() {
+        let mut repl = repl();
+        assert_eq!(repl.run("show"), Ok(repl.board.display_with_clues()));
+        assert_eq!(repl.run(""), Ok(String::new()));
+        assert_eq!(repl.run("help"), Ok(HELP.to_string()));
+    }
+}
@@ -0,0 +1,482 @@
+use board::*;
+use clue::*;
+use grid::*;
+use pos::*;
+use std::ops::Range;
+use tile::Tile::{self, *};
+
+/// How many rows or columns [`Grid::padded`] adds on each side.
+///
+/// [`Grid::padded`]: struct.Grid.html#method.padded
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Edges {
+    /// Rows added above.
+    pub top: usize,
+    /// Rows added below.
+    pub bottom: usize,
+    /// Columns added on the left.
+    pub left: usize,
+    /// Columns added on the right.
+    pub right: usize,
+}
+
+impl Edges {
+    /// The same amount on every side.
+    pub fn all(n: usize) -> Self {
+        Edges {
+            top: n,
+            bottom: n,
+            left: n,
+            right: n,
+        }
+    }
+}
+
+/// Could `tile` be or become a [`Camp`]?
+///
+/// [`Camp`]: enum.Tile.html#variant.Camp
+fn holds_camp(tile: Tile) -> bool {
+    tile == Camp || tile == Unassigned
+}
+
+impl Grid {
+    /// A copy of the part of the `Grid` in `rows` and `columns`.
+    ///
+    /// The [`RuleSet`] is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Grid;
+    /// let grid = Grid::parse("T C\n - \nT  ").unwrap();
+    /// assert_eq!(grid.cropped(0..2, 1..3).debug(), " C\n- ");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This will `panic` if `rows` or `columns` reach outside the
+    /// `Grid`.
+    ///
+    /// [`RuleSet`]: struct.RuleSet.html
+    pub fn cropped(&self, rows: Range<usize>, columns: Range<usize>) -> Grid {
+        assert!(
+            rows.start <= rows.end
+                && rows.end <= self.num_rows()
+                && columns.start <= columns.end
+                && columns.end <= self.num_columns(),
+            "{:?}x{:?} is outside the {}x{} Grid",
+            rows,
+            columns,
+            self.num_rows(),
+            self.num_columns()
+        );
+        self.reshaped(rows.len(), columns.len(), |pos| {
+            self[(rows.start + pos.row, columns.start + pos.column)]
+        })
+    }
+
+    /// A copy of the `Grid` with `edges` rows and columns of `tile`
+    /// added around it.
+    ///
+    /// The [`RuleSet`] is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Edges, Grid, Tile::*};
+    /// let grid = Grid::parse("TC").unwrap();
+    /// let edges = Edges {
+    ///     bottom: 1,
+    ///     left: 1,
+    ///     ..Edges::default()
+    /// };
+    /// assert_eq!(grid.padded(edges, Grass).debug(), "-TC\n---");
+    /// ```
+    ///
+    /// [`RuleSet`]: struct.RuleSet.html
+    pub fn padded(&self, edges: Edges, tile: Tile) -> Grid {
+        let height = edges.top + self.num_rows() + edges.bottom;
+        let width = edges.left + self.num_columns() + edges.right;
+        self.reshaped(height, width, |pos| {
+            let row = pos.row.checked_sub(edges.top);
+            let column = pos.column.checked_sub(edges.left);
+            match (row, column) {
+                (Some(row), Some(column)) => self.get(row, column).unwrap_or(tile),
+                _ => tile,
+            }
+        })
+    }
+
+    /// A copy of the `Grid` with `rows` rows and `columns` columns,
+    /// keeping the top left corner.
+    ///
+    /// Rows and columns are cut off the bottom and right, or added
+    /// there as `tile`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Grid, Tile::*};
+    /// let grid = Grid::parse("T C\n - ").unwrap();
+    /// assert_eq!(grid.resized(3, 2, Unassigned).debug(), "T \n -\n  ");
+    /// ```
+    pub fn resized(&self, rows: usize, columns: usize, tile: Tile) -> Grid {
+        let (height, width) = (self.num_rows(), self.num_columns());
+        let edges = Edges {
+            bottom: rows.saturating_sub(height),
+            right: columns.saturating_sub(width),
+            ..Edges::default()
+        };
+        self.cropped(0..height.min(rows), 0..width.min(columns))
+            .padded(edges, tile)
+    }
+
+    /// Build a `height` by `width` `Grid` with the same [`RuleSet`],
+    /// whose `Tile` at each `Pos` is `source(pos)`.
+    ///
+    /// [`RuleSet`]: struct.RuleSet.html
+    fn reshaped<F: Fn(Pos) -> Tile>(&self, height: usize, width: usize, source: F) -> Grid {
+        let mut grid = Grid::blank(height, width);
+        grid.set_rules(self.rules());
+        for row in 0..height {
+            for column in 0..width {
+                let pos = Pos::new(row, column);
+                grid[pos] = source(pos);
+            }
+        }
+        grid
+    }
+}
+
+impl Board {
+    /// A copy of the part of the `Board` in `rows` and `columns`, like
+    /// [`Grid::cropped`].
+    ///
+    /// A clue is kept if no [`Camp`] or [`Unassigned`] `Tile` was cut
+    /// off its line, since then it still counts the same [`Camp`]s.
+    /// Otherwise it is replaced by [`Clue::any`].  A clue-free `Board`
+    /// keeps its `total` the same way, and otherwise gets one [`Camp`]
+    /// per [`Tree`].  As with [`Board::rotated`], the
+    /// [`Board::limits`] are kept but the [`Board::history`] and
+    /// [`Board::observer`] aren't.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, Clue};
+    /// let board = Board::new_parse(vec![1, 1], vec![1, 0, 1], "T-C\nC-T").unwrap();
+    /// let cropped = board.cropped(0..2, 0..2);
+    /// assert_eq!(cropped.debug(), "T-\nC-");
+    /// assert_eq!(cropped.row_ranges(), vec![Clue::any(), Clue::exactly(1)]);
+    /// assert_eq!(cropped.column_ranges(), vec![Clue::exactly(1), Clue::exactly(0)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// See [`Grid::cropped`].
+    ///
+    /// [`Grid::cropped`]: struct.Grid.html#method.cropped
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Clue::any`]: struct.Clue.html#method.any
+    /// [`Board::rotated`]: struct.Board.html#method.rotated
+    /// [`Board::limits`]: struct.Board.html#structfield.limits
+    /// [`Board::history`]: struct.Board.html#structfield.history
+    /// [`Board::observer`]: struct.Board.html#structfield.observer
+    pub fn cropped(&self, rows: Range<usize>, columns: Range<usize>) -> Board {
+        let grid = (**self).cropped(rows.clone(), columns.clone());
+        let cut = |pos: Pos| !rows.contains(&pos.row) || !columns.contains(&pos.column);
+        let kept = |clue: Clue, line: Vec<(Pos, Tile)>| {
+            if line
+                .into_iter()
+                .any(|(pos, tile)| cut(pos) && holds_camp(tile))
+            {
+                Clue::any()
+            } else {
+                clue
+            }
+        };
+        let (row_clues, column_clues) = if self.is_clue_free() {
+            (Vec::new(), Vec::new())
+        } else {
+            (
+                rows.clone()
+                    .map(|row| kept(self.row_range(row), self.row(row).cells().collect()))
+                    .collect(),
+                columns
+                    .clone()
+                    .map(|column| {
+                        kept(
+                            self.column_range(column),
+                            self.column(column).cells().collect(),
+                        )
+                    })
+                    .collect(),
+            )
+        };
+        let changed = self.cells().any(|(pos, tile)| cut(pos) && holds_camp(tile));
+        self.resized_to(grid, row_clues, column_clues, changed)
+    }
+
+    /// A copy of the `Board` with `edges` rows and columns of `tile`
+    /// added around it, like [`Grid::padded`].
+    ///
+    /// Unless `tile` is a [`Camp`] or [`Unassigned`], the clues and a
+    /// clue-free `total` are kept, since the added `Tile`s don't
+    /// change any count.  The new lines then get clues of `0`.
+    /// Otherwise the clues of lines that grew and of the new lines are
+    /// [`Clue::any`], and a clue-free `Board` gets one [`Camp`] per
+    /// [`Tree`].  See [`Board::cropped`] for what else is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, Edges, Tile::*};
+    /// let board = Board::new_parse(vec![1], vec![1, 0], "CT").unwrap();
+    /// let padded = board.padded(Edges::all(1), Grass);
+    /// assert_eq!(padded.debug(), "----\n-CT-\n----");
+    /// assert_eq!(padded.row_clues(), vec![Some(0), Some(1), Some(0)]);
+    /// assert_eq!(padded.column_clues(), vec![Some(0), Some(1), Some(0), Some(0)]);
+    /// ```
+    ///
+    /// [`Grid::padded`]: struct.Grid.html#method.padded
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Clue::any`]: struct.Clue.html#method.any
+    /// [`Board::cropped`]: struct.Board.html#method.cropped
+    pub fn padded(&self, edges: Edges, tile: Tile) -> Board {
+        let grid = (**self).padded(edges, tile);
+        let changed = holds_camp(tile) && edges != Edges::default();
+        let new = if holds_camp(tile) {
+            Clue::any()
+        } else {
+            Clue::exactly(0)
+        };
+        let pad = |clues: Vec<Clue>, before: usize, after: usize, grew: bool| {
+            if self.is_clue_free() {
+                return Vec::new();
+            }
+            let grown = |clue| {
+                if grew && holds_camp(tile) {
+                    Clue::any()
+                } else {
+                    clue
+                }
+            };
+            let mut padded = vec![new; before];
+            padded.extend(clues.into_iter().map(grown));
+            padded.extend(vec![new; after]);
+            padded
+        };
+        let rows = pad(
+            self.row_ranges(),
+            edges.top,
+            edges.bottom,
+            edges.left + edges.right > 0,
+        );
+        let columns = pad(
+            self.column_ranges(),
+            edges.left,
+            edges.right,
+            edges.top + edges.bottom > 0,
+        );
+        self.resized_to(grid, rows, columns, changed)
+    }
+
+    /// A copy of the `Board` with `rows` rows and `columns` columns,
+    /// keeping the top left corner, like [`Grid::resized`].
+    ///
+    /// This is [`Board::cropped`] followed by [`Board::padded`], which
+    /// say how the clues change.
+    ///
+    /// [`Grid::resized`]: struct.Grid.html#method.resized
+    /// [`Board::cropped`]: struct.Board.html#method.cropped
+    /// [`Board::padded`]: struct.Board.html#method.padded
+    pub fn resized(&self, rows: usize, columns: usize, tile: Tile) -> Board {
+        let (height, width) = (self.num_rows(), self.num_columns());
+        let edges = Edges {
+            bottom: rows.saturating_sub(height),
+            right: columns.saturating_sub(width),
+            ..Edges::default()
+        };
+        self.cropped(0..height.min(rows), 0..width.min(columns))
+            .padded(edges, tile)
+    }
+
+    /// The part of the `Board` in `rows` and `columns`, with clues that
+    /// count only the [`Camp`]s inside it.
+    ///
+    /// Unlike [`Board::cropped`], which omits the clue of every line
+    /// that lost a [`Camp`] or an [`Unassigned`] cell, each clue has
+    /// the [`Camp`]s cut from its line taken off.  Every [`Unassigned`]
+    /// cell that was cut could still hold a [`Camp`] too, so it lowers
+    /// the least the clue allows but not the most, making an exact clue
+    /// a range.  The `total` likewise loses the [`Camp`]s that were cut,
+    /// and if an [`Unassigned`] cell was cut it is worked out again as
+    /// in [`Board::try_new_ranged`], or is one [`Camp`] per [`Tree`] for
+    /// a clue-free `Board`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// let board = Board::new_parse(vec![1, 0, 1], vec![1, 0, 1], "CT-\n---\n-TC").unwrap();
+    /// let corner = board.sub_board(0..3, 1..3);
+    /// assert_eq!(corner.debug(), "T-\n--\nTC");
+    /// assert_eq!(corner.row_clues(), vec![Some(0), Some(0), Some(1)]);
+    /// assert_eq!(corner.column_clues(), vec![Some(0), Some(1)]);
+    /// assert_eq!(corner.total(), 1);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// See [`Grid::cropped`].
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Board::cropped`]: struct.Board.html#method.cropped
+    /// [`Board::try_new_ranged`]: struct.Board.html#method.try_new_ranged
+    /// [`Grid::cropped`]: struct.Grid.html#method.cropped
+    pub fn sub_board(&self, rows: Range<usize>, columns: Range<usize>) -> Board {
+        let grid = (**self).cropped(rows.clone(), columns.clone());
+        let cut = |pos: Pos| !rows.contains(&pos.row) || !columns.contains(&pos.column);
+        let cut_count = |tile: Tile, line: &[(Pos, Tile)]| {
+            line.iter()
+                .filter(|&&(pos, x)| cut(pos) && x == tile)
+                .count()
+        };
+        let inside = |clue: Clue, line: Vec<(Pos, Tile)>| {
+            let camps = cut_count(Camp, &line);
+            let unassigned = cut_count(Unassigned, &line);
+            Clue {
+                min: clue.min.saturating_sub(camps + unassigned),
+                max: clue.max.map(|max| max.saturating_sub(camps)),
+            }
+        };
+        let (row_clues, column_clues) = if self.is_clue_free() {
+            (Vec::new(), Vec::new())
+        } else {
+            (
+                rows.clone()
+                    .map(|row| inside(self.row_range(row), self.row(row).cells().collect()))
+                    .collect(),
+                columns
+                    .clone()
+                    .map(|column| {
+                        inside(
+                            self.column_range(column),
+                            self.column(column).cells().collect(),
+                        )
+                    })
+                    .collect(),
+            )
+        };
+        let cells: Vec<(Pos, Tile)> = self.cells().collect();
+        let changed = cut_count(Unassigned, &cells) > 0;
+        let mut board = self.resized_to(grid, row_clues, column_clues, changed);
+        if !changed {
+            board.set_total(self.total().saturating_sub(cut_count(Camp, &cells)));
+        }
+        board
+    }
+
+    /// Build a `Board` of `grid` with the clues `rows` and `columns`.
+    ///
+    /// `changed` is whether `Tile`s that could hold a `Camp` were
+    /// added or removed, so the `total` can't be kept.
+    fn resized_to(&self, grid: Grid, rows: Vec<Clue>, columns: Vec<Clue>, changed: bool) -> Board {
+        let trees = grid.count(Tree);
+        let mut board = if self.is_clue_free() {
+            Board::new_clue_free(if changed { trees } else { self.total() }, grid)
+        } else {
+            Board::new_ranged(rows, columns, grid)
+        };
+        if !changed {
+            board.set_total(self.total());
+        }
+        board.limits = self.limits.clone();
+        board
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cropped_clue_free() {
+        let board = Board::new_parse_clue_free(2, "CT-\n---\nT-C").unwrap();
+        let cropped = board.cropped(0..2, 0..3);
+        assert!(cropped.is_clue_free());
+        assert_eq!(cropped.total(), 1);
+        let board = Board::new_parse_clue_free(1, "CT-\n---").unwrap();
+        assert_eq!(board.cropped(0..1, 0..2).total(), 1);
+    }
+
+    #[test]
+    fn padded_unassigned() {
+        let board = Board::new_parse(vec![1], vec![1, 0], "CT").unwrap();
+        let edges = Edges {
+            right: 1,
+            ..Edges::default()
+        };
+        let padded = board.padded(edges, Unassigned);
+        assert_eq!(padded.debug(), "CT ");
+        assert_eq!(padded.row_ranges(), vec![Clue::any()]);
+        assert_eq!(
+            padded.column_ranges(),
+            vec![Clue::exactly(1), Clue::exactly(0), Clue::any()]
+        );
+        assert_eq!(padded.total(), 1);
+    }
+
+    #[test]
+    fn resized_round_trip() {
+        let board = Board::new_parse(vec![1, 0], vec![1, 0], "CT\n--").unwrap();
+        let grown = board.resized(3, 3, Grass);
+        assert_eq!(grown.debug(), "CT-\n---\n---");
+        assert_eq!(grown.resized(2, 2, Grass), board);
+        let mut solved = board.resized(3, 2, Unassigned);
+        assert_eq!(solved.row_ranges()[2], Clue::any());
+        solved.solve().unwrap();
+        assert_eq!(solved.debug(), "CT\n--\n--");
+    }
+
+    #[test]
+    fn sub_board_unassigned_cut() {
+        let board = Board::new_parse(vec![1, 1], vec![1, 0, 1], "T C\n  T").unwrap();
+        let sub = board.sub_board(0..2, 0..2);
+        assert_eq!(sub.debug(), "T \n  ");
+        assert_eq!(sub.row_clues(), vec![Some(0), Some(1)]);
+        assert_eq!(sub.column_clues(), vec![Some(1), Some(0)]);
+        assert_eq!(sub.total(), 1);
+        let sub = board.sub_board(0..1, 0..3);
+        assert_eq!(sub.row_clues(), vec![Some(1)]);
+        assert_eq!(
+            sub.column_ranges(),
+            vec![Clue::between(0, 1), Clue::exactly(0), Clue::exactly(1)]
+        );
+        assert_eq!(sub.total(), 1);
+        let whole = board.sub_board(0..2, 0..3);
+        assert_eq!(whole, board);
+    }
+
+    #[test]
+    fn sub_board_clue_free() {
+        let board = Board::new_parse_clue_free(2, "CT-\n-TC").unwrap();
+        let sub = board.sub_board(1..2, 0..3);
+        assert!(sub.is_clue_free());
+        assert_eq!(sub.total(), 1);
+        let board = Board::new_parse_clue_free(2, "CT \n-TC").unwrap();
+        assert_eq!(board.sub_board(1..2, 1..3).total(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn cropped_outside() {
+        let _ = Grid::parse("TC").unwrap().cropped(0..1, 1..3);
+    }
+}
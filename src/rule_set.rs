@@ -0,0 +1,295 @@
+use error::*;
+use pos::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// The rules a [`Grid`] is played by.
+///
+/// The default is the classic game.  Variants change which [`Tile`]s
+/// count as touching and whether [`Camp`]s and [`Tree`]s are paired
+/// up, and every strategy, the verifier and [`Grid::set_camp`] follow
+/// them.
+///
+/// # Examples
+///
+/// On a torus the left and right edges touch, as do the top and
+/// bottom:
+///
+/// ```
+/// # use camps_and_trees::{Pos, RuleSet};
+/// assert_eq!(
+///     RuleSet::torus().neighbors4(Pos::new(0, 0), 3, 3),
+///     vec![(2, 0), (0, 2), (0, 1), (1, 0)]
+/// );
+/// assert_eq!(RuleSet::default().neighbors4(Pos::new(0, 0), 3, 3), vec![(0, 1), (1, 0)]);
+/// ```
+///
+/// Other variants are made by setting the fields:
+///
+/// ```
+/// # use camps_and_trees::{Pos, RuleSet};
+/// let rules = RuleSet {
+///     orthogonal_only: true,
+///     ..RuleSet::default()
+/// };
+/// assert_eq!(rules.touching(Pos::new(0, 0), 2, 2), vec![(0, 1), (1, 0)]);
+/// assert_eq!(rules.to_string(), "orthogonal");
+/// ```
+///
+/// [`Grid`]: struct.Grid.html
+/// [`Tile`]: enum.Tile.html
+/// [`Camp`]: enum.Tile.html#variant.Camp
+/// [`Tree`]: enum.Tile.html#variant.Tree
+/// [`Grid::set_camp`]: struct.Grid.html#method.set_camp
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RuleSet {
+    /// Rows and columns wrap around, so the first and last `Tile` of
+    /// each line touch.
+    pub wrap: bool,
+    /// `Camp`s only keep other `Camp`s out of the `Tile`s beside them,
+    /// so they may touch diagonally.
+    pub orthogonal_only: bool,
+    /// `Camp`s don't need a `Tree` of their own and `Tree`s don't need
+    /// a `Camp`, so `Tree`s only take up room.
+    pub no_matching: bool,
+}
+
+/// The names of the variants, in the order they are written.
+const NAMES: [&str; 3] = ["torus", "orthogonal", "unmatched"];
+
+impl RuleSet {
+    /// The rules of the classic game.
+    pub fn classic() -> Self {
+        RuleSet::default()
+    }
+
+    /// The rules of the game on a torus, where rows and columns wrap
+    /// around.
+    pub fn torus() -> Self {
+        RuleSet {
+            wrap: true,
+            ..RuleSet::default()
+        }
+    }
+
+    /// Do `Tile`s touch just as in the classic game?
+    ///
+    /// The strategies that work on bitmasks of whole lines only handle
+    /// this case.
+    pub fn has_classic_touching(self) -> bool {
+        !self.wrap && !self.orthogonal_only
+    }
+
+    /// The flags of the variants, in the same order as `NAMES`.
+    fn flags(&mut self) -> [&mut bool; 3] {
+        [
+            &mut self.wrap,
+            &mut self.orthogonal_only,
+            &mut self.no_matching,
+        ]
+    }
+
+    /// Move `pos` by `rows` and `columns` in a [`Grid`] with
+    /// `num_rows` rows and `num_columns` columns.
+    ///
+    /// Returns `None` if the result is outside the [`Grid`], which
+    /// never happens when it wraps.  See [`Pos::offset`].
+    ///
+    /// [`Grid`]: struct.Grid.html
+    /// [`Pos::offset`]: struct.Pos.html#method.offset
+    pub fn offset(
+        self,
+        pos: Pos,
+        rows: isize,
+        columns: isize,
+        num_rows: usize,
+        num_columns: usize,
+    ) -> Option<Pos> {
+        if !self.wrap {
+            return pos.offset(rows, columns, num_rows, num_columns);
+        }
+        if num_rows == 0 || num_columns == 0 {
+            return None;
+        }
+        let wrap = |x: usize, delta: isize, len: usize| {
+            (x as isize + delta).rem_euclid(len as isize) as usize
+        };
+        Some(Pos::new(
+            wrap(pos.row, rows, num_rows),
+            wrap(pos.column, columns, num_columns),
+        ))
+    }
+
+    /// Get the positions directly above, left, right, and below `pos`,
+    /// in that order, in a [`Grid`] with `num_rows` rows and
+    /// `num_columns` columns.
+    ///
+    /// On narrow wrapping [`Grid`]s each position is only given once,
+    /// and never `pos` itself.  See [`Pos::neighbors4`].
+    ///
+    /// [`Grid`]: struct.Grid.html
+    /// [`Pos::neighbors4`]: struct.Pos.html#method.neighbors4
+    pub fn neighbors4(self, pos: Pos, num_rows: usize, num_columns: usize) -> Vec<Pos> {
+        if !self.wrap {
+            return pos.neighbors4(num_rows, num_columns);
+        }
+        let mut neighbors = Vec::new();
+        for &(r, c) in &[(-1, 0), (0, -1), (0, 1), (1, 0)] {
+            if let Some(x) = self.offset(pos, r, c, num_rows, num_columns) {
+                if x != pos && !neighbors.contains(&x) {
+                    neighbors.push(x);
+                }
+            }
+        }
+        neighbors
+    }
+
+    /// Get the positions touching `pos`, including diagonally, in
+    /// reading order, in a [`Grid`] with `num_rows` rows and
+    /// `num_columns` columns.
+    ///
+    /// Like [`RuleSet::neighbors4`], each position is only given once.
+    /// See [`Pos::neighbors8`].
+    ///
+    /// [`Grid`]: struct.Grid.html
+    /// [`RuleSet::neighbors4`]: struct.RuleSet.html#method.neighbors4
+    /// [`Pos::neighbors8`]: struct.Pos.html#method.neighbors8
+    pub fn neighbors8(self, pos: Pos, num_rows: usize, num_columns: usize) -> Vec<Pos> {
+        if !self.wrap {
+            return pos.neighbors8(num_rows, num_columns);
+        }
+        let mut neighbors = Vec::new();
+        for r in -1..=1 {
+            for c in -1..=1 {
+                neighbors.extend(self.offset(pos, r, c, num_rows, num_columns));
+            }
+        }
+        neighbors.sort();
+        neighbors.dedup();
+        neighbors.retain(|&x| x != pos);
+        neighbors
+    }
+
+    /// Get the positions a [`Camp`] at `pos` keeps other [`Camp`]s out
+    /// of, in a [`Grid`] with `num_rows` rows and `num_columns`
+    /// columns.
+    ///
+    /// This is [`RuleSet::neighbors8`], or [`RuleSet::neighbors4`] if
+    /// `orthogonal_only` is set.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Grid`]: struct.Grid.html
+    /// [`RuleSet::neighbors8`]: struct.RuleSet.html#method.neighbors8
+    /// [`RuleSet::neighbors4`]: struct.RuleSet.html#method.neighbors4
+    pub fn touching(self, pos: Pos, num_rows: usize, num_columns: usize) -> Vec<Pos> {
+        if self.orthogonal_only {
+            self.neighbors4(pos, num_rows, num_columns)
+        } else {
+            self.neighbors8(pos, num_rows, num_columns)
+        }
+    }
+}
+
+impl fmt::Display for RuleSet {
+    /// Write `classic`, or the name of each variant joined by `+`, such
+    /// as `torus+unmatched`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut rules = *self;
+        let names: Vec<_> = NAMES
+            .iter()
+            .zip(rules.flags().iter())
+            .filter(|&(_, flag)| **flag)
+            .map(|(name, _)| *name)
+            .collect();
+        if names.is_empty() {
+            write!(f, "classic")
+        } else {
+            write!(f, "{}", names.join("+"))
+        }
+    }
+}
+
+impl FromStr for RuleSet {
+    type Err = Error;
+
+    /// Parse a `RuleSet` written by its `Display` implementation.
+    ///
+    /// # Errors
+    ///
+    /// Unknown names give an [`Error::Format`].
+    ///
+    /// [`Error::Format`]: enum.Error.html#variant.Format
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let mut rules = RuleSet::classic();
+        if s.trim() == "classic" {
+            return Ok(rules);
+        }
+        for name in s.split('+') {
+            let index = NAMES.iter().position(|&x| x == name.trim());
+            let index = index.ok_or_else(|| Error::Format {
+                format: "rules",
+                message: format!(
+                    "expected classic or some of {} joined by + but found {:?}",
+                    NAMES.join(", "),
+                    name.trim()
+                ),
+            })?;
+            *rules.flags()[index] = true;
+        }
+        Ok(rules)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn torus_neighbors8() {
+        let rules = RuleSet::torus();
+        assert_eq!(
+            rules.neighbors8(Pos::new(0, 0), 3, 4),
+            vec![
+                (0, 1),
+                (0, 3),
+                (1, 0),
+                (1, 1),
+                (1, 3),
+                (2, 0),
+                (2, 1),
+                (2, 3)
+            ]
+        );
+        assert_eq!(rules.neighbors8(Pos::new(1, 1), 3, 3).len(), 8);
+    }
+
+    #[test]
+    fn torus_narrow() {
+        let rules = RuleSet::torus();
+        assert_eq!(rules.neighbors4(Pos::new(0, 0), 1, 2), vec![(0, 1)]);
+        assert_eq!(rules.neighbors8(Pos::new(0, 0), 1, 1), Vec::<Pos>::new());
+        assert_eq!(rules.offset(Pos::new(0, 0), 0, -1, 0, 0), None);
+    }
+
+    #[test]
+    fn rules_round_trip() {
+        let all = RuleSet {
+            wrap: true,
+            orthogonal_only: true,
+            no_matching: true,
+        };
+        for &rules in &[RuleSet::classic(), RuleSet::torus(), all] {
+            assert_eq!(rules.to_string().parse(), Ok(rules));
+        }
+        assert_eq!(all.to_string(), "torus+orthogonal+unmatched");
+        assert_eq!(
+            "unmatched + torus".parse(),
+            Ok(RuleSet {
+                no_matching: true,
+                ..RuleSet::torus()
+            })
+        );
+        assert!("donut".parse::<RuleSet>().is_err());
+        assert!("classic+torus".parse::<RuleSet>().is_err());
+    }
+}
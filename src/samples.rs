@@ -0,0 +1,273 @@
+//! A library of named example puzzles.
+//!
+//! These are ready-made [`Board`]s for demos, benchmarks and
+//! documentation.  Most are solved by the default [`SolverConfig`];
+//! `12x12_trials` needs a `trial_depth` of `1`, and the solver only
+//! gets most of the way through `15x15`.
+//!
+//! # Examples
+//!
+//! ```
+//! # use camps_and_trees::samples;
+//! let mut board = samples::by_name("7x7_b15").unwrap();
+//! board.solve().unwrap();
+//! assert_eq!(board.num_rows(), 7);
+//! assert!(samples::all().len() > 10);
+//! ```
+//!
+//! [`Board`]: ../struct.Board.html
+//! [`SolverConfig`]: ../struct.SolverConfig.html
+
+use board::*;
+use grid::*;
+
+/// A named example puzzle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Sample {
+    /// The name [`by_name`] finds the puzzle by.  It starts with the
+    /// size as `ROWSxCOLUMNS`.
+    ///
+    /// [`by_name`]: fn.by_name.html
+    pub name: &'static str,
+    /// The row clues.
+    pub rows: &'static [usize],
+    /// The column clues.
+    pub columns: &'static [usize],
+    /// The [`Tree`]s, as read by [`Grid::parse`].
+    ///
+    /// [`Tree`]: ../enum.Tile.html#variant.Tree
+    /// [`Grid::parse`]: ../struct.Grid.html#method.parse
+    pub grid: &'static str,
+    /// The solved [`Grid`], if the solver can find it.
+    ///
+    /// [`Grid`]: ../struct.Grid.html
+    pub solution: Option<&'static str>,
+}
+
+impl Sample {
+    /// Make the unsolved [`Board`].
+    ///
+    /// [`Board`]: ../struct.Board.html
+    pub fn board(&self) -> Board {
+        Board::new_parse(self.rows.to_vec(), self.columns.to_vec(), self.grid).unwrap()
+    }
+
+    /// Parse the [`solution`], if there is one.
+    ///
+    /// [`solution`]: struct.Sample.html#structfield.solution
+    pub fn solution(&self) -> Option<Grid> {
+        self.solution.map(|x| Grid::parse(x).unwrap())
+    }
+}
+
+/// Every sample, from smallest to largest.
+const SAMPLES: &[Sample] = &[
+    Sample {
+        name: "5x5_1",
+        rows: &[1, 1, 0, 2, 1],
+        columns: &[2, 0, 1, 1, 1],
+        grid: "     \n T T \n     \nTT   \n    T",
+        solution: Some("---C-\nCT-T-\n-----\nTTC-C\nC---T"),
+    },
+    Sample {
+        name: "5x5_2",
+        rows: &[2, 0, 1, 0, 2],
+        columns: &[1, 1, 1, 1, 1],
+        grid: " T T \n     \n     \n T   \n TT  ",
+        solution: Some("-TCTC\n-----\n-C---\n-T---\nCTTC-"),
+    },
+    Sample {
+        name: "5x5_10",
+        rows: &[1, 2, 1, 0, 1],
+        columns: &[2, 0, 1, 1, 1],
+        grid: " T   \nT  T \n  T  \n     \n    T",
+        solution: Some("CT---\nT-CTC\nC-T--\n-----\n---CT"),
+    },
+    Sample {
+        name: "5x5_matching",
+        rows: &[1, 1, 2, 0, 2],
+        columns: &[1, 1, 1, 1, 2],
+        grid: "     \n T  T\n   T \nTT   \n  T  ",
+        solution: Some("----C\n-TC-T\nC--TC\nTT---\n-CTC-"),
+    },
+    Sample {
+        name: "4x7",
+        rows: &[3, 0, 2, 1],
+        columns: &[1, 1, 0, 1, 1, 1, 1],
+        grid: " T     \n   TT T\nT      \n      T",
+        solution: Some("CT--C-C\n---TT-T\nTC-C---\n-----CT"),
+    },
+    Sample {
+        name: "4x7_2",
+        rows: &[2, 1, 2, 0],
+        columns: &[1, 1, 0, 1, 1, 0, 1],
+        grid: "  T    \n    T  \n T    T\n   T   ",
+        solution: Some("-CT-C--\n----T-C\nCT-C--T\n---T---"),
+    },
+    Sample {
+        name: "7x4",
+        rows: &[1, 1, 0, 1, 1, 1, 1],
+        columns: &[3, 0, 2, 1],
+        grid: "  T \nT   \n    \n T  \n T  \n    \n T T",
+        solution: Some("C-T-\nT-C-\n----\n-TC-\nCT--\n---C\nCT-T"),
+    },
+    Sample {
+        name: "6x6_a5",
+        rows: &[0, 3, 0, 2, 0, 2],
+        columns: &[1, 1, 2, 1, 0, 2],
+        grid: "     T\n   T  \nT     \n  T   \n T    \n   TT ",
+        solution: Some("-----T\nC-CT-C\nT-----\n-CTC--\n-T----\n--CTTC"),
+    },
+    Sample {
+        name: "6x6_b10",
+        rows: &[1, 1, 1, 2, 1, 2],
+        columns: &[2, 1, 2, 0, 1, 2],
+        grid: "     T\nT     \n  T   \n     T\nT   T \n T T  ",
+        solution: Some("----CT\nTC----\n--T--C\nC-C--T\nT---TC\nCTCT--"),
+    },
+    Sample {
+        name: "7x7_a10",
+        rows: &[1, 2, 2, 1, 2, 0, 3],
+        columns: &[2, 1, 2, 1, 2, 0, 3],
+        grid: "   T   \n  T  T \nT    T \n      T\nT   T  \n   T  T\n T     ",
+        solution: Some("--CT---\nC-T-CT-\nT-C--TC\n----C-T\nTC--T-C\n---T--T\nCT-C--C"),
+    },
+    Sample {
+        name: "7x7_b15",
+        rows: &[2, 1, 2, 1, 2, 1, 2],
+        columns: &[2, 1, 1, 2, 2, 1, 2],
+        grid: " T T T \n   T   \nT      \n   T T \nT      \n  T T T\n       ",
+        solution: Some("-T-TCTC\n-C-T---\nT--C-C-\nC--T-T-\nT--C--C\nC-T-T-T\n--C-C--"),
+    },
+    Sample {
+        name: "7x7_b20",
+        rows: &[3, 0, 1, 1, 1, 2, 2],
+        columns: &[2, 1, 2, 1, 1, 2, 1],
+        grid: " T  T  \nT      \n    T  \n       \nTT  T T\n       \n   T T ",
+        solution: Some("CTC-TC-\nT------\n----TC-\n-C-----\nTT-CT-T\nC-----C\n--CTCT-"),
+    },
+    Sample {
+        name: "8x8_b2",
+        rows: &[2, 1, 2, 1, 3, 1, 1, 3],
+        columns: &[3, 1, 1, 2, 2, 1, 2, 2],
+        grid: "  T     \nT    T T\n     T  \n    T   \n        \nT T  T T\n  T T   \n T    T ",
+        solution: Some("-CT----C\nT---CT-T\nC----TC-\n---CT---\nC----C-C\nT-TC-T-T\n--T-T-C-\nCTC-C-T-"),
+    },
+    Sample {
+        name: "8x8_b5",
+        rows: &[1, 3, 1, 3, 1, 2, 1, 3],
+        columns: &[4, 0, 3, 1, 1, 2, 2, 2],
+        grid: "T  T    \n     T  \nT    TTT\n  T     \nT       \n  T  T  \n  T    T\n T    T ",
+        solution: Some("T-CT----\nC---CTC-\nT-C--TTT\nC-T--C-C\nT-C-----\nC-T--TC-\n--TC---T\nCT---CTC"),
+    },
+    Sample {
+        name: "8x8_b9",
+        rows: &[1, 1, 1, 2, 2, 1, 2, 2],
+        columns: &[1, 1, 2, 1, 1, 3, 1, 2],
+        grid: "        \n T   T  \n    TT  \n       T\nT  T    \n        \n  TT T  \n    T  T",
+        solution: Some("-----C--\n-TC--T--\n----TTC-\nC---C--T\nT-CT---C\n-----C--\n-CTT-T-C\n---CTC-T"),
+    },
+    Sample {
+        name: "8x8_b10",
+        rows: &[2, 2, 2, 0, 3, 0, 3, 1],
+        columns: &[1, 3, 1, 2, 1, 2, 0, 3],
+        grid: "  T T  T\n    T   \n        \n T T    \n      T \n TT    T\n     T  \nT     T ",
+        solution: Some("-CTCT--T\n----TC-C\n-C-C----\n-T-T----\n-C---CTC\n-TT----T\nC-C-CT--\nT-----TC"),
+    },
+    Sample {
+        name: "8x8_b13",
+        rows: &[3, 1, 2, 1, 1, 2, 1, 3],
+        columns: &[4, 0, 2, 1, 3, 1, 1, 2],
+        grid: "       T\nT  TTT  \n T      \n        \n  T T   \n T     T\n        \n T T TT ",
+        solution: Some("C--C-C-T\nT--TTT-C\nCT--C---\n--C-----\n--T-T--C\nCT--C--T\n------C-\nCTCTCTT-"),
+    },
+    Sample {
+        name: "8x8_b15",
+        rows: &[2, 1, 2, 1, 2, 2, 1, 1],
+        columns: &[2, 2, 1, 2, 0, 3, 1, 1],
+        grid: "T      T\n   T    \n        \n  T  T T\nT   T   \n  T  T  \n T T    \n        ",
+        solution: Some("T--C--CT\nC--T----\n--C--C--\nC-T--T-T\nT---TC-C\n-CTC-T--\n-T-T-C--\n-C------"),
+    },
+    Sample {
+        name: "8x8_pairs",
+        rows: &[1, 2, 2, 2, 1, 2, 1, 2],
+        columns: &[1, 2, 1, 1, 3, 1, 1, 3],
+        grid: "   T   T\nT       \n      T \n  TT  T \nT       \n T   T T\n        \n   T  T ",
+        solution: Some("---TC--T\nTC-----C\n---C-CT-\nC-TT--TC\nT-C-----\n-T--CTCT\n-C------\n---TC-TC"),
+    },
+    Sample {
+        name: "10x10_pairs",
+        rows: &[3, 1, 3, 0, 3, 1, 1, 3, 1, 4],
+        columns: &[3, 2, 0, 3, 1, 2, 3, 1, 2, 3],
+        grid: "  T    T T\n    T     \n T     T  \n         T\nT T T    T\n          \n T        \n     TT   \nT  T    TT\n      T T ",
+        solution: Some("-CT---CTCT\n---CT-----\nCT----CT-C\n---------T\nT-TCTC--CT\nC---------\n-T----C---\n-C--CTT--C\nT--T---CTT\nC--C-CT-TC"),
+    },
+    Sample {
+        name: "12x12_trials",
+        rows: &[2, 4, 2, 3, 0, 6, 0, 2, 4, 1, 3, 2],
+        columns: &[2, 3, 2, 3, 1, 3, 2, 3, 1, 4, 1, 4],
+        grid: " T        T \n T      T T \n    TT  T   \n  T    T    \n       T T T\n    T T     \n T         T\n      T     \n T  T   T   \nT       T TT\n   T T      \n T   T      ",
+        solution: Some("-TC------CT-\nCT---C-CT-TC\n---CTT--TC--\n-CT---CT---C\n-------T-T-T\n-C-CTCTC-C-C\n-T---------T\n----C-TC----\nCTC-T---TC-C\nT----C--T-TT\n-C-T-T--C-C-\n-T-C-TC-----"),
+    },
+    Sample {
+        name: "15x15",
+        rows: &[2, 4, 1, 2, 4, 2, 4, 2, 4, 1, 3, 3, 2, 2, 4],
+        columns: &[3, 3, 2, 4, 0, 3, 3, 2, 4, 0, 5, 0, 5, 1, 5],
+        grid: "         T   T \n  T    T      T\nT    T    T    \n             TT\nT T       T    \n      TT     T \n   T           \nT   T  T T    T\n       T     T \n  T            \nT T    T      T\n        T   T  \n T       TT    \n  T T       T  \n     T     T T ",
+        solution: None,
+    },
+    Sample {
+        name: "20x20",
+        rows: &[7, 3, 7, 1, 6, 3, 3, 5, 3, 5, 3, 4, 3, 4, 3, 5, 3, 5, 4, 5],
+        columns: &[6, 2, 5, 4, 3, 4, 4, 5, 3, 4, 4, 5, 2, 5, 2, 7, 2, 5, 3, 7],
+        grid: "  T     T T       T \nT   T  T   T   T T T\n    T     T TT    T \n            T  T    \n T T  T   T         \n     T     TT   T   \n              T T  T\n    TT   T  T     T \n T     T  T   T     \n T    T     T    TT \nT   T               \n   T  TT       T   T\nT           T T  TT \n T  T               \n  T  T   T        T \n   T      T  TT     \n      T  T T T      \nT  T       T     TT \n T T          TT   T\n   T T    T         ",
+        solution: Some("--TC---CTCTC---C-CTC\nTC--TC-T---T-C-T-T-T\n---CT--C-CTCTT-C-CTC\n------------TC-T----\nCT-T-CTC-CTC----C---\n---C-T-----TTC--T--C\n-----C--------TCTC-T\n-C--TT-C-TC-TC----TC\n-T--C--T--T---TC-C--\nCTC---TC--C-T----TTC\nT---TC------C--C----\nC-CT--TTC------T--CT\nT-----C----CT-T-CTT-\nCT-CT---------C---C-\n--T--TC-CTC-------T-\n--CTC-----T-CTTC--C-\nC-----T-CTCT-T------\nT-CT--C----T-C-C-TTC\nCT-TC------C--TT-C-T\n--CT-TC--CT---C----C"),
+    },
+];
+
+/// Get every sample, from smallest to largest.
+pub fn all() -> &'static [Sample] {
+    SAMPLES
+}
+
+/// Make the [`Board`] of the sample called `name`, or `None` if there
+/// isn't one.
+///
+/// [`Board`]: ../struct.Board.html
+pub fn by_name(name: &str) -> Option<Board> {
+    SAMPLES.iter().find(|x| x.name == name).map(Sample::board)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solver_config::*;
+
+    #[test]
+    fn samples_solve() {
+        let config = SolverConfig {
+            trial_depth: 1,
+            ..Default::default()
+        };
+        for sample in all() {
+            let mut board = sample.board();
+            assert_eq!(board.validate(), Ok(()), "{}", sample.name);
+            if let Some(solution) = sample.solution() {
+                board.solve_with(&config).unwrap();
+                assert_eq!(*board, solution, "{}", sample.name);
+            }
+        }
+    }
+
+    #[test]
+    fn sample_names() {
+        for (i, sample) in all().iter().enumerate() {
+            let board = sample.board();
+            let size = format!("{}x{}", board.num_rows(), board.num_columns());
+            assert!(sample.name.starts_with(&size), "{}", sample.name);
+            assert!(all()[..i].iter().all(|x| x.name != sample.name));
+        }
+        assert_eq!(by_name("7x7_b15").map(|x| x.num_rows()), Some(7));
+        assert_eq!(by_name("7x7"), None);
+    }
+}
@@ -0,0 +1,140 @@
+use board::*;
+use error::*;
+use grid::*;
+use solve_outcome::*;
+use tile::Tile::*;
+
+impl Board {
+    /// Find up to `limit` solutions of the `Board`.
+    ///
+    /// The solver deduces what it can, then the search tries a
+    /// [`Camp`] and then [`Grass`] on the first [`Unassigned`] `Tile`
+    /// that is left, and solves again.  The `Board` itself isn't
+    /// changed.
+    ///
+    /// # Examples
+    ///
+    /// Either cell beside the `Tree` can hold its `Camp`:
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// let board = Board::new_parse_clue_free(1, " T ").unwrap();
+    /// let solutions = board.find_solutions(5).unwrap();
+    /// assert_eq!(solutions.len(), 2);
+    /// assert_eq!(solutions[0].debug(), "CT-");
+    /// assert_eq!(solutions[1].debug(), "-TC");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the [`Board::limits`] stop the solver, [`Error::Stopped`] is
+    /// returned, since some solutions might not have been found.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Grass`]: enum.Tile.html#variant.Grass
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    /// [`Board::limits`]: struct.Board.html#structfield.limits
+    /// [`Error::Stopped`]: enum.Error.html#variant.Stopped
+    pub fn find_solutions(&self, limit: usize) -> Result<Vec<Grid>, Error> {
+        let mut board = self.clone();
+        board.observer = None;
+        board.history.clear();
+        let mut solutions = Vec::new();
+        if limit > 0 {
+            search(board, limit, &mut solutions)?;
+        }
+        Ok(solutions)
+    }
+
+    /// Count the solutions of the `Board`, stopping once there are
+    /// `limit` of them.
+    ///
+    /// See [`Board::find_solutions`].
+    ///
+    /// [`Board::find_solutions`]: struct.Board.html#method.find_solutions
+    pub fn count_solutions(&self, limit: usize) -> Result<usize, Error> {
+        self.find_solutions(limit).map(|solutions| solutions.len())
+    }
+
+    /// Does the `Board` have exactly one solution?
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+    /// assert_eq!(board.has_unique_solution(), Ok(true));
+    /// let board = Board::new_parse_clue_free(1, " T ").unwrap();
+    /// assert_eq!(board.has_unique_solution(), Ok(false));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`Board::find_solutions`].
+    ///
+    /// [`Board::find_solutions`]: struct.Board.html#method.find_solutions
+    pub fn has_unique_solution(&self) -> Result<bool, Error> {
+        self.count_solutions(2).map(|count| count == 1)
+    }
+}
+
+/// Add the solutions of `board` to `solutions` until there are `limit`.
+fn search(mut board: Board, limit: usize, solutions: &mut Vec<Grid>) -> Result<(), Error> {
+    match board.solve_outcome() {
+        SolveOutcome::Contradiction { .. } => Ok(()),
+        SolveOutcome::Stopped { .. } => Err(Error::Stopped {
+            remaining: board.positions_of(Unassigned).collect(),
+        }),
+        SolveOutcome::Solved => {
+            if board.verify_solution().is_empty() {
+                solutions.push((*board).clone());
+            }
+            Ok(())
+        }
+        SolveOutcome::Stuck { .. } => {
+            let pos = board.positions_of(Unassigned).next().unwrap();
+            let mut camp = board.clone();
+            if camp.set_camp(pos).is_ok() {
+                search(camp, limit, solutions)?;
+            }
+            if solutions.len() < limit {
+                board.set(pos, Grass);
+                search(board, limit, solutions)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cancel_token::*;
+
+    #[test]
+    fn find_solutions_limit() {
+        let board = Board::new_parse_clue_free(1, "   \n T \n   ").unwrap();
+        assert_eq!(board.count_solutions(10), Ok(4));
+        assert_eq!(board.count_solutions(2), Ok(2));
+        assert_eq!(board.count_solutions(0), Ok(0));
+    }
+
+    #[test]
+    fn find_solutions_none() {
+        let board = Board::new_parse(vec![1, 0], vec![0, 1], " T\n  ").unwrap();
+        assert_eq!(board.find_solutions(2), Ok(Vec::new()));
+        assert_eq!(board.has_unique_solution(), Ok(false));
+    }
+
+    #[test]
+    fn find_solutions_stopped() {
+        let mut board = Board::new_parse_clue_free(1, " T ").unwrap();
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        board.limits.cancel = Some(cancel);
+        match board.count_solutions(2) {
+            Err(Error::Stopped { .. }) => (),
+            x => panic!("{:?}", x),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use contradiction::*;
+use error::*;
+use grid::*;
+use pos::*;
+use tile::Tile::*;
+
+/// The result of running the solver on a [`Board`].
+///
+/// See [`Board::solve_outcome`].
+///
+/// [`Board`]: struct.Board.html
+/// [`Board::solve_outcome`]: struct.Board.html#method.solve_outcome
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveOutcome {
+    /// Every [`Tile`] has been assigned.
+    ///
+    /// [`Tile`]: enum.Tile.html
+    Solved,
+    /// The solver couldn't deduce anything else.
+    ///
+    /// `partial` is the [`Grid`] with as much information as could be
+    /// deduced.
+    ///
+    /// [`Grid`]: struct.Grid.html
+    Stuck { partial: Grid },
+    /// The [`SolverLimits`] stopped the solver before it finished.
+    ///
+    /// `partial` is the [`Grid`] with what was deduced before then.
+    ///
+    /// [`SolverLimits`]: struct.SolverLimits.html
+    /// [`Grid`]: struct.Grid.html
+    Stopped { partial: Grid },
+    /// The `Board` breaks the rules at `cell` and can't be solved.
+    Contradiction { cell: Pos, reason: Contradiction },
+}
+
+impl SolveOutcome {
+    /// Is this `SolveOutcome::Solved`?
+    pub fn is_solved(&self) -> bool {
+        *self == SolveOutcome::Solved
+    }
+}
+
+impl From<SolveOutcome> for Result<(), Error> {
+    /// Convert `Stuck` into [`Error::Unsolved`], `Stopped` into
+    /// [`Error::Stopped`] and `Contradiction` into
+    /// [`Error::Contradiction`].
+    ///
+    /// [`Error::Unsolved`]: enum.Error.html#variant.Unsolved
+    /// [`Error::Stopped`]: enum.Error.html#variant.Stopped
+    /// [`Error::Contradiction`]: enum.Error.html#variant.Contradiction
+    fn from(outcome: SolveOutcome) -> Result<(), Error> {
+        match outcome {
+            SolveOutcome::Solved => Ok(()),
+            SolveOutcome::Stuck { partial } => Err(Error::Unsolved {
+                remaining: remaining(&partial),
+            }),
+            SolveOutcome::Stopped { partial } => Err(Error::Stopped {
+                remaining: remaining(&partial),
+            }),
+            SolveOutcome::Contradiction { cell, reason } => {
+                Err(Error::Contradiction { cell, reason })
+            }
+        }
+    }
+}
+
+/// The position of every [`Unassigned`] `Tile` of `partial`.
+///
+/// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+fn remaining(partial: &Grid) -> Vec<Pos> {
+    let mut remaining = Vec::new();
+    for row in 0..partial.num_rows() {
+        for column in 0..partial.num_columns() {
+            if partial[(row, column)] == Unassigned {
+                remaining.push(Pos::new(row, column));
+            }
+        }
+    }
+    remaining
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn into_result() {
+        assert_eq!(Result::from(SolveOutcome::Solved), Ok(()));
+        assert_eq!(
+            Result::from(SolveOutcome::Stuck {
+                partial: Grid::parse("T \n -").unwrap(),
+            }),
+            Err(Error::Unsolved {
+                remaining: vec![Pos::new(0, 1), Pos::new(1, 0)],
+            })
+        );
+        assert_eq!(
+            Result::from(SolveOutcome::Stopped {
+                partial: Grid::parse("T \n--").unwrap(),
+            }),
+            Err(Error::Stopped {
+                remaining: vec![Pos::new(0, 1)],
+            })
+        );
+        assert_eq!(
+            Result::from(SolveOutcome::Contradiction {
+                cell: Pos::new(1, 2),
+                reason: Contradiction::RowOverfilled,
+            }),
+            Err(Error::Contradiction {
+                cell: Pos::new(1, 2),
+                reason: Contradiction::RowOverfilled,
+            })
+        );
+    }
+}
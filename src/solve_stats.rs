@@ -0,0 +1,146 @@
+use std::fmt;
+use std::time::Duration;
+use strategy::*;
+
+/// How much work each [`Strategy`] did while solving a `Board`.
+///
+/// These are only collected when [`SolverConfig::collect_stats`] is
+/// set.  See [`Board::stats`].
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Board, SolverConfig};
+/// let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n--").unwrap();
+/// let config = SolverConfig {
+///     collect_stats: true,
+///     ..Default::default()
+/// };
+/// board.solve_with(&config).unwrap();
+/// let stats = board.stats().unwrap();
+/// assert_eq!(stats.get("fill_camps").unwrap().tiles_changed, 1);
+/// ```
+///
+/// [`Strategy`]: trait.Strategy.html
+/// [`SolverConfig::collect_stats`]: struct.SolverConfig.html#structfield.collect_stats
+/// [`Board::stats`]: struct.Board.html#method.stats
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SolveStats {
+    /// The stats of each [`Strategy`], in the order they ran.
+    ///
+    /// [`Strategy`]: trait.Strategy.html
+    pub strategies: Vec<StrategyStats>,
+    /// How many times the solver went back to the first strategy.
+    pub iterations: usize,
+    /// How many ways of placing `Camp`s the strategies went through.
+    ///
+    /// See [`Board::record_possibilities`].
+    ///
+    /// [`Board::record_possibilities`]: struct.Board.html#method.record_possibilities
+    pub possibilities: usize,
+}
+
+/// How much work a single [`Strategy`] did.
+///
+/// [`Strategy`]: trait.Strategy.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StrategyStats {
+    /// The [`Strategy::name`].
+    ///
+    /// [`Strategy::name`]: trait.Strategy.html#tymethod.name
+    pub name: &'static str,
+    /// How many times the strategy was applied.
+    pub passes: usize,
+    /// How many `Unassigned` `Tile`s the strategy assigned.
+    pub tiles_changed: usize,
+    /// The wall time spent in the strategy.
+    pub time: Duration,
+}
+
+impl SolveStats {
+    /// Start with no work done by any of `strategies`.
+    pub fn new(strategies: &[Box<dyn Strategy>]) -> Self {
+        SolveStats {
+            strategies: strategies
+                .iter()
+                .map(|strategy| StrategyStats {
+                    name: strategy.name(),
+                    passes: 0,
+                    tiles_changed: 0,
+                    time: Duration::new(0, 0),
+                })
+                .collect(),
+            iterations: 0,
+            possibilities: 0,
+        }
+    }
+
+    /// Record a pass of the `index`th strategy.
+    pub fn record(&mut self, index: usize, tiles_changed: usize, time: Duration) {
+        let stats = &mut self.strategies[index];
+        stats.passes += 1;
+        stats.tiles_changed += tiles_changed;
+        stats.time += time;
+    }
+
+    /// The stats of the first strategy called `name`.
+    pub fn get(&self, name: &str) -> Option<&StrategyStats> {
+        self.strategies.iter().find(|stats| stats.name == name)
+    }
+}
+
+impl fmt::Display for SolveStats {
+    /// Show a line for each strategy with its passes, changed `Tile`s
+    /// and time in milliseconds, then a line with the iterations and
+    /// possibilities.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for stats in &self.strategies {
+            writeln!(
+                f,
+                "{:<20} {:>8} passes {:>8} tiles {:>10.3} ms",
+                stats.name,
+                stats.passes,
+                stats.tiles_changed,
+                stats.time.as_secs_f64() * 1e3
+            )?;
+        }
+        writeln!(
+            f,
+            "{} iterations, {} possibilities",
+            self.iterations, self.possibilities
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_adds_up() {
+        let strategies: Vec<Box<dyn Strategy>> = vec![Box::new(FillZeros), Box::new(FillCamps)];
+        let mut stats = SolveStats::new(&strategies);
+        stats.record(1, 2, Duration::from_millis(3));
+        stats.record(1, 0, Duration::from_millis(1));
+        assert_eq!(stats.get("fill_zeros").unwrap().passes, 0);
+        let fill_camps = stats.get("fill_camps").unwrap();
+        assert_eq!(fill_camps.passes, 2);
+        assert_eq!(fill_camps.tiles_changed, 2);
+        assert_eq!(fill_camps.time, Duration::from_millis(4));
+        assert_eq!(stats.get("trials"), None);
+    }
+
+    #[test]
+    fn display() {
+        let strategies: Vec<Box<dyn Strategy>> = vec![Box::new(FillCamps)];
+        let mut stats = SolveStats::new(&strategies);
+        stats.record(0, 2, Duration::from_millis(3));
+        stats.iterations = 2;
+        stats.possibilities = 5;
+        assert_eq!(
+            stats.to_string(),
+            "fill_camps                  1 passes        2 tiles      3.000 ms\n\
+             2 iterations, 5 possibilities\n"
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use error::*;
+use std::str::FromStr;
+
+/// Settings for a single run of [`Board::solve_with`].
+///
+/// The default uses only the usual deductions and checks the
+/// solution they find.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Board, SolverConfig};
+/// let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+/// let config = SolverConfig {
+///     trial_depth: 1,
+///     ..Default::default()
+/// };
+/// assert!(board.solve_with(&config).is_ok());
+/// ```
+///
+/// [`Board::solve_with`]: struct.Board.html#method.solve_with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SolverConfig {
+    /// Which deductions to use.
+    pub profile: Profile,
+    /// How deeply [`trial_placements`] may nest once the other
+    /// deductions stall.
+    ///
+    /// `0` never tries anything, `1` tries single [`Tile`]s, and each
+    /// level above that lets every trial run trials of its own.
+    ///
+    /// [`trial_placements`]: fn.trial_placements.html
+    /// [`Tile`]: enum.Tile.html
+    pub trial_depth: usize,
+    /// Never guess, even if `trial_depth` or the `profile` asks for
+    /// trials.
+    ///
+    /// Every change is then deduced from the rules and the clues alone,
+    /// and no strategy assumes there is only one solution, so solving
+    /// either succeeds by pure deduction or gets stuck.  This certifies
+    /// that a puzzle can be solved without guessing.
+    pub no_guessing: bool,
+    /// Collect [`SolveStats`] for [`Board::stats`].
+    ///
+    /// This times every strategy, which costs a little and needs a
+    /// clock, so it is off by default.
+    ///
+    /// [`SolveStats`]: struct.SolveStats.html
+    /// [`Board::stats`]: struct.Board.html#method.stats
+    pub collect_stats: bool,
+    /// Check a solved `Board` against every rule with
+    /// [`Board::verify_solution`] before [`Board::solve_with`] returns.
+    ///
+    /// A solution that breaks a rule is a bug in a strategy, reported
+    /// as [`Error::InvalidSolution`].  This is on by default since it
+    /// costs little next to solving.
+    ///
+    /// [`Board::verify_solution`]: struct.Board.html#method.verify_solution
+    /// [`Board::solve_with`]: struct.Board.html#method.solve_with
+    /// [`Error::InvalidSolution`]: enum.Error.html#variant.InvalidSolution
+    pub verify: bool,
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        SolverConfig {
+            profile: Profile::default(),
+            trial_depth: 0,
+            no_guessing: false,
+            collect_stats: false,
+            verify: true,
+        }
+    }
+}
+
+/// A named set of deductions for [`default_strategies`].
+///
+/// [`default_strategies`]: fn.default_strategies.html
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Profile {
+    /// Every deduction, cheapest first.
+    #[default]
+    Standard,
+    /// Only the techniques a person would use, in the order they are
+    /// usually taught.  This leaves out matching every [`Tree`] with a
+    /// [`Camp`] at once.
+    ///
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    HumanStyle,
+    /// The deductions that make the most progress for their cost:
+    /// counting, intersections and matching, then trials at least one
+    /// deep.
+    Fast,
+}
+
+impl FromStr for Profile {
+    type Err = Error;
+
+    /// Parse `"standard"`, `"human"` or `"fast"`.
+    ///
+    /// # Errors
+    ///
+    /// Any other string gives an [`Error::Format`].
+    ///
+    /// [`Error::Format`]: enum.Error.html#variant.Format
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s {
+            "standard" => Ok(Profile::Standard),
+            "human" => Ok(Profile::HumanStyle),
+            "fast" => Ok(Profile::Fast),
+            _ => Err(Error::Format {
+                format: "profile",
+                message: format!("expected standard, human or fast but found {:?}", s),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_from_str() {
+        assert_eq!("standard".parse(), Ok(Profile::Standard));
+        assert_eq!("human".parse(), Ok(Profile::HumanStyle));
+        assert_eq!("fast".parse(), Ok(Profile::Fast));
+        assert!("slow".parse::<Profile>().is_err());
+    }
+}
@@ -0,0 +1,47 @@
+use cancel_token::*;
+use std::time::Duration;
+
+/// Limits on how much work the solving strategies may do.
+///
+/// A strategy that would go over a limit skips that piece of work, as
+/// if it had made no progress, instead of running for a long time.
+/// The default has no limits.
+///
+/// `max_duration`, `max_iterations` and `cancel` stop the whole solve
+/// instead, with [`SolveOutcome::Stopped`].  They are checked between
+/// strategies, while going through the possibilities of a line and
+/// between trials, so a solve stops soon after one of them is hit.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Board, SolverLimits};
+/// let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+/// board.limits = SolverLimits {
+///     max_possibilities_per_line: Some(1000),
+///     ..Default::default()
+/// };
+/// assert!(board.solve().is_ok());
+/// ```
+///
+/// [`SolveOutcome::Stopped`]: enum.SolveOutcome.html#variant.Stopped
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SolverLimits {
+    /// The most ways to place the [`Camp`]s of a single row or column
+    /// that [`process_intersections`] will go through.
+    ///
+    /// Lines with more possibilities are left unchanged.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`process_intersections`]: fn.process_intersections.html
+    pub max_possibilities_per_line: Option<usize>,
+    /// The longest a single solve may run for.
+    pub max_duration: Option<Duration>,
+    /// The most passes through the strategies a single solve may make.
+    ///
+    /// A pass starts at the first strategy and ends when one of them
+    /// changes the `Board`.
+    pub max_iterations: Option<usize>,
+    /// Stops the solve once it is cancelled.
+    pub cancel: Option<CancelToken>,
+}
@@ -0,0 +1,487 @@
+use associate_trees::*;
+use board::*;
+use contradiction::*;
+use fill_camps::*;
+use fill_segments::*;
+use fill_total::*;
+use fill_tree_camps::*;
+use fill_zeros::*;
+use grid::*;
+use match_trees::*;
+use pair_intersection::*;
+use pos::*;
+use solver_config::*;
+use trial::*;
+
+/// What happened when a [`Strategy`] was applied to a `Board`.
+///
+/// [`Strategy`]: trait.Strategy.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StepResult {
+    /// Nothing was deduced.
+    Unchanged,
+    /// At least one `Tile` was changed.
+    Changed,
+    /// The `Board` breaks the rules at `cell` and can't be solved.
+    Contradiction { cell: Pos, reason: Contradiction },
+}
+
+impl From<bool> for StepResult {
+    /// Convert whether a strategy changed anything.
+    fn from(changed: bool) -> StepResult {
+        if changed {
+            StepResult::Changed
+        } else {
+            StepResult::Unchanged
+        }
+    }
+}
+
+impl From<Result<bool, (Pos, Contradiction)>> for StepResult {
+    /// Convert the result of a strategy that can find contradictions.
+    fn from(result: Result<bool, (Pos, Contradiction)>) -> StepResult {
+        match result {
+            Ok(changed) => changed.into(),
+            Err((cell, reason)) => StepResult::Contradiction { cell, reason },
+        }
+    }
+}
+
+/// A single deduction that [`Board::solve_using`] can run.
+///
+/// Strategies are run in order.  Whenever one changes the `Board`,
+/// solving starts again from the first one, so cheap strategies should
+/// come before expensive ones.  Strategies should write with
+/// [`Board::set`] so the counts and the work queue stay up to date.
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Board, FillCamps, StepResult, Strategy};
+/// struct Nothing;
+///
+/// impl Strategy for Nothing {
+///     fn apply(&self, _board: &mut Board) -> StepResult {
+///         StepResult::Unchanged
+///     }
+///
+///     fn name(&self) -> &'static str {
+///         "nothing"
+///     }
+/// }
+///
+/// let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n--").unwrap();
+/// let strategies: Vec<Box<dyn Strategy>> = vec![Box::new(Nothing), Box::new(FillCamps)];
+/// assert!(board.solve_using(&strategies).is_ok());
+/// ```
+///
+/// [`Board::solve_using`]: struct.Board.html#method.solve_using
+/// [`Board::set`]: struct.Board.html#method.set
+pub trait Strategy {
+    /// Deduce what can be deduced about `board`.
+    fn apply(&self, board: &mut Board) -> StepResult;
+
+    /// A short name for this strategy, such as `"fill_zeros"`.
+    fn name(&self) -> &'static str;
+
+    /// Whether this strategy tries out [`Tile`]s to see what happens
+    /// instead of deducing them directly.
+    ///
+    /// [`SolverConfig::no_guessing`] leaves these strategies out.
+    ///
+    /// [`Tile`]: enum.Tile.html
+    /// [`SolverConfig::no_guessing`]: struct.SolverConfig.html#structfield.no_guessing
+    fn guesses(&self) -> bool {
+        false
+    }
+
+    /// How hard this strategy is for a person, used by
+    /// [`Board::rate`].
+    ///
+    /// Simple counting is `1`, and harder techniques weigh more.
+    ///
+    /// [`Board::rate`]: struct.Board.html#method.rate
+    fn difficulty(&self) -> usize {
+        1
+    }
+}
+
+/// The strategies [`Board::solve_with`] runs for `config`, in order.
+///
+/// These depend on `config.profile`.  Trials come last, when there are
+/// any.  If `config.no_guessing` is set, strategies that guess are left
+/// out.
+///
+/// [`Board::solve_with`]: struct.Board.html#method.solve_with
+pub fn default_strategies(config: &SolverConfig) -> Vec<Box<dyn Strategy>> {
+    let mut trial_depth = config.trial_depth;
+    let mut strategies: Vec<Box<dyn Strategy>> = match config.profile {
+        Profile::Standard => vec![
+            Box::new(FillZeros),
+            Box::new(FillCamps),
+            Box::new(FillTotal),
+            Box::new(FillSegments),
+            Box::new(FillTreeCamps),
+            Box::new(Intersections),
+            Box::new(AssociateTrees),
+            Box::new(MatchTrees),
+            Box::new(GrassUnmatched),
+            Box::new(RequeueLines),
+            Box::new(PairIntersections),
+        ],
+        Profile::HumanStyle => vec![
+            Box::new(FillZeros),
+            Box::new(FillCamps),
+            Box::new(FillTotal),
+            Box::new(FillTreeCamps),
+            Box::new(AssociateTrees),
+            Box::new(FillSegments),
+            Box::new(Intersections),
+            Box::new(RequeueLines),
+            Box::new(PairIntersections),
+        ],
+        Profile::Fast => {
+            trial_depth = trial_depth.max(1);
+            vec![
+                Box::new(FillZeros),
+                Box::new(FillCamps),
+                Box::new(FillTotal),
+                Box::new(Intersections),
+                Box::new(MatchTrees),
+                Box::new(GrassUnmatched),
+                Box::new(RequeueLines),
+            ]
+        }
+    };
+    if trial_depth > 0 {
+        strategies.push(Box::new(Trials { depth: trial_depth }));
+    }
+    if config.no_guessing {
+        strategies.retain(|strategy| !strategy.guesses());
+    }
+    strategies
+}
+
+/// Runs [`fill_zeros`].
+///
+/// [`fill_zeros`]: fn.fill_zeros.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FillZeros;
+
+impl Strategy for FillZeros {
+    fn apply(&self, board: &mut Board) -> StepResult {
+        fill_zeros(board).into()
+    }
+
+    fn name(&self) -> &'static str {
+        "fill_zeros"
+    }
+}
+
+/// Runs [`fill_camps`].
+///
+/// [`fill_camps`]: fn.fill_camps.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FillCamps;
+
+impl Strategy for FillCamps {
+    fn apply(&self, board: &mut Board) -> StepResult {
+        fill_camps(board).into()
+    }
+
+    fn name(&self) -> &'static str {
+        "fill_camps"
+    }
+}
+
+/// Runs [`fill_total`].
+///
+/// [`fill_total`]: fn.fill_total.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FillTotal;
+
+impl Strategy for FillTotal {
+    fn apply(&self, board: &mut Board) -> StepResult {
+        fill_total(board).into()
+    }
+
+    fn name(&self) -> &'static str {
+        "fill_total"
+    }
+}
+
+/// Runs [`fill_segments`].
+///
+/// [`fill_segments`]: fn.fill_segments.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FillSegments;
+
+impl Strategy for FillSegments {
+    fn apply(&self, board: &mut Board) -> StepResult {
+        fill_segments(board).into()
+    }
+
+    fn name(&self) -> &'static str {
+        "fill_segments"
+    }
+
+    fn difficulty(&self) -> usize {
+        3
+    }
+}
+
+/// Runs [`fill_tree_camps`].
+///
+/// [`fill_tree_camps`]: fn.fill_tree_camps.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FillTreeCamps;
+
+impl Strategy for FillTreeCamps {
+    fn apply(&self, board: &mut Board) -> StepResult {
+        fill_tree_camps(board).into()
+    }
+
+    fn name(&self) -> &'static str {
+        "fill_tree_camps"
+    }
+
+    fn difficulty(&self) -> usize {
+        2
+    }
+}
+
+/// Runs [`Board::process_queue`].
+///
+/// [`Board::process_queue`]: struct.Board.html#method.process_queue
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Intersections;
+
+impl Strategy for Intersections {
+    fn apply(&self, board: &mut Board) -> StepResult {
+        board.process_queue().into()
+    }
+
+    fn name(&self) -> &'static str {
+        "intersections"
+    }
+
+    fn difficulty(&self) -> usize {
+        4
+    }
+}
+
+/// Runs [`associate_trees`].
+///
+/// [`associate_trees`]: fn.associate_trees.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AssociateTrees;
+
+impl Strategy for AssociateTrees {
+    fn apply(&self, board: &mut Board) -> StepResult {
+        // Work on a copy so the changes go through `Board::set`.
+        let mut grid = Grid::clone(board);
+        let result = associate_trees(&mut grid);
+        for (pos, tile) in grid.cells() {
+            if board[pos] != tile {
+                board.set(pos, tile);
+            }
+        }
+        result.into()
+    }
+
+    fn name(&self) -> &'static str {
+        "associate_trees"
+    }
+
+    fn difficulty(&self) -> usize {
+        2
+    }
+}
+
+/// Runs [`match_trees`].
+///
+/// [`match_trees`]: fn.match_trees.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MatchTrees;
+
+impl Strategy for MatchTrees {
+    fn apply(&self, board: &mut Board) -> StepResult {
+        match_trees(board).into()
+    }
+
+    fn name(&self) -> &'static str {
+        "match_trees"
+    }
+
+    fn difficulty(&self) -> usize {
+        5
+    }
+}
+
+/// Runs [`grass_unmatched`].
+///
+/// [`grass_unmatched`]: fn.grass_unmatched.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GrassUnmatched;
+
+impl Strategy for GrassUnmatched {
+    fn apply(&self, board: &mut Board) -> StepResult {
+        grass_unmatched(board).into()
+    }
+
+    fn name(&self) -> &'static str {
+        "grass_unmatched"
+    }
+
+    fn difficulty(&self) -> usize {
+        5
+    }
+}
+
+/// Runs [`Board::requeue_stale_lines`].
+///
+/// [`Board::requeue_stale_lines`]: struct.Board.html#method.requeue_stale_lines
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RequeueLines;
+
+impl Strategy for RequeueLines {
+    fn apply(&self, board: &mut Board) -> StepResult {
+        board.requeue_stale_lines().into()
+    }
+
+    fn name(&self) -> &'static str {
+        "requeue_lines"
+    }
+}
+
+/// Runs [`process_pair_intersections`].
+///
+/// [`process_pair_intersections`]: fn.process_pair_intersections.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PairIntersections;
+
+impl Strategy for PairIntersections {
+    fn apply(&self, board: &mut Board) -> StepResult {
+        process_pair_intersections(board).into()
+    }
+
+    fn name(&self) -> &'static str {
+        "pair_intersections"
+    }
+
+    fn difficulty(&self) -> usize {
+        6
+    }
+}
+
+/// Runs [`trial_placements`] up to `depth` deep.
+///
+/// [`trial_placements`]: fn.trial_placements.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Trials {
+    pub depth: usize,
+}
+
+impl Strategy for Trials {
+    fn apply(&self, board: &mut Board) -> StepResult {
+        trial_placements(board, self.depth).into()
+    }
+
+    fn name(&self) -> &'static str {
+        "trials"
+    }
+
+    fn guesses(&self) -> bool {
+        true
+    }
+
+    fn difficulty(&self) -> usize {
+        10 * self.depth
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Set the first `Unassigned` `Tile` to `Grass`.
+    struct GrassFirst;
+
+    impl Strategy for GrassFirst {
+        fn apply(&self, board: &mut Board) -> StepResult {
+            use tile::Tile::*;
+            let first = board.positions_of(Unassigned).next();
+            match first {
+                Some(pos) => {
+                    board.set(pos, Grass);
+                    StepResult::Changed
+                }
+                None => StepResult::Unchanged,
+            }
+        }
+
+        fn name(&self) -> &'static str {
+            "grass_first"
+        }
+    }
+
+    #[test]
+    fn step_result_from_result() {
+        assert_eq!(StepResult::from(Ok(true)), StepResult::Changed);
+        assert_eq!(StepResult::from(Ok(false)), StepResult::Unchanged);
+        assert_eq!(
+            StepResult::from(Err((Pos::new(1, 2), Contradiction::SharedTree))),
+            StepResult::Contradiction {
+                cell: Pos::new(1, 2),
+                reason: Contradiction::SharedTree,
+            }
+        );
+    }
+
+    #[test]
+    fn default_strategies_trials() {
+        assert_eq!(default_strategies(&SolverConfig::default()).len(), 11);
+        let config = SolverConfig {
+            trial_depth: 2,
+            ..Default::default()
+        };
+        assert_eq!(default_strategies(&config).len(), 12);
+    }
+
+    #[test]
+    fn default_strategies_fast_always_has_trials() {
+        let config = SolverConfig {
+            profile: Profile::Fast,
+            ..Default::default()
+        };
+        assert_eq!(default_strategies(&config).len(), 8);
+    }
+
+    #[test]
+    fn default_strategies_no_guessing() {
+        let config = SolverConfig {
+            profile: Profile::Fast,
+            trial_depth: 2,
+            no_guessing: true,
+            ..Default::default()
+        };
+        let strategies = default_strategies(&config);
+        assert_eq!(strategies.len(), 7);
+        assert!(strategies.iter().all(|strategy| !strategy.guesses()));
+    }
+
+    #[test]
+    fn solve_using_custom_strategy() {
+        let mut board = Board::new_parse_clue_free(0, "# \n  ").unwrap();
+        let strategies: Vec<Box<dyn Strategy>> = vec![Box::new(GrassFirst)];
+        assert!(board.solve_using(&strategies).is_ok());
+        assert_eq!(board.debug(), "#-\n--");
+    }
+
+    #[test]
+    fn solve_using_no_strategies() {
+        let mut board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+        assert!(board.solve_using(&[]).is_err());
+        assert_eq!(board.debug(), " T\n- ");
+    }
+}
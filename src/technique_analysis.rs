@@ -0,0 +1,198 @@
+use board::*;
+use solver_config::*;
+use strategy::*;
+
+impl Board {
+    /// Find a minimal set of the strategies in
+    /// [`default_strategies`]`(config)` that still solves the `Board`.
+    ///
+    /// Starting from all of them, each strategy is left out in turn,
+    /// hardest first by [`Strategy::difficulty`] and then latest first,
+    /// and stays out if the rest still solve the `Board`.  No strategy
+    /// can be removed from the result, and easy strategies are kept
+    /// over hard ones where either would do.  The names are in the
+    /// order the solver tries them.
+    ///
+    /// Returns `None` if the `Board` can't be solved even with every
+    /// strategy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, SolverConfig};
+    /// let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+    /// assert_eq!(
+    ///     board.minimal_techniques(&SolverConfig::default()),
+    ///     Some(vec!["fill_zeros", "fill_camps"])
+    /// );
+    /// ```
+    ///
+    /// [`default_strategies`]: fn.default_strategies.html
+    /// [`Strategy::difficulty`]: trait.Strategy.html#method.difficulty
+    pub fn minimal_techniques(&self, config: &SolverConfig) -> Option<Vec<&'static str>> {
+        let strategies = default_strategies(config);
+        let mut enabled = vec![true; strategies.len()];
+        if !self.solves_with(config, &enabled) {
+            return None;
+        }
+        let mut order: Vec<usize> = (0..strategies.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse((strategies[i].difficulty(), i)));
+        for i in order {
+            enabled[i] = false;
+            if !self.solves_with(config, &enabled) {
+                enabled[i] = true;
+            }
+        }
+        Some(
+            strategies
+                .iter()
+                .zip(&enabled)
+                .filter(|&(_, &enabled)| enabled)
+                .map(|(strategy, _)| strategy.name())
+                .collect(),
+        )
+    }
+
+    /// Whether a copy of the `Board` is solved by the strategies in
+    /// [`default_strategies`]`(config)` that are `enabled`.
+    ///
+    /// [`default_strategies`]: fn.default_strategies.html
+    fn solves_with(&self, config: &SolverConfig, enabled: &[bool]) -> bool {
+        let strategies: Vec<Box<dyn Strategy>> = default_strategies(config)
+            .into_iter()
+            .zip(enabled)
+            .filter(|&(_, &enabled)| enabled)
+            .map(|(strategy, _)| strategy)
+            .collect();
+        let mut board = self.clone();
+        board.observer = None;
+        board.solve_outcome_using(&strategies).is_solved()
+    }
+}
+
+/// The minimal technique sets of a corpus of puzzles, from
+/// [`TechniqueAnalysis::analyze`].
+///
+/// # Examples
+///
+/// ```
+/// # use camps_and_trees::{Board, Rating, TechniqueAnalysis};
+/// let boards = vec![
+///     Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap(),
+///     Board::new_parse(vec![1, 0], vec![0, 1], "T \n  ").unwrap(),
+/// ];
+/// let analysis = TechniqueAnalysis::analyze(&boards, &Rating::default_config());
+/// assert_eq!(analysis.unlocked_by("fill_zeros"), vec![0, 1]);
+/// ```
+///
+/// [`TechniqueAnalysis::analyze`]: struct.TechniqueAnalysis.html#method.analyze
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TechniqueAnalysis {
+    /// The [`Board::minimal_techniques`] of each puzzle, in the order
+    /// they were given.
+    ///
+    /// [`Board::minimal_techniques`]: struct.Board.html#method.minimal_techniques
+    pub puzzles: Vec<Option<Vec<&'static str>>>,
+}
+
+impl TechniqueAnalysis {
+    /// Find the [`Board::minimal_techniques`] of each of `boards`.
+    ///
+    /// [`Board::minimal_techniques`]: struct.Board.html#method.minimal_techniques
+    pub fn analyze(boards: &[Board], config: &SolverConfig) -> Self {
+        TechniqueAnalysis {
+            puzzles: boards
+                .iter()
+                .map(|board| board.minimal_techniques(config))
+                .collect(),
+        }
+    }
+
+    /// The indices of the puzzles whose minimal technique set includes
+    /// the strategy named `technique`.
+    pub fn unlocked_by(&self, technique: &str) -> Vec<usize> {
+        self.puzzles
+            .iter()
+            .enumerate()
+            .filter(|&(_, techniques)| {
+                techniques
+                    .as_ref()
+                    .is_some_and(|techniques| techniques.contains(&technique))
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// The indices of the puzzles that can't be solved with any of the
+    /// strategies.
+    pub fn unsolved(&self) -> Vec<usize> {
+        self.puzzles
+            .iter()
+            .enumerate()
+            .filter(|&(_, techniques)| techniques.is_none())
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rating::*;
+
+    #[test]
+    fn minimal_techniques_drops_trials() {
+        let board = Board::new_parse(
+            vec![1, 1, 0, 2, 1],
+            vec![2, 0, 1, 1, 1],
+            "     \n T T \n     \nTT   \n    T",
+        )
+        .unwrap();
+        let techniques = board.minimal_techniques(&Rating::default_config()).unwrap();
+        assert!(!techniques.contains(&"trials"));
+        let config = SolverConfig {
+            profile: Profile::HumanStyle,
+            ..Default::default()
+        };
+        let strategies: Vec<Box<dyn Strategy>> = default_strategies(&config)
+            .into_iter()
+            .filter(|x| techniques.contains(&x.name()))
+            .collect();
+        assert!(board.clone().solve_using(&strategies).is_ok());
+        // Leaving out any of them gets stuck.
+        for name in &techniques {
+            let fewer: Vec<Box<dyn Strategy>> = default_strategies(&config)
+                .into_iter()
+                .filter(|x| x.name() != *name && techniques.contains(&x.name()))
+                .collect();
+            assert!(board.clone().solve_using(&fewer).is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn minimal_techniques_needs_trials() {
+        let board = Board::new_parse(
+            vec![2, 0, 2, 0, 2],
+            vec![1, 2, 0, 3, 0],
+            "  T  \nT  T \n    T\n T   \n  T  ",
+        )
+        .unwrap();
+        let config = Rating::default_config();
+        let techniques = board.minimal_techniques(&config).unwrap();
+        assert!(techniques.contains(&"trials"));
+        let boards = vec![board];
+        let analysis = TechniqueAnalysis::analyze(&boards, &config);
+        assert_eq!(analysis.puzzles, vec![Some(techniques)]);
+        assert_eq!(analysis.unlocked_by("trials"), vec![0]);
+        assert_eq!(analysis.unsolved(), Vec::<usize>::new());
+
+        let config = SolverConfig {
+            profile: Profile::HumanStyle,
+            ..Default::default()
+        };
+        let analysis = TechniqueAnalysis::analyze(&boards, &config);
+        assert_eq!(analysis.puzzles, vec![None]);
+        assert_eq!(analysis.unlocked_by("trials"), Vec::<usize>::new());
+        assert_eq!(analysis.unsolved(), vec![0]);
+    }
+}
@@ -1,51 +1,95 @@
+use error::*;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use tile_charset::*;
 
 /// A single `Tile` on the [`Grid`].
 ///
+/// `Tile`s are ordered `Unassigned` < `Grass` < `Camp` < `Tree` <
+/// `Blocked`.
+///
 /// [`Grid`]: struct.Grid.html
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Tile {
     /// This `Tile` has not yet been assigned or solved for.
     Unassigned,
     Grass,
     Camp,
     Tree,
+    /// A cell that can never hold a `Camp` or a `Tree`, such as a lake
+    /// or a rock.
+    Blocked,
 }
 
 impl Tile {
     /// Parse the char into a `Tile`.
     ///
-    /// ` ` is `Unassigned`, `-` is `Grass`, `C` is `Camp`, and `T` is `Tree`.
+    /// ` ` is `Unassigned`, `-` is `Grass`, `C` is `Camp`, `T` is
+    /// `Tree`, and `#` is `Blocked`.
+    ///
+    /// # Errors
+    ///
+    /// If the char doesn't match one of the five options outlined
+    /// above, an [`Error::Parse`] is returned.
+    ///
+    /// [`Error::Parse`]: enum.Error.html#variant.Parse
+    pub fn parse(c: char) -> Result<Self, Error> {
+        TileCharset::default().parse(c)
+    }
+}
+
+impl FromStr for Tile {
+    type Err = Error;
+
+    /// Parse a string holding a single character via [`Tile::parse`].
     ///
     /// # Errors
     ///
-    /// If the char doesn't match one of the four options outlined
-    /// above, an `Err` is returned.
-    pub fn parse(c: char) -> Result<Self, String> {
-        match c {
-            ' ' => Ok(Tile::Unassigned),
-            '-' => Ok(Tile::Grass),
-            'C' => Ok(Tile::Camp),
-            'T' => Ok(Tile::Tree),
-            _ => Err(format!("Couldn't parse tile: '{}'", c)),
+    /// If the string isn't exactly one character, an
+    /// [`Error::Format`] is returned.
+    ///
+    /// [`Tile::parse`]: enum.Tile.html#method.parse
+    /// [`Error::Format`]: enum.Error.html#variant.Format
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Tile::parse(c),
+            _ => Err(Error::Format {
+                format: "tile",
+                message: format!("expected a single character but found {:?}", s),
+            }),
         }
     }
 }
 
 impl fmt::Debug for Tile {
-    /// See the method [`parse`].
+    /// See the method [`parse`] and [`TileCharset`].
     ///
     /// [`parse`]: enum.Tile.html#method.parse
+    /// [`TileCharset`]: struct.TileCharset.html
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Tile::Unassigned => ' ',
-                Tile::Grass => '-',
-                Tile::Camp => 'C',
-                Tile::Tree => 'T',
-            }
-        )
+        write!(f, "{}", TileCharset::default().format(*self))
+    }
+}
+
+impl Hash for Tile {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u8(*self as u8);
+    }
+
+    /// Hash 8 `Tile`s at a time so hashing a whole `Grid` is fast.
+    fn hash_slice<H: Hasher>(data: &[Tile], state: &mut H) {
+        let mut chunks = data.chunks_exact(8);
+        for chunk in &mut chunks {
+            state.write_u64(
+                chunk
+                    .iter()
+                    .fold(0, |word, &tile| (word << 8) | tile as u64),
+            );
+        }
+        for tile in chunks.remainder() {
+            tile.hash(state);
+        }
     }
 }
@@ -0,0 +1,129 @@
+use error::*;
+use tile::Tile::{self, *};
+
+/// The characters used to read and write each [`Tile`].
+///
+/// The default matches [`Tile::parse`]: ` ` is [`Unassigned`], `-` is
+/// [`Grass`], `C` is [`Camp`], `T` is [`Tree`], and `#` is [`Blocked`].
+/// Each `Tile` should have a distinct character, and none should be
+/// `\n`.
+///
+/// # Examples
+///
+/// ```
+/// use camps_and_trees::{Grid, TileCharset};
+/// let charset = TileCharset {
+///     unassigned: '.',
+///     grass: '*',
+///     camp: 'x',
+///     tree: 'T',
+///     blocked: '#',
+/// };
+/// let grid = Grid::parse_with("xT*\n.*.", &charset).unwrap();
+/// assert_eq!(grid, Grid::parse("CT-\n - ").unwrap());
+/// assert_eq!(grid.format_with(&charset), "xT*\n.*.");
+/// ```
+///
+/// [`Tile`]: enum.Tile.html
+/// [`Tile::parse`]: enum.Tile.html#method.parse
+/// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+/// [`Grass`]: enum.Tile.html#variant.Grass
+/// [`Camp`]: enum.Tile.html#variant.Camp
+/// [`Tree`]: enum.Tile.html#variant.Tree
+/// [`Blocked`]: enum.Tile.html#variant.Blocked
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TileCharset {
+    pub unassigned: char,
+    pub grass: char,
+    pub camp: char,
+    pub tree: char,
+    pub blocked: char,
+}
+
+impl Default for TileCharset {
+    fn default() -> Self {
+        TileCharset {
+            unassigned: ' ',
+            grass: '-',
+            camp: 'C',
+            tree: 'T',
+            blocked: '#',
+        }
+    }
+}
+
+impl TileCharset {
+    /// Parse the char into a [`Tile`].
+    ///
+    /// # Errors
+    ///
+    /// If the char isn't in the `TileCharset`, an [`Error::Parse`]
+    /// is returned.
+    ///
+    /// [`Tile`]: enum.Tile.html
+    /// [`Error::Parse`]: enum.Error.html#variant.Parse
+    pub fn parse(&self, c: char) -> Result<Tile, Error> {
+        if c == self.unassigned {
+            Ok(Unassigned)
+        } else if c == self.grass {
+            Ok(Grass)
+        } else if c == self.camp {
+            Ok(Camp)
+        } else if c == self.tree {
+            Ok(Tree)
+        } else if c == self.blocked {
+            Ok(Blocked)
+        } else {
+            Err(Error::Parse {
+                character: c,
+                position: None,
+            })
+        }
+    }
+
+    /// Get the char for a [`Tile`].
+    ///
+    /// [`Tile`]: enum.Tile.html
+    pub fn format(&self, tile: Tile) -> char {
+        match tile {
+            Unassigned => self.unassigned,
+            Grass => self.grass,
+            Camp => self.camp,
+            Tree => self.tree,
+            Blocked => self.blocked,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_round_trip() {
+        let charset = TileCharset::default();
+        for &tile in &[Unassigned, Grass, Camp, Tree, Blocked] {
+            assert_eq!(charset.parse(charset.format(tile)), Ok(tile));
+            assert_eq!(
+                charset.parse(charset.format(tile)),
+                Tile::parse(charset.format(tile))
+            );
+        }
+    }
+
+    #[test]
+    fn parse_unknown() {
+        let charset = TileCharset {
+            camp: 'x',
+            ..Default::default()
+        };
+        assert_eq!(charset.parse('x'), Ok(Camp));
+        assert_eq!(
+            charset.parse('C'),
+            Err(Error::Parse {
+                character: 'C',
+                position: None,
+            })
+        );
+    }
+}
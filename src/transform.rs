@@ -0,0 +1,296 @@
+use board::*;
+use clue::*;
+use grid::*;
+use pos::*;
+
+/// Which way [`Board::rotated`] turns a `Board`.
+///
+/// [`Board::rotated`]: struct.Board.html#method.rotated
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RotationDirection {
+    /// A quarter turn clockwise.
+    Clockwise,
+    /// A quarter turn counterclockwise.
+    CounterClockwise,
+}
+
+impl Board {
+    /// A copy of the `Board` turned a quarter turn in `direction`.
+    ///
+    /// The clues move with their lines, omitted or not.  As with the other
+    /// transformations, the [`Board::limits`] and [`RuleSet`] are kept
+    /// but the [`Board::history`] and [`Board::observer`] aren't, since
+    /// their positions no longer match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, RotationDirection};
+    /// let board = Board::new_parse(vec![1, 0], vec![0, 1, 0], " TC\n   ").unwrap();
+    /// let rotated = board.rotated(RotationDirection::Clockwise);
+    /// assert_eq!(rotated.row_clues(), vec![Some(0), Some(1), Some(0)]);
+    /// assert_eq!(rotated.column_clues(), vec![Some(0), Some(1)]);
+    /// assert_eq!(rotated.debug(), "  \n T\n C");
+    /// assert_eq!(rotated.rotated(RotationDirection::CounterClockwise), board);
+    /// ```
+    ///
+    /// [`Board::limits`]: struct.Board.html#structfield.limits
+    /// [`RuleSet`]: struct.RuleSet.html
+    /// [`Board::history`]: struct.Board.html#structfield.history
+    /// [`Board::observer`]: struct.Board.html#structfield.observer
+    pub fn rotated(&self, direction: RotationDirection) -> Board {
+        let (height, width) = (self.num_rows(), self.num_columns());
+        let reversed = |clues: Vec<Clue>| clues.into_iter().rev().collect();
+        match direction {
+            RotationDirection::Clockwise => self.transformed(
+                self.column_ranges(),
+                reversed(self.row_ranges()),
+                width,
+                height,
+                |pos| Pos::new(height - 1 - pos.column, pos.row),
+            ),
+            RotationDirection::CounterClockwise => self.transformed(
+                reversed(self.column_ranges()),
+                self.row_ranges(),
+                width,
+                height,
+                |pos| Pos::new(pos.column, width - 1 - pos.row),
+            ),
+        }
+    }
+
+    /// A copy of the `Board` mirrored left to right.
+    ///
+    /// See [`Board::rotated`] for what is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// let board = Board::new_parse(vec![1, 0], vec![0, 1, 0], " TC\n   ").unwrap();
+    /// let flipped = board.flipped_horizontal();
+    /// assert_eq!(flipped.column_clues(), vec![Some(0), Some(1), Some(0)]);
+    /// assert_eq!(flipped.debug(), "CT \n   ");
+    /// ```
+    ///
+    /// [`Board::rotated`]: struct.Board.html#method.rotated
+    pub fn flipped_horizontal(&self) -> Board {
+        let (height, width) = (self.num_rows(), self.num_columns());
+        self.transformed(
+            self.row_ranges(),
+            self.column_ranges().into_iter().rev().collect(),
+            height,
+            width,
+            |pos| Pos::new(pos.row, width - 1 - pos.column),
+        )
+    }
+
+    /// A copy of the `Board` mirrored top to bottom.
+    ///
+    /// See [`Board::rotated`] for what is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// let board = Board::new_parse(vec![1, 0], vec![0, 1, 0], " TC\n   ").unwrap();
+    /// let flipped = board.flipped_vertical();
+    /// assert_eq!(flipped.row_clues(), vec![Some(0), Some(1)]);
+    /// assert_eq!(flipped.debug(), "   \n TC");
+    /// ```
+    ///
+    /// [`Board::rotated`]: struct.Board.html#method.rotated
+    pub fn flipped_vertical(&self) -> Board {
+        let (height, width) = (self.num_rows(), self.num_columns());
+        self.transformed(
+            self.row_ranges().into_iter().rev().collect(),
+            self.column_ranges(),
+            height,
+            width,
+            |pos| Pos::new(height - 1 - pos.row, pos.column),
+        )
+    }
+
+    /// A copy of the `Board` mirrored along its main diagonal, so rows
+    /// become columns and columns become rows.
+    ///
+    /// See [`Board::rotated`] for what is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::Board;
+    /// let board = Board::new_parse(vec![1, 0], vec![0, 1, 0], " TC\n   ").unwrap();
+    /// let transposed = board.transposed();
+    /// assert_eq!(transposed.row_clues(), vec![Some(0), Some(1), Some(0)]);
+    /// assert_eq!(transposed.column_clues(), vec![Some(1), Some(0)]);
+    /// assert_eq!(transposed.debug(), "  \nT \nC ");
+    /// ```
+    ///
+    /// [`Board::rotated`]: struct.Board.html#method.rotated
+    pub fn transposed(&self) -> Board {
+        self.transformed(
+            self.column_ranges(),
+            self.row_ranges(),
+            self.num_columns(),
+            self.num_rows(),
+            |pos| Pos::new(pos.column, pos.row),
+        )
+    }
+
+    /// The 8 rotations and reflections of the `Board`, starting with an
+    /// unchanged copy.
+    ///
+    /// See [`Board::rotated`] for what is kept.
+    ///
+    /// [`Board::rotated`]: struct.Board.html#method.rotated
+    pub fn symmetries(&self) -> Vec<Board> {
+        let mut symmetries = Vec::with_capacity(8);
+        let mut board = self.transformed(
+            self.row_ranges(),
+            self.column_ranges(),
+            self.num_rows(),
+            self.num_columns(),
+            |pos| pos,
+        );
+        for _ in 0..4 {
+            let next = board.rotated(RotationDirection::Clockwise);
+            let transposed = board.transposed();
+            symmetries.push(board);
+            symmetries.push(transposed);
+            board = next;
+        }
+        symmetries
+    }
+
+    /// Build a `height` by `width` `Board` with the clues `rows` and
+    /// `columns`, whose `Tile` at each `Pos` is the one at
+    /// `source(pos)` on this `Board`.
+    fn transformed<F: Fn(Pos) -> Pos>(
+        &self,
+        rows: Vec<Clue>,
+        columns: Vec<Clue>,
+        height: usize,
+        width: usize,
+        source: F,
+    ) -> Board {
+        let mut grid = Grid::new(
+            (0..height)
+                .map(|row| {
+                    (0..width)
+                        .map(|column| self[source(Pos::new(row, column))])
+                        .collect()
+                })
+                .collect(),
+        );
+        grid.set_rules(self.rules());
+        let mut board = if self.is_clue_free() {
+            Board::new_clue_free(self.total(), grid)
+        } else {
+            Board::new_ranged(rows, columns, grid)
+        };
+        board.limits = self.limits.clone();
+        board
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tile::Tile::*;
+
+    fn board() -> Board {
+        Board::new_parse(
+            vec![1, 1, 0, 2, 1],
+            vec![2, 0, 1, 1, 1],
+            "     \n T T \n     \nTT   \n    T",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn transformations_keep_solutions() {
+        let mut solved = board();
+        solved.solve().unwrap();
+        let transforms: Vec<fn(&Board) -> Board> = vec![
+            |x| x.rotated(RotationDirection::Clockwise),
+            |x| x.rotated(RotationDirection::CounterClockwise),
+            Board::flipped_horizontal,
+            Board::flipped_vertical,
+            Board::transposed,
+        ];
+        for transform in transforms {
+            let mut board = transform(&board());
+            board.solve().unwrap();
+            assert_eq!(board, transform(&solved));
+        }
+    }
+
+    #[test]
+    fn transformations_compose() {
+        let board = board();
+        let clockwise = |x: &Board| x.rotated(RotationDirection::Clockwise);
+        assert_eq!(clockwise(&clockwise(&clockwise(&clockwise(&board)))), board);
+        assert_eq!(board.transposed().transposed(), board);
+        assert_eq!(board.flipped_horizontal().flipped_horizontal(), board);
+        assert_eq!(board.flipped_vertical().flipped_vertical(), board);
+        let symmetries = board.symmetries();
+        assert_eq!(symmetries.len(), 8);
+        assert_eq!(symmetries[0], board);
+        for transformed in &[
+            board.flipped_horizontal(),
+            board.flipped_vertical(),
+            board.transposed(),
+            board.rotated(RotationDirection::CounterClockwise),
+        ] {
+            assert!(symmetries.contains(transformed));
+        }
+        // A clockwise turn is a transpose followed by a horizontal flip.
+        assert_eq!(clockwise(&board), board.transposed().flipped_horizontal());
+        assert_eq!(
+            board.rotated(RotationDirection::CounterClockwise),
+            board.transposed().flipped_vertical()
+        );
+    }
+
+    #[test]
+    fn transformations_of_rectangles() {
+        let mut board = Board::new_parse(vec![1, 0], vec![0, 0, 1], " T \n   ").unwrap();
+        board.place(0, 2, Camp).unwrap();
+        board.limits.max_iterations = Some(3);
+        let rotated = board.rotated(RotationDirection::CounterClockwise);
+        assert_eq!(rotated.debug(), "C \nT \n  ");
+        assert_eq!(rotated.row_clues(), vec![Some(1), Some(0), Some(0)]);
+        assert_eq!(rotated.column_clues(), vec![Some(1), Some(0)]);
+        assert_eq!(rotated.limits.max_iterations, Some(3));
+        assert!(rotated.history.is_empty());
+        assert_eq!(rotated.count(Camp), 1);
+
+        let partial = Board::new_parse_partial(vec![None, Some(0)], vec![Some(0), None], " T\n  ")
+            .unwrap()
+            .rotated(RotationDirection::Clockwise);
+        assert_eq!(partial.row_clues(), vec![Some(0), None]);
+        assert_eq!(partial.column_clues(), vec![Some(0), None]);
+        assert_eq!(partial.debug(), "  \n T");
+
+        let rows = vec![Clue::at_most(1), Clue::exactly(0)];
+        let columns = vec![Clue::exactly(0), Clue::at_least(1)];
+        let ranged = Board::new_parse_ranged(rows, columns, " T\n  ")
+            .unwrap()
+            .transposed();
+        assert_eq!(
+            ranged.row_ranges(),
+            vec![Clue::exactly(0), Clue::at_least(1)]
+        );
+        assert_eq!(
+            ranged.column_ranges(),
+            vec![Clue::at_most(1), Clue::exactly(0)]
+        );
+
+        let clue_free = Board::new_parse_clue_free(1, "CT-").unwrap();
+        let transposed = clue_free.transposed();
+        assert!(transposed.is_clue_free());
+        assert_eq!(transposed.total(), 1);
+        assert_eq!(transposed.debug(), "C\nT\n-");
+    }
+}
@@ -0,0 +1,163 @@
+use board::*;
+use contradiction::*;
+use fill_camps::*;
+use fill_total::*;
+use fill_zeros::*;
+use match_trees::*;
+use pos::*;
+use tile::Tile::{self, *};
+
+/// Try placing a [`Camp`], then [`Grass`], on each [`Unassigned`]
+/// [`Tile`], and keep the other one if a cheap round of deductions
+/// finds a [`Contradiction`].
+///
+/// The deductions are [`fill_zeros`], [`fill_camps`], [`fill_total`]
+/// and [`match_trees`], along with covering the [`Tile`]s around each
+/// [`Camp`] in [`Grass`].  When `depth` is more than `1`, they also
+/// include trials of their own, up to `depth - 1` deep.  A `depth` of
+/// `0` tries nothing.  Every [`Tile`] is tried one deep before any is
+/// tried deeper, since shallow trials are much cheaper and usually
+/// enough.  This stops at the first [`Tile`] it changes, so
+/// the cheaper strategies can run again.  It also gives up once
+/// [`Board::should_stop`].
+///
+/// Return whether any values were changed.
+///
+/// # Examples
+///
+/// A [`Camp`] right of the left [`Tree`] would fill the top row,
+/// leaving the right [`Tree`] without one:
+///
+/// ```
+/// # use camps_and_trees::{Board, trial_placements};
+/// let mut board = Board::new_parse(vec![1, 1], vec![1, 0, 1, 0], "T  T\n -- ").unwrap();
+/// assert!(trial_placements(&mut board, 1));
+/// assert_eq!(board.debug(), "T- T\n -- ");
+/// ```
+///
+/// [`Tile`]: enum.Tile.html
+/// [`Tree`]: enum.Tile.html#variant.Tree
+/// [`Camp`]: enum.Tile.html#variant.Camp
+/// [`Grass`]: enum.Tile.html#variant.Grass
+/// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+/// [`Contradiction`]: enum.Contradiction.html
+/// [`fill_zeros`]: fn.fill_zeros.html
+/// [`fill_camps`]: fn.fill_camps.html
+/// [`fill_total`]: fn.fill_total.html
+/// [`match_trees`]: fn.match_trees.html
+/// [`Board::should_stop`]: struct.Board.html#method.should_stop
+pub fn trial_placements(board: &mut Board, depth: usize) -> bool {
+    if depth == 0 {
+        return false;
+    }
+    let cells: Vec<Pos> = board.positions_of(Unassigned).collect();
+    for depth in 1..=depth {
+        for &pos in &cells {
+            if board.should_stop() {
+                return false;
+            }
+            for &(tile, other) in &[(Camp, Grass), (Grass, Camp)] {
+                if fails(board, pos, tile, depth) {
+                    board.set(pos, other);
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Whether setting the `Tile` at `pos` to `tile` leads to a
+/// [`Contradiction`], using trials up to `depth - 1` deep.
+///
+/// [`Contradiction`]: enum.Contradiction.html
+fn fails(board: &Board, pos: Pos, tile: Tile, depth: usize) -> bool {
+    let mut board = board.clone();
+    // Nothing set here is kept, so don't report it.
+    board.observer = None;
+    board.set(pos, tile);
+    loop {
+        if find_contradiction(&board).is_some() {
+            return true;
+        }
+        let mut changed = grass_around_camps(&mut board);
+        changed |= fill_zeros(&mut board);
+        changed |= fill_camps(&mut board);
+        changed |= fill_total(&mut board);
+        if changed {
+            continue;
+        }
+        match match_trees(&mut board) {
+            Ok(true) => continue,
+            Ok(false) => (),
+            Err(_) => return true,
+        }
+        if !trial_placements(&mut board, depth - 1) {
+            return false;
+        }
+    }
+}
+
+/// Set the [`Unassigned`] [`Tile`]s around each [`Camp`] to [`Grass`].
+///
+/// Return whether any values were changed.
+///
+/// [`Tile`]: enum.Tile.html
+/// [`Camp`]: enum.Tile.html#variant.Camp
+/// [`Grass`]: enum.Tile.html#variant.Grass
+/// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+fn grass_around_camps(board: &mut Board) -> bool {
+    let camps: Vec<Pos> = board.positions_of(Camp).collect();
+    let mut changed = false;
+    for camp in camps {
+        for pos in board.touching(camp) {
+            if board[pos] == Unassigned {
+                board.set(pos, Grass);
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trial_placements_camp_fails() {
+        let mut board = Board::new_parse(vec![1, 1], vec![1, 0, 1, 0], "T  T\n -- ").unwrap();
+        assert!(trial_placements(&mut board, 1));
+        assert_eq!(board.debug(), "T- T\n -- ");
+    }
+
+    #[test]
+    fn trial_placements_grass_fails() {
+        // Without a `Camp` on the left, the left `Tree` has none.
+        let mut board = Board::new_parse_clue_free(1, " T-\n---").unwrap();
+        assert!(trial_placements(&mut board, 1));
+        assert_eq!(board.debug(), "CT-\n---");
+    }
+
+    #[test]
+    fn trial_placements_nothing_fails() {
+        let mut board = Board::new_parse_clue_free(1, " T ").unwrap();
+        assert!(!trial_placements(&mut board, 1));
+        assert_eq!(board.debug(), " T ");
+    }
+
+    #[test]
+    fn trial_placements_depth_0() {
+        let mut board = Board::new_parse_clue_free(1, " T-\n---").unwrap();
+        assert!(!trial_placements(&mut board, 0));
+        assert_eq!(board.debug(), " T-\n---");
+    }
+
+    #[test]
+    fn grass_around_camps_diagonals() {
+        let mut board = Board::new_parse_clue_free(1, "   \n C \n  T").unwrap();
+        assert!(grass_around_camps(&mut board));
+        assert_eq!(board.debug(), "---\n-C-\n--T");
+        assert!(!grass_around_camps(&mut board));
+    }
+}
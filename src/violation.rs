@@ -0,0 +1,655 @@
+use board::*;
+use clue::*;
+use matching::*;
+use pos::*;
+use std::fmt;
+use tile::Tile::*;
+use tile::*;
+
+/// A rule a `Board` breaks, found by [`Board::verify_solution`] or
+/// [`Board::check_consistency`].
+///
+/// [`Board::verify_solution`]: struct.Board.html#method.verify_solution
+/// [`Board::check_consistency`]: struct.Board.html#method.check_consistency
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    /// Which rule is broken.
+    pub kind: ViolationKind,
+    /// The cells breaking it.
+    pub cells: Vec<Pos>,
+}
+
+/// The rules a [`Violation`] can break.
+///
+/// [`Violation`]: struct.Violation.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// The cells are still [`Unassigned`].
+    ///
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    Unassigned,
+    /// Row `row` has `camps` [`Camp`]s, which `clue` doesn't allow.
+    /// The cells are the whole row.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    RowClue {
+        row: usize,
+        clue: Clue,
+        camps: usize,
+    },
+    /// Column `column` has `camps` [`Camp`]s, which `clue` doesn't
+    /// allow.  The cells are the whole column.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    ColumnClue {
+        column: usize,
+        clue: Clue,
+        camps: usize,
+    },
+    /// Row `row` has room for at most `most` [`Camp`]s, fewer than
+    /// `clue` needs.  The cells are the whole row.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    RowUnreachable { row: usize, clue: Clue, most: usize },
+    /// Column `column` has room for at most `most` [`Camp`]s, fewer
+    /// than `clue` needs.  The cells are the whole column.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    ColumnUnreachable {
+        column: usize,
+        clue: Clue,
+        most: usize,
+    },
+    /// A `Board` whose clues don't fix its total has `camps` [`Camp`]s
+    /// instead of `total`.  The cells are every [`Camp`].
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    Total { total: usize, camps: usize },
+    /// A `Board` whose clues don't fix its total has room for at most
+    /// `most` [`Camp`]s, fewer than `total`.  There are no cells.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    TotalUnreachable { total: usize, most: usize },
+    /// The two [`Camp`]s in the cells touch, possibly diagonally.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    AdjacentCamps,
+    /// The [`Camp`] in the cell can't have a [`Tree`] of its own.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    UnmatchedCamp,
+    /// The [`Tree`] in the cell can't have a [`Camp`] of its own.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    UnmatchedTree,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cell = |i: usize| {
+            let pos: Pos = self.cells[i];
+            format!("row {}, column {}", pos.row, pos.column)
+        };
+        match self.kind {
+            ViolationKind::Unassigned => write!(f, "{} tiles are unassigned", self.cells.len()),
+            ViolationKind::RowClue { row, clue, camps } => {
+                write!(f, "Row {} has {} camps but needs {}", row, camps, clue)
+            }
+            ViolationKind::ColumnClue {
+                column,
+                clue,
+                camps,
+            } => write!(
+                f,
+                "Column {} has {} camps but needs {}",
+                column, camps, clue
+            ),
+            ViolationKind::RowUnreachable { row, clue, most } => write!(
+                f,
+                "Row {} has room for {} camps but needs {}",
+                row, most, clue
+            ),
+            ViolationKind::ColumnUnreachable { column, clue, most } => write!(
+                f,
+                "Column {} has room for {} camps but needs {}",
+                column, most, clue
+            ),
+            ViolationKind::TotalUnreachable { total, most } => write!(
+                f,
+                "The board has room for {} camps but needs {}",
+                most, total
+            ),
+            ViolationKind::Total { total, camps } => {
+                write!(f, "The board has {} camps but needs {}", camps, total)
+            }
+            ViolationKind::AdjacentCamps => {
+                write!(f, "Camps at {} and {} touch", cell(0), cell(1))
+            }
+            ViolationKind::UnmatchedCamp => write!(f, "Camp at {} has no tree of its own", cell(0)),
+            ViolationKind::UnmatchedTree => write!(f, "Tree at {} has no camp of its own", cell(0)),
+        }
+    }
+}
+
+impl Board {
+    /// Check every rule of the game against a finished `Board`.
+    ///
+    /// Unlike [`Grid::is_solved`], which only checks that no `Tile` is
+    /// [`Unassigned`], this checks that every row and column has as
+    /// many [`Camp`]s as its clue allows, the `Board` as many as its
+    /// total if the clues don't fix it, that no two [`Camp`]s touch,
+    /// even diagonally, and that each
+    /// [`Camp`] can be paired with a [`Tree`] next to it so that every
+    /// [`Tree`] has exactly one [`Camp`].  The [`RuleSet`] can let
+    /// [`Camp`]s touch diagonally or leave out the pairing.
+    ///
+    /// Returns every [`Violation`] found, which is empty for a correct
+    /// solution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, Clue, ViolationKind};
+    /// let board = Board::new_parse(vec![1, 0], vec![1, 0], "CT\n--").unwrap();
+    /// assert_eq!(board.verify_solution(), vec![]);
+    /// let board = Board::new_parse(vec![1, 0], vec![1, 0], "-T\nC-").unwrap();
+    /// let kinds: Vec<_> = board.verify_solution().into_iter().map(|x| x.kind).collect();
+    /// assert_eq!(
+    ///     kinds,
+    ///     vec![
+    ///         ViolationKind::RowClue { row: 0, clue: Clue::exactly(1), camps: 0 },
+    ///         ViolationKind::RowClue { row: 1, clue: Clue::exactly(0), camps: 1 },
+    ///         ViolationKind::UnmatchedCamp,
+    ///         ViolationKind::UnmatchedTree,
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// [`Grid::is_solved`]: struct.Grid.html#method.is_solved
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Violation`]: struct.Violation.html
+    /// [`RuleSet`]: struct.RuleSet.html
+    pub fn verify_solution(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let unassigned: Vec<Pos> = self.positions_of(Unassigned).collect();
+        if !unassigned.is_empty() {
+            violations.push(Violation {
+                kind: ViolationKind::Unassigned,
+                cells: unassigned,
+            });
+        }
+        for (row, clue) in self.row_ranges().into_iter().enumerate() {
+            let camps = self.count_in_row(row, Camp);
+            if !clue.allows(camps) {
+                violations.push(Violation {
+                    kind: ViolationKind::RowClue { row, clue, camps },
+                    cells: self.row_cells(row),
+                });
+            }
+        }
+        for (column, clue) in self.column_ranges().into_iter().enumerate() {
+            let camps = self.count_in_column(column, Camp);
+            if !clue.allows(camps) {
+                violations.push(Violation {
+                    kind: ViolationKind::ColumnClue {
+                        column,
+                        clue,
+                        camps,
+                    },
+                    cells: self.column_cells(column),
+                });
+            }
+        }
+        let camps: Vec<Pos> = self.positions_of(Camp).collect();
+        if !self.has_exact_clues() && camps.len() != self.total() {
+            violations.push(Violation {
+                kind: ViolationKind::Total {
+                    total: self.total(),
+                    camps: camps.len(),
+                },
+                cells: camps.clone(),
+            });
+        }
+        violations.extend(self.adjacent_camps(&camps));
+        if !self.rules().no_matching {
+            violations.extend(self.unmatched_camps(&camps));
+            violations.extend(self.unmatched_trees(|tile| tile == Camp));
+        }
+        violations
+    }
+
+    /// Find the rules a partly filled in `Board` already breaks, no
+    /// matter how the rest is filled in.
+    ///
+    /// This can be called while playing or solving.  It reports rows,
+    /// columns, or a `Board` whose clues don't fix its total, with more
+    /// [`Camp`]s than their clue allows or too little room left to
+    /// reach it, [`Camp`]s that touch,
+    /// [`Camp`]s without a [`Tree`] of their own, and [`Tree`]s that
+    /// can no longer get a [`Camp`] of their own.  [`Unassigned`]
+    /// `Tile`s aren't a problem.  Like [`Board::verify_solution`], this
+    /// follows the [`RuleSet`].
+    ///
+    /// Returns every [`Violation`] found, which is empty if the `Board`
+    /// might still be solved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use camps_and_trees::{Board, Clue, ViolationKind};
+    /// let board = Board::new_parse(vec![1, 0], vec![1, 0], " T\n  ").unwrap();
+    /// assert_eq!(board.check_consistency(), vec![]);
+    /// let board = Board::new_parse(vec![1, 0], vec![1, 0], "-T\n -").unwrap();
+    /// let kinds: Vec<_> = board.check_consistency().into_iter().map(|x| x.kind).collect();
+    /// assert_eq!(
+    ///     kinds,
+    ///     vec![
+    ///         ViolationKind::RowUnreachable { row: 0, clue: Clue::exactly(1), most: 0 },
+    ///         ViolationKind::UnmatchedTree,
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    /// [`Unassigned`]: enum.Tile.html#variant.Unassigned
+    /// [`Violation`]: struct.Violation.html
+    /// [`Board::verify_solution`]: struct.Board.html#method.verify_solution
+    /// [`RuleSet`]: struct.RuleSet.html
+    pub fn check_consistency(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for (row, clue) in self.row_ranges().into_iter().enumerate() {
+            let camps = self.count_in_row(row, Camp);
+            let most = camps + self.count_in_row(row, Unassigned);
+            let kind = if clue.is_over(camps) {
+                ViolationKind::RowClue { row, clue, camps }
+            } else if !clue.is_reachable(most) {
+                ViolationKind::RowUnreachable { row, clue, most }
+            } else {
+                continue;
+            };
+            violations.push(Violation {
+                kind,
+                cells: self.row_cells(row),
+            });
+        }
+        for (column, clue) in self.column_ranges().into_iter().enumerate() {
+            let camps = self.count_in_column(column, Camp);
+            let most = camps + self.count_in_column(column, Unassigned);
+            let kind = if clue.is_over(camps) {
+                ViolationKind::ColumnClue {
+                    column,
+                    clue,
+                    camps,
+                }
+            } else if !clue.is_reachable(most) {
+                ViolationKind::ColumnUnreachable { column, clue, most }
+            } else {
+                continue;
+            };
+            violations.push(Violation {
+                kind,
+                cells: self.column_cells(column),
+            });
+        }
+        let camps: Vec<Pos> = self.positions_of(Camp).collect();
+        if !self.has_exact_clues() {
+            let total = self.total();
+            let most = camps.len() + self.count(Unassigned);
+            if camps.len() > total {
+                violations.push(Violation {
+                    kind: ViolationKind::Total {
+                        total,
+                        camps: camps.len(),
+                    },
+                    cells: camps.clone(),
+                });
+            } else if most < total {
+                violations.push(Violation {
+                    kind: ViolationKind::TotalUnreachable { total, most },
+                    cells: Vec::new(),
+                });
+            }
+        }
+        violations.extend(self.adjacent_camps(&camps));
+        if !self.rules().no_matching {
+            violations.extend(self.unmatched_camps(&camps));
+            violations.extend(self.unmatched_trees(|tile| tile == Camp || tile == Unassigned));
+        }
+        violations
+    }
+
+    /// The cells of row `row`.
+    fn row_cells(&self, row: usize) -> Vec<Pos> {
+        (0..self.num_columns())
+            .map(|column| Pos::new(row, column))
+            .collect()
+    }
+
+    /// The cells of column `column`.
+    fn column_cells(&self, column: usize) -> Vec<Pos> {
+        (0..self.num_rows())
+            .map(|row| Pos::new(row, column))
+            .collect()
+    }
+
+    /// Report each pair of `camps` that touch.
+    fn adjacent_camps(&self, camps: &[Pos]) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for &camp in camps {
+            for other in self.touching(camp) {
+                if other > camp && self[other] == Camp {
+                    violations.push(Violation {
+                        kind: ViolationKind::AdjacentCamps,
+                        cells: vec![camp, other],
+                    });
+                }
+            }
+        }
+        violations
+    }
+
+    /// Pair as many of `camps` as possible with [`Tree`]s next to
+    /// them, and report the [`Camp`]s left over.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    fn unmatched_camps(&self, camps: &[Pos]) -> Vec<Violation> {
+        let trees: Vec<Pos> = self.positions_of(Tree).collect();
+        let edges = camps
+            .iter()
+            .map(|&camp| {
+                self.surrounding_tiles(camp)
+                    .into_iter()
+                    .filter_map(|pos| trees.binary_search(&pos).ok())
+                    .collect()
+            })
+            .collect();
+        let matching = Matching::new(edges, trees.len());
+        camps
+            .iter()
+            .enumerate()
+            .filter(|&(l, _)| !matching.is_matched_left(l))
+            .map(|(_, &camp)| Violation {
+                kind: ViolationKind::UnmatchedCamp,
+                cells: vec![camp],
+            })
+            .collect()
+    }
+
+    /// Pair as many [`Tree`]s as possible with `Tile`s next to them
+    /// that `camp` accepts, and report the [`Tree`]s left over.
+    ///
+    /// [`Tree`]: enum.Tile.html#variant.Tree
+    fn unmatched_trees<F: Fn(Tile) -> bool>(&self, camp: F) -> Vec<Violation> {
+        let trees: Vec<Pos> = self.positions_of(Tree).collect();
+        let num_columns = self.num_columns();
+        let edges = trees
+            .iter()
+            .map(|&tree| {
+                self.surrounding_tiles(tree)
+                    .into_iter()
+                    .filter(|&pos| camp(self[pos]))
+                    .map(|pos| pos.row * num_columns + pos.column)
+                    .collect()
+            })
+            .collect();
+        let matching = Matching::new(edges, self.num_rows() * num_columns);
+        trees
+            .iter()
+            .enumerate()
+            .filter(|&(l, _)| !matching.is_matched_left(l))
+            .map(|(_, &tree)| Violation {
+                kind: ViolationKind::UnmatchedTree,
+                cells: vec![tree],
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rule_set::*;
+
+    fn kinds(board: &Board) -> Vec<ViolationKind> {
+        board
+            .verify_solution()
+            .into_iter()
+            .map(|x| x.kind)
+            .collect()
+    }
+
+    #[test]
+    fn verify_solution_solved() {
+        let board = Board::new_parse(
+            vec![1, 1, 0, 2, 1],
+            vec![2, 0, 1, 1, 1],
+            "---C-\nCT-T-\n-----\nTTC-C\nC---T",
+        )
+        .unwrap();
+        assert_eq!(board.verify_solution(), vec![]);
+        let board = Board::new_parse_clue_free(2, "CT\n--\nTC").unwrap();
+        assert_eq!(board.verify_solution(), vec![]);
+    }
+
+    #[test]
+    fn verify_solution_unassigned() {
+        let board = Board::new_parse(vec![1, 0], vec![1, 0], "CT\n- ").unwrap();
+        assert_eq!(
+            board.verify_solution(),
+            vec![Violation {
+                kind: ViolationKind::Unassigned,
+                cells: vec![Pos::new(1, 1)],
+            }]
+        );
+    }
+
+    #[test]
+    fn verify_solution_clues() {
+        let board = Board::new_parse(vec![0, 1], vec![1, 0], "CT\n--").unwrap();
+        assert_eq!(
+            board.verify_solution(),
+            vec![
+                Violation {
+                    kind: ViolationKind::RowClue {
+                        row: 0,
+                        clue: Clue::exactly(0),
+                        camps: 1,
+                    },
+                    cells: vec![Pos::new(0, 0), Pos::new(0, 1)],
+                },
+                Violation {
+                    kind: ViolationKind::RowClue {
+                        row: 1,
+                        clue: Clue::exactly(1),
+                        camps: 0,
+                    },
+                    cells: vec![Pos::new(1, 0), Pos::new(1, 1)],
+                },
+            ]
+        );
+        let board = Board::new_parse_clue_free(2, "CT\n--").unwrap();
+        assert_eq!(
+            kinds(&board),
+            vec![ViolationKind::Total { total: 2, camps: 1 }]
+        );
+    }
+
+    #[test]
+    fn verify_solution_adjacent_camps() {
+        // Each `Camp` has a `Tree`, but they touch diagonally.
+        let board = Board::new_parse(vec![1, 1], vec![1, 1], "CT\nTC").unwrap();
+        assert_eq!(
+            board.verify_solution(),
+            vec![Violation {
+                kind: ViolationKind::AdjacentCamps,
+                cells: vec![Pos::new(0, 0), Pos::new(1, 1)],
+            }]
+        );
+    }
+
+    #[test]
+    fn verify_solution_rule_variants() {
+        let mut board = Board::new_parse(vec![1, 1], vec![1, 1], "CT\nTC").unwrap();
+        board.set_rules(RuleSet {
+            orthogonal_only: true,
+            ..RuleSet::default()
+        });
+        assert_eq!(board.verify_solution(), vec![]);
+        let mut board = Board::new_parse(vec![2, 0], vec![1, 0, 1], "CTC\n-T-").unwrap();
+        board.set_rules(RuleSet {
+            no_matching: true,
+            ..RuleSet::default()
+        });
+        assert_eq!(board.verify_solution(), vec![]);
+        assert_eq!(board.check_consistency(), vec![]);
+    }
+
+    #[test]
+    fn verify_solution_shared_tree() {
+        // The clues match, but both `Camp`s need the middle `Tree`.
+        let board = Board::new_parse(vec![2, 0], vec![1, 0, 1], "CTC\n-T-").unwrap();
+        assert_eq!(
+            board.verify_solution(),
+            vec![
+                Violation {
+                    kind: ViolationKind::UnmatchedCamp,
+                    cells: vec![Pos::new(0, 2)],
+                },
+                Violation {
+                    kind: ViolationKind::UnmatchedTree,
+                    cells: vec![Pos::new(1, 1)],
+                },
+            ]
+        );
+    }
+
+    fn consistency_kinds(board: &Board) -> Vec<ViolationKind> {
+        board
+            .check_consistency()
+            .into_iter()
+            .map(|x| x.kind)
+            .collect()
+    }
+
+    #[test]
+    fn check_consistency_open() {
+        let board = Board::new_parse(
+            vec![1, 1, 0, 2, 1],
+            vec![2, 0, 1, 1, 1],
+            "     \n T T \n     \nTT   \n    T",
+        )
+        .unwrap();
+        assert_eq!(board.check_consistency(), vec![]);
+        let board = Board::new_parse(
+            vec![1, 1, 0, 2, 1],
+            vec![2, 0, 1, 1, 1],
+            "---C-\nCT-T-\n-----\nTTC-C\nC---T",
+        )
+        .unwrap();
+        assert_eq!(board.check_consistency(), vec![]);
+    }
+
+    #[test]
+    fn check_consistency_lines() {
+        let board = Board::new_parse(vec![0, 1, 1], vec![1, 1, 0], "CT \n-- \nT- ").unwrap();
+        assert_eq!(
+            board.check_consistency(),
+            vec![
+                Violation {
+                    kind: ViolationKind::RowClue {
+                        row: 0,
+                        clue: Clue::exactly(0),
+                        camps: 1,
+                    },
+                    cells: vec![Pos::new(0, 0), Pos::new(0, 1), Pos::new(0, 2)],
+                },
+                Violation {
+                    kind: ViolationKind::ColumnUnreachable {
+                        column: 1,
+                        clue: Clue::exactly(1),
+                        most: 0,
+                    },
+                    cells: vec![Pos::new(0, 1), Pos::new(1, 1), Pos::new(2, 1)],
+                },
+                Violation {
+                    kind: ViolationKind::UnmatchedTree,
+                    cells: vec![Pos::new(2, 0)],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn check_consistency_total() {
+        let board = Board::new_parse_clue_free(2, "T-\n-T").unwrap();
+        assert_eq!(
+            consistency_kinds(&board),
+            vec![
+                ViolationKind::TotalUnreachable { total: 2, most: 0 },
+                ViolationKind::UnmatchedTree,
+                ViolationKind::UnmatchedTree,
+            ]
+        );
+        let board = Board::new_parse_clue_free(0, "CT").unwrap();
+        assert_eq!(
+            consistency_kinds(&board),
+            vec![ViolationKind::Total { total: 0, camps: 1 }]
+        );
+    }
+
+    #[test]
+    fn check_consistency_camps() {
+        // The `Camp`s touch and share their only `Tree`.
+        let board = Board::new_parse_clue_free(2, "C  \nTC-\n---").unwrap();
+        assert_eq!(
+            board.check_consistency(),
+            vec![
+                Violation {
+                    kind: ViolationKind::AdjacentCamps,
+                    cells: vec![Pos::new(0, 0), Pos::new(1, 1)],
+                },
+                Violation {
+                    kind: ViolationKind::UnmatchedCamp,
+                    cells: vec![Pos::new(1, 1)],
+                },
+            ]
+        );
+        let board = Board::new_parse_clue_free(2, "  -\nT-C\n-T ").unwrap();
+        assert_eq!(
+            board.check_consistency(),
+            vec![Violation {
+                kind: ViolationKind::UnmatchedCamp,
+                cells: vec![Pos::new(1, 2)],
+            }]
+        );
+    }
+
+    #[test]
+    fn display() {
+        let board = Board::new_parse(vec![1, 1], vec![1, 1], "CT\nTC").unwrap();
+        assert_eq!(
+            board.verify_solution()[0].to_string(),
+            "Camps at row 0, column 0 and row 1, column 1 touch"
+        );
+        let board = Board::new_parse(vec![0, 1], vec![1, 0], "CT\n- ").unwrap();
+        let messages: Vec<String> = board
+            .verify_solution()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "1 tiles are unassigned",
+                "Row 0 has 1 camps but needs 0",
+                "Row 1 has 0 camps but needs 1",
+            ]
+        );
+    }
+}
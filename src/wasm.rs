@@ -0,0 +1,102 @@
+//! Bindings for running the solver in a browser.
+//!
+//! Puzzles are passed in and out as strings in the format read by
+//! [`Board::from_text`], or as JSON read by [`Board::from_json`].
+//! Errors are thrown as JavaScript strings.
+//!
+//! [`Board::from_text`]: ../struct.Board.html#method.from_text
+//! [`Board::from_json`]: ../struct.Board.html#method.from_json
+
+use board::*;
+use error::*;
+use render::cell_name;
+use tile::Tile::*;
+use wasm_bindgen::prelude::*;
+
+/// Parse `input` as JSON if it starts with `{` and as text otherwise.
+fn parse(input: &str) -> Result<Board, Error> {
+    if input.trim_start().starts_with('{') {
+        Board::from_json(input)
+    } else {
+        Board::from_text(input)
+    }
+}
+
+/// See [`solve_text`].
+///
+/// [`solve_text`]: fn.solve_text.html
+fn solve(input: &str) -> Result<String, Error> {
+    let mut board = parse(input)?;
+    board.validate()?;
+    board.solve()?;
+    Ok(board.to_text())
+}
+
+/// See [`hint_text`].
+///
+/// [`hint_text`]: fn.hint_text.html
+fn hint(input: &str) -> Result<Option<String>, Error> {
+    let board = parse(input)?;
+    board.validate()?;
+    let mut solved = board.clone();
+    if let Err(e @ Error::Contradiction { .. }) = solved.solve() {
+        return Err(e);
+    }
+    for row in 0..board.num_rows() {
+        for column in 0..board.num_columns() {
+            let tile = solved[(row, column)];
+            if board[(row, column)] == Unassigned && tile != Unassigned {
+                return Ok(Some(format!("{}: {:?}", cell_name(row, column), tile)));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Solve a puzzle, returning the solved `Board` as text.
+///
+/// Throws if the puzzle is malformed, breaks the rules, or can't be
+/// solved completely.
+#[wasm_bindgen]
+pub fn solve_text(input: &str) -> Result<String, JsValue> {
+    solve(input).map_err(|x| JsValue::from_str(&x.to_string()))
+}
+
+/// Find a cell that can be deduced in a puzzle.
+///
+/// The hint names the cell and the [`Tile`] that belongs there, for
+/// example `"B1: C"` for a [`Camp`].  Returns `undefined` if no cell
+/// can be deduced, and throws if the puzzle is malformed or breaks the
+/// rules.
+///
+/// [`Tile`]: ../enum.Tile.html
+/// [`Camp`]: ../enum.Tile.html#variant.Camp
+#[wasm_bindgen]
+pub fn hint_text(input: &str) -> Result<Option<String>, JsValue> {
+    hint(input).map_err(|x| JsValue::from_str(&x.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            solve("1, 0\n1, 0\n T\n  "),
+            Ok("1, 0\n1, 0\nCT\n--".to_string())
+        );
+        assert_eq!(
+            solve(r#"{"total": 1, "grid": ["T", " "]}"#),
+            Ok("total: 1\nT\nC".to_string())
+        );
+        assert!(solve("1, 0\n1, 0\n T").is_err());
+    }
+
+    #[test]
+    fn test_hint() {
+        assert_eq!(hint("1, 0\n1, 0\n T\n  "), Ok(Some("A1: C".to_string())));
+        assert_eq!(hint("1, 0\n1, 0\nCT\n--"), Ok(None));
+        assert!(hint("1, 0\n1, 0\nCC\n--").is_err());
+    }
+}
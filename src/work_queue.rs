@@ -0,0 +1,261 @@
+use pos::*;
+use std::collections::VecDeque;
+use tile::Tile::{self, *};
+
+/// A row or column of a `Board`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Line {
+    /// The row with the given index.
+    Row(usize),
+    /// The column with the given index.
+    Column(usize),
+}
+
+/// The rows and columns of a `Board` waiting to be processed again.
+///
+/// Each line is in the queue at most once.  A line taken with
+/// [`WorkQueue::pop`] isn't queued again until [`WorkQueue::finish`]
+/// is called, so the changes made while processing it don't queue it
+/// again.
+///
+/// [`WorkQueue::pop`]: struct.WorkQueue.html#method.pop
+/// [`WorkQueue::finish`]: struct.WorkQueue.html#method.finish
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WorkQueue {
+    lines: VecDeque<Line>,
+    rows: Vec<bool>,
+    columns: Vec<bool>,
+    stale: bool,
+}
+
+impl WorkQueue {
+    /// Queue every row, then every column.
+    pub fn new(num_rows: usize, num_columns: usize) -> Self {
+        let mut queue = WorkQueue {
+            lines: VecDeque::new(),
+            rows: vec![false; num_rows],
+            columns: vec![false; num_columns],
+            stale: false,
+        };
+        queue.push_all();
+        queue
+    }
+
+    /// Queue every line that isn't queued yet.
+    pub fn push_all(&mut self) {
+        self.stale = false;
+        for row in 0..self.rows.len() {
+            self.push(Line::Row(row));
+        }
+        for column in 0..self.columns.len() {
+            self.push(Line::Column(column));
+        }
+    }
+
+    /// Queue `line` unless it is already queued.
+    pub fn push(&mut self, line: Line) {
+        let queued = match line {
+            Line::Row(row) => &mut self.rows[row],
+            Line::Column(column) => &mut self.columns[column],
+        };
+        if !*queued {
+            *queued = true;
+            self.lines.push_back(line);
+        }
+    }
+
+    /// Queue the lines affected by setting the `Tile` at `pos` to
+    /// `tile`.
+    ///
+    /// These are the row and column through `pos`.  A [`Camp`] also
+    /// rules out the `Tile`s around it, so the rows and columns on
+    /// either side are queued too, wrapping around the edges if `wrap`
+    /// is set.
+    ///
+    /// [`Camp`]: enum.Tile.html#variant.Camp
+    pub fn push_tile(&mut self, pos: Pos, tile: Tile, wrap: bool) {
+        if self.lines.len() < self.rows.len() + self.columns.len() {
+            self.stale = true;
+        }
+        let reach = if tile == Camp { 1 } else { 0 };
+        for row in around(pos.row, reach, self.rows.len(), wrap) {
+            self.push(Line::Row(row));
+        }
+        for column in around(pos.column, reach, self.columns.len(), wrap) {
+            self.push(Line::Column(column));
+        }
+    }
+
+    /// Whether a `Tile` has changed since every line was last queued.
+    ///
+    /// A line's placements also depend on the lines crossing it, which
+    /// [`WorkQueue::push_tile`] doesn't queue.  Changes made while
+    /// every line is still queued don't count.
+    ///
+    /// [`WorkQueue::push_tile`]: struct.WorkQueue.html#method.push_tile
+    pub fn is_stale(&self) -> bool {
+        self.stale
+    }
+
+    /// Empty the queue if every line is in it.
+    ///
+    /// Returns whether it was emptied, in which case the caller should
+    /// process every line at once.
+    pub fn take_all(&mut self) -> bool {
+        if self.lines.len() < self.rows.len() + self.columns.len() {
+            return false;
+        }
+        self.lines.clear();
+        for queued in self.rows.iter_mut().chain(&mut self.columns) {
+            *queued = false;
+        }
+        true
+    }
+
+    /// Take the line that has been queued the longest.
+    ///
+    /// Call [`WorkQueue::finish`] once the line has been processed.
+    ///
+    /// [`WorkQueue::finish`]: struct.WorkQueue.html#method.finish
+    pub fn pop(&mut self) -> Option<Line> {
+        self.lines.pop_front()
+    }
+
+    /// Allow `line`, taken by [`WorkQueue::pop`], to be queued again.
+    ///
+    /// [`WorkQueue::pop`]: struct.WorkQueue.html#method.pop
+    pub fn finish(&mut self, line: Line) {
+        match line {
+            Line::Row(row) => self.rows[row] = false,
+            Line::Column(column) => self.columns[column] = false,
+        }
+    }
+}
+
+/// The lines within `reach` of line `x` out of `len`, wrapping around
+/// if `wrap` is set.
+fn around(x: usize, reach: usize, len: usize, wrap: bool) -> Vec<usize> {
+    if !wrap {
+        return (x.saturating_sub(reach)..len.min(x + reach + 1)).collect();
+    }
+    let mut lines = Vec::new();
+    for line in x + len - reach.min(len)..=x + len + reach {
+        if !lines.contains(&(line % len)) {
+            lines.push(line % len);
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Take every queued line, finishing each one.
+    fn drain(queue: &mut WorkQueue) -> Vec<Line> {
+        let mut lines = Vec::new();
+        while let Some(line) = queue.pop() {
+            queue.finish(line);
+            lines.push(line);
+        }
+        lines
+    }
+
+    #[test]
+    fn new_queues_rows_then_columns() {
+        let mut queue = WorkQueue::new(2, 1);
+        assert_eq!(
+            drain(&mut queue),
+            vec![Line::Row(0), Line::Row(1), Line::Column(0)]
+        );
+    }
+
+    #[test]
+    fn push_skips_queued_lines() {
+        let mut queue = WorkQueue::new(2, 2);
+        drain(&mut queue);
+        queue.push(Line::Column(1));
+        queue.push(Line::Row(0));
+        queue.push(Line::Column(1));
+        assert_eq!(drain(&mut queue), vec![Line::Column(1), Line::Row(0)]);
+    }
+
+    #[test]
+    fn push_skips_lines_until_finished() {
+        let mut queue = WorkQueue::new(1, 1);
+        assert_eq!(queue.pop(), Some(Line::Row(0)));
+        queue.push(Line::Row(0));
+        assert_eq!(drain(&mut queue), vec![Line::Column(0)]);
+        queue.finish(Line::Row(0));
+        queue.push(Line::Row(0));
+        assert_eq!(drain(&mut queue), vec![Line::Row(0)]);
+    }
+
+    #[test]
+    fn push_tile_marks_stale() {
+        let mut queue = WorkQueue::new(2, 2);
+        queue.push_tile(Pos::new(0, 0), Grass, false);
+        assert!(!queue.is_stale());
+        drain(&mut queue);
+        queue.push_tile(Pos::new(0, 0), Grass, false);
+        assert!(queue.is_stale());
+        queue.push_all();
+        assert!(!queue.is_stale());
+    }
+
+    #[test]
+    fn take_all_only_when_full() {
+        let mut queue = WorkQueue::new(2, 1);
+        assert_eq!(queue.pop(), Some(Line::Row(0)));
+        assert!(!queue.take_all());
+        queue.finish(Line::Row(0));
+        queue.push(Line::Row(0));
+        assert!(queue.take_all());
+        assert_eq!(queue.pop(), None);
+        queue.push(Line::Column(0));
+        assert_eq!(drain(&mut queue), vec![Line::Column(0)]);
+    }
+
+    #[test]
+    fn push_tile_grass() {
+        let mut queue = WorkQueue::new(3, 4);
+        drain(&mut queue);
+        queue.push_tile(Pos::new(1, 2), Grass, false);
+        assert_eq!(drain(&mut queue), vec![Line::Row(1), Line::Column(2)]);
+    }
+
+    #[test]
+    fn push_tile_camp_queues_neighboring_lines() {
+        let mut queue = WorkQueue::new(3, 4);
+        drain(&mut queue);
+        queue.push_tile(Pos::new(0, 2), Camp, false);
+        assert_eq!(
+            drain(&mut queue),
+            vec![
+                Line::Row(0),
+                Line::Row(1),
+                Line::Column(1),
+                Line::Column(2),
+                Line::Column(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn push_tile_camp_wraps() {
+        let mut queue = WorkQueue::new(3, 4);
+        drain(&mut queue);
+        queue.push_tile(Pos::new(0, 3), Camp, true);
+        assert_eq!(
+            drain(&mut queue),
+            vec![
+                Line::Row(2),
+                Line::Row(0),
+                Line::Row(1),
+                Line::Column(2),
+                Line::Column(3),
+                Line::Column(0),
+            ]
+        );
+    }
+}